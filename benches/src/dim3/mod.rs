@@ -12,6 +12,7 @@ use crate::Benchmark;
 
 mod large_pyramid;
 mod many_pyramids;
+mod spatial_queries;
 
 /// All benchmarks for `avian3d`.
 pub const BENCHMARKS: &[Benchmark] = &[
@@ -21,6 +22,9 @@ pub const BENCHMARKS: &[Benchmark] = &[
     Benchmark::new("Many Pyramids 3D", "many_pyramids", || {
         many_pyramids::create_bench(10, 10, 10)
     }),
+    Benchmark::new("Spatial Queries 3D", "spatial_queries", || {
+        spatial_queries::create_bench(20, 1000)
+    }),
 ];
 
 /// A plugin group that includes the minimal set of plugins used for benchmarking `avian3d`.
@@ -0,0 +1,74 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use super::Benchmark3dPlugins;
+
+pub fn create_bench(grid_size: usize, query_count: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins((Benchmark3dPlugins, PhysicsPlugins::default()));
+    app.add_systems(Startup, move |commands: Commands| {
+        setup(commands, grid_size)
+    });
+    app.add_systems(Update, move |spatial_query: SpatialQuery| {
+        run_queries(spatial_query, grid_size, query_count)
+    });
+    app
+}
+
+fn setup(mut commands: Commands, grid_size: usize) {
+    let cube = Collider::cuboid(1.0, 1.0, 1.0);
+    let ball = Collider::sphere(0.5);
+
+    // A grid of static colliders with gaps between them.
+    for x in 0..grid_size {
+        for y in 0..grid_size {
+            for z in 0..grid_size {
+                let collider = if (x + y + z) % 2 == 0 {
+                    cube.clone()
+                } else {
+                    ball.clone()
+                };
+                commands.spawn((
+                    RigidBody::Static,
+                    collider,
+                    Transform::from_xyz(3.0 * x as f32, 3.0 * y as f32, 3.0 * z as f32),
+                ));
+            }
+        }
+    }
+}
+
+/// Casts rays and shapes into the grid and projects points onto it.
+fn run_queries(spatial_query: SpatialQuery, grid_size: usize, query_count: usize) {
+    let extent = 3.0 * grid_size as f32;
+    let filter = SpatialQueryFilter::default();
+    let shape = Collider::sphere(0.25);
+
+    for i in 0..query_count {
+        // Spread the queries over the grid deterministically.
+        let t = i as f32 / query_count as f32;
+        let origin = Vec3::new(-5.0, extent * t, extent * (1.0 - t));
+        let direction = Dir3::new(Vec3::new(1.0, 0.3 * (t - 0.5), 0.2 * (0.5 - t))).unwrap();
+
+        core::hint::black_box(spatial_query.cast_ray(
+            origin,
+            direction,
+            2.0 * extent,
+            true,
+            &filter,
+        ));
+        core::hint::black_box(spatial_query.cast_shape(
+            &shape,
+            origin,
+            Quat::IDENTITY,
+            direction,
+            &ShapeCastConfig::from_max_distance(2.0 * extent),
+            &filter,
+        ));
+        core::hint::black_box(spatial_query.project_point(
+            origin + direction * extent * t,
+            true,
+            &filter,
+        ));
+    }
+}
@@ -16,10 +16,13 @@ fn main() {
             PhysicsPlugins::default().with_length_unit(10.0),
             // Add collider backend for our custom collider.
             // This handles things like initializing and updating required components
-            // and managing collider hierarchies.
-            ColliderBackendPlugin::<CircleCollider>::default(),
+            // and managing collider hierarchies. Debug rendering is enabled
+            // using the `DebugRenderCollider` implementation of the collider.
+            ColliderBackendPlugin::<CircleCollider>::default().with_debug_render(),
             // Enable collision detection for our custom collider.
             NarrowPhasePlugin::<CircleCollider>::default(),
+            // Enable spatial queries like raycasts for our custom collider.
+            SpatialQueryBackendPlugin::<CircleCollider>::default(),
         ))
         .insert_resource(ClearColor(Color::srgb(0.01, 0.01, 0.025)))
         .insert_resource(Gravity::ZERO)
@@ -32,7 +35,7 @@ fn main() {
 }
 
 /// A basic collider with a circle shape. Only supports uniform scaling.
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct CircleCollider {
     /// The radius of the circle collider. This may be scaled by the `Transform` scale.
     radius: Scalar,
@@ -148,6 +151,107 @@ impl ScalableCollider for CircleCollider {
     }
 }
 
+// Implement spatial queries for the collider shape.
+// This makes the collider visible to raycasts, point queries, and picking.
+impl QueryCollider for CircleCollider {
+    fn query_aabb(&self, position: Vector, _rotation: Rotation) -> ColliderAabb {
+        ColliderAabb::new(position, Vector::splat(self.radius))
+    }
+
+    fn cast_ray(
+        &self,
+        position: Vector,
+        _rotation: Rotation,
+        ray_origin: Vector,
+        ray_direction: Vector,
+        max_distance: Scalar,
+        solid: bool,
+    ) -> Option<(Scalar, Vector)> {
+        let offset = ray_origin - position;
+        let inside = offset.length_squared() <= self.radius.powi(2);
+
+        if inside && solid {
+            return Some((0.0, Vector::ZERO));
+        }
+
+        // Solve `|offset + t * direction| = radius` for `t`.
+        let a = ray_direction.length_squared();
+        let b = offset.dot(ray_direction);
+        let c = offset.length_squared() - self.radius.powi(2);
+        let discriminant = b * b - a * c;
+
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        // Inside of a hollow circle, the ray hits the far side.
+        let distance = if inside {
+            (-b + discriminant.sqrt()) / a
+        } else {
+            (-b - discriminant.sqrt()) / a
+        };
+
+        if distance < 0.0 || distance > max_distance {
+            return None;
+        }
+
+        let normal = (offset + ray_direction * distance).normalize_or_zero();
+        Some((distance, normal))
+    }
+
+    fn project_point(
+        &self,
+        position: Vector,
+        _rotation: Rotation,
+        point: Vector,
+        solid: bool,
+    ) -> (Vector, bool) {
+        let offset = point - position;
+        let inside = offset.length_squared() <= self.radius.powi(2);
+
+        if inside && solid {
+            (point, true)
+        } else {
+            let direction = offset.try_normalize().unwrap_or(Vector::X);
+            (position + direction * self.radius, inside)
+        }
+    }
+
+    fn contains_point(&self, position: Vector, _rotation: Rotation, point: Vector) -> bool {
+        point.distance_squared(position) <= self.radius.powi(2)
+    }
+
+    fn intersects_shape(
+        &self,
+        position: Vector,
+        _rotation: Rotation,
+        shape: &Self,
+        shape_position: Vector,
+        _shape_rotation: Rotation,
+    ) -> bool {
+        position.distance_squared(shape_position) <= (self.radius + shape.radius).powi(2)
+    }
+}
+
+// Implement debug rendering for the collider shape.
+// This draws the collider when the `PhysicsDebugPlugin` is enabled.
+impl DebugRenderCollider for CircleCollider {
+    fn debug_render(
+        &self,
+        position: Vector,
+        _rotation: Rotation,
+        _context: &(),
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        color: Color,
+    ) {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(position.f32()),
+            self.radius as f32,
+            color,
+        );
+    }
+}
+
 /// A marker component for the rotating body at the center.
 #[derive(Component)]
 struct CenterBody;
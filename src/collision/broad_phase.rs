@@ -90,8 +90,16 @@ where
                 let entity = trigger.entity;
 
                 // Re-enable the collider.
-                if let Ok((entity, collider_of, aabb, layers, is_sensor, events_enabled, hooks)) =
-                    query.get(entity)
+                if let Ok((
+                    entity,
+                    collider_of,
+                    aabb,
+                    layers,
+                    group,
+                    is_sensor,
+                    events_enabled,
+                    hooks,
+                )) = query.get(entity)
                 {
                     let flags = init_aabb_interval_flags(
                         collider_of,
@@ -105,6 +113,7 @@ where
                         collider_of.map_or(ColliderOf { body: entity }, |p| *p),
                         *aabb,
                         *layers,
+                        group.map(|group| group.0),
                         flags,
                     );
 
@@ -123,8 +132,16 @@ where
                 let entity = trigger.entity;
 
                 // Re-enable the collider.
-                if let Ok((entity, collider_of, aabb, layers, is_sensor, events_enabled, hooks)) =
-                    query.get(entity)
+                if let Ok((
+                    entity,
+                    collider_of,
+                    aabb,
+                    layers,
+                    group,
+                    is_sensor,
+                    events_enabled,
+                    hooks,
+                )) = query.get(entity)
                 {
                     let flags = init_aabb_interval_flags(
                         collider_of,
@@ -138,6 +155,7 @@ where
                         collider_of.map_or(ColliderOf { body: entity }, |p| *p),
                         *aabb,
                         *layers,
+                        group.map(|group| group.0),
                         flags,
                     );
 
@@ -181,6 +199,7 @@ type AabbInterval = (
     ColliderOf,
     ColliderAabb,
     CollisionLayers,
+    Option<Entity>,
     AabbIntervalFlags,
 );
 
@@ -205,6 +224,9 @@ impl MapEntities for AabbIntervals {
     fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
         for interval in self.0.iter_mut() {
             interval.0 = entity_mapper.get_mapped(interval.0);
+            if let Some(group) = interval.4.as_mut() {
+                *group = entity_mapper.get_mapped(*group);
+            }
         }
     }
 }
@@ -217,6 +239,7 @@ fn update_aabb_intervals(
             &ColliderAabb,
             Option<&ColliderOf>,
             &CollisionLayers,
+            Option<&SelfCollisionGroup>,
             Has<Sensor>,
             Has<CollisionEventsEnabled>,
             Option<&ActiveCollisionHooks>,
@@ -227,13 +250,13 @@ fn update_aabb_intervals(
     rbs: Query<(&RigidBody, Has<RigidBodyDisabled>)>,
     mut intervals: ResMut<AabbIntervals>,
 ) {
-    intervals
-        .0
-        .retain_mut(|(collider_entity, collider_of, aabb, layers, flags)| {
+    intervals.0.retain_mut(
+        |(collider_entity, collider_of, aabb, layers, group, flags)| {
             if let Ok((
                 new_aabb,
                 new_collider_of,
                 new_layers,
+                new_group,
                 is_sensor,
                 events_enabled,
                 hooks,
@@ -252,6 +275,7 @@ fn update_aabb_intervals(
                     |p| *p,
                 );
                 *layers = *new_layers;
+                *group = new_group.map(|group| group.0);
 
                 let rb = new_collider_of.and_then(|collider_of| rbs.get(collider_of.body).ok());
                 let is_static = rb.is_some_and(|(body, _)| body.is_static());
@@ -276,7 +300,8 @@ fn update_aabb_intervals(
             } else {
                 false
             }
-        });
+        },
+    );
 }
 
 type AabbIntervalQueryData = (
@@ -284,6 +309,7 @@ type AabbIntervalQueryData = (
     Option<Read<ColliderOf>>,
     Read<ColliderAabb>,
     Read<CollisionLayers>,
+    Option<Read<SelfCollisionGroup>>,
     Has<Sensor>,
     Has<CollisionEventsEnabled>,
     Option<Read<ActiveCollisionHooks>>,
@@ -299,7 +325,7 @@ fn add_new_aabb_intervals(
     mut intervals: ResMut<AabbIntervals>,
 ) {
     let aabbs = added_aabbs.iter().map(
-        |(entity, collider_of, aabb, layers, is_sensor, events_enabled, hooks)| {
+        |(entity, collider_of, aabb, layers, group, is_sensor, events_enabled, hooks)| {
             let flags =
                 init_aabb_interval_flags(collider_of, &rbs, is_sensor, events_enabled, hooks);
             (
@@ -307,6 +333,7 @@ fn add_new_aabb_intervals(
                 collider_of.map_or(ColliderOf { body: entity }, |p| *p),
                 *aabb,
                 *layers,
+                group.map(|group| group.0),
                 flags,
             )
         },
@@ -384,8 +411,12 @@ fn sweep_and_prune<H: CollisionHooks>(
 
     // Find potential collisions by checking for AABB intersections along all axes.
     // TODO: Find pairs in parallel, but create contact pairs serially for determinism.
-    for (i, (entity1, collider_of1, aabb1, layers1, flags1)) in intervals.0.iter().enumerate() {
-        for (entity2, collider_of2, aabb2, layers2, flags2) in intervals.0.iter().skip(i + 1) {
+    for (i, (entity1, collider_of1, aabb1, layers1, group1, flags1)) in
+        intervals.0.iter().enumerate()
+    {
+        for (entity2, collider_of2, aabb2, layers2, group2, flags2) in
+            intervals.0.iter().skip(i + 1)
+        {
             // x doesn't intersect; check this first so we can discard as soon as possible.
             if aabb2.min.x > aabb1.max.x {
                 break;
@@ -402,13 +433,14 @@ fn sweep_and_prune<H: CollisionHooks>(
                 continue;
             }
 
-            // No collisions between bodies that haven't moved or colliders with incompatible layers
-            // or colliders attached to the same rigid body.
+            // No collisions between bodies that haven't moved, colliders with incompatible layers,
+            // colliders attached to the same rigid body, or colliders in the same self-collision group.
             if flags1
                 .intersection(*flags2)
                 .contains(AabbIntervalFlags::IS_INACTIVE)
                 || !layers1.interacts_with(*layers2)
                 || collider_of1 == collider_of2
+                || (group1.is_some() && group1 == group2)
             {
                 continue;
             }
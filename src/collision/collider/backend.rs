@@ -62,9 +62,13 @@ use mass_properties::{MassPropertySystems, components::RecomputeMassProperties};
 /// Assuming you have implemented the required traits correctly,
 /// it should now work with the rest of the engine just like normal [`Collider`]s!
 ///
-/// **Note**: [Spatial queries](spatial_query) are not supported for custom colliders yet.
+/// To support [spatial queries](spatial_query) for the collider, implement [`QueryCollider`]
+/// and add the [`SpatialQueryBackendPlugin`]. To render the collider with the `PhysicsDebugPlugin`,
+/// implement `DebugRenderCollider` and use `ColliderBackendPlugin::with_debug_render`.
 pub struct ColliderBackendPlugin<C: ScalableCollider> {
     schedule: Interned<dyn ScheduleLabel>,
+    #[cfg(feature = "debug-plugin")]
+    debug_render: Option<fn(&mut App)>,
     _phantom: PhantomData<C>,
 }
 
@@ -75,15 +79,30 @@ impl<C: ScalableCollider> ColliderBackendPlugin<C> {
     pub fn new(schedule: impl ScheduleLabel) -> Self {
         Self {
             schedule: schedule.intern(),
+            #[cfg(feature = "debug-plugin")]
+            debug_render: None,
             _phantom: PhantomData,
         }
     }
 }
 
+#[cfg(feature = "debug-plugin")]
+impl<C: ScalableCollider + DebugRenderCollider> ColliderBackendPlugin<C> {
+    /// Enables debug rendering for colliders of type `C` using their [`DebugRenderCollider`] implementation.
+    ///
+    /// The colliders are only rendered if the [`PhysicsDebugPlugin`] has been added.
+    pub fn with_debug_render(mut self) -> Self {
+        self.debug_render = Some(crate::debug_render::add_collider_debug_render::<C>);
+        self
+    }
+}
+
 impl<C: ScalableCollider> Default for ColliderBackendPlugin<C> {
     fn default() -> Self {
         Self {
             schedule: FixedPostUpdate.intern(),
+            #[cfg(feature = "debug-plugin")]
+            debug_render: None,
             _phantom: PhantomData,
         }
     }
@@ -278,6 +297,17 @@ impl<C: ScalableCollider> Plugin for ColliderBackendPlugin<C> {
             ),
         );
     }
+
+    #[cfg_attr(not(feature = "debug-plugin"), allow(unused_variables))]
+    fn finish(&self, app: &mut App) {
+        // Enable debug rendering for the collider type.
+        #[cfg(feature = "debug-plugin")]
+        if let Some(add_debug_render) = self.debug_render
+            && app.is_plugin_added::<PhysicsDebugPlugin>()
+        {
+            add_debug_render(app);
+        }
+    }
 }
 
 /// A marker component for colliders. Inserted and removed automatically.
@@ -291,16 +321,20 @@ pub struct ColliderMarker;
 ///
 /// If a [`ColliderConstructor`] requires a mesh, the system keeps running
 /// until the mesh associated with the mesh handle is available.
+/// Likewise, if it requires an image, the system waits until the image of the `Sprite` is available.
 ///
 /// # Panics
 ///
-/// Panics if the [`ColliderConstructor`] requires a mesh but no mesh handle is found.
+/// Panics if the [`ColliderConstructor`] requires a mesh but no mesh handle is found,
+/// or if it requires an image but no `Sprite` is found.
 #[cfg(feature = "default-collider")]
 fn init_collider_constructors(
     mut commands: Commands,
     #[cfg(feature = "collider-from-mesh")] meshes: Res<Assets<Mesh>>,
     #[cfg(feature = "collider-from-mesh")] mesh_handles: Query<&Mesh3d>,
     #[cfg(feature = "collider-from-mesh")] mut collider_cache: Option<ResMut<ColliderCache>>,
+    #[cfg(feature = "collider-from-image")] images: Res<Assets<Image>>,
+    #[cfg(feature = "collider-from-image")] sprites: Query<&Sprite>,
     constructors: Query<(
         Entity,
         Option<&Collider>,
@@ -335,7 +369,21 @@ fn init_collider_constructors(
             Collider::try_from_constructor(constructor.clone(), None)
         };
 
-        #[cfg(not(feature = "collider-from-mesh"))]
+        #[cfg(all(feature = "collider-from-image", not(feature = "collider-from-mesh")))]
+        let collider = if constructor.requires_image() {
+            let sprite = sprites.get(entity).unwrap_or_else(|_| panic!(
+                "Tried to add a collider to entity {name} via {constructor:#?} that requires an image, \
+                but no sprite was found"));
+            let Some(image) = images.get(&sprite.image) else {
+                // Image required, but not loaded yet
+                continue;
+            };
+            Collider::try_from_constructor_with_sprite(constructor.clone(), sprite, image)
+        } else {
+            Collider::try_from_constructor(constructor.clone())
+        };
+
+        #[cfg(not(any(feature = "collider-from-mesh", feature = "collider-from-image")))]
         let collider = Collider::try_from_constructor(constructor.clone());
 
         if let Some(collider) = collider {
@@ -354,12 +402,15 @@ fn init_collider_constructors(
 /// Generates [`Collider`]s for descendants of entities with the [`ColliderConstructorHierarchy`] component.
 ///
 /// If an entity has a `SceneInstance`, its collider hierarchy is only generated once the scene is ready.
+/// If the hierarchy requires images, it is only generated once the images of all descendant `Sprite`s have loaded.
 #[cfg(feature = "default-collider")]
 fn init_collider_constructor_hierarchies(
     mut commands: Commands,
     #[cfg(feature = "collider-from-mesh")] meshes: Res<Assets<Mesh>>,
     #[cfg(feature = "collider-from-mesh")] mesh_handles: Query<&Mesh3d>,
     #[cfg(feature = "collider-from-mesh")] mut collider_cache: Option<ResMut<ColliderCache>>,
+    #[cfg(feature = "collider-from-image")] images: Res<Assets<Image>>,
+    #[cfg(feature = "collider-from-image")] sprites: Query<&Sprite>,
     #[cfg(feature = "bevy_scene")] scene_spawner: Res<SceneSpawner>,
     #[cfg(feature = "bevy_scene")] scenes: Query<&SceneRoot>,
     #[cfg(feature = "bevy_scene")] scene_instances: Query<&SceneInstance>,
@@ -385,6 +436,34 @@ fn init_collider_constructor_hierarchies(
             }
         }
 
+        #[cfg(feature = "collider-from-image")]
+        {
+            let requires_image = collider_constructor_hierarchy
+                .default_constructor
+                .as_ref()
+                .is_some_and(ColliderConstructor::requires_image)
+                || collider_constructor_hierarchy
+                    .config
+                    .values()
+                    .any(|config| {
+                        config
+                            .as_ref()
+                            .and_then(|config| config.constructor.as_ref())
+                            .is_some_and(ColliderConstructor::requires_image)
+                    });
+
+            if requires_image
+                && children.iter_descendants(scene_entity).any(|entity| {
+                    sprites
+                        .get(entity)
+                        .is_ok_and(|sprite| !images.contains(&sprite.image))
+                })
+            {
+                // Wait for the images of the sprites to load
+                continue;
+            }
+        }
+
         for child_entity in children.iter_descendants(scene_entity) {
             let Ok((name, existing_collider)) = child_query.get(child_entity) else {
                 continue;
@@ -450,7 +529,22 @@ fn init_collider_constructor_hierarchies(
                 Collider::try_from_constructor(constructor.clone(), None)
             };
 
-            #[cfg(not(feature = "collider-from-mesh"))]
+            #[cfg(all(feature = "collider-from-image", not(feature = "collider-from-mesh")))]
+            let collider = if constructor.requires_image() {
+                let Ok(sprite) = sprites.get(child_entity) else {
+                    // This child entity does not have a sprite, so we skip it.
+                    continue;
+                };
+                let Some(image) = images.get(&sprite.image) else {
+                    // Image required, but not loaded yet
+                    continue;
+                };
+                Collider::try_from_constructor_with_sprite(constructor, sprite, image)
+            } else {
+                Collider::try_from_constructor(constructor)
+            };
+
+            #[cfg(not(any(feature = "collider-from-mesh", feature = "collider-from-image")))]
             let collider = Collider::try_from_constructor(constructor);
 
             if let Some(collider) = collider {
@@ -8,6 +8,12 @@ use itertools::Either;
 /// The type of the generated collider can be specified using [`ColliderConstructor`].
 /// This supports computing the shape dynamically from the mesh, in which case only the descendants
 /// with a [`Mesh`] will have colliders generated.
+#[cfg_attr(
+    feature = "collider-from-image",
+    doc = "Similarly, shapes can be traced from the images of sprites with [`ColliderConstructor::FromImage`],
+in which case only the descendants with a [`Sprite`] will have colliders generated,
+and collider generation waits until the images of all of the sprites have loaded."
+)]
 ///
 /// In contrast to [`ColliderConstructor`], this component will *not* generate a collider on its own entity.
 ///
@@ -260,6 +266,10 @@ pub struct ColliderConstructorHierarchyConfig {
 
 /// A component that will automatically generate a [`Collider`] at runtime using [`Collider::try_from_constructor`].
 /// Enabling the `collider-from-mesh` feature activates support for computing the shape dynamically from the mesh attached to the same entity.
+#[cfg_attr(
+    feature = "collider-from-image",
+    doc = "Enabling the `collider-from-image` feature activates support for tracing the shape from the image of the [`Sprite`] attached to the same entity."
+)]
 ///
 /// Since [`Collider`] is not [`Reflect`], you can use this type to statically specify a collider's shape instead.
 ///
@@ -273,6 +283,10 @@ pub struct ColliderConstructorHierarchyConfig {
 ///
 /// The system handling the generation of colliders will panic if the specified [`ColliderConstructor`]
 /// requires a mesh, but the entity does not have a `Handle<Mesh>` component.
+#[cfg_attr(
+    feature = "collider-from-image",
+    doc = "Likewise, it will panic if the [`ColliderConstructor`] requires an image, but the entity does not have a [`Sprite`]."
+)]
 ///
 /// # Example
 ///
@@ -322,6 +336,13 @@ pub enum ColliderConstructor {
         half_width: Scalar,
         half_height: Scalar,
     },
+    /// Constructs a collider with [`Collider::ellipsoid`].
+    #[cfg(feature = "3d")]
+    Ellipsoid {
+        half_x: Scalar,
+        half_y: Scalar,
+        half_z: Scalar,
+    },
     /// Constructs a collider with [`Collider::rectangle`].
     #[cfg(feature = "2d")]
     Rectangle { x_length: Scalar, y_length: Scalar },
@@ -481,6 +502,13 @@ pub enum ColliderConstructor {
         voxel_size: Scalar,
         fill_mode: FillMode,
     },
+    /// Constructs a collider with [`Collider::from_sprite`] from the `Sprite` on the same entity.
+    #[cfg(feature = "collider-from-image")]
+    FromImage {
+        alpha_threshold: f32,
+        simplification: Scalar,
+        mode: ImageColliderMode,
+    },
     /// Constructs a collider with [`Collider::compound`].
     Compound(Vec<(Position, Rotation, ColliderConstructor)>),
 }
@@ -500,6 +528,12 @@ impl ColliderConstructor {
         )
     }
 
+    /// Returns `true` if the collider type requires the image of a `Sprite` to be generated.
+    #[cfg(feature = "collider-from-image")]
+    pub fn requires_image(&self) -> bool {
+        matches!(self, Self::FromImage { .. })
+    }
+
     /// Construct a [`ColliderConstructor::Compound`] from arbitrary [`Position`] and [`Rotation`] representations.
     pub fn compound<P, R>(shapes: Vec<(P, R, ColliderConstructor)>) -> Self
     where
@@ -432,6 +432,50 @@ impl Default for CollisionLayers {
     }
 }
 
+/// A component that disables collisions between colliders belonging to the same group.
+///
+/// The group is identified by an entity, typically the root of a composite object such as a ragdoll.
+/// Colliders in the same group never collide with each other, but still collide with other colliders
+/// based on their [`CollisionLayers`]. Unlike layers, this does not use up a layer for each object.
+///
+/// The group is only taken into account when contact pairs are created in the broad phase,
+/// so changing the group of a collider does not remove contacts that already exist.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+/// # use bevy::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     let vehicle = commands.spawn_empty().id();
+///
+///     // The parts of the vehicle don't collide with each other.
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::rectangle(4.0, 1.0), SelfCollisionGroup(vehicle)));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::cuboid(4.0, 1.0, 2.0), SelfCollisionGroup(vehicle)));"
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::circle(0.5), SelfCollisionGroup(vehicle)));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::sphere(0.5), SelfCollisionGroup(vehicle)));"
+)]
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct SelfCollisionGroup(#[entities] pub Entity);
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
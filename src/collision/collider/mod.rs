@@ -1,5 +1,7 @@
 //! Components, traits, and plugins related to collider functionality.
 
+use core::any::TypeId;
+
 use crate::prelude::*;
 use bevy::{
     ecs::{
@@ -254,6 +256,17 @@ pub trait AnyCollider: Component<Mutability = Mutable> + ComputeMassProperties {
         manifolds: &mut Vec<ContactManifold>,
         context: ContactManifoldContext<Self::Context>,
     );
+
+    /// Returns the [`TypeId`] of the shape of the collider.
+    ///
+    /// This is used for looking up specialized contact algorithms
+    /// registered in the [`ContactDispatcher`] for pairs of shape types.
+    ///
+    /// By default, this is the type of the collider itself. Collider types that can represent
+    /// several kinds of shapes should return the type of the underlying shape.
+    fn shape_type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
 }
 
 /// A simplified wrapper around [`AnyCollider`] that doesn't require passing in the context for
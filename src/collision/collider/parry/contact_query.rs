@@ -183,9 +183,55 @@ pub fn contact_manifolds(
         &mut None,
     );
 
+    // Fix contact normals against internal edges of triangle meshes, heightfields, and voxels.
+    super::internal_edges::correct_internal_edges(
+        collider1,
+        collider2,
+        &isometry12,
+        &mut new_manifolds,
+    );
+
     // Clear the old manifolds.
     manifolds.clear();
 
+    // Converts a contact in the local space of the first collider into a `ContactPoint`.
+    let contact_point = |local_point1: Vector, normal: Vector, distance: Scalar| {
+        // The contact point is the midpoint of the two points in world space.
+        // The anchors are relative to the positions of the colliders.
+        let point1 = rotation1 * local_point1;
+        let anchor1 = point1 + normal * distance * 0.5;
+        let anchor2 = anchor1 + (position1.0 - position2.0);
+        let world_point = position1.0 + anchor1;
+        ContactPoint::new(anchor1, anchor2, world_point, -distance)
+    };
+
+    // Compute contacts between signed distance fields and convex shapes.
+    if result.is_err()
+        && let Some(sdf_manifolds) = super::sdf::contact_manifolds_sdf(
+            &isometry12,
+            collider1.shape_scaled().0.as_ref(),
+            collider2.shape_scaled().0.as_ref(),
+            prediction_distance,
+        )
+    {
+        manifolds.extend(sdf_manifolds.into_iter().filter_map(|manifold| {
+            let normal = rotation1 * manifold.normal;
+
+            // Make sure the normal is valid
+            if !normal.is_normalized() {
+                return None;
+            }
+
+            let points = manifold
+                .points
+                .iter()
+                .map(|contact| contact_point(contact.point1, normal, contact.distance));
+
+            Some(ContactManifold::new(points, normal))
+        }));
+        return;
+    }
+
     // Fall back to support map contacts for unsupported (custom) shapes.
     if result.is_err()
         && let (Some(shape1), Some(shape2)) = (
@@ -206,20 +252,7 @@ pub fn contact_manifolds(
             return;
         }
 
-        let local_point1: Vector = contact.point1.into();
-
-        // The contact point is the midpoint of the two points in world space.
-        // The anchors are relative to the positions of the colliders.
-        let point1 = rotation1 * local_point1;
-        let anchor1 = point1 + normal * contact.dist * 0.5;
-        let anchor2 = anchor1 + (position1.0 - position2.0);
-        let world_point = position1.0 + anchor1;
-        let points = [ContactPoint::new(
-            anchor1,
-            anchor2,
-            world_point,
-            -contact.dist,
-        )];
+        let points = [contact_point(contact.point1.into(), normal, contact.dist)];
 
         manifolds.push(ContactManifold::new(points, normal));
     }
@@ -244,13 +277,8 @@ pub fn contact_manifolds(
         }
 
         let points = manifold.contacts().iter().map(|contact| {
-            // The contact point is the midpoint of the two points in world space.
-            // The anchors are relative to the positions of the colliders.
-            let point1 = rotation1 * Vector::from(subpos1.transform_point(&contact.local_p1));
-            let anchor1 = point1 + normal * contact.dist * 0.5;
-            let anchor2 = anchor1 + (position1.0 - position2.0);
-            let world_point = position1.0 + anchor1;
-            ContactPoint::new(anchor1, anchor2, world_point, -contact.dist)
+            let local_point1 = Vector::from(subpos1.transform_point(&contact.local_p1));
+            contact_point(local_point1, normal, contact.dist)
                 .with_feature_ids(contact.fid1.into(), contact.fid2.into())
         });
 
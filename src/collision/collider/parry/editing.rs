@@ -0,0 +1,237 @@
+//! Handles side effects of editing [`Collider`] shapes in place.
+//!
+//! See [`ColliderEditingPlugin`].
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// A plugin for handling side effects of editing [`Collider`] shapes in place
+/// with methods like [`Collider::set_voxel`] and [`Collider::set_heights_in_region`].
+///
+/// - Wakes up sleeping bodies touching the edited regions of colliders.
+pub struct ColliderEditingPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl ColliderEditingPlugin {
+    /// Creates a [`ColliderEditingPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `FixedPostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for ColliderEditingPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: FixedPostUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for ColliderEditingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            wake_on_collider_edits.in_set(PhysicsSystems::Prepare),
+        );
+    }
+}
+
+/// Wakes up sleeping bodies touching regions of colliders that were edited in place
+/// with methods like [`Collider::set_voxel`] and [`Collider::set_heights_in_region`].
+fn wake_on_collider_edits(
+    mut commands: Commands,
+    mut colliders: Query<(Entity, &mut Collider, &Position, &Rotation), Changed<Collider>>,
+    aabbs: Query<&ColliderAabb>,
+    sleeping: Query<(), With<Sleeping>>,
+    contact_graph: Res<ContactGraph>,
+) {
+    for (entity, mut collider, position, rotation) in &mut colliders {
+        // Taking the edited region should not trigger change detection.
+        let Some(region) = collider.bypass_change_detection().take_edited_region() else {
+            continue;
+        };
+
+        // Transform the local region to world space.
+        let region = crate::parry::bounding_volume::Aabb::new(region.min.into(), region.max.into())
+            .transform_by(&crate::make_isometry(*position, *rotation));
+        let region = ColliderAabb::from_min_max(region.mins.into(), region.maxs.into());
+
+        for edge in contact_graph.contact_edges_with(entity) {
+            let (other_collider, other_body) = if edge.collider1 == entity {
+                (edge.collider2, edge.body2)
+            } else {
+                (edge.collider1, edge.body1)
+            };
+
+            let Some(other_body) = other_body else {
+                continue;
+            };
+
+            if sleeping.contains(other_body)
+                && aabbs
+                    .get(other_collider)
+                    .is_ok_and(|aabb| aabb.intersects(&region))
+            {
+                commands.queue(WakeBody(other_body));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::create_test_app;
+
+    fn create_app() -> App {
+        let mut app = create_test_app(());
+        app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+        app
+    }
+
+    /// Spawns a static ground collider and a box that falls on it,
+    /// and steps the app until the box is sleeping.
+    fn spawn_sleeping_box(app: &mut App, ground: Collider) -> (Entity, Entity) {
+        let ground = app.world_mut().spawn((RigidBody::Static, ground)).id();
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Position(Vector::Y * 2.0),
+            ))
+            .id();
+
+        for _ in 0..600 {
+            app.update();
+            if app.world().entity(body).contains::<Sleeping>() {
+                break;
+            }
+        }
+
+        assert!(app.world().entity(body).contains::<Sleeping>());
+
+        (ground, body)
+    }
+
+    /// Steps the app and returns how far the body fell.
+    fn fall_distance(app: &mut App, body: Entity, steps: usize) -> Scalar {
+        let start = app.world().get::<Position>(body).unwrap().y;
+        for _ in 0..steps {
+            app.update();
+        }
+        start - app.world().get::<Position>(body).unwrap().y
+    }
+
+    #[test]
+    fn set_voxel_wakes_resting_bodies() {
+        let mut app = create_app();
+
+        // A floor of voxels below the origin.
+        #[cfg(feature = "2d")]
+        let coordinates: Vec<IVector> = (-5..=5).map(|x| IVector::new(x, -1)).collect();
+        #[cfg(feature = "3d")]
+        let coordinates: Vec<IVector> = (-5..=5)
+            .flat_map(|x| (-5..=5).map(move |z| IVector::new(x, -1, z)))
+            .collect();
+
+        let (ground, body) =
+            spawn_sleeping_box(&mut app, Collider::voxels(Vector::ONE, &coordinates));
+
+        let before = app
+            .world()
+            .get::<Collider>(ground)
+            .unwrap()
+            .aabb(Vector::ZERO, Rotation::default());
+
+        // Remove the voxels below the box.
+        let mut collider = app.world_mut().get_mut::<Collider>(ground).unwrap();
+        for coordinate in &coordinates {
+            let near_origin = coordinate.x.abs() <= 2;
+            #[cfg(feature = "3d")]
+            let near_origin = near_origin && coordinate.z.abs() <= 2;
+            if near_origin {
+                assert!(collider.set_voxel(*coordinate, false));
+            }
+        }
+
+        // Fill a voxel far away from the box, which extends the shape.
+        #[cfg(feature = "2d")]
+        let far_away = IVector::new(10, -1);
+        #[cfg(feature = "3d")]
+        let far_away = IVector::new(10, -1, 0);
+        assert!(collider.set_voxel(far_away, true));
+
+        let after = collider.aabb(Vector::ZERO, Rotation::default());
+        assert!(after.max.x > before.max.x);
+
+        app.update();
+
+        assert!(!app.world().entity(body).contains::<Sleeping>());
+
+        // The box falls through the hole.
+        assert!(fall_distance(&mut app, body, 60) > 1.0);
+    }
+
+    #[test]
+    fn set_heights_in_region_wakes_resting_bodies() {
+        let mut app = create_app();
+
+        // A flat heightfield with subdivision points at every integer coordinate from -5 to 5.
+        #[cfg(feature = "2d")]
+        let heightfield = Collider::heightfield(vec![0.0; 11], Vector::new(10.0, 1.0));
+        #[cfg(feature = "3d")]
+        let heightfield =
+            Collider::heightfield(vec![vec![0.0; 11]; 11], Vector::new(10.0, 1.0, 10.0));
+
+        let (ground, body) = spawn_sleeping_box(&mut app, heightfield);
+
+        // Dig a pit below the box.
+        let mut collider = app.world_mut().get_mut::<Collider>(ground).unwrap();
+        #[cfg(feature = "2d")]
+        {
+            assert!(!collider.set_heights_in_region(10, &[-3.0; 3]));
+            assert!(collider.set_heights_in_region(3, &[-3.0; 5]));
+
+            let heights = collider.shape().as_heightfield().unwrap().heights();
+            assert_eq!(heights[2], 0.0);
+            assert_eq!(heights[5], -3.0);
+            assert_eq!(heights[8], 0.0);
+        }
+        #[cfg(feature = "3d")]
+        {
+            assert!(!collider.set_heights_in_region(10, 10, &[vec![-3.0; 3]]));
+            assert!(collider.set_heights_in_region(3, 3, &vec![vec![-3.0; 5]; 5]));
+
+            let heightfield = collider.shape().as_heightfield().unwrap();
+            assert_eq!(heightfield.heights()[(2, 2)], 0.0);
+            assert_eq!(heightfield.heights()[(5, 5)], -3.0);
+            assert_eq!(heightfield.heights()[(8, 8)], 0.0);
+
+            // The flags of the heightfield are kept.
+            assert!(
+                heightfield
+                    .flags()
+                    .contains(crate::parry::shape::HeightFieldFlags::FIX_INTERNAL_EDGES)
+            );
+        }
+
+        app.update();
+
+        assert!(!app.world().entity(body).contains::<Sleeping>());
+
+        // The box falls into the pit.
+        assert!(fall_distance(&mut app, body, 60) > 1.0);
+    }
+}
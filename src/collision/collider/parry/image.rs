@@ -0,0 +1,342 @@
+//! Collider generation from the opaque regions of [`Image`]s.
+
+use crate::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Determines the kind of [`Collider`] generated from the opaque regions of an [`Image`].
+///
+/// See [`Collider::from_image`] and [`ColliderConstructor::FromImage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq, Hash)]
+pub enum ImageColliderMode {
+    /// A [polyline](Collider::polyline) following the outlines of the opaque regions.
+    ///
+    /// The collider is hollow, which makes it best suited for static level geometry.
+    Polyline,
+    /// A [convex decomposition](Collider::convex_decomposition) of the opaque regions.
+    ///
+    /// The collider is solid, which makes it suitable for dynamic bodies.
+    #[default]
+    ConvexDecomposition,
+    /// A [voxel](Collider::voxels) collider with one voxel per opaque pixel.
+    ///
+    /// The outlines are not traced, so the simplification tolerance has no effect.
+    /// If the width or height of the image is odd, the voxels are offset by half a pixel
+    /// along that axis to align them with the voxel grid.
+    Voxels,
+}
+
+/// Creates a collider from the pixels of the `image` with an alpha value greater than `alpha_threshold`.
+///
+/// The collider is centered on the image, and each pixel is scaled by `scale`.
+/// Negative scale components mirror the collider along the corresponding axis.
+pub(crate) fn collider_from_image(
+    image: &Image,
+    alpha_threshold: f32,
+    simplification: Scalar,
+    mode: ImageColliderMode,
+    scale: Vector,
+) -> Option<Collider> {
+    if scale.x == 0.0 || scale.y == 0.0 {
+        return None;
+    }
+
+    let mask = AlphaMask::from_image(image, alpha_threshold)?;
+    let half_size = Vector::new(mask.width as Scalar, mask.height as Scalar) * 0.5;
+
+    // Pixel coordinates have the origin at the top left corner of the image and the y-axis pointing down.
+    let to_local =
+        |point: Vector| Vector::new(point.x - half_size.x, half_size.y - point.y) * scale;
+
+    match mode {
+        ImageColliderMode::Polyline => {
+            let (vertices, indices) = mask.outlines(simplification, to_local)?;
+            Some(Collider::polyline(vertices, Some(indices)))
+        }
+        ImageColliderMode::ConvexDecomposition => {
+            let (vertices, indices) = mask.outlines(simplification, to_local)?;
+            Some(Collider::convex_decomposition(vertices, indices))
+        }
+        ImageColliderMode::Voxels => {
+            let pixel_centers: Vec<Vector> = mask
+                .opaque_pixels()
+                .map(|pixel| to_local(pixel + 0.5))
+                .collect();
+            (!pixel_centers.is_empty())
+                .then(|| Collider::voxels_from_points(scale.abs(), &pixel_centers))
+        }
+    }
+}
+
+/// The opacity of each pixel in an image.
+struct AlphaMask {
+    width: u32,
+    height: u32,
+    opaque: Vec<bool>,
+}
+
+impl AlphaMask {
+    /// Reads the alpha values of the `image`.
+    ///
+    /// Returns `None` if the image is empty or its data cannot be accessed,
+    /// for example because it only exists on the GPU.
+    fn from_image(image: &Image, alpha_threshold: f32) -> Option<Self> {
+        let (width, height) = (image.width(), image.height());
+
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let mut opaque = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let color = image.get_color_at(x, y).ok()?;
+                opaque.push(color.alpha() > alpha_threshold);
+            }
+        }
+
+        Some(Self {
+            width,
+            height,
+            opaque,
+        })
+    }
+
+    /// Returns `true` if the pixel at the given coordinates is opaque.
+    /// Pixels outside of the image are transparent.
+    fn is_opaque(&self, x: i32, y: i32) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as u32) < self.width
+            && (y as u32) < self.height
+            && self.opaque[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Returns the coordinates of the top left corners of the opaque pixels.
+    fn opaque_pixels(&self) -> impl Iterator<Item = Vector> + '_ {
+        let width = self.width as usize;
+        self.opaque
+            .iter()
+            .enumerate()
+            .filter(|(_, opaque)| **opaque)
+            .map(move |(i, _)| Vector::new((i % width) as Scalar, (i / width) as Scalar))
+    }
+
+    /// Traces the outlines of the opaque regions, simplifies them, and maps them to local space with `to_local`.
+    ///
+    /// The outlines are returned as vertex and index buffers for polylines consisting of closed loops.
+    /// Returns `None` if there are no opaque regions.
+    fn outlines(
+        &self,
+        simplification: Scalar,
+        to_local: impl Fn(Vector) -> Vector,
+    ) -> Option<(Vec<Vector>, Vec<[u32; 2]>)> {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for contour in self.contours() {
+            let contour = simplify_contour(&contour, simplification);
+
+            if contour.len() < 3 {
+                continue;
+            }
+
+            let start = vertices.len() as u32;
+            let len = contour.len() as u32;
+            vertices.extend(contour.into_iter().map(&to_local));
+            indices.extend((0..len).map(|i| [start + i, start + (i + 1) % len]));
+        }
+
+        (!vertices.is_empty()).then_some((vertices, indices))
+    }
+
+    /// Traces the outlines of the opaque regions using marching squares.
+    ///
+    /// The grid used for marching squares has its corners at the pixel centers,
+    /// and is padded by one transparent pixel on each side so that every contour is closed.
+    /// The returned contours are closed loops in pixel coordinates.
+    fn contours(&self) -> Vec<Vec<Vector>> {
+        let (width, height) = (self.width as i32, self.height as i32);
+
+        // Contour points lie at the midpoints of cell edges. Each point is identified by the sum
+        // of the pixel coordinates at the ends of its edge, which is unique for each edge.
+        // Segments are stored as a map from their start point to their end point.
+        let mut segments = HashMap::<IVec2, IVec2>::default();
+        let mut starts = Vec::new();
+
+        for y in -1..height {
+            for x in -1..width {
+                // The corners of the cell in clockwise order, starting from the top left.
+                let corners = [
+                    IVec2::new(x, y),
+                    IVec2::new(x + 1, y),
+                    IVec2::new(x + 1, y + 1),
+                    IVec2::new(x, y + 1),
+                ];
+                let opaque = corners.map(|corner| self.is_opaque(corner.x, corner.y));
+
+                for i in 0..4 {
+                    // Segments start at edges that go from transparent to opaque in clockwise order,
+                    // and end at the next edge that goes from opaque to transparent. Neighboring cells
+                    // traverse shared edges in opposite directions, so each point is the start of
+                    // exactly one segment and the end of exactly one segment.
+                    if opaque[i] || !opaque[(i + 1) % 4] {
+                        continue;
+                    }
+
+                    let mut j = (i + 1) % 4;
+                    while !opaque[j] || opaque[(j + 1) % 4] {
+                        j = (j + 1) % 4;
+                    }
+
+                    let start = corners[i] + corners[(i + 1) % 4];
+                    let end = corners[j] + corners[(j + 1) % 4];
+                    segments.insert(start, end);
+                    starts.push(start);
+                }
+            }
+        }
+
+        // Link the segments into closed loops. The starts are traversed in the order
+        // in which they were found to keep the output deterministic.
+        let mut contours = Vec::new();
+        for start in starts {
+            let mut contour = Vec::new();
+            let mut current = start;
+
+            while let Some(next) = segments.remove(&current) {
+                contour.push(Vector::new(
+                    current.x as Scalar * 0.5 + 0.5,
+                    current.y as Scalar * 0.5 + 0.5,
+                ));
+                current = next;
+            }
+
+            if !contour.is_empty() {
+                contours.push(contour);
+            }
+        }
+
+        contours
+    }
+}
+
+/// Simplifies the given closed `contour` using the Douglas-Peucker algorithm,
+/// removing points that are within `tolerance` of the simplified contour.
+fn simplify_contour(contour: &[Vector], tolerance: Scalar) -> Vec<Vector> {
+    let len = contour.len();
+
+    if len < 3 {
+        return contour.to_vec();
+    }
+
+    // Split the loop into two open polylines at the point furthest from the first point.
+    // Indices wrap around, so the second polyline ends at the first point.
+    let first = contour[0];
+    let split = (1..len)
+        .max_by(|&a, &b| {
+            first
+                .distance_squared(contour[a])
+                .total_cmp(&first.distance_squared(contour[b]))
+        })
+        .unwrap_or(1);
+
+    let mut keep = vec![false; len];
+    keep[0] = true;
+    keep[split] = true;
+
+    let mut stack = vec![(0, split), (split, len)];
+    while let Some((start, end)) = stack.pop() {
+        let (a, b) = (contour[start], contour[end % len]);
+        let furthest = (start + 1..end)
+            .map(|i| (i, distance_to_segment(contour[i], a, b)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((i, distance)) = furthest
+            && distance > tolerance
+        {
+            keep[i] = true;
+            stack.push((start, i));
+            stack.push((i, end));
+        }
+    }
+
+    contour
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(*point))
+        .collect()
+}
+
+/// Computes the distance from the `point` to the line segment between `a` and `b`.
+fn distance_to_segment(point: Vector, a: Vector, b: Vector) -> Scalar {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    let t = if length_squared > 0.0 {
+        ((point - a).dot(ab) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance(a + t * ab)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask_from_rows(rows: &[&str]) -> AlphaMask {
+        AlphaMask {
+            width: rows[0].len() as u32,
+            height: rows.len() as u32,
+            opaque: rows
+                .iter()
+                .flat_map(|row| row.chars().map(|c| c == '#'))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_trace_image_contours() {
+        let mask = mask_from_rows(&[
+            "......", //
+            ".####.", //
+            ".####.", //
+            ".####.", //
+            ".####.", //
+            "......", //
+        ]);
+
+        let contours = mask.contours();
+        assert_eq!(contours.len(), 1);
+
+        // The square is traced through the pixel edges, with the corners cut diagonally.
+        // Simplification removes the collinear points along the edges.
+        let contour = simplify_contour(&contours[0], 0.0);
+        assert_eq!(contour.len(), 8);
+        for point in &contour {
+            assert!(point.cmpge(Vector::splat(1.0)).all());
+            assert!(point.cmple(Vector::splat(5.0)).all());
+        }
+
+        // Diagonally adjacent pixels form separate regions.
+        let mask = mask_from_rows(&[
+            "##..", //
+            "##..", //
+            "..##", //
+            "..##", //
+        ]);
+        assert_eq!(mask.contours().len(), 2);
+
+        // Holes have their own contours.
+        let mask = mask_from_rows(&[
+            "#####", //
+            "#####", //
+            "##.##", //
+            "#####", //
+            "#####", //
+        ]);
+        assert_eq!(mask.contours().len(), 2);
+    }
+}
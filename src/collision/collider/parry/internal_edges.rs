@@ -0,0 +1,444 @@
+//! Internal edge correction for mesh-like colliders.
+//!
+//! Shapes sliding across a [polyline](Collider::polyline), [heightfield](Collider::heightfield),
+//! or [voxels](Collider::voxels) can catch on the vertices between adjacent segments or the edges between
+//! adjacent voxels, even if the surface is perfectly flat. This happens because each segment or voxel
+//! is handled separately, and a contact with a vertex or edge produces a normal that points away from
+//! the feature instead of away from the surface. These are often called *ghost collisions*.
+//!
+//! To fix this, contacts with a vertex of a segment are checked against the neighboring segments
+//! that share the vertex, found from the topology of the shape. If all of them are collinear or form
+//! a concave angle with the segment, the vertex is *internal*, and the contact normal is clamped
+//! to the normal of the segment. For voxels, an edge or vertex is internal if a neighboring voxel
+//! has a face contact with the same normal in another contact manifold of the same contact pair.
+//!
+//! 3D triangle meshes and heightfields are instead corrected by Parry using pseudo-normals
+//! if [`TrimeshFlags::FIX_INTERNAL_EDGES`] is set, which is the default for [`Collider::trimesh`]
+//! and [`Collider::heightfield`].
+
+use crate::prelude::*;
+use parry::{
+    math::Isometry,
+    query::ContactManifold as ParryContactManifold,
+    shape::{Shape, TypedShape},
+};
+
+/// The kind of a mesh-like shape, determining how internal edges are detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MeshKind {
+    /// A shape made out of segments, like a polyline or heightfield.
+    #[cfg(feature = "2d")]
+    Segments,
+    /// A shape made out of axis-aligned cuboids.
+    Voxels,
+}
+
+impl MeshKind {
+    fn from_shape(shape: &dyn Shape) -> Option<Self> {
+        match shape.as_typed_shape() {
+            #[cfg(feature = "2d")]
+            TypedShape::Polyline(_) | TypedShape::HeightField(_) => Some(Self::Segments),
+            TypedShape::Voxels(_) => Some(Self::Voxels),
+            _ => None,
+        }
+    }
+}
+
+/// A segment of a polyline or heightfield.
+#[cfg(feature = "2d")]
+type Segment = [Vector; 2];
+
+/// Returns the segment with the given subshape index.
+#[cfg(feature = "2d")]
+fn segment(shape: &dyn Shape, index: usize) -> Option<Segment> {
+    match shape.as_typed_shape() {
+        TypedShape::Polyline(polyline) => (index < polyline.indices().len()).then(|| {
+            let segment = polyline.segment(index as u32);
+            [segment.a.into(), segment.b.into()]
+        }),
+        TypedShape::HeightField(heightfield) => heightfield
+            .segment_at(index)
+            .map(|segment| [segment.a.into(), segment.b.into()]),
+        _ => None,
+    }
+}
+
+/// Returns the segments sharing the given vertex of the segment with the given subshape index.
+///
+/// `vertex` is `0` for the first vertex and `1` for the second vertex of the segment.
+#[cfg(feature = "2d")]
+fn neighbor_segments(shape: &dyn Shape, index: usize, vertex: usize) -> Vec<Segment> {
+    match shape.as_typed_shape() {
+        TypedShape::Polyline(polyline) => {
+            let indices = polyline.indices();
+            let Some(vertex_index) = indices.get(index).map(|segment| segment[vertex]) else {
+                return Vec::new();
+            };
+            indices
+                .iter()
+                .enumerate()
+                .filter(|(i, other)| *i != index && other.contains(&vertex_index))
+                .filter_map(|(i, _)| segment(shape, i))
+                .collect()
+        }
+        TypedShape::HeightField(_) => {
+            // Heightfield segments are ordered from left to right,
+            // so the neighbors are the previous and next segments.
+            let neighbor = if vertex == 0 {
+                index.checked_sub(1)
+            } else {
+                index.checked_add(1)
+            };
+            neighbor
+                .and_then(|neighbor| segment(shape, neighbor))
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Contact manifold data expressed from the point of view of the mesh-like shape.
+struct MeshManifold {
+    /// The subshape index of the segment or voxel.
+    subshape: u32,
+    /// The isometry of the subshape relative to the mesh-like shape.
+    subshape_isometry: Isometry<Scalar>,
+    /// The contact normal pointing away from the mesh, in the local space of the subshape.
+    local_normal: Vector,
+}
+
+impl MeshManifold {
+    fn new(manifold: &ParryContactManifold<(), ()>, flipped: bool) -> Self {
+        let (subshape, subshape_pos, local_normal) = if flipped {
+            (
+                manifold.subshape2,
+                manifold.subshape_pos2,
+                manifold.local_n2,
+            )
+        } else {
+            (
+                manifold.subshape1,
+                manifold.subshape_pos1,
+                manifold.local_n1,
+            )
+        };
+        Self {
+            subshape,
+            subshape_isometry: subshape_pos.unwrap_or_default(),
+            local_normal: local_normal.into(),
+        }
+    }
+
+    /// Returns the contact normal in the local space of the mesh-like shape.
+    fn normal(&self) -> Vector {
+        self.subshape_isometry
+            .transform_vector(&self.local_normal.into())
+            .into()
+    }
+
+    /// Returns `true` if all contacts of the manifold are on faces of the mesh-like shape.
+    fn is_face_contact(manifold: &ParryContactManifold<(), ()>, flipped: bool) -> bool {
+        manifold.points.iter().all(|contact| {
+            let feature = PackedFeatureId::from(if flipped { contact.fid2 } else { contact.fid1 });
+            feature.is_face()
+        })
+    }
+}
+
+/// The cosine of the maximum angle at which a contact normal is considered to be aligned with a face normal.
+const NORMAL_ALIGNMENT_THRESHOLD: Scalar = 0.9999;
+
+/// Corrects the normals of contacts against internal edges and vertices of a mesh-like shape.
+///
+/// If either collider is a 2D [polyline](Collider::polyline) or [heightfield](Collider::heightfield),
+/// or [voxels](Collider::voxels), contacts with its internal vertices and edges are clamped to the normal
+/// of the face they belong to, and the contact points and distances are updated accordingly.
+/// If both colliders are mesh-like, only the first one is considered.
+///
+/// `isometry12` is the isometry of the second collider relative to the first collider.
+pub(crate) fn correct_internal_edges(
+    collider1: &Collider,
+    collider2: &Collider,
+    isometry12: &Isometry<Scalar>,
+    manifolds: &mut [ParryContactManifold<(), ()>],
+) {
+    let (mesh_shape, kind, flipped) =
+        if let Some(kind) = MeshKind::from_shape(collider1.shape_scaled().0.as_ref()) {
+            (collider1.shape_scaled().0.as_ref(), kind, false)
+        } else if let Some(kind) = MeshKind::from_shape(collider2.shape_scaled().0.as_ref()) {
+            (collider2.shape_scaled().0.as_ref(), kind, true)
+        } else {
+            return;
+        };
+
+    // Internal voxel edges can only be detected if there are neighboring manifolds.
+    if kind == MeshKind::Voxels && manifolds.len() < 2 {
+        return;
+    }
+
+    // The isometry of the other shape relative to the mesh-like shape.
+    let isometry = if flipped {
+        isometry12.inverse()
+    } else {
+        *isometry12
+    };
+
+    for i in 0..manifolds.len() {
+        let manifold = &manifolds[i];
+
+        if manifold.points.is_empty() || MeshManifold::is_face_contact(manifold, flipped) {
+            continue;
+        }
+
+        let mesh_manifold = MeshManifold::new(manifold, flipped);
+
+        let corrected_normal = match kind {
+            #[cfg(feature = "2d")]
+            MeshKind::Segments => {
+                internal_segment_normal(mesh_shape, &mesh_manifold, manifold, flipped)
+            }
+            MeshKind::Voxels => internal_voxel_normal(&mesh_manifold, manifolds, flipped),
+        };
+
+        if let Some(local_normal) = corrected_normal {
+            apply_normal(
+                &mut manifolds[i],
+                &mesh_manifold,
+                local_normal,
+                &isometry,
+                flipped,
+            );
+        }
+    }
+}
+
+/// Returns the segment normal to use for a manifold against a segment
+/// if all of its vertex contacts are on internal vertices.
+#[cfg(feature = "2d")]
+fn internal_segment_normal(
+    mesh_shape: &dyn Shape,
+    mesh_manifold: &MeshManifold,
+    manifold: &ParryContactManifold<(), ()>,
+    flipped: bool,
+) -> Option<Vector> {
+    let index = mesh_manifold.subshape as usize;
+    let segment = segment(mesh_shape, index)?;
+    let mut normal = (segment[1] - segment[0]).perp().try_normalize()?;
+    let contact_normal = mesh_manifold.normal();
+
+    // Orient the segment normal towards the other shape.
+    if normal.dot(contact_normal) < 0.0 {
+        normal = -normal;
+    }
+
+    if normal.dot(contact_normal) >= NORMAL_ALIGNMENT_THRESHOLD {
+        return None;
+    }
+
+    // The tolerance used for the convexity test, relative to the length of the segment.
+    let tolerance = segment[0].distance(segment[1]) * 1e-4;
+
+    for contact in &manifold.points {
+        let feature = PackedFeatureId::from(if flipped { contact.fid2 } else { contact.fid1 });
+
+        if feature.is_face() {
+            continue;
+        }
+
+        // Unknown features are treated as active.
+        let vertex = feature.code() as usize;
+        if !feature.is_vertex() || vertex > 1 {
+            return None;
+        }
+
+        let neighbors = neighbor_segments(mesh_shape, index, vertex);
+
+        // Vertices without neighbors are on the boundary of the mesh.
+        if neighbors.is_empty() {
+            return None;
+        }
+
+        // If a vertex of a neighbor is below the line of the segment, the vertex is convex
+        // and the contact normal is valid.
+        let is_convex = neighbors
+            .iter()
+            .flatten()
+            .any(|neighbor_vertex| (*neighbor_vertex - segment[vertex]).dot(normal) < -tolerance);
+
+        if is_convex {
+            return None;
+        }
+    }
+
+    // Segments are in the local space of the mesh-like shape, but the normal is stored relative to the subshape.
+    let local_normal = mesh_manifold
+        .subshape_isometry
+        .inverse_transform_vector(&normal.into());
+    Some(local_normal.into())
+}
+
+/// Returns the face normal to use for a manifold against a voxel
+/// if it has an edge or vertex contact on a surface that a neighboring voxel has a face contact with.
+fn internal_voxel_normal(
+    mesh_manifold: &MeshManifold,
+    manifolds: &[ParryContactManifold<(), ()>],
+    flipped: bool,
+) -> Option<Vector> {
+    // Voxels are axis-aligned, so the face normal is the closest axis.
+    let local_normal = mesh_manifold.local_normal;
+    let abs_normal = local_normal.abs();
+    let axis = abs_normal.max_element();
+    let face_normal = Vector::select(
+        abs_normal.cmpeq(Vector::splat(axis)),
+        local_normal.signum(),
+        Vector::ZERO,
+    );
+
+    if face_normal.length_squared() != 1.0
+        || face_normal.dot(local_normal) >= NORMAL_ALIGNMENT_THRESHOLD
+    {
+        return None;
+    }
+
+    let normal = mesh_manifold
+        .subshape_isometry
+        .transform_vector(&face_normal.into());
+    let normal: Vector = normal.into();
+
+    // The edge or vertex is internal if a neighboring voxel has a face contact with the same normal.
+    let is_internal = manifolds.iter().any(|other| {
+        if other.points.is_empty() || !MeshManifold::is_face_contact(other, flipped) {
+            return false;
+        }
+        let other = MeshManifold::new(other, flipped);
+        other.subshape != mesh_manifold.subshape
+            && other.normal().dot(normal) >= NORMAL_ALIGNMENT_THRESHOLD
+    });
+
+    is_internal.then_some(face_normal)
+}
+
+/// Replaces the normal of the manifold with the given normal in the local space of the mesh subshape,
+/// and projects the contact points on the mesh onto the plane of the face.
+fn apply_normal(
+    manifold: &mut ParryContactManifold<(), ()>,
+    mesh_manifold: &MeshManifold,
+    local_normal: Vector,
+    isometry: &Isometry<Scalar>,
+    flipped: bool,
+) {
+    let other_subshape_pos = if flipped {
+        manifold.subshape_pos1
+    } else {
+        manifold.subshape_pos2
+    };
+
+    // The isometry of the other subshape relative to the mesh subshape.
+    let other_to_mesh = mesh_manifold.subshape_isometry.inverse()
+        * *isometry
+        * other_subshape_pos.unwrap_or_default();
+
+    let mesh_normal: parry::math::Vector<Scalar> = local_normal.into();
+    let other_normal = -other_to_mesh.inverse_transform_vector(&mesh_normal);
+
+    for contact in manifold.points.iter_mut() {
+        let (mesh_point, other_point) = if flipped {
+            (&mut contact.local_p2, contact.local_p1)
+        } else {
+            (&mut contact.local_p1, contact.local_p2)
+        };
+
+        // The mesh point lies on the face, so the distance is measured along the face normal.
+        let other_point = other_to_mesh.transform_point(&other_point);
+        let distance = (other_point - *mesh_point).dot(&mesh_normal);
+
+        *mesh_point = other_point - mesh_normal * distance;
+        contact.dist = distance;
+    }
+
+    if flipped {
+        manifold.local_n2 = mesh_normal;
+        manifold.local_n1 = other_normal;
+    } else {
+        manifold.local_n1 = mesh_normal;
+        manifold.local_n2 = other_normal;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, tests::create_test_app};
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    fn create_app() -> App {
+        let mut app = create_test_app(());
+        app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+        app
+    }
+
+    #[test]
+    fn box_slides_across_coplanar_faces() {
+        let mut app = create_app();
+
+        // Two collinear segments (in 2D) or two coplanar triangles (in 3D) sharing a vertex or edge at x = 0.
+        #[cfg(feature = "2d")]
+        let ground = Collider::polyline(
+            vec![
+                Vector::new(-10.0, 0.0),
+                Vector::new(0.0, 0.0),
+                Vector::new(10.0, 0.0),
+            ],
+            None,
+        );
+        #[cfg(feature = "3d")]
+        let ground = Collider::trimesh(
+            vec![
+                Vector::new(-10.0, 0.0, -10.0),
+                Vector::new(10.0, 0.0, -10.0),
+                Vector::new(10.0, 0.0, 10.0),
+                Vector::new(-10.0, 0.0, 10.0),
+            ],
+            vec![[0, 2, 1], [0, 3, 2]],
+        );
+
+        app.world_mut().spawn((RigidBody::Static, ground));
+
+        let speed = 4.0;
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Position(Vector::Y * 0.5 - Vector::X * 2.0),
+                LinearVelocity(Vector::X * speed),
+                Friction::ZERO,
+            ))
+            .id();
+
+        // The box crosses the shared edge after half a second.
+        for _ in 0..60 {
+            app.update();
+
+            let body_ref = app.world().entity(body);
+            let velocity = body_ref.get::<LinearVelocity>().unwrap().0;
+            let angular_velocity = body_ref.get::<AngularVelocity>().unwrap().0;
+
+            // The box should keep sliding without bumping up or slowing down.
+            assert_relative_eq!(velocity.x, speed, epsilon = 1e-2);
+            assert!(velocity.y.abs() < 0.05, "vertical velocity {}", velocity.y);
+            #[cfg(feature = "2d")]
+            assert!(angular_velocity.abs() < 1e-2);
+            #[cfg(feature = "3d")]
+            assert!(angular_velocity.length() < 1e-2);
+        }
+
+        let position = app.world().get::<Position>(body).unwrap().0;
+        assert!(position.x > 1.5);
+        assert_relative_eq!(position.y, 0.5, epsilon = 2e-2);
+    }
+}
@@ -2,23 +2,36 @@
 
 pub mod contact_query;
 
+mod editing;
+#[cfg(feature = "collider-from-image")]
+mod image;
+mod internal_edges;
+mod sdf;
+
 #[cfg(feature = "2d")]
 mod primitives2d;
 #[cfg(feature = "3d")]
 mod primitives3d;
 
+pub use editing::ColliderEditingPlugin;
+#[cfg(feature = "collider-from-image")]
+pub use image::ImageColliderMode;
 #[cfg(feature = "2d")]
 pub use primitives2d::{EllipseColliderShape, RegularPolygonColliderShape};
+#[cfg(feature = "3d")]
+pub use primitives3d::{CapsuleHullColliderShape, EllipsoidColliderShape};
+pub use sdf::{SdfColliderShape, SdfGrid};
 
 use crate::{make_isometry, prelude::*};
 #[cfg(feature = "collider-from-mesh")]
 use bevy::mesh::{Indices, VertexAttributeValues};
 use bevy::{log, prelude::*};
 use contact_query::UnsupportedShape;
+use core::any::{Any, TypeId};
 use itertools::Either;
 use parry::{
     math::Point,
-    shape::{RoundShape, SharedShape, TypedShape, Voxels},
+    shape::{Compound, HeightField, RoundShape, Shape, SharedShape, TypedShape, Voxels},
 };
 
 impl<T: IntoCollider<Collider>> From<T> for Collider {
@@ -208,6 +221,9 @@ bitflags::bitflags! {
         ///
         /// This is achieved by taking into account adjacent triangle normals when computing contact
         /// points for a given triangle.
+        ///
+        /// This flag is used by default for [`Collider::trimesh`] in 3D.
+        /// See [internal edge correction](Collider#internal-edge-correction) for more information.
         const FIX_INTERNAL_EDGES = 0b1000_0000 | Self::ORIENTED.bits() | Self::MERGE_DUPLICATE_VERTICES.bits();
     }
 }
@@ -321,6 +337,10 @@ pub type TrimeshBuilderError = parry::shape::TriMeshBuilderError;
 /// }
 /// ```
 ///
+/// Compound colliders can also be edited after creation with [`Collider::compound_push`],
+/// [`Collider::compound_remove`], and [`Collider::compound_set_transform`], which share the shapes
+/// of the unchanged parts instead of cloning them.
+///
 /// Colliders can be arbitrarily nested and transformed relative to the parent.
 /// The rigid body that a collider is attached to can be accessed using the [`ColliderOf`] component.
 ///
@@ -328,6 +348,29 @@ pub type TrimeshBuilderError = parry::shape::TriMeshBuilderError;
 /// [friction](Friction), [restitution](Restitution), [collision layers](CollisionLayers),
 /// and other configuration options, and they send separate [collision events](crate::collision#collision-events).
 ///
+/// ## Internal Edge Correction
+///
+/// Shapes sliding across [triangle meshes](Collider::trimesh), [polylines](Collider::polyline),
+/// [heightfields](Collider::heightfield), or [voxels](Collider::voxels) can catch on the edges between
+/// adjacent triangles, segments, or voxels, even if the surface is flat. These are often called *ghost collisions*.
+///
+/// To prevent this, contacts against *internal* edges and vertices, which are shared with coplanar
+/// or concave neighbors, are corrected. The normals of these contacts are clamped to the normal
+/// of the face they belong to, so that the surface behaves as if it was smooth.
+///
+/// In 3D, triangle meshes are corrected by Parry using the pseudo-normals of the mesh if
+/// [`TrimeshFlags::FIX_INTERNAL_EDGES`] is set. The flag is set by default for [`Collider::trimesh`],
+/// and it can be left out using [`Collider::trimesh_with_config`].
+#[cfg_attr(
+    feature = "2d",
+    doc = "Polylines, heightfields, and voxels are always corrected in the narrow phase using the topology of the shape."
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "Heightfields created with [`Collider::heightfield`] are corrected by Parry in the same way,
+and voxels are always corrected in the narrow phase."
+)]
+///
 /// # See More
 ///
 /// - [Rigid bodies](RigidBody)
@@ -374,15 +417,28 @@ pub struct Collider {
     scaled_shape: SharedShape,
     /// The global scale used for the collider shape.
     scale: Vector,
+    /// The local region of the scaled shape that was edited since the last time
+    /// bodies touching it were woken up.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    edited_region: Option<ColliderAabb>,
+    /// The cached mass properties of the scaled shape with a density of `1.0`, if it is a compound shape.
+    ///
+    /// This avoids summing the mass properties of all parts every time they are queried.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    compound_mass_properties: Option<parry::mass_properties::MassProperties>,
 }
 
 impl From<SharedShape> for Collider {
     fn from(value: SharedShape) -> Self {
-        Self {
+        let mut collider = Self {
             shape: value.clone(),
             scaled_shape: value,
             scale: Vector::ONE,
-        }
+            edited_region: None,
+            compound_mass_properties: None,
+        };
+        collider.update_compound_mass_properties();
+        collider
     }
 }
 
@@ -439,6 +495,67 @@ impl AnyCollider for Collider {
             manifolds,
         )
     }
+
+    fn shape_type_id(&self) -> TypeId {
+        // Use the type of the Parry shape so that specialized contact algorithms
+        // can be registered for pairs of shape types in the `ContactDispatcher`.
+        <dyn Shape as Any>::type_id(self.shape_scaled().0.as_ref())
+    }
+}
+
+impl ContactDispatcher<Collider> {
+    /// Registers a function that computes [`ContactManifold`]s between [`Collider`]s
+    /// whose Parry shapes are of types `S1` and `S2`.
+    ///
+    /// The shapes passed to the function have the [scale](Collider::scale) of the colliders applied.
+    /// If a function has already been registered for the pair, it is replaced.
+    ///
+    /// See [`ContactDispatcher`] for more information.
+    #[allow(clippy::type_complexity)]
+    pub fn register_shapes<S1: Shape, S2: Shape>(
+        &mut self,
+        function: impl Fn(
+            &S1,
+            Vector,
+            Rotation,
+            &S2,
+            Vector,
+            Rotation,
+            Scalar,
+            &mut Vec<ContactManifold>,
+        ) + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.register::<S1, S2>(
+            move |collider1,
+                  position1,
+                  rotation1,
+                  collider2,
+                  position2,
+                  rotation2,
+                  prediction_distance,
+                  manifolds| {
+                let (Some(shape1), Some(shape2)) = (
+                    collider1.shape_scaled().as_shape::<S1>(),
+                    collider2.shape_scaled().as_shape::<S2>(),
+                ) else {
+                    manifolds.clear();
+                    return;
+                };
+                function(
+                    shape1,
+                    position1,
+                    rotation1,
+                    shape2,
+                    position2,
+                    rotation2,
+                    prediction_distance,
+                    manifolds,
+                );
+            },
+        )
+    }
 }
 
 // TODO: `bevy_heavy` supports computing the individual mass properties efficiently for Bevy's primitive shapes,
@@ -446,7 +563,7 @@ impl AnyCollider for Collider {
 #[cfg(feature = "2d")]
 impl ComputeMassProperties for Collider {
     fn mass(&self, density: f32) -> f32 {
-        let props = self.shape_scaled().mass_properties(density as Scalar);
+        let props = self.parry_mass_properties(density as Scalar);
         props.mass() as f32
     }
 
@@ -455,17 +572,17 @@ impl ComputeMassProperties for Collider {
     }
 
     fn angular_inertia(&self, mass: f32) -> f32 {
-        let props = self.shape_scaled().mass_properties(mass as Scalar);
+        let props = self.parry_mass_properties(mass as Scalar);
         props.principal_inertia() as f32
     }
 
     fn center_of_mass(&self) -> Vec2 {
-        let props = self.shape_scaled().mass_properties(1.0);
+        let props = self.parry_mass_properties(1.0);
         Vector::from(props.local_com).f32()
     }
 
     fn mass_properties(&self, density: f32) -> MassProperties {
-        let props = self.shape_scaled().mass_properties(density as Scalar);
+        let props = self.parry_mass_properties(density as Scalar);
 
         MassProperties {
             mass: props.mass() as f32,
@@ -483,7 +600,7 @@ impl ComputeMassProperties for Collider {
 #[cfg(feature = "3d")]
 impl ComputeMassProperties for Collider {
     fn mass(&self, density: f32) -> f32 {
-        let props = self.shape_scaled().mass_properties(density as Scalar);
+        let props = self.parry_mass_properties(density as Scalar);
         props.mass() as f32
     }
 
@@ -492,22 +609,22 @@ impl ComputeMassProperties for Collider {
     }
 
     fn principal_angular_inertia(&self, mass: f32) -> Vec3 {
-        let props = self.shape_scaled().mass_properties(mass as Scalar);
+        let props = self.parry_mass_properties(mass as Scalar);
         Vector::from(props.principal_inertia()).f32()
     }
 
     fn local_inertial_frame(&self) -> Quat {
-        let props = self.shape_scaled().mass_properties(1.0);
+        let props = self.parry_mass_properties(1.0);
         Quaternion::from(props.principal_inertia_local_frame).f32()
     }
 
     fn center_of_mass(&self) -> Vec3 {
-        let props = self.shape_scaled().mass_properties(1.0);
+        let props = self.parry_mass_properties(1.0);
         Vector::from(props.local_com).f32()
     }
 
     fn mass_properties(&self, density: f32) -> MassProperties {
-        let props = self.shape_scaled().mass_properties(density as Scalar);
+        let props = self.parry_mass_properties(density as Scalar);
 
         MassProperties {
             mass: props.mass() as f32,
@@ -532,6 +649,148 @@ impl ScalableCollider for Collider {
     }
 }
 
+impl QueryCollider for Collider {
+    fn query_aabb(&self, position: Vector, rotation: Rotation) -> ColliderAabb {
+        self.aabb(position, rotation)
+    }
+
+    fn cast_ray(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        ray_origin: Vector,
+        ray_direction: Vector,
+        max_distance: Scalar,
+        solid: bool,
+    ) -> Option<(Scalar, Vector)> {
+        Collider::cast_ray(
+            self,
+            position,
+            rotation,
+            ray_origin,
+            ray_direction,
+            max_distance,
+            solid,
+        )
+    }
+
+    fn project_point(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        point: Vector,
+        solid: bool,
+    ) -> (Vector, bool) {
+        Collider::project_point(self, position, rotation, point, solid)
+    }
+
+    fn contains_point(&self, position: Vector, rotation: Rotation, point: Vector) -> bool {
+        Collider::contains_point(self, position, rotation, point)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn cast_shape(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        shape: &Self,
+        shape_origin: Vector,
+        shape_rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+    ) -> Option<ShapeHitData> {
+        let isometry1 = make_isometry(position, rotation);
+        let isometry2 = make_isometry(shape_origin, shape_rotation);
+
+        let hit = parry::query::cast_shapes(
+            &isometry1,
+            &Vector::ZERO.into(),
+            self.shape_scaled().0.as_ref(),
+            &isometry2,
+            &direction.adjust_precision().into(),
+            shape.shape_scaled().0.as_ref(),
+            parry::query::ShapeCastOptions {
+                max_time_of_impact: config.max_distance,
+                target_distance: config.target_distance,
+                stop_at_penetration: !config.ignore_origin_penetration,
+                compute_impact_geometry_on_penetration: config.compute_contact_on_penetration,
+            },
+        )
+        .ok()??;
+
+        // The witnesses are computed in the local space of each shape, at the time of impact.
+        let shape_position = shape_origin + direction.adjust_precision() * hit.time_of_impact;
+
+        Some(ShapeHitData {
+            entity: Entity::PLACEHOLDER,
+            distance: hit.time_of_impact,
+            point1: position + rotation * Vector::from(hit.witness1),
+            point2: shape_position + shape_rotation * Vector::from(hit.witness2),
+            normal1: rotation * Vector::from(hit.normal1),
+            normal2: shape_rotation * Vector::from(hit.normal2),
+        })
+    }
+
+    fn intersects_shape(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        shape: &Self,
+        shape_position: Vector,
+        shape_rotation: Rotation,
+    ) -> bool {
+        contact_query::intersection_test(
+            self,
+            position,
+            rotation,
+            shape,
+            shape_position,
+            shape_rotation,
+        )
+        .is_ok_and(|intersects| intersects)
+    }
+
+    // The pipeline methods use parry's best-first traversal, which visits the closest nodes first
+    // and supports all shape types through the query dispatcher.
+    fn pipeline_cast_ray(
+        pipeline: &SpatialQueryPipeline<Self>,
+        origin: Vector,
+        direction: Dir,
+        max_distance: Scalar,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<RayHitData> {
+        pipeline.cast_ray_best_first(origin, direction, max_distance, solid, filter, predicate)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn pipeline_cast_shape(
+        pipeline: &SpatialQueryPipeline<Self>,
+        shape: &Self,
+        origin: Vector,
+        rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<ShapeHitData> {
+        pipeline.cast_shape_best_first(
+            shape, origin, rotation, direction, config, filter, predicate,
+        )
+    }
+
+    fn pipeline_project_point(
+        pipeline: &SpatialQueryPipeline<Self>,
+        point: Vector,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<PointProjection> {
+        pipeline.project_point_best_first(point, solid, filter, predicate)
+    }
+}
+
 impl Collider {
     /// Returns the raw unscaled shape of the collider.
     pub fn shape(&self) -> &SharedShape {
@@ -558,6 +817,8 @@ impl Collider {
         } else {
             log::error!("Failed to create convex hull for scaled collider.");
         }
+
+        self.update_compound_mass_properties();
     }
 
     /// Returns the global scale of the collider.
@@ -571,7 +832,8 @@ impl Collider {
     /// represented as a supported shape, the shape is approximated as
     /// a convex polygon or polyhedron using `num_subdivisions`.
     ///
-    /// For example, if a ball was scaled to an ellipse, the new shape would be approximated.
+    /// Balls are scaled exactly into ellipses in 2D and ellipsoids in 3D.
+    /// In 3D, capsules are also scaled exactly into swept ellipsoids.
     pub fn set_scale(&mut self, scale: Vector, num_subdivisions: u32) {
         if scale == self.scale {
             return;
@@ -581,15 +843,14 @@ impl Collider {
             // Trivial case.
             self.scaled_shape = self.shape.clone();
             self.scale = Vector::ONE;
-            return;
-        }
-
-        if let Ok(scaled) = scale_shape(&self.shape, scale, num_subdivisions) {
+        } else if let Ok(scaled) = scale_shape(&self.shape, scale, num_subdivisions) {
             self.scaled_shape = scaled;
             self.scale = scale;
         } else {
             log::error!("Failed to create convex hull for scaled collider.");
         }
+
+        self.update_compound_mass_properties();
     }
 
     /// Projects the given `point` onto `self` transformed by `translation` and `rotation`.
@@ -720,6 +981,142 @@ impl Collider {
         SharedShape::compound(shapes).into()
     }
 
+    /// Adds a shape to a [compound](Collider::compound) collider with the given position and rotation
+    /// relative to the collider, returning the index of the new shape.
+    ///
+    /// The shapes of the other parts are shared instead of being cloned, and the mass properties
+    /// of the collider are recomputed from its parts.
+    ///
+    /// Returns `None` if the collider is not a compound.
+    pub fn compound_push(
+        &mut self,
+        position: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+        collider: impl Into<Collider>,
+    ) -> Option<usize> {
+        let isometry = make_isometry(*position.into(), rotation.into());
+        let shape = collider.into().shape_scaled().clone();
+
+        let mut index = None;
+        self.edit_compound(|shapes| {
+            index = Some(shapes.len());
+            shapes.push((isometry, shape));
+            true
+        });
+        index
+    }
+
+    /// Removes the shape at the given index from a [compound](Collider::compound) collider,
+    /// returning it as a collider. The indices of the shapes after it are shifted down by one.
+    ///
+    /// The shapes of the other parts are shared instead of being cloned, and the mass properties
+    /// of the collider are recomputed from its parts.
+    ///
+    /// Returns `None` if the collider is not a compound, if the index is out of bounds,
+    /// or if the shape is the last shape of the compound.
+    pub fn compound_remove(&mut self, index: usize) -> Option<Collider> {
+        let mut removed_shape = None;
+        self.edit_compound(|shapes| {
+            if index >= shapes.len() || shapes.len() == 1 {
+                return false;
+            }
+            removed_shape = Some(shapes.remove(index).1);
+            true
+        });
+        removed_shape.map(Collider::from)
+    }
+
+    /// Sets the position and rotation of the shape at the given index in a [compound](Collider::compound) collider,
+    /// relative to the collider.
+    ///
+    /// The shapes of the other parts are shared instead of being cloned, and the mass properties
+    /// of the collider are recomputed from its parts.
+    ///
+    /// Returns `false` if the collider is not a compound, or if the index is out of bounds.
+    pub fn compound_set_transform(
+        &mut self,
+        index: usize,
+        position: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+    ) -> bool {
+        let isometry = make_isometry(*position.into(), rotation.into());
+        self.edit_compound(|shapes| {
+            let Some((shape_isometry, _)) = shapes.get_mut(index) else {
+                return false;
+            };
+            *shape_isometry = isometry;
+            true
+        })
+    }
+
+    /// Edits the shapes of a compound collider with `edit`, and recomputes the scaled shape
+    /// and the cached mass properties from the edited parts.
+    ///
+    /// `edit` should return `false` if the compound could not be edited.
+    fn edit_compound(
+        &mut self,
+        edit: impl FnOnce(&mut Vec<(parry::math::Isometry<Scalar>, SharedShape)>) -> bool,
+    ) -> bool {
+        let Some(compound) = self.shape.as_shape::<Compound>() else {
+            return false;
+        };
+
+        // Cloning the parts only clones the reference-counted shapes.
+        let mut shapes = compound.shapes().to_vec();
+        if !edit(&mut shapes) {
+            return false;
+        }
+
+        // Parry's `Compound` doesn't expose its BVH mutably, so it is rebuilt from the AABBs of the parts.
+        self.shape = SharedShape::compound(shapes);
+
+        if self.scale == Vector::ONE {
+            self.scaled_shape = self.shape.clone();
+        } else if let Ok(scaled) = scale_shape(&self.shape, self.scale, 10) {
+            self.scaled_shape = scaled;
+        } else {
+            log::error!("Failed to create convex hull for scaled collider.");
+        }
+
+        self.update_compound_mass_properties();
+        true
+    }
+
+    /// Recomputes the cached mass properties of the scaled shape if it is a compound shape.
+    fn update_compound_mass_properties(&mut self) {
+        self.compound_mass_properties = self
+            .scaled_shape
+            .as_shape::<Compound>()
+            .map(|compound| compound.mass_properties(1.0));
+    }
+
+    /// Computes the mass properties of the scaled shape with the given density,
+    /// using the cached mass properties for compound shapes.
+    fn parry_mass_properties(&self, density: Scalar) -> parry::mass_properties::MassProperties {
+        let Some(unit_mass_properties) = self.compound_mass_properties else {
+            return self.shape_scaled().mass_properties(density);
+        };
+
+        // Mass and angular inertia scale linearly with density.
+        #[cfg(feature = "2d")]
+        {
+            parry::mass_properties::MassProperties::new(
+                unit_mass_properties.local_com,
+                unit_mass_properties.mass() * density,
+                unit_mass_properties.principal_inertia() * density,
+            )
+        }
+        #[cfg(feature = "3d")]
+        {
+            parry::mass_properties::MassProperties::with_principal_inertia_frame(
+                unit_mass_properties.local_com,
+                unit_mass_properties.mass() * density,
+                unit_mass_properties.principal_inertia() * density,
+                unit_mass_properties.principal_inertia_local_frame,
+            )
+        }
+    }
+
     /// Creates a collider with a circle shape defined by its radius.
     #[cfg(feature = "2d")]
     pub fn circle(radius: Scalar) -> Self {
@@ -742,6 +1139,16 @@ impl Collider {
         .into()
     }
 
+    /// Creates a collider with an ellipsoid shape defined by its half-extents
+    /// along the local `X`, `Y`, and `Z` axes.
+    #[cfg(feature = "3d")]
+    pub fn ellipsoid(half_x: Scalar, half_y: Scalar, half_z: Scalar) -> Self {
+        SharedShape::new(EllipsoidColliderShape::new(Vector::new(
+            half_x, half_y, half_z,
+        )))
+        .into()
+    }
+
     /// Creates a collider with a rectangle shape defined by its extents.
     #[cfg(feature = "2d")]
     pub fn rectangle(x_length: Scalar, y_length: Scalar) -> Self {
@@ -874,6 +1281,12 @@ impl Collider {
     /// The [`CollisionMargin`] component can be used to add thickness to the shape if needed.
     /// For thin shapes like triangle meshes, it can help improve collision stability and performance.
     ///
+    #[cfg_attr(
+        feature = "3d",
+        doc = "[`TrimeshFlags::FIX_INTERNAL_EDGES`] is used to prevent shapes from catching on the edges between triangles.
+See [internal edge correction](Collider#internal-edge-correction) for more information."
+    )]
+    ///
     /// # Panics
     ///
     /// Panics if the given vertex and index buffers do not contain any triangles,
@@ -891,6 +1304,12 @@ impl Collider {
     /// The [`CollisionMargin`] component can be used to add thickness to the shape if needed.
     /// For thin shapes like triangle meshes, it can help improve collision stability and performance.
     ///
+    #[cfg_attr(
+        feature = "3d",
+        doc = "[`TrimeshFlags::FIX_INTERNAL_EDGES`] is used to prevent shapes from catching on the edges between triangles.
+See [internal edge correction](Collider#internal-edge-correction) for more information."
+    )]
+    ///
     /// # Errors
     ///
     /// Returns a [`TrimeshBuilderError`] if the given vertex and index buffers do not contain any triangles,
@@ -900,7 +1319,15 @@ impl Collider {
         indices: Vec<[u32; 3]>,
     ) -> Result<Self, TrimeshBuilderError> {
         let vertices = vertices.into_iter().map(|v| v.into()).collect();
-        SharedShape::trimesh(vertices, indices).map(|trimesh| trimesh.into())
+        #[cfg(feature = "2d")]
+        let trimesh = SharedShape::trimesh(vertices, indices);
+        #[cfg(feature = "3d")]
+        let trimesh = SharedShape::trimesh_with_flags(
+            vertices,
+            indices,
+            TrimeshFlags::FIX_INTERNAL_EDGES.into(),
+        );
+        trimesh.map(|trimesh| trimesh.into())
     }
 
     /// Creates a collider with a triangle mesh shape defined by its vertex and index buffers
@@ -1017,6 +1444,32 @@ impl Collider {
         SharedShape::convex_polyline(points).map(Into::into)
     }
 
+    /// Creates a collider with a signed distance field (SDF) shape defined by the given distance function.
+    ///
+    /// The function should return the signed distance from the given local point to the surface of the shape,
+    /// negative inside of the shape and positive outside of it. The shape is limited to the region between `min` and `max`.
+    ///
+    /// SDF colliders support contacts against convex shapes, ray casts, point queries, and mass properties.
+    /// See [`SdfColliderShape`] for more details.
+    ///
+    /// To avoid evaluating an expensive function at runtime, consider sampling it into an [`SdfGrid`]
+    /// and using [`Collider::sdf_grid`] instead.
+    pub fn sdf(
+        min: Vector,
+        max: Vector,
+        distance: impl Fn(Vector) -> Scalar + Send + Sync + 'static,
+    ) -> Self {
+        SharedShape::new(SdfColliderShape::from_fn(min, max, distance)).into()
+    }
+
+    /// Creates a collider with a signed distance field (SDF) shape interpolated from the given [`SdfGrid`].
+    ///
+    /// SDF colliders support contacts against convex shapes, ray casts, point queries, and mass properties.
+    /// See [`SdfColliderShape`] for more details.
+    pub fn sdf_grid(grid: SdfGrid) -> Self {
+        SharedShape::new(SdfColliderShape::from_grid(grid)).into()
+    }
+
     /// Creates a collider shape made of voxels.
     ///
     /// Each voxel has the size `voxel_size` and grid coordinate given by `grid_coordinates`.
@@ -1163,6 +1616,12 @@ impl Collider {
     /// subdivisions along the `Z` axis.
     ///
     /// `scale` controls the scaling factor along each axis.
+    ///
+    /// The heightfield uses [`HeightFieldFlags::FIX_INTERNAL_EDGES`] to prevent shapes from catching
+    /// on the edges between triangles. See [internal edge correction](Collider#internal-edge-correction)
+    /// for more information.
+    ///
+    /// [`HeightFieldFlags::FIX_INTERNAL_EDGES`]: parry::shape::HeightFieldFlags::FIX_INTERNAL_EDGES
     #[cfg(feature = "3d")]
     pub fn heightfield(heights: Vec<Vec<Scalar>>, scale: Vector) -> Self {
         let row_count = heights.len();
@@ -1176,7 +1635,212 @@ impl Collider {
         );
 
         let heights = nalgebra::DMatrix::from_vec(row_count, column_count, data);
-        SharedShape::heightfield(heights, scale.into()).into()
+        SharedShape::heightfield_with_flags(
+            heights,
+            scale.into(),
+            parry::shape::HeightFieldFlags::FIX_INTERNAL_EDGES,
+        )
+        .into()
+    }
+
+    /// Sets whether the voxel at the given grid coordinate is filled,
+    /// if the collider is a [voxel collider](Collider::voxels).
+    ///
+    /// The shape is edited in place, so only the data of the edited voxel and its neighbors is updated
+    /// instead of rebuilding the whole shape. Existing contact pairs are preserved,
+    /// and sleeping bodies touching the voxel are woken up.
+    ///
+    /// Returns `false` if the collider is not a voxel collider.
+    pub fn set_voxel(&mut self, grid_coordinate: IVector, is_filled: bool) -> bool {
+        #[cfg(feature = "2d")]
+        let key = Point::new(grid_coordinate.x, grid_coordinate.y);
+        #[cfg(feature = "3d")]
+        let key = Point::new(grid_coordinate.x, grid_coordinate.y, grid_coordinate.z);
+
+        let edited = self.edit_shape(|shape| {
+            let Some(voxels) = shape.as_shape_mut::<Voxels>() else {
+                return false;
+            };
+            voxels.set_voxel(key, is_filled);
+            true
+        });
+
+        if let Some(voxels) = edited
+            .then(|| self.scaled_shape.as_shape::<Voxels>())
+            .flatten()
+        {
+            let aabb = voxels.voxel_aabb(key);
+            self.mark_edited(ColliderAabb::from_min_max(
+                aabb.mins.into(),
+                aabb.maxs.into(),
+            ));
+        }
+
+        edited
+    }
+
+    /// Sets the heights of a contiguous region of subdivision points starting at the index `start`,
+    /// if the collider is a [heightfield](Collider::heightfield).
+    ///
+    /// Only the edited heights are written, and removed segments are kept. Heightfields are traversed
+    /// as a regular grid instead of a BVH, so there is no acceleration structure to refit.
+    /// Existing contact pairs are preserved, and sleeping bodies touching the edited region are woken up.
+    ///
+    /// Returns `false` if the collider is not a heightfield, or if the region is out of bounds.
+    #[cfg(feature = "2d")]
+    pub fn set_heights_in_region(&mut self, start: usize, heights: &[Scalar]) -> bool {
+        let edited = self.edit_shape(|shape| {
+            let Some(heightfield) = shape.as_shape_mut::<HeightField>() else {
+                return false;
+            };
+
+            let mut new_heights = heightfield.heights().clone();
+            if start + heights.len() > new_heights.len() {
+                return false;
+            }
+
+            new_heights
+                .rows_mut(start, heights.len())
+                .copy_from_slice(heights);
+
+            // Parry doesn't expose the heights mutably, so the patched heights are moved
+            // into a new heightfield, which only recomputes the vertical bounds of its AABB.
+            let mut patched = HeightField::new(new_heights, *heightfield.scale());
+            for i in 0..heightfield.num_cells() {
+                if heightfield.is_segment_removed(i) {
+                    patched.set_segment_removed(i, true);
+                }
+            }
+            *heightfield = patched;
+            true
+        });
+
+        if let Some(heightfield) = edited
+            .then(|| self.scaled_shape.as_shape::<HeightField>())
+            .flatten()
+        {
+            let aabb = self.scaled_shape.compute_local_aabb();
+            let count = heightfield.heights().len();
+            let width = heightfield.scale().x;
+
+            // The segments adjacent to the edited points are affected as well.
+            let min_x = heightfield_coordinate(start.saturating_sub(1), count, width);
+            let max_x = heightfield_coordinate(start + heights.len(), count, width);
+
+            self.mark_edited(ColliderAabb::from_min_max(
+                Vector::new(min_x, aabb.mins.y),
+                Vector::new(max_x, aabb.maxs.y),
+            ));
+        }
+
+        edited
+    }
+
+    /// Sets the heights of a rectangular region of subdivision points starting at the given row and column,
+    /// if the collider is a [heightfield](Collider::heightfield).
+    ///
+    /// `heights` is a matrix of heights with the same layout as the one given to [`Collider::heightfield`].
+    ///
+    /// Only the edited heights are written, and the flags and cell statuses of the heightfield are kept.
+    /// Heightfields are traversed as a regular grid instead of a BVH, so there is no acceleration structure
+    /// to refit. Existing contact pairs are preserved, and sleeping bodies touching the edited region are woken up.
+    ///
+    /// Returns `false` if the collider is not a heightfield, or if the region is out of bounds.
+    #[cfg(feature = "3d")]
+    pub fn set_heights_in_region(
+        &mut self,
+        start_row: usize,
+        start_column: usize,
+        heights: &[Vec<Scalar>],
+    ) -> bool {
+        let row_count = heights.len();
+        let column_count = heights.first().map_or(0, |row| row.len());
+
+        if heights.iter().any(|row| row.len() != column_count) {
+            return false;
+        }
+
+        let edited = self.edit_shape(|shape| {
+            let Some(heightfield) = shape.as_shape_mut::<HeightField>() else {
+                return false;
+            };
+
+            let mut new_heights = heightfield.heights().clone();
+            if start_row + row_count > new_heights.nrows()
+                || start_column + column_count > new_heights.ncols()
+            {
+                return false;
+            }
+
+            for (i, row) in heights.iter().enumerate() {
+                for (j, height) in row.iter().enumerate() {
+                    new_heights[(start_row + i, start_column + j)] = *height;
+                }
+            }
+
+            // Parry doesn't expose the heights mutably, so the patched heights are moved
+            // into a new heightfield, which only recomputes the vertical bounds of its AABB.
+            let mut patched =
+                HeightField::with_flags(new_heights, *heightfield.scale(), heightfield.flags());
+            patched
+                .cells_statuses_mut()
+                .copy_from(heightfield.cells_statuses());
+            *heightfield = patched;
+            true
+        });
+
+        if let Some(heightfield) = edited
+            .then(|| self.scaled_shape.as_shape::<HeightField>())
+            .flatten()
+        {
+            let aabb = self.scaled_shape.compute_local_aabb();
+            let scale = heightfield.scale();
+
+            // Rows are laid out along the `Z` axis and columns along the `X` axis.
+            // The cells adjacent to the edited points are affected as well.
+            let (nrows, ncols) = heightfield.heights().shape();
+            let min_x = heightfield_coordinate(start_column.saturating_sub(1), ncols, scale.x);
+            let max_x = heightfield_coordinate(start_column + column_count, ncols, scale.x);
+            let min_z = heightfield_coordinate(start_row.saturating_sub(1), nrows, scale.z);
+            let max_z = heightfield_coordinate(start_row + row_count, nrows, scale.z);
+
+            self.mark_edited(ColliderAabb::from_min_max(
+                Vector::new(min_x, aabb.mins.y, min_z),
+                Vector::new(max_x, aabb.maxs.y, max_z),
+            ));
+        }
+
+        edited
+    }
+
+    /// Edits the unscaled and scaled shapes in place with the given function.
+    ///
+    /// The function should return `false` if the shape could not be edited.
+    fn edit_shape(&mut self, mut edit: impl FnMut(&mut dyn Shape) -> bool) -> bool {
+        if self.scale == Vector::ONE {
+            // The scaled shape shares the unscaled shape. Release it so that
+            // the shape can be edited in place without cloning it.
+            self.scaled_shape = SharedShape::ball(0.0);
+            let edited = edit(self.shape.make_mut());
+            self.scaled_shape = self.shape.clone();
+            return edited;
+        }
+
+        edit(self.shape.make_mut()) && edit(self.scaled_shape.make_mut())
+    }
+
+    /// Adds the given local region of the scaled shape to the edited region.
+    fn mark_edited(&mut self, region: ColliderAabb) {
+        self.edited_region = Some(
+            self.edited_region
+                .map_or(region, |edited_region| edited_region.merged(region)),
+        );
+    }
+
+    /// Takes the local region of the scaled shape that was edited with methods like [`Collider::set_voxel`]
+    /// since the last call, leaving `None` in its place.
+    pub(crate) fn take_edited_region(&mut self) -> Option<ColliderAabb> {
+        self.edited_region.take()
     }
 
     /// Creates a collider with a triangle mesh shape from a `Mesh`.
@@ -1327,6 +1991,59 @@ impl Collider {
         })
     }
 
+    /// Creates a collider from the opaque regions of an [`Image`], such as the texture of a sprite.
+    ///
+    /// Pixels with an alpha value greater than `alpha_threshold` are considered opaque.
+    /// The outlines of the opaque regions are traced using marching squares and simplified
+    /// with the Douglas-Peucker algorithm, removing details smaller than `simplification` pixels.
+    /// The [`ImageColliderMode`] determines the kind of collider that is built from the outlines.
+    ///
+    /// The collider is centered on the image, with one unit per pixel and the y-axis pointing up.
+    /// This matches a `Sprite` that uses the image at its default size.
+    ///
+    /// Returns `None` if the image has no opaque pixels or its data cannot be accessed,
+    /// for example because it only exists on the GPU.
+    ///
+    /// This method is only available if the `collider-from-image` feature is enabled.
+    #[cfg(feature = "collider-from-image")]
+    pub fn from_image(
+        image: &Image,
+        alpha_threshold: f32,
+        simplification: Scalar,
+        mode: ImageColliderMode,
+    ) -> Option<Self> {
+        image::collider_from_image(image, alpha_threshold, simplification, mode, Vector::ONE)
+    }
+
+    /// Creates a collider from the opaque regions of the [`Image`] of a [`Sprite`].
+    ///
+    /// This works like [`Collider::from_image`], but also takes the [`custom_size`](Sprite::custom_size),
+    /// [`flip_x`](Sprite::flip_x), and [`flip_y`](Sprite::flip_y) of the sprite into account.
+    /// Texture atlases and [`rect`](Sprite::rect) are not supported, so the whole image is always used.
+    ///
+    /// This method is only available if the `collider-from-image` feature is enabled.
+    #[cfg(feature = "collider-from-image")]
+    pub fn from_sprite(
+        sprite: &Sprite,
+        image: &Image,
+        alpha_threshold: f32,
+        simplification: Scalar,
+        mode: ImageColliderMode,
+    ) -> Option<Self> {
+        let image_size = image.size_f32();
+        let size = sprite.custom_size.unwrap_or(image_size);
+        let mut scale = (size / image_size).adjust_precision();
+
+        if sprite.flip_x {
+            scale.x = -scale.x;
+        }
+        if sprite.flip_y {
+            scale.y = -scale.y;
+        }
+
+        image::collider_from_image(image, alpha_threshold, simplification, mode, scale)
+    }
+
     /// Attempts to create a collider with the given [`ColliderConstructor`].
     /// By using this, you can serialize and deserialize the collider's creation method
     /// separately from the collider itself via the [`ColliderConstructor`] enum.
@@ -1338,7 +2055,13 @@ impl Collider {
 - Creating the collider from the given [`ColliderConstructor`] failed."
     )]
     #[cfg_attr(
-        not(feature = "collider-from-mesh"),
+        feature = "collider-from-image",
+        doc = "Returns `None` in the following cases:
+- The given [`ColliderConstructor`] requires an image. Use [`Collider::try_from_constructor_with_sprite`] instead.
+- Creating the collider from the given [`ColliderConstructor`] failed."
+    )]
+    #[cfg_attr(
+        not(any(feature = "collider-from-mesh", feature = "collider-from-image")),
         doc = "Returns `None` if creating the collider from the given [`ColliderConstructor`] failed."
     )]
     pub fn try_from_constructor(
@@ -1355,6 +2078,12 @@ impl Collider {
                 half_width,
                 half_height,
             } => Some(Self::ellipse(half_width, half_height)),
+            #[cfg(feature = "3d")]
+            ColliderConstructor::Ellipsoid {
+                half_x,
+                half_y,
+                half_z,
+            } => Some(Self::ellipsoid(half_x, half_y, half_z)),
             #[cfg(feature = "2d")]
             ColliderConstructor::Rectangle { x_length, y_length } => {
                 Some(Self::rectangle(x_length, y_length))
@@ -1495,6 +2224,8 @@ impl Collider {
                 voxel_size,
                 fill_mode,
             } => Self::voxelized_trimesh_from_mesh(mesh?, voxel_size, fill_mode),
+            #[cfg(feature = "collider-from-image")]
+            ColliderConstructor::FromImage { .. } => None,
             ColliderConstructor::Compound(compound_constructors) => {
                 let shapes: Vec<_> =
                     ColliderConstructor::flatten_compound_constructors(compound_constructors)
@@ -1513,6 +2244,31 @@ impl Collider {
             }
         }
     }
+
+    /// Attempts to create a collider with the given [`ColliderConstructor`] for a [`Sprite`]
+    /// using the given [`Image`].
+    ///
+    /// Unlike [`Collider::try_from_constructor`], this also supports [`ColliderConstructor::FromImage`],
+    /// which creates the collider with [`Collider::from_sprite`].
+    ///
+    /// Returns `None` if creating the collider from the given [`ColliderConstructor`] failed.
+    ///
+    /// This method is only available if the `collider-from-image` feature is enabled.
+    #[cfg(feature = "collider-from-image")]
+    pub fn try_from_constructor_with_sprite(
+        collider_constructor: ColliderConstructor,
+        sprite: &Sprite,
+        image: &Image,
+    ) -> Option<Self> {
+        match collider_constructor {
+            ColliderConstructor::FromImage {
+                alpha_threshold,
+                simplification,
+                mode,
+            } => Self::from_sprite(sprite, image, alpha_threshold, simplification, mode),
+            collider_constructor => Self::try_from_constructor(collider_constructor),
+        }
+    }
 }
 
 #[cfg(feature = "collider-from-mesh")]
@@ -1548,6 +2304,14 @@ fn extract_mesh_vertices_indices(mesh: &Mesh) -> Option<VerticesIndices> {
     Some((vtx, idx))
 }
 
+/// Returns the local coordinate of the subdivision point at the given index along an axis
+/// of a heightfield with `count` points and the given extent along the axis.
+fn heightfield_coordinate(index: usize, count: usize, extent: Scalar) -> Scalar {
+    let index = index.min(count.saturating_sub(1));
+    let spacing = 1.0 / count.saturating_sub(1).max(1) as Scalar;
+    (-0.5 + index as Scalar * spacing) * extent
+}
+
 fn scale_shape(
     shape: &SharedShape,
     scale: Vector,
@@ -1560,14 +2324,23 @@ fn scale_shape(
             border_radius: s.border_radius,
             inner_shape: s.inner_shape.scaled(&scale.abs().into()),
         })),
-        TypedShape::Capsule(c) => match c.scaled(&scale.abs().into(), num_subdivisions) {
-            None => {
-                log::error!("Failed to apply scale {} to Capsule shape.", scale);
-                Ok(SharedShape::ball(0.0))
+        TypedShape::Capsule(c) => {
+            // A 3D capsule becomes a swept ellipsoid when scaled non-uniformly.
+            #[cfg(feature = "3d")]
+            if scale.x != scale.y || scale.x != scale.z {
+                return Ok(SharedShape::new(
+                    CapsuleHullColliderShape::from_scaled_capsule(c, scale),
+                ));
             }
-            Some(Either::Left(b)) => Ok(SharedShape::new(b)),
-            Some(Either::Right(b)) => Ok(SharedShape::new(b)),
-        },
+            match c.scaled(&scale.abs().into(), num_subdivisions) {
+                None => {
+                    log::error!("Failed to apply scale {} to Capsule shape.", scale);
+                    Ok(SharedShape::ball(0.0))
+                }
+                Some(Either::Left(b)) => Ok(SharedShape::new(b)),
+                Some(Either::Right(b)) => Ok(SharedShape::new(b)),
+            }
+        }
         TypedShape::Ball(b) => {
             #[cfg(feature = "2d")]
             {
@@ -1581,13 +2354,15 @@ fn scale_shape(
                 }
             }
             #[cfg(feature = "3d")]
-            match b.scaled(&scale.abs().into(), num_subdivisions) {
-                None => {
-                    log::error!("Failed to apply scale {} to Ball shape.", scale);
-                    Ok(SharedShape::ball(0.0))
+            {
+                if scale.x == scale.y && scale.x == scale.z {
+                    Ok(SharedShape::ball(b.radius * scale.x.abs()))
+                } else {
+                    // A 3D sphere becomes an ellipsoid when scaled non-uniformly.
+                    Ok(SharedShape::new(EllipsoidColliderShape::new(
+                        Vector::splat(b.radius) * scale,
+                    )))
                 }
-                Some(Either::Left(b)) => Ok(SharedShape::new(b)),
-                Some(Either::Right(b)) => Ok(SharedShape::new(b)),
             }
         }
         TypedShape::Segment(s) => Ok(SharedShape::new(s.scaled(&scale.into()))),
@@ -1723,15 +2498,33 @@ fn scale_shape(
             }
             Ok(SharedShape::compound(scaled))
         }
-        TypedShape::Custom(_shape) => {
+        TypedShape::Custom(shape) => {
+            if let Some(sdf) = shape.as_shape::<SdfColliderShape>() {
+                return Ok(SharedShape::new(sdf.scaled(scale)));
+            }
+            #[cfg(feature = "3d")]
+            {
+                if let Some(ellipsoid) = shape.as_shape::<EllipsoidColliderShape>() {
+                    return Ok(SharedShape::new(EllipsoidColliderShape::new(
+                        ellipsoid.half_size * scale,
+                    )));
+                }
+                if let Some(capsule) = shape.as_shape::<CapsuleHullColliderShape>() {
+                    return Ok(SharedShape::new(CapsuleHullColliderShape::new(
+                        capsule.a * scale,
+                        capsule.b * scale,
+                        capsule.half_size * scale,
+                    )));
+                }
+            }
             #[cfg(feature = "2d")]
             {
-                if let Some(ellipse) = _shape.as_shape::<EllipseColliderShape>() {
+                if let Some(ellipse) = shape.as_shape::<EllipseColliderShape>() {
                     return Ok(SharedShape::new(EllipseColliderShape(Ellipse {
                         half_size: ellipse.half_size * scale.f32().abs(),
                     })));
                 }
-                if let Some(polygon) = _shape.as_shape::<RegularPolygonColliderShape>() {
+                if let Some(polygon) = shape.as_shape::<RegularPolygonColliderShape>() {
                     if scale.x == scale.y {
                         return Ok(SharedShape::new(RegularPolygonColliderShape(
                             RegularPolygon::new(
@@ -1854,4 +2647,267 @@ mod tests {
             epsilon = 1e-6
         );
     }
+
+    #[test]
+    fn test_compound_editing_mass_properties() {
+        let mut collider = Collider::compound(vec![(
+            Position(Vector::new(-1.0, 0.0, 0.0)),
+            Rotation::default(),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        )]);
+
+        // Add two shapes, move one, and remove another.
+        assert_eq!(
+            collider.compound_push(
+                Vector::new(1.0, 0.0, 0.0),
+                Rotation::default(),
+                Collider::sphere(0.5),
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            collider.compound_push(
+                Vector::new(0.0, 2.0, 0.0),
+                Rotation::default(),
+                Collider::cuboid(0.5, 0.5, 0.5),
+            ),
+            Some(2)
+        );
+        assert!(collider.compound_set_transform(
+            1,
+            Vector::new(0.0, 0.0, 3.0),
+            Rotation::default()
+        ));
+
+        // The bounding box should contain the moved sphere.
+        let aabb = collider.aabb(Vector::ZERO, Rotation::default());
+        assert_relative_eq!(aabb.min, Vector::new(-1.5, -0.5, -0.5), epsilon = 1e-4);
+        assert_relative_eq!(aabb.max, Vector::new(0.5, 2.25, 3.5), epsilon = 1e-4);
+
+        assert!(collider.compound_remove(2).is_some());
+        assert!(collider.compound_remove(2).is_none());
+
+        let expected = Collider::compound(vec![
+            (
+                Position(Vector::new(-1.0, 0.0, 0.0)),
+                Rotation::default(),
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ),
+            (
+                Position(Vector::new(0.0, 0.0, 3.0)),
+                Rotation::default(),
+                Collider::sphere(0.5),
+            ),
+        ]);
+
+        let actual_props = collider.mass_properties(2.0);
+        let expected_props = expected.mass_properties(2.0);
+
+        assert_relative_eq!(actual_props.mass, expected_props.mass, epsilon = 1e-4);
+        assert_relative_eq!(
+            actual_props.center_of_mass,
+            expected_props.center_of_mass,
+            epsilon = 1e-4
+        );
+
+        // Compare the full inertia tensors, since the principal axes may be ordered differently.
+        let inertia_tensor = |props: &MassProperties| {
+            let frame = Mat3::from_quat(props.local_inertial_frame);
+            frame * Mat3::from_diagonal(props.principal_angular_inertia) * frame.transpose()
+        };
+        assert_relative_eq!(
+            inertia_tensor(&actual_props),
+            inertia_tensor(&expected_props),
+            epsilon = 1e-3
+        );
+
+        let aabb = collider.aabb(Vector::ZERO, Rotation::default());
+        let expected_aabb = expected.aabb(Vector::ZERO, Rotation::default());
+        assert_relative_eq!(aabb.min, expected_aabb.min, epsilon = 1e-4);
+        assert_relative_eq!(aabb.max, expected_aabb.max, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_sdf_collider() {
+        let grid = SdfGrid::from_fn(Vector::splat(-1.5), Vector::splat(1.5), 0.05, |point| {
+            point.length() - 1.0
+        });
+        let collider = Collider::sdf_grid(grid);
+
+        // The mass properties should match a unit sphere.
+        let props = collider.mass_properties(1.0);
+        assert_relative_eq!(props.mass, 4.0 / 3.0 * PI, max_relative = 0.02);
+        assert_relative_eq!(props.center_of_mass, Vector::ZERO, epsilon = 1e-3);
+
+        // Ray marching should hit the surface of the sphere.
+        let (distance, normal) = collider
+            .cast_ray(
+                Vector::ZERO,
+                Rotation::default(),
+                Vector::new(-5.0, 0.0, 0.0),
+                Vector::X,
+                10.0,
+                true,
+            )
+            .unwrap();
+        assert_relative_eq!(distance, 4.0, epsilon = 1e-2);
+        assert_relative_eq!(normal, Vector::NEG_X, epsilon = 1e-2);
+
+        // A ball resting on the sphere should produce a single contact.
+        let mut manifolds = Vec::new();
+        contact_query::contact_manifolds(
+            &collider,
+            Vector::ZERO,
+            Rotation::default(),
+            &Collider::sphere(0.5),
+            Vector::new(0.0, 1.4, 0.0),
+            Rotation::default(),
+            0.0,
+            &mut manifolds,
+        );
+        assert_eq!(manifolds.len(), 1);
+        assert_relative_eq!(manifolds[0].normal, Vector::Y, epsilon = 1e-2);
+        assert_relative_eq!(manifolds[0].points[0].penetration, 0.1, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_non_uniformly_scaled_round_shapes() {
+        // Non-uniformly scaled spheres should become exact ellipsoids.
+        let mut sphere = Collider::sphere(1.0);
+        sphere.set_scale(Vector::new(1.0, 2.0, 3.0), 10);
+        let ellipsoid = sphere
+            .shape_scaled()
+            .as_shape::<EllipsoidColliderShape>()
+            .expect("scaled sphere should be an ellipsoid");
+        assert_eq!(ellipsoid.half_size, Vector::new(1.0, 2.0, 3.0));
+        assert_relative_eq!(
+            sphere.mass_properties(1.0).mass,
+            (4.0 / 3.0 * PI * 6.0) as f32,
+            max_relative = 1e-4
+        );
+
+        let aabb = sphere.aabb(Vector::ZERO, Quaternion::from_rotation_x(PI / 2.0));
+        assert_relative_eq!(aabb.max, Vector::new(1.0, 3.0, 2.0), epsilon = 1e-4);
+
+        // Negative scales should mirror the shape without producing negative radii or half-extents.
+        let mut sphere = Collider::sphere(1.0);
+        sphere.set_scale(Vector::splat(-2.0), 10);
+        let ball = sphere
+            .shape_scaled()
+            .as_ball()
+            .expect("uniformly scaled sphere should be a ball");
+        assert_eq!(ball.radius, 2.0);
+        assert_relative_eq!(
+            sphere.mass_properties(1.0).mass,
+            (4.0 / 3.0 * PI * 8.0) as f32,
+            max_relative = 1e-4
+        );
+
+        let mut sphere = Collider::sphere(1.0);
+        sphere.set_scale(Vector::new(-1.0, 2.0, -3.0), 10);
+        let ellipsoid = sphere
+            .shape_scaled()
+            .as_shape::<EllipsoidColliderShape>()
+            .expect("scaled sphere should be an ellipsoid");
+        assert_eq!(ellipsoid.half_size, Vector::new(1.0, 2.0, 3.0));
+
+        // Non-uniformly scaled capsules should become swept ellipsoids.
+        let mut capsule = Collider::capsule(0.5, 2.0);
+        capsule.set_scale(Vector::new(2.0, 1.0, 1.0), 10);
+        assert!(
+            capsule
+                .shape_scaled()
+                .as_shape::<CapsuleHullColliderShape>()
+                .is_some()
+        );
+        let aabb = capsule.aabb(Vector::ZERO, Rotation::default());
+        assert_relative_eq!(aabb.max, Vector::new(1.0, 1.5, 0.5), epsilon = 1e-4);
+
+        // A uniformly scaled capsule hull should have the same mass properties as a scaled capsule.
+        let hull = Collider::from(SharedShape::new(
+            CapsuleHullColliderShape::from_scaled_capsule(
+                &parry::shape::Capsule::new_y(1.0, 0.5),
+                Vector::splat(2.0),
+            ),
+        ));
+        let expected = Collider::capsule(1.0, 4.0).mass_properties(1.0);
+        let props = hull.mass_properties(1.0);
+        assert_relative_eq!(props.mass, expected.mass, max_relative = 1e-4);
+
+        // The principal axes may be ordered differently, so compare the sorted principal moments.
+        let sorted = |inertia: Vec3| {
+            let mut inertia = inertia.to_array();
+            inertia.sort_by(f32::total_cmp);
+            Vec3::from_array(inertia)
+        };
+        assert_relative_eq!(
+            sorted(props.principal_angular_inertia),
+            sorted(expected.principal_angular_inertia),
+            max_relative = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_contact_dispatcher() {
+        let mut dispatcher = ContactDispatcher::<Collider>::default();
+        dispatcher.register_shapes::<parry::shape::Ball, parry::shape::Cuboid>(
+            |ball, position1, _, _, _, _, _, manifolds| {
+                let point = Vector::new(0.0, -ball.radius, 0.0);
+                manifolds.clear();
+                manifolds.push(ContactManifold::new(
+                    [ContactPoint::new(
+                        point,
+                        Vector::ZERO,
+                        position1 + point,
+                        0.25,
+                    )],
+                    Vector::NEG_Y,
+                ));
+            },
+        );
+
+        let ball = Collider::sphere(0.5);
+        let cuboid = Collider::cuboid(1.0, 1.0, 1.0);
+        let mut manifolds = Vec::new();
+
+        // The registered function should be used for the ball-cuboid pair.
+        assert!(dispatcher.contact_manifolds(
+            &ball,
+            Vector::ZERO,
+            Rotation::default(),
+            &cuboid,
+            Vector::NEG_Y,
+            Rotation::default(),
+            0.0,
+            &mut manifolds,
+        ));
+        assert_eq!(manifolds[0].normal, Vector::NEG_Y);
+        assert_eq!(manifolds[0].points[0].anchor1, Vector::new(0.0, -0.5, 0.0));
+
+        // The manifolds should be flipped for the cuboid-ball pair.
+        assert!(dispatcher.contact_manifolds(
+            &cuboid,
+            Vector::NEG_Y,
+            Rotation::default(),
+            &ball,
+            Vector::ZERO,
+            Rotation::default(),
+            0.0,
+            &mut manifolds,
+        ));
+        assert_eq!(manifolds[0].normal, Vector::Y);
+        assert_eq!(manifolds[0].points[0].anchor2, Vector::new(0.0, -0.5, 0.0));
+
+        // Other pairs should fall back to the default contact query.
+        assert!(!dispatcher.contact_manifolds(
+            &ball,
+            Vector::ZERO,
+            Rotation::default(),
+            &ball,
+            Vector::X,
+            Rotation::default(),
+            0.0,
+            &mut manifolds,
+        ));
+    }
 }
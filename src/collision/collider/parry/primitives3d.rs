@@ -2,9 +2,20 @@ use bevy_math::primitives::{
     Capsule3d, Cone, Cuboid, Cylinder, InfinitePlane3d, Line3d, Plane3d, Polyline3d, Segment3d,
     Sphere,
 };
-use parry::shape::SharedShape;
+use nalgebra::{Matrix3, Point3, Vector3};
+use parry::{
+    bounding_volume::{Aabb, BoundingSphere},
+    mass_properties::MassProperties,
+    math::Isometry,
+    query::{
+        PointProjection, PointQuery, Ray, RayCast, RayIntersection,
+        details::local_ray_intersection_with_support_map_with_params, gjk::VoronoiSimplex,
+        point::local_point_projection_on_support_map,
+    },
+    shape::{Capsule, FeatureId, Shape, ShapeType, SharedShape, SupportMap, TypedShape},
+};
 
-use crate::{AdjustPrecision, Collider, IntoCollider, Quaternion, Vector};
+use crate::{AdjustPrecision, Collider, IntoCollider, PI, Quaternion, Scalar, Vector};
 
 impl IntoCollider<Collider> for Sphere {
     fn collider(&self) -> Collider {
@@ -99,3 +110,359 @@ impl IntoCollider<Collider> for Cone {
 
 // TODO: ConicalFrustum
 // TODO: Torus
+
+/// An ellipsoid shape that can be stored in a [`SharedShape`] for an ellipsoid [`Collider`].
+///
+/// Ellipsoids are also used for exact non-uniform scaling of spheres.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EllipsoidColliderShape {
+    /// The half-extents of the ellipsoid along its local `X`, `Y`, and `Z` axes.
+    pub half_size: Vector,
+}
+
+impl EllipsoidColliderShape {
+    /// Creates a new [`EllipsoidColliderShape`] with the given half-extents
+    /// along its local `X`, `Y`, and `Z` axes.
+    pub fn new(half_size: Vector) -> Self {
+        Self {
+            half_size: half_size.abs(),
+        }
+    }
+
+    /// Returns the volume of the ellipsoid.
+    pub fn volume(&self) -> Scalar {
+        4.0 / 3.0 * PI * self.half_size.element_product()
+    }
+}
+
+impl SupportMap for EllipsoidColliderShape {
+    #[inline]
+    fn local_support_point(&self, direction: &Vector3<Scalar>) -> Point3<Scalar> {
+        ellipsoid_support_point(self.half_size, Vector::from(*direction)).into()
+    }
+}
+
+impl Shape for EllipsoidColliderShape {
+    fn clone_dyn(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+
+    fn scale_dyn(
+        &self,
+        scale: &parry::math::Vector<Scalar>,
+        _num_subdivisions: u32,
+    ) -> Option<Box<dyn Shape>> {
+        Some(Box::new(Self::new(self.half_size * Vector::from(*scale))))
+    }
+
+    fn compute_local_aabb(&self) -> Aabb {
+        Aabb::new((-self.half_size).into(), self.half_size.into())
+    }
+
+    fn compute_aabb(&self, position: &Isometry<Scalar>) -> Aabb {
+        let center = Vector::from(position.translation);
+        let half_extents =
+            ellipsoid_aabb_half_extents(self.half_size, Quaternion::from(position.rotation));
+        Aabb::new(
+            (center - half_extents).into(),
+            (center + half_extents).into(),
+        )
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        BoundingSphere::new(Point3::origin(), self.half_size.max_element())
+    }
+
+    fn compute_bounding_sphere(&self, position: &Isometry<Scalar>) -> BoundingSphere {
+        self.compute_local_bounding_sphere().transform_by(position)
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+
+    fn mass_properties(&self, density: Scalar) -> MassProperties {
+        let mass = self.volume() * density;
+        let [a2, b2, c2] = (self.half_size * self.half_size).to_array();
+        let principal_inertia = Vector3::new(b2 + c2, a2 + c2, a2 + b2) * mass / 5.0;
+        MassProperties::new(Point3::origin(), mass, principal_inertia)
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape<'_> {
+        TypedShape::Custom(self)
+    }
+
+    fn ccd_thickness(&self) -> Scalar {
+        self.half_size.min_element()
+    }
+
+    fn ccd_angular_thickness(&self) -> Scalar {
+        PI
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap> {
+        Some(self as &dyn SupportMap)
+    }
+}
+
+impl RayCast for EllipsoidColliderShape {
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Scalar,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        local_ray_intersection_with_support_map_with_params(
+            self,
+            &mut VoronoiSimplex::new(),
+            ray,
+            max_toi,
+            solid,
+        )
+    }
+}
+
+impl PointQuery for EllipsoidColliderShape {
+    fn project_local_point(&self, pt: &Point3<Scalar>, solid: bool) -> PointProjection {
+        local_point_projection_on_support_map(self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        pt: &Point3<Scalar>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(pt, false), FeatureId::Unknown)
+    }
+}
+
+/// A capsule-like shape formed by sweeping an ellipsoid along a line segment,
+/// or equivalently, the convex hull of two identical ellipsoids centered at the endpoints of the segment.
+/// It can be stored in a [`SharedShape`] for a [`Collider`].
+///
+/// This is the exact shape of a non-uniformly scaled capsule.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapsuleHullColliderShape {
+    /// The first endpoint of the segment.
+    pub a: Vector,
+    /// The second endpoint of the segment.
+    pub b: Vector,
+    /// The half-extents of the swept ellipsoid along its local `X`, `Y`, and `Z` axes.
+    pub half_size: Vector,
+}
+
+impl CapsuleHullColliderShape {
+    /// Creates a new [`CapsuleHullColliderShape`] by sweeping an ellipsoid with the given half-extents
+    /// along the segment between `a` and `b`.
+    pub fn new(a: Vector, b: Vector, half_size: Vector) -> Self {
+        Self {
+            a,
+            b,
+            half_size: half_size.abs(),
+        }
+    }
+
+    /// Creates a new [`CapsuleHullColliderShape`] by scaling the given capsule non-uniformly.
+    pub fn from_scaled_capsule(capsule: &Capsule, scale: Vector) -> Self {
+        Self::new(
+            Vector::from(capsule.segment.a) * scale,
+            Vector::from(capsule.segment.b) * scale,
+            Vector::splat(capsule.radius) * scale,
+        )
+    }
+
+    /// Returns the unit-radius capsule that this shape is formed from when scaled by the half-extents,
+    /// or `None` if any of the half-extents is zero.
+    fn unit_capsule(&self) -> Option<Capsule> {
+        (self.half_size.min_element() > 0.0).then(|| {
+            Capsule::new(
+                (self.a / self.half_size).into(),
+                (self.b / self.half_size).into(),
+                1.0,
+            )
+        })
+    }
+
+    /// Computes the vertices and indices of an outline of the shape,
+    /// with the given number of subdivisions for the ellipsoidal caps.
+    pub fn to_outline(&self, num_subdivisions: u32) -> (Vec<Vector>, Vec<[u32; 2]>) {
+        let Some(capsule) = self.unit_capsule() else {
+            return (vec![self.a, self.b], vec![[0, 1]]);
+        };
+        let (vertices, indices) = capsule.to_outline(num_subdivisions);
+        let vertices = vertices
+            .into_iter()
+            .map(|v| Vector::from(v) * self.half_size)
+            .collect();
+        (vertices, indices)
+    }
+}
+
+impl SupportMap for CapsuleHullColliderShape {
+    #[inline]
+    fn local_support_point(&self, direction: &Vector3<Scalar>) -> Point3<Scalar> {
+        let direction = Vector::from(*direction);
+        let endpoint = if self.a.dot(direction) >= self.b.dot(direction) {
+            self.a
+        } else {
+            self.b
+        };
+        (endpoint + ellipsoid_support_point(self.half_size, direction)).into()
+    }
+}
+
+impl Shape for CapsuleHullColliderShape {
+    fn clone_dyn(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+
+    fn scale_dyn(
+        &self,
+        scale: &parry::math::Vector<Scalar>,
+        _num_subdivisions: u32,
+    ) -> Option<Box<dyn Shape>> {
+        let scale = Vector::from(*scale);
+        Some(Box::new(Self::new(
+            self.a * scale,
+            self.b * scale,
+            self.half_size * scale,
+        )))
+    }
+
+    fn compute_local_aabb(&self) -> Aabb {
+        Aabb::new(
+            (self.a.min(self.b) - self.half_size).into(),
+            (self.a.max(self.b) + self.half_size).into(),
+        )
+    }
+
+    fn compute_aabb(&self, position: &Isometry<Scalar>) -> Aabb {
+        let rotation = Quaternion::from(position.rotation);
+        let translation = Vector::from(position.translation);
+        let a = translation + rotation * self.a;
+        let b = translation + rotation * self.b;
+        let half_extents = ellipsoid_aabb_half_extents(self.half_size, rotation);
+        Aabb::new(
+            (a.min(b) - half_extents).into(),
+            (a.max(b) + half_extents).into(),
+        )
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        let center = (self.a + self.b) * 0.5;
+        let radius = self.a.distance(self.b) * 0.5 + self.half_size.max_element();
+        BoundingSphere::new(center.into(), radius)
+    }
+
+    fn compute_bounding_sphere(&self, position: &Isometry<Scalar>) -> BoundingSphere {
+        self.compute_local_bounding_sphere().transform_by(position)
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(*self)
+    }
+
+    fn mass_properties(&self, density: Scalar) -> MassProperties {
+        let Some(capsule) = self.unit_capsule() else {
+            return MassProperties::new(Point3::origin(), 0.0, Vector3::zeros());
+        };
+
+        // The shape is the unit-radius capsule transformed by the linear map `S = diag(half_size)`.
+        // The mass scales by `det(S)`, and the second moment of area `C` is transformed as `det(S) * S * C * S`.
+        // The inertia tensor is related to the second moment by `I = tr(C) * Id - C`.
+        let unit_props = capsule.mass_properties(density);
+        let det = self.half_size.element_product();
+        let scale = Matrix3::from_diagonal(&Vector3::from(self.half_size));
+
+        let unit_inertia = unit_props.reconstruct_inertia_matrix();
+        let unit_second_moment = Matrix3::identity() * (unit_inertia.trace() * 0.5) - unit_inertia;
+        let second_moment = scale * unit_second_moment * scale * det;
+        let inertia = Matrix3::identity() * second_moment.trace() - second_moment;
+
+        MassProperties::with_inertia_matrix(
+            scale * unit_props.local_com,
+            unit_props.mass() * det,
+            inertia,
+        )
+    }
+
+    fn is_convex(&self) -> bool {
+        true
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape<'_> {
+        TypedShape::Custom(self)
+    }
+
+    fn ccd_thickness(&self) -> Scalar {
+        self.half_size.min_element()
+    }
+
+    fn ccd_angular_thickness(&self) -> Scalar {
+        PI
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap> {
+        Some(self as &dyn SupportMap)
+    }
+}
+
+impl RayCast for CapsuleHullColliderShape {
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Scalar,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        local_ray_intersection_with_support_map_with_params(
+            self,
+            &mut VoronoiSimplex::new(),
+            ray,
+            max_toi,
+            solid,
+        )
+    }
+}
+
+impl PointQuery for CapsuleHullColliderShape {
+    fn project_local_point(&self, pt: &Point3<Scalar>, solid: bool) -> PointProjection {
+        local_point_projection_on_support_map(self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        pt: &Point3<Scalar>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(pt, false), FeatureId::Unknown)
+    }
+}
+
+/// Computes the support point of an ellipsoid with the given half-extents in the given direction.
+#[inline]
+fn ellipsoid_support_point(half_size: Vector, direction: Vector) -> Vector {
+    let scaled_direction = direction * half_size;
+    let length = scaled_direction.length();
+    if length == 0.0 {
+        return Vector::ZERO;
+    }
+    half_size * scaled_direction / length
+}
+
+/// Computes the half-extents of the AABB of an ellipsoid with the given half-extents and rotation.
+fn ellipsoid_aabb_half_extents(half_size: Vector, rotation: Quaternion) -> Vector {
+    let x = rotation * (Vector::X * half_size.x);
+    let y = rotation * (Vector::Y * half_size.y);
+    let z = rotation * (Vector::Z * half_size.z);
+    (x * x + y * y + z * z).map(Scalar::sqrt)
+}
@@ -0,0 +1,763 @@
+//! Signed distance field (SDF) shapes that can be used for [`Collider`]s.
+//!
+//! See [`Collider::sdf`] and [`Collider::sdf_grid`].
+
+use alloc::sync::Arc;
+use core::fmt;
+
+use crate::{math::DIM, prelude::*};
+use parry::{
+    bounding_volume::{Aabb, BoundingSphere},
+    mass_properties::MassProperties,
+    math::Isometry,
+    query::{PointProjection, PointQuery, Ray, RayCast, RayIntersection},
+    shape::{FeatureId, Shape, SupportMap},
+};
+
+/// The resolution used along each axis when integrating the mass properties of SDFs defined by a function.
+const FUNCTION_INTEGRATION_RESOLUTION: usize = 32;
+
+/// The maximum number of steps used for ray marching.
+const MAX_RAY_MARCH_STEPS: usize = 128;
+
+/// The maximum number of contact points in a single contact manifold against an SDF.
+#[cfg(feature = "2d")]
+const MAX_MANIFOLD_POINTS: usize = 2;
+/// The maximum number of contact points in a single contact manifold against an SDF.
+#[cfg(feature = "3d")]
+const MAX_MANIFOLD_POINTS: usize = 4;
+
+/// A regular grid of signed distance samples.
+///
+/// The distance at an arbitrary point is computed by interpolating between the nearest samples.
+/// Outside of the grid, the distance is extrapolated from the closest point on the grid.
+///
+/// Distances should be negative inside of the shape and positive outside of it.
+///
+/// An [`SdfGrid`] can be turned into a [`Collider`] using [`Collider::sdf_grid`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(try_from = "SdfGridData"))]
+pub struct SdfGrid {
+    /// The local position of the first sample.
+    min: Vector,
+    /// The distance between adjacent samples.
+    spacing: Scalar,
+    /// The number of samples along each axis.
+    size: [usize; DIM],
+    /// The distance samples, ordered by the `X` axis first, then the `Y` axis, and so on.
+    values: Vec<Scalar>,
+}
+
+impl SdfGrid {
+    /// Creates a new [`SdfGrid`] with the given minimum corner, sample spacing,
+    /// number of samples along each axis, and distance samples.
+    ///
+    #[cfg_attr(
+        feature = "2d",
+        doc = "The samples are ordered by the `X` axis first, and then the `Y` axis."
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "The samples are ordered by the `X` axis first, then the `Y` axis, and finally the `Z` axis."
+    )]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spacing` is not positive, if there are less than two samples along any axis,
+    /// or if the number of values does not match the size of the grid.
+    pub fn new(min: Vector, spacing: Scalar, size: [usize; DIM], values: Vec<Scalar>) -> Self {
+        if let Err(message) = Self::validate(spacing, size, values.len()) {
+            panic!("{message}");
+        }
+        Self {
+            min,
+            spacing,
+            size,
+            values,
+        }
+    }
+
+    /// Checks that the given grid parameters are valid, returning an error message if they are not.
+    fn validate(
+        spacing: Scalar,
+        size: [usize; DIM],
+        value_count: usize,
+    ) -> Result<(), &'static str> {
+        if spacing.is_nan() || spacing <= 0.0 {
+            return Err("the spacing of an `SdfGrid` must be positive");
+        }
+        if size.iter().any(|&count| count < 2) {
+            return Err("an `SdfGrid` must have at least two samples along each axis");
+        }
+        if value_count != size.iter().product::<usize>() {
+            return Err("the number of values must match the size of the `SdfGrid`");
+        }
+        Ok(())
+    }
+
+    /// Creates a new [`SdfGrid`] by sampling the given signed distance function
+    /// in the region between `min` and `max` with the given sample spacing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spacing` is not positive.
+    pub fn from_fn(
+        min: Vector,
+        max: Vector,
+        spacing: Scalar,
+        distance: impl Fn(Vector) -> Scalar,
+    ) -> Self {
+        assert!(
+            spacing > 0.0,
+            "the spacing of an `SdfGrid` must be positive"
+        );
+
+        let counts = ((max - min) / spacing).ceil().max(Vector::ONE) + 1.0;
+        let size: [usize; DIM] = counts.to_array().map(|count| count as usize);
+
+        let mut values = Vec::with_capacity(size.iter().product());
+        for index in 0..size.iter().product::<usize>() {
+            values.push(distance(min + grid_coordinate(index, size) * spacing));
+        }
+
+        Self::new(min, spacing, size, values)
+    }
+
+    /// Returns the local position of the first sample.
+    pub fn min(&self) -> Vector {
+        self.min
+    }
+
+    /// Returns the local position of the last sample.
+    pub fn max(&self) -> Vector {
+        self.min + (Vector::from_array(self.size.map(|count| count as Scalar)) - 1.0) * self.spacing
+    }
+
+    /// Returns the distance between adjacent samples.
+    pub fn spacing(&self) -> Scalar {
+        self.spacing
+    }
+
+    /// Returns the number of samples along each axis.
+    pub fn size(&self) -> [usize; DIM] {
+        self.size
+    }
+
+    /// Returns the distance samples.
+    pub fn values(&self) -> &[Scalar] {
+        &self.values
+    }
+
+    /// Computes the signed distance at the given local point by interpolating between the nearest samples.
+    pub fn distance(&self, point: Vector) -> Scalar {
+        // Clamp the point to the grid, and extrapolate the distance outside of it.
+        let clamped = point.clamp(self.min, self.max());
+        let outside_distance = point.distance(clamped);
+
+        let coordinates = (clamped - self.min) / self.spacing;
+        let max_cell = Vector::from_array(self.size.map(|count| (count - 2) as Scalar));
+        let cell = coordinates.floor().min(max_cell);
+        let t = coordinates - cell;
+        let cell = cell.to_array().map(|c| c as usize);
+
+        let value = |offset: [usize; DIM]| {
+            let mut index = 0;
+            let mut stride = 1;
+            for axis in 0..DIM {
+                index += (cell[axis] + offset[axis]) * stride;
+                stride *= self.size[axis];
+            }
+            self.values[index]
+        };
+
+        let lerp = |a: Scalar, b: Scalar, t: Scalar| a + (b - a) * t;
+
+        #[cfg(feature = "2d")]
+        let interpolated = {
+            let bottom = lerp(value([0, 0]), value([1, 0]), t.x);
+            let top = lerp(value([0, 1]), value([1, 1]), t.x);
+            lerp(bottom, top, t.y)
+        };
+        #[cfg(feature = "3d")]
+        let interpolated = {
+            let lerp_x = |y, z| lerp(value([0, y, z]), value([1, y, z]), t.x);
+            let back = lerp(lerp_x(0, 0), lerp_x(1, 0), t.y);
+            let front = lerp(lerp_x(0, 1), lerp_x(1, 1), t.y);
+            lerp(back, front, t.z)
+        };
+
+        interpolated + outside_distance
+    }
+}
+
+/// The serialized form of an [`SdfGrid`], validated before it is turned into a grid.
+#[cfg(feature = "serialize")]
+#[derive(serde::Deserialize)]
+struct SdfGridData {
+    min: Vector,
+    spacing: Scalar,
+    size: [usize; DIM],
+    values: Vec<Scalar>,
+}
+
+#[cfg(feature = "serialize")]
+impl TryFrom<SdfGridData> for SdfGrid {
+    type Error = &'static str;
+
+    fn try_from(data: SdfGridData) -> Result<Self, Self::Error> {
+        Self::validate(data.spacing, data.size, data.values.len())?;
+        Ok(Self {
+            min: data.min,
+            spacing: data.spacing,
+            size: data.size,
+            values: data.values,
+        })
+    }
+}
+
+/// Returns the grid coordinate of the sample or cell with the given linear index.
+fn grid_coordinate(index: usize, size: [usize; DIM]) -> Vector {
+    let mut coordinate = [0.0; DIM];
+    let mut remainder = index;
+    for axis in 0..DIM {
+        coordinate[axis] = (remainder % size[axis]) as Scalar;
+        remainder /= size[axis];
+    }
+    Vector::from_array(coordinate)
+}
+
+/// The source of the distances of an [`SdfColliderShape`].
+#[derive(Clone)]
+enum SdfSource {
+    /// Distances interpolated from a grid of samples.
+    Grid(Arc<SdfGrid>),
+    /// Distances computed by a user-defined function.
+    Function(Arc<dyn Fn(Vector) -> Scalar + Send + Sync>),
+}
+
+/// A signed distance field (SDF) shape that can be stored in a [`SharedShape`](parry::shape::SharedShape)
+/// for an SDF [`Collider`].
+///
+/// The distances are either interpolated from an [`SdfGrid`] or computed by a user-defined function,
+/// and are negative inside of the shape and positive outside of it. The shape is limited to a bounding box.
+///
+/// SDF colliders support:
+///
+/// - Contacts against convex shapes, computed by sampling support points of the convex shape.
+/// - Ray casts using ray marching, and point projection using the gradient of the field.
+/// - Mass properties computed by integrating over the interior of the field.
+///
+/// Contacts between two SDFs or between SDFs and non-convex shapes like triangle meshes are not supported.
+///
+/// If the collider is scaled non-uniformly, the distances are scaled by the smallest scaling factor,
+/// which underestimates the distance along the other axes.
+#[derive(Clone)]
+pub struct SdfColliderShape {
+    source: SdfSource,
+    /// The unscaled minimum corner of the bounding box.
+    min: Vector,
+    /// The unscaled maximum corner of the bounding box.
+    max: Vector,
+    /// The scale applied to the field.
+    scale: Vector,
+}
+
+impl fmt::Debug for SdfColliderShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match &self.source {
+            SdfSource::Grid(_) => "Grid",
+            SdfSource::Function(_) => "Function",
+        };
+        f.debug_struct("SdfColliderShape")
+            .field("source", &source)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("scale", &self.scale)
+            .finish()
+    }
+}
+
+impl SdfColliderShape {
+    /// Creates a new [`SdfColliderShape`] with distances interpolated from the given [`SdfGrid`].
+    pub fn from_grid(grid: SdfGrid) -> Self {
+        Self {
+            min: grid.min(),
+            max: grid.max(),
+            source: SdfSource::Grid(Arc::new(grid)),
+            scale: Vector::ONE,
+        }
+    }
+
+    /// Creates a new [`SdfColliderShape`] with distances computed by the given function
+    /// in the region between `min` and `max`.
+    pub fn from_fn(
+        min: Vector,
+        max: Vector,
+        distance: impl Fn(Vector) -> Scalar + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            source: SdfSource::Function(Arc::new(distance)),
+            min: min.min(max),
+            max: min.max(max),
+            scale: Vector::ONE,
+        }
+    }
+
+    /// Returns the [`SdfGrid`] that the distances are interpolated from, if any.
+    pub fn grid(&self) -> Option<&SdfGrid> {
+        match &self.source {
+            SdfSource::Grid(grid) => Some(grid),
+            SdfSource::Function(_) => None,
+        }
+    }
+
+    /// Returns the scale applied to the field.
+    pub fn scale(&self) -> Vector {
+        self.scale
+    }
+
+    /// Returns a copy of the shape with the given scale applied on top of the current scale.
+    pub fn scaled(&self, scale: Vector) -> Self {
+        Self {
+            scale: self.scale * scale,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the minimum and maximum corners of the local bounding box of the scaled shape.
+    pub fn bounds(&self) -> (Vector, Vector) {
+        let (a, b) = (self.min * self.scale, self.max * self.scale);
+        (a.min(b), a.max(b))
+    }
+
+    /// Computes the signed distance from the given local point to the surface of the shape.
+    pub fn distance(&self, point: Vector) -> Scalar {
+        let unscaled_point = point / self.scale;
+        let distance = match &self.source {
+            SdfSource::Grid(grid) => grid.distance(unscaled_point),
+            SdfSource::Function(function) => function(unscaled_point),
+        };
+        distance * self.scale.abs().min_element()
+    }
+
+    /// Computes the normalized gradient of the field at the given local point.
+    ///
+    /// The gradient points away from the surface on the outside of the shape, and towards it on the inside.
+    pub fn gradient(&self, point: Vector) -> Vector {
+        let step = self.gradient_step();
+        let mut gradient = Vector::ZERO;
+        for axis in 0..DIM {
+            let mut offset = Vector::ZERO;
+            offset[axis] = step;
+            gradient[axis] = self.distance(point + offset) - self.distance(point - offset);
+        }
+        gradient.normalize_or(Vector::Y)
+    }
+
+    /// Returns the step size used for finite differences and ray marching.
+    fn gradient_step(&self) -> Scalar {
+        let min_scale = self.scale.abs().min_element();
+        match &self.source {
+            SdfSource::Grid(grid) => 0.5 * grid.spacing() * min_scale,
+            SdfSource::Function(_) => {
+                (self.max - self.min).min_element() * min_scale
+                    / (2.0 * FUNCTION_INTEGRATION_RESOLUTION as Scalar)
+            }
+        }
+    }
+
+    /// Returns the number of cells along each axis used for integrating the mass properties.
+    fn integration_resolution(&self) -> [usize; DIM] {
+        match &self.source {
+            SdfSource::Grid(grid) => grid.size(),
+            SdfSource::Function(_) => [FUNCTION_INTEGRATION_RESOLUTION; DIM],
+        }
+    }
+}
+
+impl Shape for SdfColliderShape {
+    fn clone_dyn(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn scale_dyn(
+        &self,
+        scale: &parry::math::Vector<Scalar>,
+        _num_subdivisions: u32,
+    ) -> Option<Box<dyn Shape>> {
+        Some(Box::new(self.scaled(Vector::from(*scale))))
+    }
+
+    fn compute_local_aabb(&self) -> Aabb {
+        let (min, max) = self.bounds();
+        Aabb::new(min.into(), max.into())
+    }
+
+    fn compute_aabb(&self, position: &Isometry<Scalar>) -> Aabb {
+        self.compute_local_aabb().transform_by(position)
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        let (min, max) = self.bounds();
+        BoundingSphere::new(((min + max) * 0.5).into(), (max - min).length() * 0.5)
+    }
+
+    fn compute_bounding_sphere(&self, position: &Isometry<Scalar>) -> BoundingSphere {
+        self.compute_local_bounding_sphere().transform_by(position)
+    }
+
+    fn clone_box(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn mass_properties(&self, density: Scalar) -> MassProperties {
+        let (min, max) = self.bounds();
+        let resolution = self.integration_resolution();
+        let cell_size = (max - min) / Vector::from_array(resolution.map(|count| count as Scalar));
+        let cell_mass = cell_size.element_product() * density;
+
+        // Integrate over the cells whose centers are inside of the shape.
+        let mut mass = 0.0;
+        let mut first_moment = Vector::ZERO;
+        #[cfg(feature = "2d")]
+        let mut inertia = 0.0;
+        #[cfg(feature = "3d")]
+        let mut inertia = Matrix::ZERO;
+
+        for index in 0..resolution.iter().product::<usize>() {
+            let center = min + (grid_coordinate(index, resolution) + 0.5) * cell_size;
+
+            if self.distance(center) >= 0.0 {
+                continue;
+            }
+
+            mass += cell_mass;
+            first_moment += center * cell_mass;
+
+            // The inertia of the cell about its center, shifted to the origin.
+            #[cfg(feature = "2d")]
+            {
+                inertia +=
+                    cell_mass * (cell_size.length_squared() / 12.0 + center.length_squared());
+            }
+            #[cfg(feature = "3d")]
+            {
+                let squared = cell_size * cell_size;
+                let cell_inertia = Matrix::from_diagonal(
+                    Vector::new(
+                        squared.y + squared.z,
+                        squared.x + squared.z,
+                        squared.x + squared.y,
+                    ) / 12.0,
+                );
+                inertia += (cell_inertia + point_mass_inertia(center)) * cell_mass;
+            }
+        }
+
+        if mass <= 0.0 {
+            return MassProperties::zero();
+        }
+
+        let center_of_mass = first_moment / mass;
+
+        // Shift the angular inertia to the center of mass.
+        #[cfg(feature = "2d")]
+        {
+            inertia -= mass * center_of_mass.length_squared();
+            MassProperties::new(center_of_mass.into(), mass, inertia)
+        }
+        #[cfg(feature = "3d")]
+        {
+            inertia -= point_mass_inertia(center_of_mass) * mass;
+            MassProperties::with_inertia_matrix(center_of_mass.into(), mass, inertia.into())
+        }
+    }
+
+    fn is_convex(&self) -> bool {
+        false
+    }
+
+    fn shape_type(&self) -> parry::shape::ShapeType {
+        parry::shape::ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> parry::shape::TypedShape<'_> {
+        parry::shape::TypedShape::Custom(self)
+    }
+
+    fn ccd_thickness(&self) -> Scalar {
+        self.gradient_step()
+    }
+
+    fn ccd_angular_thickness(&self) -> Scalar {
+        crate::math::PI
+    }
+}
+
+/// Returns the angular inertia tensor of a unit point mass at the given offset from the origin.
+#[cfg(feature = "3d")]
+fn point_mass_inertia(offset: Vector) -> Matrix {
+    Matrix::from_diagonal(Vector::splat(offset.length_squared()))
+        - Matrix::from_cols(offset * offset.x, offset * offset.y, offset * offset.z)
+}
+
+impl RayCast for SdfColliderShape {
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let origin = Vector::from(ray.origin);
+        let direction = Vector::from(ray.dir);
+        let speed = direction.length();
+
+        if speed == 0.0 {
+            return None;
+        }
+
+        let direction = direction / speed;
+        let max_distance = max_time_of_impact * speed;
+
+        let inside = self.distance(origin) < 0.0;
+        if inside && solid {
+            return Some(RayIntersection::new(
+                0.0,
+                Vector::ZERO.into(),
+                FeatureId::Unknown,
+            ));
+        }
+
+        // Only march inside of the bounding box.
+        let (min, max) = self.bounds();
+        let (entry, exit) = clip_ray(origin, direction, min, max)?;
+        let exit = exit.min(max_distance);
+        let mut distance = entry.max(0.0);
+        let tolerance = self.gradient_step() * 0.01;
+
+        for _ in 0..MAX_RAY_MARCH_STEPS {
+            if distance > exit {
+                return None;
+            }
+
+            let point = origin + direction * distance;
+            let field_distance = self.distance(point);
+
+            // When starting inside of a hollow shape, march until the ray exits the surface.
+            let step = if inside {
+                -field_distance
+            } else {
+                field_distance
+            };
+
+            if step <= tolerance {
+                let normal = self.gradient(point);
+                return Some(RayIntersection::new(
+                    distance / speed,
+                    normal.into(),
+                    FeatureId::Unknown,
+                ));
+            }
+
+            distance += step.max(tolerance);
+        }
+
+        None
+    }
+}
+
+/// Clips a ray with a normalized direction against a bounding box,
+/// returning the distances at which the ray enters and exits the box.
+fn clip_ray(
+    origin: Vector,
+    direction: Vector,
+    min: Vector,
+    max: Vector,
+) -> Option<(Scalar, Scalar)> {
+    let inv_direction = direction.recip();
+    let t1 = (min - origin) * inv_direction;
+    let t2 = (max - origin) * inv_direction;
+    let entry = t1.min(t2).max_element();
+    let exit = t1.max(t2).min_element();
+    (entry <= exit && exit >= 0.0).then_some((entry, exit))
+}
+
+impl PointQuery for SdfColliderShape {
+    fn project_local_point(&self, pt: &parry::math::Point<Scalar>, solid: bool) -> PointProjection {
+        let point = Vector::from(*pt);
+        let distance = self.distance(point);
+        let is_inside = distance < 0.0;
+
+        if is_inside && solid {
+            return PointProjection::new(true, *pt);
+        }
+
+        // Move the point along the gradient onto the surface.
+        let mut projection = point;
+        let mut distance = distance;
+        for _ in 0..4 {
+            projection -= self.gradient(projection) * distance;
+            distance = self.distance(projection);
+        }
+
+        PointProjection::new(is_inside, projection.into())
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        pt: &parry::math::Point<Scalar>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(pt, false), FeatureId::Unknown)
+    }
+}
+
+/// A contact manifold involving an [`SdfColliderShape`], in the local space of the first shape.
+pub(crate) struct SdfManifold {
+    /// The contact normal pointing away from the first shape.
+    pub normal: Vector,
+    /// The contact points.
+    pub points: Vec<SdfContactPoint>,
+}
+
+/// A contact point involving an [`SdfColliderShape`], in the local space of the first shape.
+pub(crate) struct SdfContactPoint {
+    /// The contact point on the first shape.
+    pub point1: Vector,
+    /// The contact point on the second shape.
+    pub point2: Vector,
+    /// The signed distance between the points. Negative values indicate penetration.
+    pub distance: Scalar,
+}
+
+/// Computes the contact manifolds between two shapes if one of them is an [`SdfColliderShape`]
+/// and the other one is a support map, like a ball, cuboid, or convex hull.
+///
+/// `isometry12` is the isometry of the second shape relative to the first shape.
+/// Returns `None` if the shapes are not supported.
+pub(crate) fn contact_manifolds_sdf(
+    isometry12: &Isometry<Scalar>,
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+    prediction_distance: Scalar,
+) -> Option<Vec<SdfManifold>> {
+    if let (Some(sdf), Some(other)) = (
+        shape1.as_shape::<SdfColliderShape>(),
+        shape2.as_support_map(),
+    ) {
+        return Some(sdf_support_map_manifolds(
+            sdf,
+            other,
+            isometry12,
+            prediction_distance,
+        ));
+    }
+
+    let (Some(other), Some(sdf)) = (
+        shape1.as_support_map(),
+        shape2.as_shape::<SdfColliderShape>(),
+    ) else {
+        return None;
+    };
+
+    // Compute the contacts in the local space of the SDF, and swap them back into the space of the first shape.
+    let mut manifolds =
+        sdf_support_map_manifolds(sdf, other, &isometry12.inverse(), prediction_distance);
+    for manifold in manifolds.iter_mut() {
+        manifold.normal = -Vector::from(isometry12 * parry::math::Vector::from(manifold.normal));
+        for contact in manifold.points.iter_mut() {
+            let point1 = isometry12 * parry::math::Point::from(contact.point2);
+            let point2 = isometry12 * parry::math::Point::from(contact.point1);
+            contact.point1 = point1.into();
+            contact.point2 = point2.into();
+        }
+    }
+    Some(manifolds)
+}
+
+/// Computes the contact manifolds between an [`SdfColliderShape`] and a convex shape
+/// by sampling support points of the convex shape and evaluating the field at them.
+///
+/// `isometry` is the isometry of the convex shape relative to the SDF.
+pub(crate) fn sdf_support_map_manifolds(
+    sdf: &SdfColliderShape,
+    other: &dyn SupportMap,
+    isometry: &Isometry<Scalar>,
+    prediction_distance: Scalar,
+) -> Vec<SdfManifold> {
+    let mut samples: Vec<Vector> = Vec::new();
+    let mut sample = |direction: Vector| {
+        let point = Vector::from(other.support_point(isometry, &direction.into()));
+        let tolerance = sdf.gradient_step() * 0.01;
+        if samples
+            .iter()
+            .all(|sample| sample.distance_squared(point) > tolerance * tolerance)
+        {
+            samples.push(point);
+        }
+        point
+    };
+
+    // Follow the gradient from the center of the shape to find the deepest point.
+    let mut point = Vector::from(isometry.translation.vector);
+    for _ in 0..3 {
+        point = sample(-sdf.gradient(point));
+    }
+
+    // Sample support points in a fixed set of directions.
+    #[cfg(feature = "2d")]
+    for i in 0..16 {
+        let angle = i as Scalar * crate::math::TAU / 16.0;
+        sample(Vector::new(angle.cos(), angle.sin()));
+    }
+    #[cfg(feature = "3d")]
+    for x in -1..=1 {
+        for y in -1..=1 {
+            for z in -1..=1 {
+                if x != 0 || y != 0 || z != 0 {
+                    sample(Vector::new(x as Scalar, y as Scalar, z as Scalar).normalize());
+                }
+            }
+        }
+    }
+
+    // Compute the contacts for samples within the prediction distance, deepest first.
+    let mut contacts: Vec<(Vector, SdfContactPoint)> = samples
+        .into_iter()
+        .filter_map(|other_point| {
+            let distance = sdf.distance(other_point);
+            (distance <= prediction_distance).then(|| {
+                let normal = sdf.gradient(other_point);
+                (
+                    normal,
+                    SdfContactPoint {
+                        point1: other_point - normal * distance,
+                        point2: other_point,
+                        distance,
+                    },
+                )
+            })
+        })
+        .collect();
+    contacts.sort_by(|(_, a), (_, b)| a.distance.total_cmp(&b.distance));
+
+    // Group the contacts into manifolds with similar normals.
+    let mut manifolds: Vec<SdfManifold> = Vec::new();
+    for (normal, contact) in contacts {
+        if let Some(manifold) = manifolds
+            .iter_mut()
+            .find(|manifold| manifold.normal.dot(normal) > 0.9)
+        {
+            if manifold.points.len() < MAX_MANIFOLD_POINTS {
+                manifold.points.push(contact);
+            }
+        } else {
+            manifolds.push(SdfManifold {
+                normal,
+                points: vec![contact],
+            });
+        }
+    }
+
+    manifolds
+}
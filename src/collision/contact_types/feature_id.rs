@@ -60,6 +60,11 @@ impl PackedFeatureId {
     pub fn is_unknown(self) -> bool {
         self == Self::UNKNOWN
     }
+
+    /// Returns the index of the identified vertex, edge, or face, without the feature type.
+    pub fn code(self) -> u32 {
+        self.0 & Self::CODE_MASK
+    }
 }
 
 impl From<u32> for PackedFeatureId {
@@ -84,8 +84,8 @@ pub mod prelude {
     pub use super::collider::{
         AabbContext, AnyCollider, ColliderAabb, ColliderBackendPlugin, ColliderDisabled,
         ColliderMarker, CollidingEntities, CollisionLayers, CollisionMargin,
-        ContactManifoldContext, IntoCollider, LayerMask, PhysicsLayer, ScalableCollider, Sensor,
-        SimpleCollider,
+        ContactManifoldContext, IntoCollider, LayerMask, PhysicsLayer, ScalableCollider,
+        SelfCollisionGroup, Sensor, SimpleCollider,
         collider_hierarchy::{ColliderHierarchyPlugin, ColliderOf, RigidBodyColliders},
         collider_transform::{ColliderTransform, ColliderTransformPlugin},
     };
@@ -95,8 +95,8 @@ pub mod prelude {
     ))]
     pub use super::collider::{
         Collider, ColliderConstructor, ColliderConstructorHierarchy,
-        ColliderConstructorHierarchyReady, ColliderConstructorReady, FillMode, TrimeshFlags,
-        VhacdParameters,
+        ColliderConstructorHierarchyReady, ColliderConstructorReady, ColliderEditingPlugin,
+        FillMode, SdfGrid, TrimeshFlags, VhacdParameters,
     };
     #[expect(deprecated)]
     pub use super::collision_events::{
@@ -109,7 +109,7 @@ pub mod prelude {
     pub use super::hooks::{ActiveCollisionHooks, CollisionHooks};
     #[expect(deprecated)]
     pub use super::narrow_phase::{
-        NarrowPhaseConfig, NarrowPhasePlugin, NarrowPhaseSet, NarrowPhaseSystems,
+        ContactDispatcher, NarrowPhaseConfig, NarrowPhasePlugin, NarrowPhaseSet, NarrowPhaseSystems,
     };
 }
 
@@ -0,0 +1,200 @@
+use core::any::TypeId;
+
+use alloc::sync::Arc;
+
+use crate::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// A function that computes [`ContactManifold`]s between two colliders.
+///
+/// The arguments are the same as for [`AnyCollider::contact_manifolds_with_context`],
+/// except for the context.
+pub type ContactManifoldsFn<C> = Arc<
+    dyn Fn(&C, Vector, Rotation, &C, Vector, Rotation, Scalar, &mut Vec<ContactManifold>)
+        + Send
+        + Sync,
+>;
+
+/// A resource for registering specialized contact algorithms for pairs of shape types.
+///
+/// Before computing contacts for a pair of colliders of type `C`,
+/// the [narrow phase](NarrowPhasePlugin) looks up the [shape types](AnyCollider::shape_type_id) of the colliders in the dispatcher.
+/// If a function has been registered for the pair, it is used to compute the [`ContactManifold`]s.
+/// Otherwise, the narrow phase falls back to [`AnyCollider::contact_manifolds_with_context`].
+///
+/// Functions only need to be registered for one order of the shape types.
+/// If the colliders are in the opposite order, the function is called with the colliders swapped,
+/// and the computed manifolds are flipped.
+///
+/// The resource is initialized by the [`NarrowPhasePlugin`] for each collider type.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct MyCollider;
+/// #
+/// # impl ComputeMassProperties2d for MyCollider {
+/// #     fn mass(&self, density: f32) -> f32 { 0.0 }
+/// #     fn unit_angular_inertia(&self) -> f32 { 0.0 }
+/// #     fn center_of_mass(&self) -> Vec2 { Vec2::ZERO }
+/// # }
+/// #
+/// # impl ComputeMassProperties3d for MyCollider {
+/// #     fn mass(&self, density: f32) -> f32 { 0.0 }
+/// #     fn unit_principal_angular_inertia(&self) -> Vec3 { Vec3::ZERO }
+/// #     fn center_of_mass(&self) -> Vec3 { Vec3::ZERO }
+/// # }
+/// #
+/// # impl AnyCollider for MyCollider {
+/// #     type Context = ();
+/// #     fn aabb_with_context(
+/// #         &self,
+/// #         position: Vector,
+/// #         _: impl Into<Rotation>,
+/// #         _: AabbContext<Self::Context>,
+/// #     ) -> ColliderAabb {
+/// #         ColliderAabb::new(position, Vector::splat(0.5))
+/// #     }
+/// #     fn contact_manifolds_with_context(
+/// #         &self,
+/// #         _: &Self,
+/// #         _: Vector,
+/// #         _: impl Into<Rotation>,
+/// #         _: Vector,
+/// #         _: impl Into<Rotation>,
+/// #         _: Scalar,
+/// #         _: &mut Vec<ContactManifold>,
+/// #         _: ContactManifoldContext<Self::Context>,
+/// #     ) {}
+/// # }
+///
+/// fn setup(mut dispatcher: ResMut<ContactDispatcher<MyCollider>>) {
+///     dispatcher.register::<MyCollider, MyCollider>(
+///         |collider1, pos1, rot1, collider2, pos2, rot2, prediction_distance, manifolds| {
+///             // Compute contact manifolds between the colliders...
+///         },
+///     );
+/// }
+/// ```
+#[derive(Resource)]
+pub struct ContactDispatcher<C: AnyCollider> {
+    functions: HashMap<(TypeId, TypeId), ContactManifoldsFn<C>>,
+}
+
+impl<C: AnyCollider> Default for ContactDispatcher<C> {
+    fn default() -> Self {
+        Self {
+            functions: HashMap::default(),
+        }
+    }
+}
+
+impl<C: AnyCollider> ContactDispatcher<C> {
+    /// Registers a function that computes [`ContactManifold`]s between colliders
+    /// whose shapes are of types `S1` and `S2`.
+    ///
+    /// The shape types are compared against [`AnyCollider::shape_type_id`].
+    /// If a function has already been registered for the pair, it is replaced.
+    ///
+    /// The function is given the colliders and their positions and rotations in the order `S1`, `S2`,
+    /// and an empty `manifolds` vector that it should push the computed manifolds into.
+    /// The normals of the manifolds should point from the first collider to the second.
+    /// If the colliders are in the order `S2`, `S1`, the arguments are swapped before calling the function,
+    /// and the resulting manifolds are flipped.
+    #[allow(clippy::type_complexity)]
+    pub fn register<S1: 'static, S2: 'static>(
+        &mut self,
+        function: impl Fn(&C, Vector, Rotation, &C, Vector, Rotation, Scalar, &mut Vec<ContactManifold>)
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.functions
+            .insert((TypeId::of::<S1>(), TypeId::of::<S2>()), Arc::new(function));
+        self
+    }
+
+    /// Removes the function registered for the shape types `S1` and `S2`.
+    ///
+    /// Returns `true` if a function was registered for the pair.
+    pub fn unregister<S1: 'static, S2: 'static>(&mut self) -> bool {
+        self.functions
+            .remove(&(TypeId::of::<S1>(), TypeId::of::<S2>()))
+            .is_some()
+    }
+
+    /// Returns `true` if a function has been registered for the shape types `S1` and `S2`
+    /// in either order.
+    pub fn contains<S1: 'static, S2: 'static>(&self) -> bool {
+        let (type1, type2) = (TypeId::of::<S1>(), TypeId::of::<S2>());
+        self.functions.contains_key(&(type1, type2)) || self.functions.contains_key(&(type2, type1))
+    }
+
+    /// Computes all [`ContactManifold`]s between two colliders using the function
+    /// registered for their shape types.
+    ///
+    /// Any existing manifolds in `manifolds` are replaced by the computed manifolds.
+    /// Returns `false` if no function has been registered for the pair,
+    /// in which case `manifolds` is left unchanged.
+    #[allow(clippy::too_many_arguments)]
+    pub fn contact_manifolds(
+        &self,
+        collider1: &C,
+        position1: Vector,
+        rotation1: Rotation,
+        collider2: &C,
+        position2: Vector,
+        rotation2: Rotation,
+        prediction_distance: Scalar,
+        manifolds: &mut Vec<ContactManifold>,
+    ) -> bool {
+        if self.functions.is_empty() {
+            return false;
+        }
+
+        let (type1, type2) = (collider1.shape_type_id(), collider2.shape_type_id());
+
+        if let Some(function) = self.functions.get(&(type1, type2)) {
+            manifolds.clear();
+            function(
+                collider1,
+                position1,
+                rotation1,
+                collider2,
+                position2,
+                rotation2,
+                prediction_distance,
+                manifolds,
+            );
+            return true;
+        }
+
+        if let Some(function) = self.functions.get(&(type2, type1)) {
+            manifolds.clear();
+            function(
+                collider2,
+                position2,
+                rotation2,
+                collider1,
+                position1,
+                rotation1,
+                prediction_distance,
+                manifolds,
+            );
+
+            // Flip the manifolds so that the normals point from the first collider to the second.
+            for manifold in manifolds.iter_mut() {
+                manifold.normal = -manifold.normal;
+                manifold.points.iter_mut().for_each(ContactPoint::flip);
+            }
+            return true;
+        }
+
+        false
+    }
+}
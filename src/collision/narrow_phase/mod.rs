@@ -2,7 +2,9 @@
 //!
 //! See [`NarrowPhasePlugin`].
 
+mod contact_dispatcher;
 mod system_param;
+pub use contact_dispatcher::{ContactDispatcher, ContactManifoldsFn};
 use system_param::ContactStatusBits;
 pub use system_param::NarrowPhase;
 #[cfg(feature = "parallel")]
@@ -56,6 +58,9 @@ use super::{CollisionDiagnostics, contact_types::ContactEdgeFlags};
 /// The plugin takes a collider type. This should be [`Collider`] for
 /// the vast majority of applications, but for custom collision backends
 /// you may use any collider that implements the [`AnyCollider`] trait.
+///
+/// Specialized contact algorithms for specific pairs of shape types
+/// can be registered in the [`ContactDispatcher`] resource for the collider type.
 pub struct NarrowPhasePlugin<C: AnyCollider, H: CollisionHooks = ()> {
     schedule: Interned<dyn ScheduleLabel>,
     /// If `true`, the narrow phase will generate [`ContactConstraint`]s
@@ -108,6 +113,7 @@ where
 
         app.init_resource::<NarrowPhaseConfig>()
             .init_resource::<ContactGraph>()
+            .init_resource::<ContactDispatcher<C>>()
             .init_resource::<ContactStatusBits>()
             .init_resource::<DefaultFriction>()
             .init_resource::<DefaultRestitution>();
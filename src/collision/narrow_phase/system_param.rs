@@ -80,6 +80,7 @@ pub struct NarrowPhase<'w, 's, C: AnyCollider> {
     #[cfg(feature = "parallel")]
     thread_local_contact_status_bits: ResMut<'w, ThreadLocalContactStatusBits>,
     pub config: Res<'w, NarrowPhaseConfig>,
+    pub contact_dispatcher: Res<'w, ContactDispatcher<C>>,
     default_friction: Res<'w, DefaultFriction>,
     default_restitution: Res<'w, DefaultRestitution>,
     length_unit: Res<'w, PhysicsLengthUnit>,
@@ -695,21 +696,34 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
                 // TODO: It'd be good to persist the manifolds and let Parry match contacts.
                 //       This isn't currently done because it requires using Parry's contact manifold type.
                 // Compute the contact manifolds using the effective speculative margin.
-                let context = ContactManifoldContext::new(
-                    collider1.entity,
-                    collider2.entity,
-                    collider_context,
-                );
-                collider1.shape.contact_manifolds_with_context(
-                    collider2.shape,
+                // Specialized contact algorithms registered for the shape pair take precedence.
+                let dispatched = self.contact_dispatcher.contact_manifolds(
+                    collider1.shape,
                     collider1.position.0,
                     *collider1.rotation,
+                    collider2.shape,
                     collider2.position.0,
                     *collider2.rotation,
                     max_contact_distance,
                     &mut contacts.manifolds,
-                    context,
                 );
+                if !dispatched {
+                    let context = ContactManifoldContext::new(
+                        collider1.entity,
+                        collider2.entity,
+                        collider_context,
+                    );
+                    collider1.shape.contact_manifolds_with_context(
+                        collider2.shape,
+                        collider1.position.0,
+                        *collider1.rotation,
+                        collider2.position.0,
+                        *collider2.rotation,
+                        max_contact_distance,
+                        &mut contacts.manifolds,
+                        context,
+                    );
+                }
 
                 // Transform and prune contact data.
                 contacts.manifolds.iter_mut().for_each(|manifold| {
@@ -468,6 +468,38 @@ impl PhysicsGizmoExt for Gizmos<'_, '_, PhysicsGizmos> {
                         self.primitive_2d(&polygon.0, isometry, color);
                     }
                 }
+                #[cfg(feature = "3d")]
+                {
+                    use crate::collision::collider::{
+                        CapsuleHullColliderShape, EllipsoidColliderShape,
+                    };
+                    use core::f32::consts::FRAC_PI_2;
+
+                    if let Some(ellipsoid) =
+                        collider.shape_scaled().as_shape::<EllipsoidColliderShape>()
+                    {
+                        // Draw the outlines of the ellipsoid along its principal planes.
+                        let half_size = ellipsoid.half_size.f32();
+                        let rotation = rotation.f32();
+                        for (plane_rotation, half_size) in [
+                            (Quat::IDENTITY, half_size.xy()),
+                            (Quat::from_rotation_x(FRAC_PI_2), half_size.xz()),
+                            (Quat::from_rotation_y(FRAC_PI_2), half_size.zy()),
+                        ] {
+                            self.ellipse(
+                                Isometry3d::new(position.f32(), rotation * plane_rotation),
+                                half_size,
+                                color,
+                            );
+                        }
+                    } else if let Some(capsule) = collider
+                        .shape_scaled()
+                        .as_shape::<CapsuleHullColliderShape>()
+                    {
+                        let (vertices, indices) = capsule.to_outline(32);
+                        self.draw_polyline(&vertices, &indices, position, rotation, color);
+                    }
+                }
             }
         }
     }
@@ -33,7 +33,7 @@ use bevy::{
 ///
 /// - The axes and center of mass of [rigid bodies](RigidBody)
 /// - [AABBs](ColliderAabb)
-/// - [Collider] wireframes
+/// - [Collider] wireframes, and custom colliders that implement [`DebugRenderCollider`]
 /// - Using different colors for [sleeping](Sleeping) bodies
 /// - [Contacts](ContactPair)
 /// - [Joints](dynamics::joints)
@@ -110,13 +110,17 @@ impl Plugin for PhysicsDebugPlugin {
                     feature = "default-collider",
                     any(feature = "parry-f32", feature = "parry-f64")
                 ))]
-                debug_render_colliders,
+                debug_render_colliders::<Collider>,
                 debug_render_contacts,
                 // TODO: Refactor joints to allow iterating over all of them without generics
                 debug_render_constraint::<FixedJoint, 2>,
                 debug_render_constraint::<PrismaticJoint, 2>,
                 debug_render_constraint::<DistanceJoint, 2>,
                 debug_render_constraint::<RevoluteJoint, 2>,
+                debug_render_constraint::<PulleyJoint, 2>,
+                debug_render_constraint::<GearJoint, 2>,
+                debug_render_constraint::<RackAndPinionJoint, 2>,
+                debug_render_constraint::<TargetJoint, 1>,
                 #[cfg(feature = "3d")]
                 debug_render_constraint::<SphericalJoint, 2>,
                 debug_render_raycasts,
@@ -248,15 +252,47 @@ fn debug_render_aabbs(
     }
 }
 
+/// A trait for rendering colliders for debugging purposes.
+///
+/// [`Collider`] is rendered by the [`PhysicsDebugPlugin`] by default. Custom colliders can implement
+/// this trait and enable debug rendering with [`ColliderBackendPlugin::with_debug_render`].
+/// The collider color and sleeping color multiplier configured in [`PhysicsGizmos`] and [`DebugRender`]
+/// are applied in the same way as for [`Collider`]. [AABBs](ColliderAabb) are rendered for all collider types.
+pub trait DebugRenderCollider: AnyCollider {
+    /// Renders the collider with the given position, rotation, and color.
+    fn debug_render(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        context: &SystemParamItem<Self::Context>,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        color: Color,
+    );
+}
+
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
 ))]
+impl DebugRenderCollider for Collider {
+    fn debug_render(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        _context: &SystemParamItem<Self::Context>,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        color: Color,
+    ) {
+        gizmos.draw_collider(self, position, rotation, color);
+    }
+}
+
+/// A system that renders all colliders of type `C` using their [`DebugRenderCollider`] implementation.
 #[allow(clippy::type_complexity)]
-fn debug_render_colliders(
-    mut colliders: Query<(
+pub fn debug_render_colliders<C: DebugRenderCollider>(
+    colliders: Query<(
         Entity,
-        &Collider,
+        &C,
         &GlobalTransform,
         Option<&ColliderOf>,
         Option<&DebugRender>,
@@ -264,9 +300,10 @@ fn debug_render_colliders(
     sleeping: Query<(), With<Sleeping>>,
     mut gizmos: Gizmos<PhysicsGizmos>,
     store: Res<GizmoConfigStore>,
+    context: StaticSystemParam<C::Context>,
 ) {
     let config = store.config::<PhysicsGizmos>().1;
-    for (entity, collider, transform, collider_rb, render_config) in &mut colliders {
+    for (entity, collider, transform, collider_rb, render_config) in &colliders {
         let position = Position::from(transform);
         let rotation = Rotation::from(transform);
         if let Some(mut color) = render_config.map_or(config.collider_color, |c| c.collider_color) {
@@ -281,11 +318,21 @@ fn debug_render_colliders(
                     color = Hsla::from_vec4(hsla * Vec4::from_array(mul)).into();
                 }
             }
-            gizmos.draw_collider(collider, position, rotation, color);
+            collider.debug_render(position.0, rotation, &context, &mut gizmos, color);
         }
     }
 }
 
+/// Adds the [`debug_render_colliders`] system for colliders of type `C`.
+pub(crate) fn add_collider_debug_render<C: DebugRenderCollider>(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        debug_render_colliders::<C>
+            .after(TransformSystems::Propagate)
+            .run_if(|store: Res<GizmoConfigStore>| store.config::<PhysicsGizmos>().0.enabled),
+    );
+}
+
 fn debug_render_contacts(
     collisions: Collisions,
     mut gizmos: Gizmos<PhysicsGizmos>,
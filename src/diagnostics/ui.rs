@@ -230,6 +230,11 @@ fn build_diagnostic_texts(cmd: &mut RelatedSpawnerCommands<ChildOf>) {
             "Contact Constraints",
             SolverDiagnostics::CONTACT_CONSTRAINT_COUNT,
         );
+        cmd.counter_text(
+            "Joint Constraints",
+            SolverDiagnostics::JOINT_CONSTRAINT_COUNT,
+        );
+        cmd.counter_text("CCD Iterations", SolverDiagnostics::SWEPT_CCD_ITERATIONS);
     });
 
     // Collision detection and solver timers
@@ -0,0 +1,526 @@
+use crate::{
+    dynamics::joints::{EntityConstraint, JointSystems},
+    prelude::*,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A joint connecting a link of an [`Articulation`] to its parent link.
+///
+/// Unlike ordinary [joints](dynamics::joints), an articulation joint is not a constraint.
+/// Instead, it defines the degrees of freedom of the child link relative to its parent,
+/// and the pose of the child link is computed from the joint [`position`](Self::position)
+/// and [`velocity`](Self::velocity). This means that articulation joints can never drift apart.
+///
+/// Like other joints, each articulation joint is defined by a [`JointFrame`] on each body.
+/// The [`kind`](Self::kind) of the joint determines how the second frame can move
+/// relative to the first frame.
+///
+/// Collisions between the parent and child link are disabled with [`JointCollisionDisabled`].
+///
+/// See the [module-level documentation](super) for more information.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[require(JointCollisionDisabled)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, MapEntities, PartialEq)]
+pub struct ArticulationJoint {
+    /// The parent link.
+    pub body1: Entity,
+    /// The child link.
+    pub body2: Entity,
+    /// The reference frame of the parent link, defining the joint anchor and basis
+    /// relative to the body transform.
+    pub frame1: JointFrame,
+    /// The reference frame of the child link, defining the joint anchor and basis
+    /// relative to the body transform.
+    pub frame2: JointFrame,
+    /// The kind of the joint, determining its degrees of freedom.
+    pub kind: ArticulationJointKind,
+    /// The axis of the joint in the basis of the first [`JointFrame`].
+    ///
+    #[cfg_attr(
+        feature = "2d",
+        doc = "For [prismatic](ArticulationJointKind::Prismatic) joints, this is the axis of translation.
+It is ignored for other kinds of joints."
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "For [revolute](ArticulationJointKind::Revolute) joints, this is the axis of rotation,
+and for [prismatic](ArticulationJointKind::Prismatic) joints, this is the axis of translation.
+It is ignored for other kinds of joints."
+    )]
+    pub axis: Vector,
+    /// The limits of the joint [`position`](Self::position).
+    ///
+    /// Only used for [revolute](ArticulationJointKind::Revolute) and
+    /// [prismatic](ArticulationJointKind::Prismatic) joints.
+    pub limit: Option<ArticulationLimit>,
+    /// The drive of the joint, driving it towards a target position and velocity.
+    pub drive: Option<ArticulationDrive>,
+    /// The damping coefficient of the joint, resisting the [`velocity`](Self::velocity) of the joint.
+    pub damping: Scalar,
+    /// The position of the joint.
+    ///
+    /// This is the angle in radians for [revolute](ArticulationJointKind::Revolute) joints,
+    /// and the distance along the [`axis`](Self::axis) for [prismatic](ArticulationJointKind::Prismatic) joints.
+    ///
+    /// Setting this teleports the child link and its descendants.
+    pub position: Scalar,
+    /// The velocity of the joint.
+    ///
+    /// This is the angular velocity in radians per second for [revolute](ArticulationJointKind::Revolute) joints,
+    /// and the linear velocity along the [`axis`](Self::axis) for [prismatic](ArticulationJointKind::Prismatic) joints.
+    pub velocity: Scalar,
+    /// The rotation of a [spherical](ArticulationJointKind::Spherical) joint,
+    /// relative to the basis of the first [`JointFrame`].
+    ///
+    /// Setting this teleports the child link and its descendants.
+    #[cfg(feature = "3d")]
+    pub rotation: Quaternion,
+    /// The angular velocity of a [spherical](ArticulationJointKind::Spherical) joint,
+    /// in the basis of the first [`JointFrame`].
+    #[cfg(feature = "3d")]
+    pub angular_velocity: Vector,
+}
+
+/// The kind of an [`ArticulationJoint`], determining its degrees of freedom.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum ArticulationJointKind {
+    /// The child link is rigidly attached to its parent, with no degrees of freedom.
+    #[default]
+    Fixed,
+    #[cfg_attr(
+        feature = "2d",
+        doc = "The child link can rotate relative to its parent, with one degree of freedom."
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "The child link can rotate about the joint axis, with one degree of freedom."
+    )]
+    Revolute,
+    /// The child link can translate along the joint axis, with one degree of freedom.
+    Prismatic,
+    /// The child link can rotate freely about the joint anchor, with three degrees of freedom.
+    #[cfg(feature = "3d")]
+    Spherical,
+}
+
+impl ArticulationJointKind {
+    /// Returns the number of degrees of freedom of the joint.
+    #[inline]
+    pub const fn dof_count(self) -> usize {
+        match self {
+            Self::Fixed => 0,
+            Self::Revolute | Self::Prismatic => 1,
+            #[cfg(feature = "3d")]
+            Self::Spherical => 3,
+        }
+    }
+}
+
+/// The limits of the [`position`](ArticulationJoint::position) of an [`ArticulationJoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ArticulationLimit {
+    /// The minimum position of the joint.
+    pub min: Scalar,
+    /// The maximum position of the joint.
+    pub max: Scalar,
+}
+
+impl ArticulationLimit {
+    /// Creates a new [`ArticulationLimit`] with the given minimum and maximum positions.
+    #[inline]
+    pub const fn new(min: Scalar, max: Scalar) -> Self {
+        Self { min, max }
+    }
+}
+
+/// A drive for an [`ArticulationJoint`], applying a force in joint space
+/// to drive the joint towards a target position and velocity.
+///
+/// The force is computed like for a spring-damper:
+///
+/// ```text
+/// force = stiffness * (target_position - position) + damping * (target_velocity - velocity)
+/// ```
+///
+/// The drive is integrated implicitly, so even very high stiffness and damping values are stable.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ArticulationDrive {
+    /// The target position of the joint.
+    ///
+    /// Only used for [revolute](ArticulationJointKind::Revolute) and
+    /// [prismatic](ArticulationJointKind::Prismatic) joints.
+    pub target_position: Scalar,
+    /// The target velocity of the joint.
+    ///
+    /// Only used for [revolute](ArticulationJointKind::Revolute) and
+    /// [prismatic](ArticulationJointKind::Prismatic) joints.
+    pub target_velocity: Scalar,
+    /// The target rotation of a [spherical](ArticulationJointKind::Spherical) joint,
+    /// relative to the basis of the first [`JointFrame`].
+    ///
+    /// The target angular velocity of spherical joints is always zero.
+    #[cfg(feature = "3d")]
+    pub target_rotation: Quaternion,
+    /// The stiffness of the drive, driving the joint towards the target position.
+    pub stiffness: Scalar,
+    /// The damping of the drive, driving the joint towards the target velocity.
+    pub damping: Scalar,
+    /// The maximum force or torque applied by the drive for each degree of freedom.
+    ///
+    /// Defaults to infinity.
+    pub max_force: Scalar,
+}
+
+impl ArticulationDrive {
+    /// Creates a new [`ArticulationDrive`] with the given stiffness and damping.
+    ///
+    /// The targets are initialized to zero, and the maximum force to infinity.
+    #[inline]
+    pub const fn new(stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            target_position: 0.0,
+            target_velocity: 0.0,
+            #[cfg(feature = "3d")]
+            target_rotation: Quaternion::IDENTITY,
+            stiffness,
+            damping,
+            max_force: Scalar::INFINITY,
+        }
+    }
+
+    /// Sets the target position of the drive.
+    #[inline]
+    pub const fn with_target_position(mut self, target: Scalar) -> Self {
+        self.target_position = target;
+        self
+    }
+
+    /// Sets the target velocity of the drive.
+    #[inline]
+    pub const fn with_target_velocity(mut self, target: Scalar) -> Self {
+        self.target_velocity = target;
+        self
+    }
+
+    /// Sets the target rotation of the drive for [spherical](ArticulationJointKind::Spherical) joints.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub const fn with_target_rotation(mut self, target: Quaternion) -> Self {
+        self.target_rotation = target;
+        self
+    }
+
+    /// Sets the maximum force or torque applied by the drive for each degree of freedom.
+    #[inline]
+    pub const fn with_max_force(mut self, max_force: Scalar) -> Self {
+        self.max_force = max_force;
+        self
+    }
+}
+
+impl EntityConstraint<2> for ArticulationJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.body1, self.body2]
+    }
+}
+
+impl ArticulationJoint {
+    /// Creates a new [`ArticulationJoint`] of the given kind between a parent and child link.
+    #[inline]
+    pub const fn new(body1: Entity, body2: Entity, kind: ArticulationJointKind) -> Self {
+        Self {
+            body1,
+            body2,
+            frame1: JointFrame::IDENTITY,
+            frame2: JointFrame::IDENTITY,
+            kind,
+            #[cfg(feature = "2d")]
+            axis: Vector::X,
+            #[cfg(feature = "3d")]
+            axis: match kind {
+                ArticulationJointKind::Revolute => Vector::Z,
+                _ => Vector::X,
+            },
+            limit: None,
+            drive: None,
+            damping: 0.0,
+            position: 0.0,
+            velocity: 0.0,
+            #[cfg(feature = "3d")]
+            rotation: Quaternion::IDENTITY,
+            #[cfg(feature = "3d")]
+            angular_velocity: Vector::ZERO,
+        }
+    }
+
+    /// Creates a new [fixed](ArticulationJointKind::Fixed) [`ArticulationJoint`] between a parent and child link.
+    #[inline]
+    pub const fn fixed(body1: Entity, body2: Entity) -> Self {
+        Self::new(body1, body2, ArticulationJointKind::Fixed)
+    }
+
+    /// Creates a new [revolute](ArticulationJointKind::Revolute) [`ArticulationJoint`] between a parent and child link.
+    #[inline]
+    pub const fn revolute(body1: Entity, body2: Entity) -> Self {
+        Self::new(body1, body2, ArticulationJointKind::Revolute)
+    }
+
+    /// Creates a new [prismatic](ArticulationJointKind::Prismatic) [`ArticulationJoint`] between a parent and child link.
+    #[inline]
+    pub const fn prismatic(body1: Entity, body2: Entity) -> Self {
+        Self::new(body1, body2, ArticulationJointKind::Prismatic)
+    }
+
+    /// Creates a new [spherical](ArticulationJointKind::Spherical) [`ArticulationJoint`] between a parent and child link.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub const fn spherical(body1: Entity, body2: Entity) -> Self {
+        Self::new(body1, body2, ArticulationJointKind::Spherical)
+    }
+
+    /// Sets the local [`JointFrame`] of the first body, configuring both the [`JointAnchor`] and [`JointBasis`].
+    #[inline]
+    pub fn with_local_frame1(mut self, frame: impl Into<Isometry>) -> Self {
+        self.frame1 = JointFrame::local(frame);
+        self
+    }
+
+    /// Sets the local [`JointFrame`] of the second body, configuring both the [`JointAnchor`] and [`JointBasis`].
+    #[inline]
+    pub fn with_local_frame2(mut self, frame: impl Into<Isometry>) -> Self {
+        self.frame2 = JointFrame::local(frame);
+        self
+    }
+
+    /// Sets the global anchor point on both bodies.
+    ///
+    /// This configures the [`JointAnchor`] of each [`JointFrame`].
+    #[inline]
+    pub const fn with_anchor(mut self, anchor: Vector) -> Self {
+        self.frame1.anchor = JointAnchor::FromGlobal(anchor);
+        self.frame2.anchor = JointAnchor::FromGlobal(anchor);
+        self
+    }
+
+    /// Sets the local anchor point on the first body.
+    ///
+    /// This configures the [`JointAnchor`] of the first [`JointFrame`].
+    #[inline]
+    pub const fn with_local_anchor1(mut self, anchor: Vector) -> Self {
+        self.frame1.anchor = JointAnchor::Local(anchor);
+        self
+    }
+
+    /// Sets the local anchor point on the second body.
+    ///
+    /// This configures the [`JointAnchor`] of the second [`JointFrame`].
+    #[inline]
+    pub const fn with_local_anchor2(mut self, anchor: Vector) -> Self {
+        self.frame2.anchor = JointAnchor::Local(anchor);
+        self
+    }
+
+    /// Sets the global basis for both bodies.
+    ///
+    /// This configures the [`JointBasis`] of each [`JointFrame`].
+    #[inline]
+    pub fn with_basis(mut self, basis: impl Into<Rot>) -> Self {
+        let basis = basis.into();
+        self.frame1.basis = JointBasis::FromGlobal(basis);
+        self.frame2.basis = JointBasis::FromGlobal(basis);
+        self
+    }
+
+    /// Sets the local basis for the first body.
+    ///
+    /// This configures the [`JointBasis`] of the first [`JointFrame`].
+    #[inline]
+    pub fn with_local_basis1(mut self, basis: impl Into<Rot>) -> Self {
+        self.frame1.basis = JointBasis::Local(basis.into());
+        self
+    }
+
+    /// Sets the local basis for the second body.
+    ///
+    /// This configures the [`JointBasis`] of the second [`JointFrame`].
+    #[inline]
+    pub fn with_local_basis2(mut self, basis: impl Into<Rot>) -> Self {
+        self.frame2.basis = JointBasis::Local(basis.into());
+        self
+    }
+
+    /// Sets the axis of the joint in the basis of the first [`JointFrame`].
+    #[inline]
+    pub const fn with_axis(mut self, axis: Vector) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Sets the limits of the joint [`position`](Self::position).
+    #[inline]
+    pub const fn with_limits(mut self, min: Scalar, max: Scalar) -> Self {
+        self.limit = Some(ArticulationLimit::new(min, max));
+        self
+    }
+
+    /// Sets the [`ArticulationDrive`] of the joint.
+    #[inline]
+    pub const fn with_drive(mut self, drive: ArticulationDrive) -> Self {
+        self.drive = Some(drive);
+        self
+    }
+
+    /// Sets the damping coefficient of the joint.
+    #[inline]
+    pub const fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the initial position of the joint.
+    #[inline]
+    pub const fn with_position(mut self, position: Scalar) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets the initial velocity of the joint.
+    #[inline]
+    pub const fn with_velocity(mut self, velocity: Scalar) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Sets the initial rotation of a [spherical](ArticulationJointKind::Spherical) joint.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub const fn with_rotation(mut self, rotation: Quaternion) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Returns the local [`JointFrame`] of the first body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, or the [`JointBasis`] is set to
+    /// [`FromGlobal`](JointBasis::FromGlobal), and the local basis has not yet
+    /// been computed, this will return `None`.
+    #[inline]
+    pub fn local_frame1(&self) -> Option<Isometry> {
+        self.frame1.get_local_isometry()
+    }
+
+    /// Returns the local [`JointFrame`] of the second body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, or the [`JointBasis`] is set to
+    /// [`FromGlobal`](JointBasis::FromGlobal), and the local basis has not yet
+    /// been computed, this will return `None`.
+    #[inline]
+    pub fn local_frame2(&self) -> Option<Isometry> {
+        self.frame2.get_local_isometry()
+    }
+
+    /// Returns the local anchor point on the first body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, this will return `None`.
+    #[inline]
+    pub const fn local_anchor1(&self) -> Option<Vector> {
+        match self.frame1.anchor {
+            JointAnchor::Local(anchor) => Some(anchor),
+            _ => None,
+        }
+    }
+
+    /// Returns the local anchor point on the second body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, this will return `None`.
+    #[inline]
+    pub const fn local_anchor2(&self) -> Option<Vector> {
+        match self.frame2.anchor {
+            JointAnchor::Local(anchor) => Some(anchor),
+            _ => None,
+        }
+    }
+
+    /// Returns the local basis of the first body.
+    ///
+    /// If the [`JointBasis`] is set to [`FromGlobal`](JointBasis::FromGlobal),
+    /// and the local basis has not yet been computed, this will return `None`.
+    #[inline]
+    pub fn local_basis1(&self) -> Option<Rot> {
+        match self.frame1.basis {
+            JointBasis::Local(basis) => Some(basis),
+            _ => None,
+        }
+    }
+
+    /// Returns the local basis of the second body.
+    ///
+    /// If the [`JointBasis`] is set to [`FromGlobal`](JointBasis::FromGlobal),
+    /// and the local basis has not yet been computed, this will return `None`.
+    #[inline]
+    pub fn local_basis2(&self) -> Option<Rot> {
+        match self.frame2.basis {
+            JointBasis::Local(basis) => Some(basis),
+            _ => None,
+        }
+    }
+}
+
+impl MapEntities for ArticulationJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.body1 = entity_mapper.get_mapped(self.body1);
+        self.body2 = entity_mapper.get_mapped(self.body2);
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        update_local_frames.in_set(JointSystems::PrepareLocalFrames),
+    );
+}
+
+fn update_local_frames(
+    mut joints: Query<&mut ArticulationJoint, Changed<ArticulationJoint>>,
+    bodies: Query<(&Position, &Rotation)>,
+) {
+    for mut joint in &mut joints {
+        if matches!(joint.frame1.anchor, JointAnchor::Local(_))
+            && matches!(joint.frame2.anchor, JointAnchor::Local(_))
+            && matches!(joint.frame1.basis, JointBasis::Local(_))
+            && matches!(joint.frame2.basis, JointBasis::Local(_))
+        {
+            continue;
+        }
+
+        let Ok([(pos1, rot1), (pos2, rot2)]) = bodies.get_many(joint.entities()) else {
+            continue;
+        };
+
+        let [frame1, frame2] =
+            JointFrame::compute_local(joint.frame1, joint.frame2, pos1.0, pos2.0, rot1, rot2);
+        joint.frame1 = frame1;
+        joint.frame2 = frame2;
+    }
+}
@@ -0,0 +1,515 @@
+//! Articulations simulated in reduced coordinates, for robotic arms, ragdolls, and other mechanisms
+//! that require exact joints.
+//!
+//! See [`ArticulationPlugin`].
+//!
+//! # Overview
+//!
+//! An [`Articulation`] is a tree of rigid bodies, called *links*, connected by [`ArticulationJoint`]s.
+//! Unlike ordinary [joints](crate::dynamics::joints), which are constraints between independently
+//! simulated bodies, articulations are simulated in *reduced coordinates*: the state of the articulation
+//! is the position and velocity of each joint, and the poses of the links are computed from them.
+//! This means that the joints can never drift apart or stretch, no matter the mass ratios between the links.
+//!
+//! The following kinds of joints are supported:
+//!
+//! - [`Fixed`](ArticulationJointKind::Fixed): Attaches the link rigidly to its parent.
+//! - [`Revolute`](ArticulationJointKind::Revolute): Rotates the link about an axis.
+//! - [`Prismatic`](ArticulationJointKind::Prismatic): Translates the link along an axis.
+#![cfg_attr(
+    feature = "3d",
+    doc = "- [`Spherical`](ArticulationJointKind::Spherical): Rotates the link freely about the joint anchor."
+)]
+//!
+//! Revolute and prismatic joints can be given [limits](ArticulationLimit), and all joints
+//! can be driven towards a target position and velocity with an [`ArticulationDrive`]
+//! or slowed down with [damping](ArticulationJoint::damping). Drives are applied in joint space
+//! and integrated implicitly, so stiff drives are stable.
+//!
+//! # Root
+//!
+//! The [`Articulation`] component is added to the root link. If the root is a [dynamic](RigidBody::Dynamic)
+//! body, the articulation has a *floating base*, and the root can move freely.
+//! Otherwise, the root is fixed in place, or moves with its velocity if it is [kinematic](RigidBody::Kinematic).
+//!
+//! The links of the articulation are found by following the [`ArticulationJoint`]s from the root,
+//! where [`body1`](ArticulationJoint::body1) is the parent and [`body2`](ArticulationJoint::body2) is the child.
+//! Each link can only have one parent, and loops are not supported. All links except the root
+//! must be [dynamic](RigidBody::Dynamic) bodies with finite mass.
+//!
+//! # Collisions
+//!
+//! The links are ordinary rigid bodies with colliders, so they collide with other bodies through
+//! the normal [collision detection](crate::collision) pipeline, and contacts are solved by the contact solver.
+//! The velocity changes applied to the links by contacts and other constraints are projected into joint space
+//! after each solver stage, which also applies the resulting forces to the rest of the articulation.
+//!
+//! Collisions between links connected by a joint are disabled with [`JointCollisionDisabled`].
+//!
+//! # Dynamics Queries
+//!
+//! The degrees of freedom of an articulation are ordered such that the degrees of freedom of a floating base
+//! come first, followed by the degrees of freedom of each joint in breadth-first order.
+//! Use [`Articulation::joint_dofs`] to find the degrees of freedom of a joint.
+//!
+//! The joint-space [mass matrix](Articulation::mass_matrix) and [joint velocities](Articulation::velocities)
+//! can be read from the [`Articulation`], and [`Articulation::inverse_dynamics`] computes the joint forces
+//! required to produce given joint accelerations, which is useful for feedforward control.
+//!
+//! # Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     // The root of the articulation is static, so it is fixed in place.
+//!     let base = commands.spawn((RigidBody::Static, Articulation::default())).id();
+//!
+//!     // An arm rotating about the base.
+//!     let arm = commands
+//!         .spawn((
+//!             RigidBody::Dynamic,
+#![cfg_attr(feature = "2d", doc = "            Collider::rectangle(0.2, 2.0),")]
+#![cfg_attr(feature = "3d", doc = "            Collider::cuboid(0.2, 2.0, 0.2),")]
+//!             Transform::from_xyz(0.0, -1.0, 0.0),
+//!         ))
+//!         .id();
+//!
+//!     // Drive the arm towards an angle of one radian.
+//!     commands.spawn(
+//!         ArticulationJoint::revolute(base, arm)
+//!             .with_local_anchor2(Vector::Y)
+//!             .with_drive(ArticulationDrive::new(500.0, 50.0).with_target_position(1.0)),
+//!     );
+//! }
+//! ```
+
+mod joint;
+mod solver;
+
+pub use joint::{ArticulationDrive, ArticulationJoint, ArticulationJointKind, ArticulationLimit};
+
+use core::ops::Range;
+
+use crate::{
+    dynamics::{
+        integrator::IntegrationSystems,
+        solver::{
+            joint_graph::JointGraphPlugin, schedule::SubstepSolverSystems, solver_body::SolverBody,
+        },
+    },
+    prelude::*,
+};
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use solver::{ArticulationSolver, Link};
+
+/// A plugin for simulating [articulations](self) in reduced coordinates.
+///
+/// The plugin is not included in [`PhysicsPlugins`] by default, and must be added manually.
+///
+/// The articulations are prepared in [`SolverSystems::PreSubstep`], and simulated in the [`SubstepSchedule`]
+/// in the [`ArticulationSystems`] sets. The joint states are written back to the [`ArticulationJoint`]s
+/// in [`SolverSystems::PostSubstep`].
+pub struct ArticulationPlugin;
+
+impl Plugin for ArticulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(JointGraphPlugin::<ArticulationJoint>::default());
+
+        joint::plugin(app);
+
+        app.add_systems(
+            PhysicsSchedule,
+            (
+                prepare_articulations
+                    .in_set(SolverSystems::PreSubstep)
+                    .after(IntegrationSystems::UpdateVelocityIncrements),
+                writeback_articulation_joints.in_set(SolverSystems::PostSubstep),
+            ),
+        );
+
+        app.configure_sets(
+            SubstepSchedule,
+            (
+                ArticulationSystems::ForwardDynamics
+                    .after(IntegrationSystems::Velocity)
+                    .before(SubstepSolverSystems::WarmStart),
+                ArticulationSystems::ProjectVelocities
+                    .after(SubstepSolverSystems::SolveConstraints)
+                    .before(IntegrationSystems::Position),
+                ArticulationSystems::IntegratePositions
+                    .after(IntegrationSystems::Position)
+                    .before(SubstepSolverSystems::Relax),
+                ArticulationSystems::ProjectRelaxedVelocities
+                    .after(SubstepSolverSystems::Relax)
+                    .before(SubstepSolverSystems::Damping),
+            ),
+        );
+
+        // Project the relaxed velocities before the XPBD solver uses them.
+        #[cfg(feature = "xpbd_joints")]
+        app.configure_sets(
+            SubstepSchedule,
+            ArticulationSystems::ProjectRelaxedVelocities
+                .before(crate::dynamics::solver::xpbd::XpbdSolverSystems::SolveConstraints),
+        );
+
+        app.add_systems(
+            SubstepSchedule,
+            (
+                solve_articulation_velocities.in_set(ArticulationSystems::ForwardDynamics),
+                project_articulation_velocities.in_set(ArticulationSystems::ProjectVelocities),
+                integrate_articulation_positions.in_set(ArticulationSystems::IntegratePositions),
+                project_articulation_velocities
+                    .in_set(ArticulationSystems::ProjectRelaxedVelocities),
+            ),
+        );
+    }
+}
+
+/// System sets for simulating [articulations](self) in the [`SubstepSchedule`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArticulationSystems {
+    /// Applies external forces, drives, and joint limits to the joint velocities.
+    ///
+    /// Runs after [`IntegrationSystems::Velocity`] and before [`SubstepSolverSystems::WarmStart`].
+    ForwardDynamics,
+    /// Projects the velocity changes applied to the links by contacts into joint space.
+    ///
+    /// Runs after [`SubstepSolverSystems::SolveConstraints`] and before [`IntegrationSystems::Position`].
+    ProjectVelocities,
+    /// Integrates the joint positions and updates the poses of the links.
+    ///
+    /// Runs after [`IntegrationSystems::Position`] and before [`SubstepSolverSystems::Relax`].
+    IntegratePositions,
+    /// Projects the velocity changes applied to the links by relaxation into joint space.
+    ///
+    /// Runs after [`SubstepSolverSystems::Relax`] and before [`SubstepSolverSystems::Damping`].
+    ProjectRelaxedVelocities,
+}
+
+/// The root of an [articulation](self), a tree of rigid bodies connected by [`ArticulationJoint`]s
+/// that is simulated in reduced coordinates.
+///
+/// If the root is a [dynamic](RigidBody::Dynamic) body, the articulation has a floating base.
+/// Otherwise, the root is fixed.
+///
+/// The data returned by the methods of this component is computed during the last physics step.
+/// It is empty if the articulation has not been simulated yet, or if it is sleeping.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct Articulation {
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    solver: ArticulationSolver,
+}
+
+impl Articulation {
+    /// Returns `true` if the articulation was simulated during the last physics step.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        !self.solver.links.is_empty()
+    }
+
+    /// Returns `true` if the root of the articulation can move freely.
+    #[inline]
+    pub fn is_floating_base(&self) -> bool {
+        self.solver.floating_base
+    }
+
+    /// Returns an iterator over the link entities of the articulation, starting from the root.
+    ///
+    /// Parents always come before their children.
+    #[inline]
+    pub fn links(&self) -> impl ExactSizeIterator<Item = Entity> + '_ {
+        self.solver.links.iter().map(|link| link.entity)
+    }
+
+    /// Returns the total number of degrees of freedom of the articulation,
+    /// including the degrees of freedom of a floating base.
+    #[inline]
+    pub fn dof_count(&self) -> usize {
+        self.solver.dof_count()
+    }
+
+    /// Returns the indices of the degrees of freedom of the given [`ArticulationJoint`] entity,
+    /// or `None` if the joint is not a part of the articulation.
+    pub fn joint_dofs(&self, joint_entity: Entity) -> Option<Range<usize>> {
+        self.solver.links.iter().find_map(|link| {
+            let joint = link.joint.as_ref()?;
+            (joint.entity == joint_entity)
+                .then(|| joint.dof_start..joint.dof_start + joint.kind.dof_count())
+        })
+    }
+
+    /// Returns the velocities of the degrees of freedom.
+    #[inline]
+    pub fn velocities(&self) -> &[Scalar] {
+        &self.solver.velocities
+    }
+
+    /// Returns the joint-space mass matrix of the articulation in row-major order.
+    ///
+    /// The matrix has [`dof_count`](Self::dof_count) rows and columns.
+    #[inline]
+    pub fn mass_matrix(&self) -> &[Scalar] {
+        &self.solver.mass_matrix
+    }
+
+    /// Computes the joint forces required to produce the given accelerations
+    /// of the degrees of freedom, taking into account the velocity-dependent forces
+    /// and the external forces such as gravity from the last substep.
+    ///
+    /// For a floating base, the first forces are the force and torque that would need to be
+    /// applied to the root, which cannot be produced by the joints.
+    ///
+    /// Returns `None` if the number of accelerations does not match [`dof_count`](Self::dof_count),
+    /// or if the articulation has not been simulated yet.
+    #[inline]
+    pub fn inverse_dynamics(&self, accelerations: &[Scalar]) -> Option<Vec<Scalar>> {
+        self.solver.inverse_dynamics(accelerations)
+    }
+}
+
+/// Builds the articulations from their joints, and computes the initial poses and velocities of the links.
+#[allow(clippy::type_complexity)]
+fn prepare_articulations(
+    mut articulations: Query<(Entity, &mut Articulation, &RigidBody)>,
+    joints: Query<(Entity, &ArticulationJoint), (Without<RigidBody>, Without<JointDisabled>)>,
+    bodies: Query<
+        (
+            &Position,
+            &Rotation,
+            &ComputedMass,
+            &ComputedAngularInertia,
+            &ComputedCenterOfMass,
+            Has<SolverBody>,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+    mut solver_bodies: Query<&mut SolverBody>,
+) {
+    // Find the child joints of each body.
+    let mut child_joints = EntityHashMap::<Vec<Entity>>::default();
+    for (entity, joint) in &joints {
+        child_joints.entry(joint.body1).or_default().push(entity);
+    }
+
+    for (root_entity, mut articulation, rigid_body) in &mut articulations {
+        // Keep the scratch buffers of the previous step to avoid reallocating them.
+        articulation.solver.clear();
+
+        let Ok((position, rotation, mass, angular_inertia, center_of_mass, is_active)) =
+            bodies.get(root_entity)
+        else {
+            continue;
+        };
+
+        // Sleeping articulations are not simulated.
+        let floating_base = rigid_body.is_dynamic();
+        if floating_base && !is_active {
+            continue;
+        }
+
+        let mut root = Link::new(
+            root_entity,
+            mass.value(),
+            *angular_inertia,
+            center_of_mass.0,
+            position.0,
+            Rot::from(*rotation),
+        );
+        if let Ok(solver_body) = solver_bodies.get(root_entity) {
+            root.linear_velocity = solver_body.linear_velocity;
+            root.angular_velocity = solver_body.angular_velocity;
+            root.solver_linear_velocity = solver_body.linear_velocity;
+            root.solver_angular_velocity = solver_body.angular_velocity;
+        }
+
+        let mut solver = ArticulationSolver::new(root, floating_base);
+        solver.scratch = core::mem::take(&mut articulation.solver.scratch);
+        let mut visited = EntityHashSet::default();
+        visited.insert(root_entity);
+        let mut is_valid = true;
+
+        // Add the links in breadth-first order.
+        let mut index = 0;
+        while index < solver.links.len() && is_valid {
+            let parent_entity = solver.links[index].entity;
+            for &joint_entity in child_joints.get(&parent_entity).into_iter().flatten() {
+                let Ok((_, joint)) = joints.get(joint_entity) else {
+                    continue;
+                };
+
+                // Loops are not supported.
+                if !visited.insert(joint.body2) {
+                    continue;
+                }
+
+                let Ok((position, rotation, mass, angular_inertia, center_of_mass, is_active)) =
+                    bodies.get(joint.body2)
+                else {
+                    is_valid = false;
+                    break;
+                };
+
+                // Every link must be an awake dynamic body with finite mass.
+                if !is_active || !mass.value().is_finite() {
+                    is_valid = false;
+                    break;
+                }
+
+                let link = Link::new(
+                    joint.body2,
+                    mass.value(),
+                    *angular_inertia,
+                    center_of_mass.0,
+                    position.0,
+                    Rot::from(*rotation),
+                );
+                if !solver.push_link(link, index, joint_entity, joint) {
+                    is_valid = false;
+                    break;
+                }
+            }
+            index += 1;
+        }
+
+        if !is_valid {
+            continue;
+        }
+
+        solver.update_kinematics();
+        write_solver_bodies(&solver, &mut solver_bodies);
+        articulation.solver = solver;
+    }
+}
+
+/// Applies external forces, drives, and joint limits to the joint velocities.
+fn solve_articulation_velocities(
+    mut articulations: Query<&mut Articulation>,
+    mut solver_bodies: Query<&mut SolverBody>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut articulation in &mut articulations {
+        let solver = &mut articulation.solver;
+        if solver.links.is_empty() {
+            continue;
+        }
+        read_solver_bodies(solver, &solver_bodies);
+        solver.solve_velocities(delta_secs);
+        write_solver_bodies(solver, &mut solver_bodies);
+    }
+}
+
+/// Projects the velocity changes applied to the links by the solver into joint space.
+fn project_articulation_velocities(
+    mut articulations: Query<&mut Articulation>,
+    mut solver_bodies: Query<&mut SolverBody>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut articulation in &mut articulations {
+        let solver = &mut articulation.solver;
+        if solver.links.is_empty() {
+            continue;
+        }
+        read_solver_bodies(solver, &solver_bodies);
+        solver.project_velocities(delta_secs);
+        write_solver_bodies(solver, &mut solver_bodies);
+    }
+}
+
+/// Integrates the joint positions and updates the poses of the links.
+fn integrate_articulation_positions(
+    mut articulations: Query<&mut Articulation>,
+    mut solver_bodies: Query<&mut SolverBody>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut articulation in &mut articulations {
+        let solver = &mut articulation.solver;
+        let Some(root) = solver.links.first_mut() else {
+            continue;
+        };
+
+        // The root is moved by the integrator like other bodies.
+        if let Ok(solver_body) = solver_bodies.get(root.entity) {
+            #[cfg(feature = "2d")]
+            {
+                root.rotation = solver_body.delta_rotation * root.start_rotation;
+            }
+            #[cfg(feature = "3d")]
+            {
+                root.rotation = solver_body.delta_rotation.0 * root.start_rotation;
+            }
+            root.position = root.start_center_of_mass() + solver_body.delta_position
+                - root.rotation * root.local_center_of_mass;
+        }
+
+        read_solver_bodies(solver, &solver_bodies);
+        solver.integrate_positions(delta_secs);
+        write_solver_bodies(solver, &mut solver_bodies);
+    }
+}
+
+/// Writes the joint positions and velocities back to the [`ArticulationJoint`]s.
+fn writeback_articulation_joints(
+    articulations: Query<&Articulation>,
+    mut joints: Query<&mut ArticulationJoint>,
+) {
+    for articulation in &articulations {
+        let solver = &articulation.solver;
+        for link in solver.links.iter().skip(1) {
+            let Some(link_joint) = &link.joint else {
+                continue;
+            };
+            if let Ok(mut joint) = joints.get_mut(link_joint.entity) {
+                solver.write_joint(link, &mut joint);
+            }
+        }
+    }
+}
+
+/// Reads the velocities of the solver bodies of the links.
+fn read_solver_bodies(solver: &mut ArticulationSolver, solver_bodies: &Query<&mut SolverBody>) {
+    for link in &mut solver.links {
+        let (linear_velocity, angular_velocity) = solver_bodies
+            .get(link.entity)
+            .map_or((Vector::ZERO, AngularVector::default()), |body| {
+                (body.linear_velocity, body.angular_velocity)
+            });
+        link.solver_linear_velocity = linear_velocity;
+        link.solver_angular_velocity = angular_velocity;
+    }
+}
+
+/// Writes the poses and velocities of the links that are moved by the articulation to their solver bodies.
+fn write_solver_bodies(solver: &ArticulationSolver, solver_bodies: &mut Query<&mut SolverBody>) {
+    for link in &solver.links {
+        // A fixed root is moved like any other body.
+        if link.parent.is_none() && !solver.floating_base {
+            continue;
+        }
+        let Ok(mut solver_body) = solver_bodies.get_mut(link.entity) else {
+            continue;
+        };
+        solver_body.delta_position = link.center_of_mass - link.start_center_of_mass();
+        solver_body.delta_rotation = Rotation::from(link.rotation * link.start_rotation.inverse());
+        solver_body.linear_velocity = link.linear_velocity;
+        solver_body.angular_velocity = link.angular_velocity;
+    }
+}
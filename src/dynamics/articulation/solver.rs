@@ -0,0 +1,1062 @@
+//! Forward and inverse dynamics for [articulations](super) in reduced coordinates.
+//!
+//! The motion of each link is expressed with a Jacobian that maps the joint velocities
+//! to the linear velocity of the link's center of mass and its angular velocity.
+//! The joint-space mass matrix is then assembled as `H = Σ Jᵀ M J` over all links,
+//! and the joint accelerations are solved from `H * qdd = τ + Σ Jᵀ f - C`,
+//! where `C` contains the velocity-dependent bias forces, and `f` contains the external forces.
+
+use super::{ArticulationDrive, ArticulationJoint, ArticulationJointKind, ArticulationLimit};
+use crate::{SymmetricTensor, prelude::*};
+use bevy::prelude::*;
+
+/// The number of degrees of freedom of a floating base.
+#[cfg(feature = "2d")]
+pub(super) const BASE_DOF_COUNT: usize = 3;
+/// The number of degrees of freedom of a floating base.
+#[cfg(feature = "3d")]
+pub(super) const BASE_DOF_COUNT: usize = 6;
+
+/// The number of iterations used for solving joint limits.
+const LIMIT_ITERATIONS: usize = 4;
+
+/// The relative regularization added to the diagonal of the mass matrix
+/// if it is not positive definite.
+const REGULARIZATION: Scalar = 1e-5;
+
+/// The state of an articulation used for simulating it in reduced coordinates.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct ArticulationSolver {
+    /// The links of the articulation, ordered such that parents come before their children.
+    /// The first link is the root.
+    pub links: Vec<Link>,
+    /// Whether the root link can move freely.
+    pub floating_base: bool,
+    /// The velocities of the degrees of freedom.
+    pub velocities: Vec<Scalar>,
+    /// The degrees of freedom of the articulation.
+    dofs: Vec<Dof>,
+    /// The joint-space mass matrix in row-major order.
+    pub mass_matrix: Vec<Scalar>,
+    /// The generalized velocity-dependent bias forces.
+    pub bias_forces: Vec<Scalar>,
+    /// The generalized external forces.
+    pub external_forces: Vec<Scalar>,
+    /// The Cholesky factor of the mass matrix and the implicit drive terms in row-major order.
+    factor: Vec<Scalar>,
+    /// Buffers reused across substeps and time steps to avoid allocations while solving.
+    pub scratch: SolverScratch,
+}
+
+/// Temporary buffers used by an [`ArticulationSolver`].
+///
+/// The contents are only meaningful within a single substep.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct SolverScratch {
+    /// The system matrix, including the implicit drive terms, in row-major order.
+    matrix: Vec<Scalar>,
+    /// The right-hand side of the system.
+    rhs: Vec<Scalar>,
+    /// The change in joint velocities solved from the system.
+    delta: Vec<Scalar>,
+    /// The implicit force terms of the drives and joint damping.
+    drives: Vec<DriveTerm>,
+    /// The joint limits and their accumulated impulses.
+    limits: Vec<LimitTerm>,
+    /// The change in joint velocities caused by a unit impulse for each limit,
+    /// with one entry per degree of freedom.
+    responses: Vec<Scalar>,
+}
+
+/// A link of an [`ArticulationSolver`].
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Link {
+    /// The rigid body entity of the link.
+    pub entity: Entity,
+    /// The index of the parent link.
+    pub parent: Option<usize>,
+    /// The joint connecting the link to its parent.
+    pub joint: Option<LinkJoint>,
+    /// The mass of the link.
+    pub mass: Scalar,
+    /// The angular inertia of the link in local space.
+    pub local_inertia: ComputedAngularInertia,
+    /// The center of mass of the link in local space.
+    pub local_center_of_mass: Vector,
+    /// The indices of the degrees of freedom affecting the motion of the link.
+    pub dofs: Vec<usize>,
+    /// The position of the link at the start of the time step.
+    pub start_position: Vector,
+    /// The rotation of the link at the start of the time step.
+    pub start_rotation: Rot,
+    /// The position of the link.
+    pub position: Vector,
+    /// The rotation of the link.
+    pub rotation: Rot,
+    /// The center of mass of the link in world space.
+    pub center_of_mass: Vector,
+    /// The angular inertia of the link in world space.
+    inertia: SymmetricTensor,
+    /// The linear velocity of the link's center of mass.
+    pub linear_velocity: Vector,
+    /// The angular velocity of the link.
+    pub angular_velocity: AngularVector,
+    /// The linear acceleration of the link's center of mass when the joint accelerations are zero.
+    linear_bias: Vector,
+    /// The angular acceleration of the link when the joint accelerations are zero.
+    angular_bias: AngularVector,
+    /// The linear and angular Jacobian columns for each degree of freedom in [`Link::dofs`].
+    columns: Vec<(Vector, AngularVector)>,
+    /// The linear velocity of the link's solver body.
+    pub solver_linear_velocity: Vector,
+    /// The angular velocity of the link's solver body.
+    pub solver_angular_velocity: AngularVector,
+    /// The linear acceleration caused by external forces in the last substep.
+    linear_acceleration: Vector,
+    /// The angular acceleration caused by external forces in the last substep.
+    angular_acceleration: AngularVector,
+}
+
+impl Link {
+    /// Creates a new [`Link`] for the given body.
+    pub fn new(
+        entity: Entity,
+        mass: Scalar,
+        local_inertia: ComputedAngularInertia,
+        local_center_of_mass: Vector,
+        position: Vector,
+        rotation: Rot,
+    ) -> Self {
+        let mut link = Self {
+            entity,
+            parent: None,
+            joint: None,
+            mass,
+            local_inertia,
+            local_center_of_mass,
+            dofs: Vec::new(),
+            start_position: position,
+            start_rotation: rotation,
+            position,
+            rotation,
+            center_of_mass: Vector::ZERO,
+            inertia: SymmetricTensor::default(),
+            linear_velocity: Vector::ZERO,
+            angular_velocity: AngularVector::default(),
+            linear_bias: Vector::ZERO,
+            angular_bias: AngularVector::default(),
+            columns: Vec::new(),
+            solver_linear_velocity: Vector::ZERO,
+            solver_angular_velocity: AngularVector::default(),
+            linear_acceleration: Vector::ZERO,
+            angular_acceleration: AngularVector::default(),
+        };
+        link.update_mass_properties();
+        link
+    }
+
+    /// Returns the center of mass of the link in world space at the start of the time step.
+    pub fn start_center_of_mass(&self) -> Vector {
+        self.start_position + self.start_rotation * self.local_center_of_mass
+    }
+
+    /// Updates the center of mass and angular inertia of the link in world space.
+    fn update_mass_properties(&mut self) {
+        self.center_of_mass = self.position + self.rotation * self.local_center_of_mass;
+        #[cfg(feature = "2d")]
+        {
+            self.inertia = self.local_inertia.value();
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.inertia = self.local_inertia.rotated(self.rotation).value();
+        }
+    }
+}
+
+/// The joint connecting a [`Link`] to its parent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct LinkJoint {
+    /// The [`ArticulationJoint`] entity.
+    pub entity: Entity,
+    /// The kind of the joint.
+    pub kind: ArticulationJointKind,
+    /// The axis of the joint in the basis of the first joint frame.
+    pub axis: Vector,
+    /// The anchor of the joint in the local space of the parent.
+    pub local_anchor1: Vector,
+    /// The anchor of the joint in the local space of the child.
+    pub local_anchor2: Vector,
+    /// The basis of the joint in the local space of the parent.
+    pub local_basis1: Rot,
+    /// The basis of the joint in the local space of the child.
+    pub local_basis2: Rot,
+    /// The limits of the joint position.
+    pub limit: Option<ArticulationLimit>,
+    /// The drive of the joint.
+    pub drive: Option<ArticulationDrive>,
+    /// The damping coefficient of the joint.
+    pub damping: Scalar,
+    /// The position of a revolute or prismatic joint.
+    pub position: Scalar,
+    /// The rotation of a spherical joint.
+    #[cfg(feature = "3d")]
+    pub rotation: Quaternion,
+    /// The index of the first degree of freedom of the joint.
+    pub dof_start: usize,
+}
+
+/// A degree of freedom of an articulation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Dof {
+    /// The angular velocity caused by a unit velocity of the degree of freedom.
+    angular: AngularVector,
+    /// The linear velocity at [`Dof::point`] caused by a unit velocity of the degree of freedom.
+    linear: Vector,
+    /// The point about which the degree of freedom rotates in world space.
+    point: Vector,
+}
+
+/// A joint-space force that is integrated implicitly, with `force(Δ) = force - coefficient * Δ`
+/// for a velocity change `Δ`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct DriveTerm {
+    dof: usize,
+    coefficient: Scalar,
+    force: Scalar,
+    max_force: Scalar,
+}
+
+/// A joint limit restricting the velocity of a degree of freedom,
+/// with the impulses accumulated for its lower and upper bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LimitTerm {
+    dof: usize,
+    min_velocity: Scalar,
+    max_velocity: Scalar,
+    lower: Scalar,
+    upper: Scalar,
+}
+
+impl ArticulationSolver {
+    /// Creates a new [`ArticulationSolver`] with the given root link.
+    ///
+    /// If `floating_base` is `true`, the velocity of the root link is used
+    /// for the degrees of freedom of the base.
+    pub fn new(mut root: Link, floating_base: bool) -> Self {
+        let mut solver = Self {
+            floating_base,
+            ..default()
+        };
+
+        if floating_base {
+            root.dofs = (0..BASE_DOF_COUNT).collect();
+            solver.dofs = vec![Dof::default(); BASE_DOF_COUNT];
+            solver
+                .velocities
+                .extend_from_slice(&root.linear_velocity.to_array());
+            #[cfg(feature = "2d")]
+            solver.velocities.push(root.angular_velocity);
+            #[cfg(feature = "3d")]
+            solver
+                .velocities
+                .extend_from_slice(&root.angular_velocity.to_array());
+
+            // The linear degrees of freedom move along the world axes,
+            // and the angular degrees of freedom rotate about them.
+            for (i, dof) in solver.dofs.iter_mut().enumerate() {
+                if i < DIM {
+                    dof.linear = Vector::AXES[i];
+                } else {
+                    #[cfg(feature = "2d")]
+                    {
+                        dof.angular = 1.0;
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        dof.angular = Vector::AXES[i - DIM];
+                    }
+                }
+            }
+        }
+
+        solver.links.push(root);
+        solver
+    }
+
+    /// Removes all links and degrees of freedom, keeping the scratch buffers.
+    pub fn clear(&mut self) {
+        let scratch = core::mem::take(&mut self.scratch);
+        *self = Self {
+            scratch,
+            ..default()
+        };
+    }
+
+    /// Returns the number of degrees of freedom.
+    pub fn dof_count(&self) -> usize {
+        self.dofs.len()
+    }
+
+    /// Adds a link connected to the `parent` link by the given joint.
+    ///
+    /// Returns `false` if the local frames of the joint have not been computed yet.
+    pub fn push_link(
+        &mut self,
+        mut link: Link,
+        parent: usize,
+        joint_entity: Entity,
+        joint: &ArticulationJoint,
+    ) -> bool {
+        let (Some(local_anchor1), Some(local_anchor2), Some(local_basis1), Some(local_basis2)) = (
+            joint.local_anchor1(),
+            joint.local_anchor2(),
+            joint.local_basis1(),
+            joint.local_basis2(),
+        ) else {
+            return false;
+        };
+
+        let dof_start = self.dofs.len();
+        let dof_count = joint.kind.dof_count();
+
+        link.parent = Some(parent);
+        link.dofs = self.links[parent].dofs.clone();
+        link.dofs.extend(dof_start..dof_start + dof_count);
+        link.joint = Some(LinkJoint {
+            entity: joint_entity,
+            kind: joint.kind,
+            axis: joint.axis.normalize_or_zero(),
+            local_anchor1,
+            local_anchor2,
+            local_basis1,
+            local_basis2,
+            limit: joint.limit,
+            drive: joint.drive,
+            damping: joint.damping,
+            position: joint.position,
+            #[cfg(feature = "3d")]
+            rotation: joint.rotation.normalize(),
+            dof_start,
+        });
+
+        self.dofs
+            .extend(core::iter::repeat_n(Dof::default(), dof_count));
+        match joint.kind {
+            ArticulationJointKind::Fixed => {}
+            ArticulationJointKind::Revolute | ArticulationJointKind::Prismatic => {
+                self.velocities.push(joint.velocity);
+            }
+            #[cfg(feature = "3d")]
+            ArticulationJointKind::Spherical => {
+                self.velocities
+                    .extend_from_slice(&joint.angular_velocity.to_array());
+            }
+        }
+
+        self.links.push(link);
+        true
+    }
+
+    /// Writes the position and velocity of the joint of the given link to the [`ArticulationJoint`].
+    pub fn write_joint(&self, link: &Link, joint: &mut ArticulationJoint) {
+        let Some(link_joint) = &link.joint else {
+            return;
+        };
+        let start = link_joint.dof_start;
+        match link_joint.kind {
+            ArticulationJointKind::Fixed => {}
+            ArticulationJointKind::Revolute | ArticulationJointKind::Prismatic => {
+                joint.position = link_joint.position;
+                joint.velocity = self.velocities[start];
+            }
+            #[cfg(feature = "3d")]
+            ArticulationJointKind::Spherical => {
+                joint.rotation = link_joint.rotation;
+                joint.angular_velocity = Vector::from_slice(&self.velocities[start..start + 3]);
+            }
+        }
+    }
+
+    /// Computes the poses, velocities, and bias accelerations of the links
+    /// from the pose and velocity of the root and the joint state.
+    pub fn update_kinematics(&mut self) {
+        let Some(root) = self.links.first_mut() else {
+            return;
+        };
+
+        root.update_mass_properties();
+        root.linear_bias = Vector::ZERO;
+        root.angular_bias = AngularVector::default();
+
+        if self.floating_base {
+            root.linear_velocity = Vector::from_slice(&self.velocities[..DIM]);
+            #[cfg(feature = "2d")]
+            {
+                root.angular_velocity = self.velocities[DIM];
+            }
+            #[cfg(feature = "3d")]
+            {
+                root.angular_velocity = Vector::from_slice(&self.velocities[DIM..BASE_DOF_COUNT]);
+            }
+            for dof in &mut self.dofs[DIM..BASE_DOF_COUNT] {
+                dof.point = root.center_of_mass;
+            }
+        } else {
+            // The velocity of a fixed base is only non-zero for kinematic bodies.
+            // Their acceleration is assumed to be zero.
+            root.linear_velocity = root.solver_linear_velocity;
+            root.angular_velocity = root.solver_angular_velocity;
+        }
+
+        for index in 1..self.links.len() {
+            let Some(parent_index) = self.links[index].parent else {
+                continue;
+            };
+            let parent = &self.links[parent_index];
+            let parent_position = parent.position;
+            let parent_rotation = parent.rotation;
+            let parent_center = parent.center_of_mass;
+            let parent_linear_velocity = parent.linear_velocity;
+            let parent_angular_velocity = parent.angular_velocity;
+            let parent_linear_bias = parent.linear_bias;
+            let parent_angular_bias = parent.angular_bias;
+
+            let link = &mut self.links[index];
+            let Some(joint) = link.joint else {
+                continue;
+            };
+
+            // Compute the pose of the link from the joint state.
+            let basis1 = parent_rotation * joint.local_basis1;
+            let anchor1 = parent_position + parent_rotation * joint.local_anchor1;
+            let (joint_rotation, joint_translation) = match joint.kind {
+                ArticulationJointKind::Fixed => (Rot::IDENTITY, Vector::ZERO),
+                #[cfg(feature = "2d")]
+                ArticulationJointKind::Revolute => {
+                    (Rotation::radians(joint.position), Vector::ZERO)
+                }
+                #[cfg(feature = "3d")]
+                ArticulationJointKind::Revolute => (
+                    Quaternion::from_axis_angle(joint.axis, joint.position),
+                    Vector::ZERO,
+                ),
+                ArticulationJointKind::Prismatic => (Rot::IDENTITY, joint.axis * joint.position),
+                #[cfg(feature = "3d")]
+                ArticulationJointKind::Spherical => (joint.rotation, Vector::ZERO),
+            };
+            let anchor = anchor1 + basis1 * joint_translation;
+            link.rotation = basis1 * joint_rotation * joint.local_basis2.inverse();
+            link.position = anchor - link.rotation * joint.local_anchor2;
+            link.update_mass_properties();
+
+            // Compute the motion of the joint's degrees of freedom.
+            let mut angular_motion = AngularVector::default();
+            let mut linear_motion = Vector::ZERO;
+            for i in 0..joint.kind.dof_count() {
+                let dof_index = joint.dof_start + i;
+                let dof = joint_dof(joint.kind, basis1, joint.axis, i, anchor);
+                angular_motion += dof.angular * self.velocities[dof_index];
+                linear_motion += dof.linear * self.velocities[dof_index];
+                self.dofs[dof_index] = dof;
+            }
+
+            // Propagate the velocities and bias accelerations from the parent to the anchor.
+            let offset = anchor - parent_center;
+            let angular_velocity = parent_angular_velocity + angular_motion;
+            let angular_bias =
+                parent_angular_bias + cross_angulars(parent_angular_velocity, angular_motion);
+            let anchor_velocity = parent_linear_velocity
+                + cross_angular(parent_angular_velocity, offset)
+                + linear_motion;
+            let anchor_bias = parent_linear_bias
+                + cross_angular(parent_angular_bias, offset)
+                + cross_angular(
+                    parent_angular_velocity,
+                    cross_angular(parent_angular_velocity, offset),
+                )
+                + 2.0 * cross_angular(parent_angular_velocity, linear_motion);
+
+            // Propagate them from the anchor to the center of mass of the link.
+            let offset = link.center_of_mass - anchor;
+            link.angular_velocity = angular_velocity;
+            link.angular_bias = angular_bias;
+            link.linear_velocity = anchor_velocity + cross_angular(angular_velocity, offset);
+            link.linear_bias = anchor_bias
+                + cross_angular(angular_bias, offset)
+                + cross_angular(angular_velocity, cross_angular(angular_velocity, offset));
+        }
+
+        // Compute the Jacobian columns of each link.
+        for link in &mut self.links {
+            link.columns.clear();
+            for &dof_index in &link.dofs {
+                let dof = &self.dofs[dof_index];
+                let linear =
+                    dof.linear + cross_angular(dof.angular, link.center_of_mass - dof.point);
+                link.columns.push((linear, dof.angular));
+            }
+        }
+    }
+
+    /// Computes the joint-space mass matrix along with the bias and external forces.
+    fn update_dynamics(&mut self) {
+        let n = self.dof_count();
+        self.mass_matrix.clear();
+        self.mass_matrix.resize(n * n, 0.0);
+        self.bias_forces.clear();
+        self.bias_forces.resize(n, 0.0);
+        self.external_forces.clear();
+        self.external_forces.resize(n, 0.0);
+
+        for link in &self.links {
+            // Gyroscopic torque is applied to the link velocities by the integrator,
+            // so it is included in the external forces rather than the bias forces.
+            let bias_force = link.mass * link.linear_bias;
+            let bias_torque = link.inertia * link.angular_bias;
+            let external_force = link.mass * link.linear_acceleration;
+            let external_torque = link.inertia * link.angular_acceleration;
+
+            for (&row, &(linear1, angular1)) in link.dofs.iter().zip(&link.columns) {
+                let linear_momentum = link.mass * linear1;
+                let angular_momentum = link.inertia * angular1;
+                for (&column, &(linear2, angular2)) in link.dofs.iter().zip(&link.columns) {
+                    self.mass_matrix[row * n + column] +=
+                        linear_momentum.dot(linear2) + dot_angular(angular_momentum, angular2);
+                }
+                self.bias_forces[row] +=
+                    linear1.dot(bias_force) + dot_angular(angular1, bias_torque);
+                self.external_forces[row] +=
+                    linear1.dot(external_force) + dot_angular(angular1, external_torque);
+            }
+        }
+    }
+
+    /// Advances the joint velocities by one substep.
+    ///
+    /// The external forces are taken from the difference between the velocities
+    /// of the solver bodies and the velocities of the links.
+    pub fn solve_velocities(&mut self, delta_secs: Scalar) {
+        let n = self.dof_count();
+        if n == 0 || delta_secs <= 0.0 {
+            return;
+        }
+
+        self.update_kinematics();
+
+        for link in &mut self.links {
+            if link.dofs.is_empty() {
+                continue;
+            }
+            link.linear_acceleration =
+                (link.solver_linear_velocity - link.linear_velocity) / delta_secs;
+            link.angular_acceleration =
+                (link.solver_angular_velocity - link.angular_velocity) / delta_secs;
+        }
+
+        self.update_dynamics();
+
+        // H * Δ = h * (τ + f - C)
+        let scratch = &mut self.scratch;
+        scratch.matrix.clone_from(&self.mass_matrix);
+        scratch.rhs.clear();
+        scratch
+            .rhs
+            .extend((0..n).map(|i| delta_secs * (self.external_forces[i] - self.bias_forces[i])));
+
+        // Integrate drives and joint damping implicitly for stability.
+        self.update_drive_terms(delta_secs);
+        let scratch = &mut self.scratch;
+        for term in &scratch.drives {
+            scratch.matrix[term.dof * n + term.dof] += delta_secs * term.coefficient;
+            scratch.rhs[term.dof] += delta_secs * term.force;
+        }
+
+        if !self.factorize_and_solve() {
+            return;
+        }
+
+        // Apply drive forces that exceed their maximum explicitly, clamped to the maximum.
+        let scratch = &mut self.scratch;
+        let mut clamped = false;
+        for term in &scratch.drives {
+            let force = term.force - term.coefficient * scratch.delta[term.dof];
+            if force.abs() > term.max_force {
+                let clamped_force = force.clamp(-term.max_force, term.max_force);
+                scratch.matrix[term.dof * n + term.dof] -= delta_secs * term.coefficient;
+                scratch.rhs[term.dof] += delta_secs * (clamped_force - term.force);
+                clamped = true;
+            }
+        }
+        if clamped && !self.factorize_and_solve() {
+            return;
+        }
+
+        for (velocity, delta) in self.velocities.iter_mut().zip(&self.scratch.delta) {
+            *velocity += delta;
+        }
+
+        self.solve_limits(delta_secs);
+        self.update_kinematics();
+    }
+
+    /// Projects the velocity changes applied to the solver bodies of the links, for example by contacts,
+    /// into joint space, and updates the joint velocities.
+    pub fn project_velocities(&mut self, delta_secs: Scalar) {
+        let n = self.dof_count();
+        if n == 0 || delta_secs <= 0.0 || self.factor.len() != n * n {
+            return;
+        }
+
+        // Δ = H⁻¹ * Σ Jᵀ M Δv
+        let delta = &mut self.scratch.delta;
+        delta.clear();
+        delta.resize(n, 0.0);
+        for link in &self.links {
+            let linear_impulse = link.mass * (link.solver_linear_velocity - link.linear_velocity);
+            let angular_impulse =
+                link.inertia * (link.solver_angular_velocity - link.angular_velocity);
+            for (&dof, &(linear, angular)) in link.dofs.iter().zip(&link.columns) {
+                delta[dof] += linear.dot(linear_impulse) + dot_angular(angular, angular_impulse);
+            }
+        }
+        cholesky_solve(&self.factor, n, delta);
+
+        for (velocity, delta) in self.velocities.iter_mut().zip(delta.iter()) {
+            *velocity += delta;
+        }
+
+        self.solve_limits(delta_secs);
+        self.update_kinematics();
+    }
+
+    /// Integrates the joint positions and updates the poses of the links.
+    ///
+    /// The pose of the root link must be updated before calling this.
+    pub fn integrate_positions(&mut self, delta_secs: Scalar) {
+        for link in &mut self.links {
+            let Some(joint) = &mut link.joint else {
+                continue;
+            };
+            let start = joint.dof_start;
+            match joint.kind {
+                ArticulationJointKind::Fixed => {}
+                ArticulationJointKind::Revolute | ArticulationJointKind::Prismatic => {
+                    joint.position += delta_secs * self.velocities[start];
+                }
+                #[cfg(feature = "3d")]
+                ArticulationJointKind::Spherical => {
+                    let angular_velocity = Vector::from_slice(&self.velocities[start..start + 3]);
+                    joint.rotation = (Quaternion::from_scaled_axis(angular_velocity * delta_secs)
+                        * joint.rotation)
+                        .normalize();
+                }
+            }
+        }
+
+        self.update_kinematics();
+    }
+
+    /// Computes the joint forces required to produce the given joint accelerations,
+    /// including the forces needed to counteract the external forces of the last substep.
+    ///
+    /// Returns `None` if the number of accelerations does not match the number of degrees of freedom,
+    /// or if the articulation has not been simulated yet.
+    pub fn inverse_dynamics(&self, accelerations: &[Scalar]) -> Option<Vec<Scalar>> {
+        let n = self.dof_count();
+        if accelerations.len() != n || self.mass_matrix.len() != n * n {
+            return None;
+        }
+
+        // τ = H * qdd + C - Σ Jᵀ f
+        Some(
+            (0..n)
+                .map(|i| {
+                    let row = &self.mass_matrix[i * n..(i + 1) * n];
+                    let inertial_force: Scalar = row
+                        .iter()
+                        .zip(accelerations)
+                        .map(|(mass, acceleration)| mass * acceleration)
+                        .sum();
+                    inertial_force + self.bias_forces[i] - self.external_forces[i]
+                })
+                .collect(),
+        )
+    }
+
+    /// Collects the implicit force terms of the drives and joint damping into the scratch buffers.
+    fn update_drive_terms(&mut self, delta_secs: Scalar) {
+        let terms = &mut self.scratch.drives;
+        terms.clear();
+
+        for link in &self.links {
+            let Some(joint) = &link.joint else {
+                continue;
+            };
+            let start = joint.dof_start;
+            let dof_count = joint.kind.dof_count();
+
+            if joint.damping > 0.0 {
+                for dof in start..start + dof_count {
+                    terms.push(DriveTerm {
+                        dof,
+                        coefficient: joint.damping,
+                        force: -joint.damping * self.velocities[dof],
+                        max_force: Scalar::INFINITY,
+                    });
+                }
+            }
+
+            let Some(drive) = joint.drive else {
+                continue;
+            };
+
+            // The position error is predicted at the end of the substep,
+            // which makes the stiffness term implicit.
+            let coefficient = delta_secs * drive.stiffness + drive.damping;
+            match joint.kind {
+                ArticulationJointKind::Fixed => {}
+                ArticulationJointKind::Revolute | ArticulationJointKind::Prismatic => {
+                    let velocity = self.velocities[start];
+                    let error = drive.target_position - joint.position - delta_secs * velocity;
+                    terms.push(DriveTerm {
+                        dof: start,
+                        coefficient,
+                        force: drive.stiffness * error
+                            + drive.damping * (drive.target_velocity - velocity),
+                        max_force: drive.max_force,
+                    });
+                }
+                #[cfg(feature = "3d")]
+                ArticulationJointKind::Spherical => {
+                    let mut rotation_error = drive.target_rotation * joint.rotation.inverse();
+                    // Take the shortest path.
+                    if rotation_error.w < 0.0 {
+                        rotation_error = -rotation_error;
+                    }
+                    let error = rotation_error.to_scaled_axis();
+                    for i in 0..3 {
+                        let velocity = self.velocities[start + i];
+                        terms.push(DriveTerm {
+                            dof: start + i,
+                            coefficient,
+                            force: drive.stiffness * (error[i] - delta_secs * velocity)
+                                - drive.damping * velocity,
+                            max_force: drive.max_force,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Factorizes the system matrix in the scratch buffers, storing the factor for [`Self::project_velocities`],
+    /// and solves the system for the right-hand side, storing the solution in the scratch buffers.
+    ///
+    /// Returns `false` if the matrix could not be factorized.
+    fn factorize_and_solve(&mut self) -> bool {
+        let n = self.dof_count();
+        let SolverScratch {
+            matrix, rhs, delta, ..
+        } = &mut self.scratch;
+
+        if !cholesky(matrix, n, &mut self.factor) {
+            // The matrix can be singular, for example if a link has no mass.
+            // Regularize it and try again.
+            let max_diagonal = (0..n)
+                .map(|i| matrix[i * n + i].abs())
+                .fold(0.0, Scalar::max);
+            let regularization = REGULARIZATION * max_diagonal.max(1.0);
+            for i in 0..n {
+                matrix[i * n + i] += regularization;
+            }
+            if !cholesky(matrix, n, &mut self.factor) {
+                self.factor.clear();
+                return false;
+            }
+        }
+
+        delta.clone_from(rhs);
+        cholesky_solve(&self.factor, n, delta);
+        true
+    }
+
+    /// Solves the joint limits by applying joint-space impulses.
+    ///
+    /// The limits are treated as speculative constraints: the velocity of a joint is only
+    /// restricted if it would move the joint past a limit by the end of the substep.
+    fn solve_limits(&mut self, delta_secs: Scalar) {
+        let n = self.dof_count();
+        if n == 0 || self.factor.len() != n * n {
+            return;
+        }
+
+        // Collect the velocity bounds and responses of the limits.
+        // The response is the change in joint velocities caused by a unit impulse.
+        let SolverScratch {
+            limits, responses, ..
+        } = &mut self.scratch;
+        limits.clear();
+        responses.clear();
+        for link in &self.links {
+            let Some(joint) = &link.joint else {
+                continue;
+            };
+            let Some(limit) = joint.limit else {
+                continue;
+            };
+            if !matches!(
+                joint.kind,
+                ArticulationJointKind::Revolute | ArticulationJointKind::Prismatic
+            ) {
+                continue;
+            }
+
+            let dof = joint.dof_start;
+            let start = responses.len();
+            responses.resize(start + n, 0.0);
+            let response = &mut responses[start..];
+            response[dof] = 1.0;
+            cholesky_solve(&self.factor, n, response);
+            if response[dof] <= Scalar::EPSILON {
+                responses.truncate(start);
+                continue;
+            }
+
+            limits.push(LimitTerm {
+                dof,
+                min_velocity: (limit.min - joint.position) / delta_secs,
+                max_velocity: (limit.max - joint.position) / delta_secs,
+                lower: 0.0,
+                upper: 0.0,
+            });
+        }
+
+        if limits.is_empty() {
+            return;
+        }
+
+        for _ in 0..LIMIT_ITERATIONS {
+            for (limit, response) in limits.iter_mut().zip(responses.chunks_exact(n)) {
+                let dof = limit.dof;
+                let inverse_effective_mass = response[dof];
+
+                // Lower limit
+                let impulse = (limit.min_velocity - self.velocities[dof]) / inverse_effective_mass;
+                let new_impulse = (limit.lower + impulse).max(0.0);
+                apply_impulse(&mut self.velocities, response, new_impulse - limit.lower);
+                limit.lower = new_impulse;
+
+                // Upper limit
+                let impulse = (limit.max_velocity - self.velocities[dof]) / inverse_effective_mass;
+                let new_impulse = (limit.upper + impulse).min(0.0);
+                apply_impulse(&mut self.velocities, response, new_impulse - limit.upper);
+                limit.upper = new_impulse;
+            }
+        }
+    }
+}
+
+/// Returns the degree of freedom with the given index for a joint of the given kind.
+fn joint_dof(
+    kind: ArticulationJointKind,
+    basis1: Rot,
+    axis: Vector,
+    #[cfg_attr(feature = "2d", allow(unused_variables))] index: usize,
+    anchor: Vector,
+) -> Dof {
+    match kind {
+        ArticulationJointKind::Fixed => Dof {
+            point: anchor,
+            ..default()
+        },
+        ArticulationJointKind::Revolute => Dof {
+            #[cfg(feature = "2d")]
+            angular: 1.0,
+            #[cfg(feature = "3d")]
+            angular: basis1 * axis,
+            linear: Vector::ZERO,
+            point: anchor,
+        },
+        ArticulationJointKind::Prismatic => Dof {
+            angular: AngularVector::default(),
+            linear: basis1 * axis,
+            point: anchor,
+        },
+        #[cfg(feature = "3d")]
+        ArticulationJointKind::Spherical => Dof {
+            angular: basis1 * Vector::AXES[index],
+            linear: Vector::ZERO,
+            point: anchor,
+        },
+    }
+}
+
+/// Applies a joint-space impulse with the given response to the joint velocities.
+fn apply_impulse(velocities: &mut [Scalar], response: &[Scalar], impulse: Scalar) {
+    for (velocity, response) in velocities.iter_mut().zip(response) {
+        *velocity += response * impulse;
+    }
+}
+
+/// Computes the Cholesky factorization `L * Lᵀ` of a symmetric positive definite `n x n` matrix,
+/// storing the lower triangular `L` in `factor` in row-major order.
+///
+/// Returns `false` if the matrix is not positive definite.
+fn cholesky(matrix: &[Scalar], n: usize, factor: &mut Vec<Scalar>) -> bool {
+    factor.clear();
+    factor.resize(n * n, 0.0);
+
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = matrix[i * n + j];
+            for k in 0..j {
+                sum -= factor[i * n + k] * factor[j * n + k];
+            }
+            if i == j {
+                if sum <= 0.0 || !sum.is_finite() {
+                    return false;
+                }
+                factor[i * n + i] = sum.sqrt();
+            } else {
+                factor[i * n + j] = sum / factor[j * n + j];
+            }
+        }
+    }
+
+    true
+}
+
+/// Solves `L * Lᵀ * x = b` in place for a Cholesky factor `L` computed with [`cholesky`].
+fn cholesky_solve(factor: &[Scalar], n: usize, b: &mut [Scalar]) {
+    // Forward substitution: L * y = b
+    for i in 0..n {
+        let mut sum = b[i];
+        for k in 0..i {
+            sum -= factor[i * n + k] * b[k];
+        }
+        b[i] = sum / factor[i * n + i];
+    }
+
+    // Back substitution: Lᵀ * x = y
+    for i in (0..n).rev() {
+        let mut sum = b[i];
+        for k in i + 1..n {
+            sum -= factor[k * n + i] * b[k];
+        }
+        b[i] = sum / factor[i * n + i];
+    }
+}
+
+/// Computes the cross product of an angular vector and a vector.
+#[cfg(feature = "2d")]
+#[inline]
+fn cross_angular(angular: Scalar, vector: Vector) -> Vector {
+    angular * vector.perp()
+}
+
+/// Computes the cross product of an angular vector and a vector.
+#[cfg(feature = "3d")]
+#[inline]
+fn cross_angular(angular: Vector, vector: Vector) -> Vector {
+    angular.cross(vector)
+}
+
+/// Computes the cross product of two angular vectors, which is always zero in 2D.
+#[cfg(feature = "2d")]
+#[inline]
+fn cross_angulars(_a: Scalar, _b: Scalar) -> Scalar {
+    0.0
+}
+
+/// Computes the cross product of two angular vectors.
+#[cfg(feature = "3d")]
+#[inline]
+fn cross_angulars(a: Vector, b: Vector) -> Vector {
+    a.cross(b)
+}
+
+/// Computes the dot product of two angular vectors.
+#[cfg(feature = "2d")]
+#[inline]
+fn dot_angular(a: Scalar, b: Scalar) -> Scalar {
+    a * b
+}
+
+/// Computes the dot product of two angular vectors.
+#[cfg(feature = "3d")]
+#[inline]
+fn dot_angular(a: Vector, b: Vector) -> Scalar {
+    a.dot(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn link(mass: Scalar) -> Link {
+        #[cfg(feature = "2d")]
+        let inertia = ComputedAngularInertia::new(1.0);
+        #[cfg(feature = "3d")]
+        let inertia = ComputedAngularInertia::new(Vector::ONE);
+        Link::new(
+            Entity::PLACEHOLDER,
+            mass,
+            inertia,
+            Vector::ZERO,
+            Vector::ZERO,
+            Rot::IDENTITY,
+        )
+    }
+
+    #[test]
+    fn prismatic_free_fall() {
+        let joint = ArticulationJoint::prismatic(Entity::PLACEHOLDER, Entity::PLACEHOLDER)
+            .with_axis(Vector::Y);
+
+        let mut solver = ArticulationSolver::new(link(1.0), false);
+        assert!(solver.push_link(link(2.0), 0, Entity::PLACEHOLDER, &joint));
+        solver.update_kinematics();
+
+        // Apply gravity as a velocity change of the link.
+        let h = 1.0 / 60.0;
+        solver.links[1].solver_linear_velocity = Vector::NEG_Y * 9.81 * h;
+        solver.solve_velocities(h);
+
+        assert_relative_eq!(solver.mass_matrix[0], 2.0);
+        assert_relative_eq!(solver.velocities[0], -9.81 * h, epsilon = 1e-5);
+        assert_relative_eq!(solver.links[1].linear_velocity.y, -9.81 * h, epsilon = 1e-5);
+
+        // No joint force is needed for the link to accelerate with gravity,
+        // and holding it in place requires a force that cancels gravity.
+        let forces = solver.inverse_dynamics(&[-9.81]).unwrap();
+        assert_relative_eq!(forces[0], 0.0, epsilon = 1e-3);
+        let forces = solver.inverse_dynamics(&[0.0]).unwrap();
+        assert_relative_eq!(forces[0], 2.0 * 9.81, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn revolute_limit() {
+        let joint = ArticulationJoint::revolute(Entity::PLACEHOLDER, Entity::PLACEHOLDER)
+            .with_local_anchor2(Vector::NEG_X)
+            .with_limits(-0.1, 0.1)
+            .with_velocity(100.0);
+
+        let mut solver = ArticulationSolver::new(link(1.0), false);
+        assert!(solver.push_link(link(1.0), 0, Entity::PLACEHOLDER, &joint));
+        solver.update_kinematics();
+
+        let h = 1.0 / 60.0;
+        solver.solve_velocities(h);
+
+        // The joint can only move up to the limit during the substep.
+        assert_relative_eq!(solver.velocities[0], 0.1 / h, epsilon = 1e-3);
+
+        solver.integrate_positions(h);
+        let Some(joint) = &solver.links[1].joint else {
+            panic!("link should have a joint");
+        };
+        assert_relative_eq!(joint.position, 0.1, epsilon = 1e-5);
+    }
+}
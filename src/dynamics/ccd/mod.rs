@@ -203,9 +203,38 @@
 //! However, speculative contacts do detect secondary collisions quite well,
 //! so using the two together can help mitigate the issue.
 //!
-//! Time loss and chain reactions could also be better accounted for with a substepped
-//! time-of-impact solver, but that would be much more expensive and complex,
-//! so it is not yet supported.
+//! Time loss can be avoided by setting [`SweptCcd::max_iterations`] to a value greater than one.
+//! Instead of discarding the rest of the motion at the time of impact, the impact is then resolved
+//! using the [`Restitution`] and [`Friction`] of the colliders, and the remaining motion is reflected
+//! or slid along the surface and swept again, up to the given number of times per time step.
+//! This is more expensive, so it is primarily useful for things like fast bullets and pinballs.
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     // Spawn a bouncy ball that continues its motion after hitting a surface,
+//!     // resolving up to four impacts per time step.
+//!     commands.spawn((
+//!         RigidBody::Dynamic,
+#![cfg_attr(feature = "2d", doc = "        Collider::circle(0.1),")]
+#![cfg_attr(feature = "3d", doc = "        Collider::sphere(0.1),")]
+//!         Restitution::new(0.9),
+//!         SweptCcd::default().with_max_iterations(4),
+//!     ));
+//! }
+//! ```
+//!
+//! Swept CCD normally stops the swept body at the time of impact, which is not desirable
+//! for [kinematic](RigidBody::Kinematic) bodies that should follow their velocity exactly.
+//! For fast kinematic bodies such as swinging hammers or elevators moved by animation,
+//! [`SweptCcd::push_dynamic`] can be enabled to instead push the dynamic bodies hit by the sweep,
+//! moving them to the time of impact and matching their velocity with the kinematic body.
+//!
+//! The total number of CCD iterations performed during a time step is reported
+//! in [`SolverDiagnostics::swept_ccd_iterations`](crate::dynamics::solver::SolverDiagnostics::swept_ccd_iterations).
 //!
 //! ## Other Ways to Avoid Tunneling
 //!
@@ -231,7 +260,7 @@
 //! However, this comes at the cost of worse performance for the entire simulation.
 
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
-use super::solver::solver_body::SolverBody;
+use super::solver::solver_body::{SolverBody, SolverBodyInertia};
 use crate::prelude::*;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 use bevy::ecs::query::QueryData;
@@ -416,6 +445,26 @@ pub struct SweptCcd {
     ///
     /// The default is `0.0`, meaning that CCD is performed regardless of the relative velocity.
     pub angular_threshold: Scalar,
+    /// The maximum number of time-of-impact iterations performed for the body per time step.
+    ///
+    /// If `1`, the body is stopped at the first time of impact, and the rest of its motion
+    /// for the time step is discarded. This can lead to [time loss](self#caveats-of-swept-ccd).
+    ///
+    /// If greater than `1`, the impact is resolved using the [`Restitution`] and [`Friction`]
+    /// of the colliders, and the remaining motion is reflected or slid along the surface
+    /// and swept again until no impact is found or the iterations run out.
+    ///
+    /// The default is `1`.
+    pub max_iterations: u32,
+    /// If `true` and the body is [kinematic](RigidBody::Kinematic), the body is not stopped
+    /// at the time of impact. Instead, the dynamic bodies hit by its sweep are pushed
+    /// along with it, preventing fast kinematic bodies from tunneling through them.
+    ///
+    /// This is useful for things like swinging hammers, elevators moved by animation,
+    /// and kinematic characters. It has no effect for dynamic bodies.
+    ///
+    /// The default is `false`.
+    pub push_dynamic: bool,
 }
 
 impl Default for SweptCcd {
@@ -444,6 +493,8 @@ impl SweptCcd {
             include_dynamic: true,
             linear_threshold: 0.0,
             angular_threshold: 0.0,
+            max_iterations: 1,
+            push_dynamic: false,
         }
     }
 
@@ -466,6 +517,26 @@ impl SweptCcd {
         self.include_dynamic = should_include;
         self
     }
+
+    /// Sets the maximum number of time-of-impact iterations performed for the body per time step.
+    ///
+    /// With more than one iteration, the motion of the body continues after the time of impact
+    /// instead of being discarded. See [`SweptCcd::max_iterations`] for more information.
+    #[inline]
+    pub const fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets whether a [kinematic](RigidBody::Kinematic) body pushes the dynamic bodies
+    /// hit by its sweep instead of stopping at the time of impact.
+    ///
+    /// See [`SweptCcd::push_dynamic`] for more information.
+    #[inline]
+    pub const fn push_dynamic(mut self, should_push: bool) -> Self {
+        self.push_dynamic = should_push;
+        self
+    }
 }
 
 /// The algorithm used for [Swept Continuous Collision Detection](self#swept-ccd).
@@ -478,8 +549,7 @@ impl SweptCcd {
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
 pub enum SweepMode {
     /// [Swept CCD](self#swept-ccd) is performed using linear time-of-impact queries
-    /// from the previous positions of [`SweptCcd`] bodies to their current positions,
-    /// stopping the bodies at the first time of impact.
+    /// from the previous positions of [`SweptCcd`] bodies to their current positions.
     ///
     /// This mode only considers translational motion, and can lead to tunneling
     /// against thin, fast-spinning objects. For the more expensive version
@@ -487,8 +557,7 @@ pub enum SweepMode {
     Linear,
 
     /// [Swept CCD](self#swept-ccd) is performed using non-linear time-of-impact queries
-    /// from the previous positions of [`SweptCcd`] bodies to their current positions,
-    /// stopping the bodies at the first time of impact.
+    /// from the previous positions of [`SweptCcd`] bodies to their current positions.
     ///
     /// This mode considers both translational and rotational motion.
     /// For the cheaper version that only considers translational motion,
@@ -503,48 +572,86 @@ pub enum SweepMode {
 struct SweptCcdBodyQuery {
     entity: Entity,
     solver_body: Option<&'static mut SolverBody>,
+    inertia: Option<&'static SolverBodyInertia>,
     rb: &'static RigidBody,
     pos: &'static Position,
     rot: &'static Rotation,
     ccd: Option<&'static SweptCcd>,
     collider: &'static Collider,
     com: &'static ComputedCenterOfMass,
+    friction: Option<&'static Friction>,
+    restitution: Option<&'static Restitution>,
+}
+
+/// The first impact found when sweeping a [`SweptCcd`] body.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+struct CcdImpact {
+    /// The time of impact relative to the start of the sweep.
+    toi: Scalar,
+    /// The entity of the body that was hit.
+    body: Entity,
+    /// The entity of the collider that was hit.
+    collider: Entity,
+    /// The local-space outward normal on the shape of the swept body at the time of impact.
+    local_normal: Vector,
+    /// The local-space contact point on the shape of the swept body at the time of impact.
+    local_point1: Vector,
+    /// The local-space contact point on the shape of the body that was hit at the time of impact.
+    local_point2: Vector,
 }
 
 /// Performs [sweep-based mContinuous Collision Detection](self#swept-ccd)
 /// by performing time-of-impact queries from the previous positions of [`SweptCcd`] bodies
-/// to their current positions, stopping the bodies at the first time of impact.
+/// to their current positions.
+///
+/// By default, the bodies are stopped at the first time of impact. This approach can lead
+/// to "time loss" or "time stealing", because the bodies are essentially moved back in time,
+/// making them appear to momentarily move slower. Secondary contacts are also not accounted for.
 ///
-/// This approach can lead to "time loss" or "time stealing", because the bodies
-/// are essentially moved back in time, making them appear to momentarily move slower.
-/// Secondary contacts are also not accounted for.
+/// If [`SweptCcd::max_iterations`] is greater than one, the impact is instead resolved,
+/// and the rest of the motion is swept again from the time of impact.
 #[allow(clippy::useless_conversion)]
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 fn solve_swept_ccd(
     ccd_query: Query<Entity, With<SweptCcd>>,
     bodies: Query<SweptCcdBodyQuery>,
-    colliders: Query<(&Collider, &ColliderOf)>,
+    colliders: Query<(
+        Entity,
+        &Collider,
+        &ColliderOf,
+        Option<&Friction>,
+        Option<&Restitution>,
+    )>,
     time: Res<Time>,
     contact_graph: Res<ContactGraph>,
     narrow_phase_config: Res<NarrowPhaseConfig>,
+    default_friction: Res<DefaultFriction>,
+    default_restitution: Res<DefaultRestitution>,
+    mut commands: Commands,
     mut diagnostics: ResMut<SolverDiagnostics>,
 ) {
     let start = crate::utils::Instant::now();
 
     let delta_secs = time.delta_seconds_adjusted();
+    let prediction_distance = narrow_phase_config.default_speculative_margin;
 
     let mut dummy_body = SolverBody::default();
+    let mut iteration_count = 0;
 
     // TODO: Parallelize.
     for entity in &ccd_query {
         // Get the CCD body.
         let Ok(SweptCcdBodyQueryItem {
             solver_body: Some(mut solver_body1),
+            inertia: inertia1,
+            rb: rb1,
             pos: &prev_pos1,
             rot: &prev_rot1,
             ccd: Some(ccd1),
             collider: collider1,
             com: com1,
+            friction: friction1,
+            restitution: restitution1,
             ..
         }) = (
             // Safety: `get_unchecked` is only unsafe if there are multiple mutable references
@@ -559,92 +666,77 @@ fn solve_swept_ccd(
         let lin_vel1 = solver_body1.linear_velocity;
         let ang_vel1 = solver_body1.angular_velocity;
 
-        // The smallest time of impact found. Starts at the largest value,
-        // how long a body moves during a single frame due to position integration.
-        let (mut min_toi, mut min_toi_entity) = (delta_secs, None);
-
-        // Iterate through colliders intersecting the AABB of the CCD body.
-        let intersecting_entities = contact_graph.entities_colliding_with(entity);
-        for (collider2, &ColliderOf { body: entity2 }) in colliders.iter_many(intersecting_entities)
-        {
-            debug_assert_ne!(entity, entity2, "collider AABB cannot intersect itself");
-
-            // Get the body associated with the collider.
-            // Safety: `AabbIntersections` should never contain the entity of a collider
-            //         attached to the first body, and the entities are also ensured to be different above.
-            if let Ok(body2) = unsafe { bodies.get_unchecked(entity2) } {
-                if !ccd1.include_dynamic && body2.rb.is_dynamic() {
-                    continue;
-                }
+        if ccd1.push_dynamic && rb1.is_kinematic() {
+            iteration_count += 1;
 
-                // Get the solver body associated with the second body.
-                let solver_body2 = body2
-                    .solver_body
-                    .map_or(&dummy_body, |body| body.into_inner());
+            let motion1 = NonlinearRigidMotion::new(
+                make_isometry(prev_pos1, prev_rot1),
+                com1.0.into(),
+                lin_vel1.into(),
+                ang_vel1.into(),
+            );
 
-                let prev_pos2 = *body2.pos;
-                let prev_rot2 = *body2.rot;
-                let lin_vel2 = solver_body2.linear_velocity;
-                let ang_vel2 = solver_body2.angular_velocity;
+            // Kinematic bodies follow their velocity, so instead of stopping the body,
+            // push the dynamic bodies that it would hit.
+            // Safety: The CCD body is excluded from the sweep, so there are no aliasing mutable references.
+            unsafe {
+                push_dynamic_bodies(
+                    entity,
+                    ccd1,
+                    collider1,
+                    &motion1,
+                    lin_vel1,
+                    ang_vel1,
+                    prev_rot1,
+                    com1.0,
+                    delta_secs,
+                    &bodies,
+                    &colliders,
+                    &contact_graph,
+                    prediction_distance,
+                    &mut commands,
+                );
+            }
 
-                #[cfg(feature = "2d")]
-                let ang_vel_below_threshold = (ang_vel1 - ang_vel2).abs() < ccd1.angular_threshold;
-                #[cfg(feature = "3d")]
-                let ang_vel_below_threshold =
-                    (ang_vel1 - ang_vel2).length_squared() < ccd1.angular_threshold.powi(2);
-
-                // If both the relative linear and relative angular velocity
-                // are below the defined thresholds, skip this body.
-                if ang_vel_below_threshold
-                    && (lin_vel1 - lin_vel2).length_squared() < ccd1.linear_threshold.powi(2)
-                {
-                    continue;
-                }
+            continue;
+        }
 
-                let iso1 = make_isometry(prev_pos1, prev_rot1);
-                let iso2 = make_isometry(prev_pos2, prev_rot2);
+        if ccd1.max_iterations <= 1 {
+            iteration_count += 1;
 
-                // TODO: Support child colliders
-                let motion1 = NonlinearRigidMotion::new(
-                    iso1,
-                    com1.0.into(),
-                    lin_vel1.into(),
-                    ang_vel1.into(),
-                );
-                let motion2 = NonlinearRigidMotion::new(
-                    iso2,
-                    body2.com.0.into(),
-                    lin_vel2.into(),
-                    ang_vel2.into(),
-                );
+            let motion1 = NonlinearRigidMotion::new(
+                make_isometry(prev_pos1, prev_rot1),
+                com1.0.into(),
+                lin_vel1.into(),
+                ang_vel1.into(),
+            );
 
-                let sweep_mode = if ccd1.mode == SweepMode::Linear
-                    && body2.ccd.is_none_or(|ccd| ccd.mode == SweepMode::Linear)
-                {
-                    SweepMode::Linear
-                } else {
-                    SweepMode::NonLinear
-                };
-
-                if let Some(toi) = compute_ccd_toi(
-                    sweep_mode,
-                    &motion1,
+            // Find the smallest time of impact during the time step.
+            // Safety: The CCD body is excluded from the sweep, so there are no aliasing mutable references.
+            let Some(impact) = (unsafe {
+                find_first_impact(
+                    entity,
+                    ccd1,
                     collider1,
-                    &motion2,
-                    collider2,
-                    min_toi,
-                    narrow_phase_config.default_speculative_margin,
-                ) {
-                    min_toi = toi;
-                    min_toi_entity = Some(entity2);
-                }
-            }
-        }
+                    &motion1,
+                    lin_vel1,
+                    ang_vel1,
+                    0.0,
+                    delta_secs,
+                    &bodies,
+                    &colliders,
+                    &contact_graph,
+                    prediction_distance,
+                )
+            }) else {
+                continue;
+            };
+
+            // Advance the bodies from the previous poses to the first time of impact.
+            let Ok(body2) = (unsafe { bodies.get_unchecked(impact.body) }) else {
+                continue;
+            };
 
-        // Advance the bodies from the previous poses to the first time of impact.
-        if let Some(Ok(body2)) =
-            min_toi_entity.map(|entity| unsafe { bodies.get_unchecked(entity) })
-        {
             // Get the solver body for the entity that was hit.
             let solver_body2 = body2
                 .solver_body
@@ -654,7 +746,7 @@ fn solve_swept_ccd(
             let ang_vel2 = solver_body2.angular_velocity;
 
             // Overshoot slightly to make sure the bodies advance and don't get stuck.
-            let min_toi = min_toi * 1.0001;
+            let min_toi = impact.toi * 1.0001;
 
             solver_body1.delta_position = min_toi * lin_vel1;
 
@@ -680,10 +772,536 @@ fn solve_swept_ccd(
                 let delta_rot = Quaternion::from_scaled_axis(ang_vel2 * min_toi);
                 solver_body2.delta_rotation.0 = delta_rot * solver_body2.delta_rotation.0;
             }
+
+            continue;
+        }
+
+        // Sub-stepped CCD: Advance the body to each time of impact, resolve the impact,
+        // and sweep the remaining motion again, up to `max_iterations` times.
+        let inertia1 = inertia1.unwrap_or(&SolverBodyInertia::DUMMY);
+        let mut lin_vel1 = lin_vel1;
+        let mut ang_vel1 = ang_vel1;
+        let mut delta_position = Vector::ZERO;
+        let mut delta_rotation = Rotation::IDENTITY;
+        let mut elapsed = 0.0;
+        let mut hit_any = false;
+
+        for iteration in 0..ccd1.max_iterations {
+            iteration_count += 1;
+
+            let remaining = delta_secs - elapsed;
+            if remaining <= 0.0 {
+                break;
+            }
+
+            // Compute the current pose of the body from the accumulated deltas.
+            let rotation = delta_rotation * prev_rot1;
+            let position = prev_pos1.0 + prev_rot1 * com1.0 + delta_position - rotation * com1.0;
+            let motion1 = NonlinearRigidMotion::new(
+                make_isometry(position, rotation),
+                com1.0.into(),
+                lin_vel1.into(),
+                ang_vel1.into(),
+            );
+
+            // Safety: The CCD body is excluded from the sweep, so there are no aliasing mutable references.
+            let impact = unsafe {
+                find_first_impact(
+                    entity,
+                    ccd1,
+                    collider1,
+                    &motion1,
+                    lin_vel1,
+                    ang_vel1,
+                    elapsed,
+                    remaining,
+                    &bodies,
+                    &colliders,
+                    &contact_graph,
+                    prediction_distance,
+                )
+            };
+
+            let Some(impact) = impact else {
+                // No impact, so the body is free to move for the rest of the time step.
+                if hit_any {
+                    delta_position += lin_vel1 * remaining;
+                    delta_rotation = integrate_rotation(delta_rotation, ang_vel1, remaining);
+                }
+                break;
+            };
+
+            hit_any = true;
+
+            // Advance the body to the time of impact.
+            delta_position += lin_vel1 * impact.toi;
+            delta_rotation = integrate_rotation(delta_rotation, ang_vel1, impact.toi);
+            elapsed += impact.toi;
+
+            // If the iterations ran out, the rest of the motion is discarded.
+            if iteration + 1 == ccd1.max_iterations {
+                break;
+            }
+
+            let (Ok(body2), Ok((_, _, _, collider_friction2, collider_restitution2))) = (
+                unsafe { bodies.get_unchecked(impact.body) },
+                colliders.get(impact.collider),
+            ) else {
+                break;
+            };
+
+            // Compute the contact normal and the anchors of the contact point relative to the centers of mass.
+            // The body that was hit is moved to its pose at the time of impact.
+            let rotation1 = delta_rotation * prev_rot1;
+            let normal = rotation1 * impact.local_normal;
+            let anchor1 = rotation1 * (impact.local_point1 - com1.0);
+            let solver_body2 = body2
+                .solver_body
+                .map_or(&mut dummy_body, |body| body.into_inner());
+            let rotation2 = integrate_rotation(*body2.rot, solver_body2.angular_velocity, elapsed);
+            let anchor2 = rotation2 * (impact.local_point2 - body2.com.0);
+
+            let friction = friction1
+                .copied()
+                .unwrap_or(default_friction.0)
+                .combine(
+                    collider_friction2
+                        .or(body2.friction)
+                        .copied()
+                        .unwrap_or(default_friction.0),
+                )
+                .dynamic_coefficient;
+            let restitution = restitution1
+                .copied()
+                .unwrap_or(default_restitution.0)
+                .combine(
+                    collider_restitution2
+                        .or(body2.restitution)
+                        .copied()
+                        .unwrap_or(default_restitution.0),
+                )
+                .coefficient;
+
+            // Only dynamic bodies that are hit receive an impulse.
+            let inertia2 = body2
+                .inertia
+                .filter(|_| body2.rb.is_dynamic())
+                .unwrap_or(&SolverBodyInertia::DUMMY);
+            let inv_mass1 = inertia1.effective_inv_mass();
+            let inv_mass2 = inertia2.effective_inv_mass();
+            let inv_angular_inertia1 = inertia1.effective_inv_angular_inertia();
+            let inv_angular_inertia2 = inertia2.effective_inv_angular_inertia();
+
+            // Resolve the impact with restitution and friction, reflecting or sliding the remaining motion.
+            let impulse = compute_ccd_impulse(
+                velocity_at_point(lin_vel1, ang_vel1, anchor1)
+                    - solver_body2.velocity_at_point(anchor2),
+                normal,
+                [anchor1, anchor2],
+                [inv_mass1, inv_mass2],
+                [&inv_angular_inertia1, &inv_angular_inertia2],
+                friction,
+                restitution,
+            );
+            lin_vel1 += inv_mass1 * impulse;
+            ang_vel1 += inv_angular_inertia1 * cross(anchor1, impulse);
+
+            // The body that was hit moves with its old velocity until the time of impact,
+            // and with the new velocity for the rest of the time step.
+            let lin_vel_change2 = -inv_mass2 * impulse;
+            let ang_vel_change2 = -(inv_angular_inertia2 * cross(anchor2, impulse));
+            let remaining = delta_secs - elapsed;
+            solver_body2.linear_velocity += lin_vel_change2;
+            solver_body2.angular_velocity += ang_vel_change2;
+            solver_body2.delta_position += lin_vel_change2 * remaining;
+            solver_body2.delta_rotation =
+                integrate_rotation(solver_body2.delta_rotation, ang_vel_change2, remaining);
+        }
+
+        if hit_any {
+            solver_body1.linear_velocity = lin_vel1;
+            solver_body1.angular_velocity = ang_vel1;
+            solver_body1.delta_position = delta_position;
+            solver_body1.delta_rotation = delta_rotation;
         }
     }
 
     diagnostics.swept_ccd += start.elapsed();
+    diagnostics.swept_ccd_iterations = iteration_count;
+}
+
+/// Finds the first impact of the given motion of a [`SweptCcd`] body against the bodies
+/// whose colliders are intersecting its AABB.
+///
+/// The other bodies are moved to their poses at `elapsed` seconds into the time step,
+/// and the sweep covers the next `max_toi` seconds.
+///
+/// # Safety
+///
+/// The caller must not hold mutable references to the components of the bodies being swept against.
+#[allow(clippy::too_many_arguments, clippy::useless_conversion)]
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+unsafe fn find_first_impact(
+    entity: Entity,
+    ccd1: &SweptCcd,
+    collider1: &Collider,
+    motion1: &NonlinearRigidMotion,
+    lin_vel1: Vector,
+    ang_vel1: AngularVector,
+    elapsed: Scalar,
+    max_toi: Scalar,
+    bodies: &Query<SweptCcdBodyQuery>,
+    colliders: &Query<(
+        Entity,
+        &Collider,
+        &ColliderOf,
+        Option<&Friction>,
+        Option<&Restitution>,
+    )>,
+    contact_graph: &ContactGraph,
+    prediction_distance: Scalar,
+) -> Option<CcdImpact> {
+    // The smallest time of impact found. Starts at the largest value,
+    // how long a body moves during the rest of the time step.
+    let mut min_toi = max_toi;
+    let mut first_impact = None;
+
+    // Iterate through colliders intersecting the AABB of the CCD body.
+    let intersecting_entities = contact_graph.entities_colliding_with(entity);
+    for (collider_entity, collider2, &ColliderOf { body: entity2 }, ..) in
+        colliders.iter_many(intersecting_entities)
+    {
+        debug_assert_ne!(entity, entity2, "collider AABB cannot intersect itself");
+
+        // Get the body associated with the collider.
+        // Safety: `AabbIntersections` should never contain the entity of a collider
+        //         attached to the first body, and the entities are also ensured to be different above.
+        let Ok(body2) = (unsafe { bodies.get_unchecked(entity2) }) else {
+            continue;
+        };
+
+        if !ccd1.include_dynamic && body2.rb.is_dynamic() {
+            continue;
+        }
+
+        // Get the velocities of the second body.
+        let (lin_vel2, ang_vel2) = body2
+            .solver_body
+            .as_ref()
+            .map_or((Vector::ZERO, AngularVector::default()), |body| {
+                (body.linear_velocity, body.angular_velocity)
+            });
+
+        // If both the relative linear and relative angular velocity
+        // are below the defined thresholds, skip this body.
+        if is_below_velocity_threshold(ccd1, lin_vel1 - lin_vel2, ang_vel1 - ang_vel2) {
+            continue;
+        }
+
+        // TODO: Support child colliders
+        let mut motion2 = NonlinearRigidMotion::new(
+            make_isometry(*body2.pos, *body2.rot),
+            body2.com.0.into(),
+            lin_vel2.into(),
+            ang_vel2.into(),
+        );
+
+        // Move the second body to its pose at the start of the sweep.
+        if elapsed > 0.0 {
+            motion2.start = motion2.position_at_time(elapsed);
+        }
+
+        let sweep_mode = if ccd1.mode == SweepMode::Linear
+            && body2.ccd.is_none_or(|ccd| ccd.mode == SweepMode::Linear)
+        {
+            SweepMode::Linear
+        } else {
+            SweepMode::NonLinear
+        };
+
+        if let Some(hit) = compute_ccd_toi(
+            sweep_mode,
+            motion1,
+            collider1,
+            &motion2,
+            collider2,
+            min_toi,
+            prediction_distance,
+        ) {
+            min_toi = hit.time_of_impact;
+            first_impact = Some(CcdImpact {
+                toi: hit.time_of_impact,
+                body: entity2,
+                collider: collider_entity,
+                local_normal: Vector::from(hit.normal1),
+                local_point1: Vector::from(hit.witness1),
+                local_point2: Vector::from(hit.witness2),
+            });
+        }
+    }
+
+    first_impact
+}
+
+/// Sweeps a kinematic [`SweptCcd`] body against the dynamic bodies whose colliders
+/// are intersecting its AABB, and pushes the bodies that it would hit during the time step.
+///
+/// Each hit body moves with its old velocity until the time of impact, where an impulse is applied
+/// at the contact point so that its velocity along the contact normal matches the velocity
+/// of the kinematic body. The body then moves and rotates with the new velocity for the rest
+/// of the time step, staying ahead of the kinematic body.
+///
+/// Sleeping bodies cannot be moved by the solver, so they are woken up instead.
+///
+/// # Safety
+///
+/// The caller must not hold mutable references to the components of the bodies being swept against.
+#[allow(clippy::too_many_arguments, clippy::useless_conversion)]
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+unsafe fn push_dynamic_bodies(
+    entity: Entity,
+    ccd1: &SweptCcd,
+    collider1: &Collider,
+    motion1: &NonlinearRigidMotion,
+    lin_vel1: Vector,
+    ang_vel1: AngularVector,
+    prev_rot1: Rotation,
+    com1: Vector,
+    delta_secs: Scalar,
+    bodies: &Query<SweptCcdBodyQuery>,
+    colliders: &Query<(
+        Entity,
+        &Collider,
+        &ColliderOf,
+        Option<&Friction>,
+        Option<&Restitution>,
+    )>,
+    contact_graph: &ContactGraph,
+    prediction_distance: Scalar,
+    commands: &mut Commands,
+) {
+    let intersecting_entities = contact_graph.entities_colliding_with(entity);
+    for (_, collider2, &ColliderOf { body: entity2 }, ..) in
+        colliders.iter_many(intersecting_entities)
+    {
+        debug_assert_ne!(entity, entity2, "collider AABB cannot intersect itself");
+
+        // Safety: The entities are ensured to be different above.
+        let Ok(body2) = (unsafe { bodies.get_unchecked(entity2) }) else {
+            continue;
+        };
+
+        if !body2.rb.is_dynamic() {
+            continue;
+        }
+
+        let Some(solver_body2) = body2.solver_body else {
+            // The body is sleeping. Wake it up so that it can be pushed by contacts.
+            if compute_ccd_toi(
+                ccd1.mode,
+                motion1,
+                collider1,
+                &NonlinearRigidMotion::constant_position(make_isometry(*body2.pos, *body2.rot)),
+                collider2,
+                delta_secs,
+                prediction_distance,
+            )
+            .is_some()
+            {
+                commands.queue(WakeBody(entity2));
+            }
+            continue;
+        };
+        let solver_body2 = solver_body2.into_inner();
+
+        let lin_vel2 = solver_body2.linear_velocity;
+        let ang_vel2 = solver_body2.angular_velocity;
+
+        if is_below_velocity_threshold(ccd1, lin_vel1 - lin_vel2, ang_vel1 - ang_vel2) {
+            continue;
+        }
+
+        let motion2 = NonlinearRigidMotion::new(
+            make_isometry(*body2.pos, *body2.rot),
+            body2.com.0.into(),
+            lin_vel2.into(),
+            ang_vel2.into(),
+        );
+
+        let sweep_mode = if ccd1.mode == SweepMode::Linear
+            && body2.ccd.is_none_or(|ccd| ccd.mode == SweepMode::Linear)
+        {
+            SweepMode::Linear
+        } else {
+            SweepMode::NonLinear
+        };
+
+        let Some(hit) = compute_ccd_toi(
+            sweep_mode,
+            motion1,
+            collider1,
+            &motion2,
+            collider2,
+            delta_secs,
+            prediction_distance,
+        ) else {
+            continue;
+        };
+
+        let Some(inertia2) = body2.inertia else {
+            continue;
+        };
+
+        let toi = hit.time_of_impact;
+
+        // Compute the contact normal and the anchors of the contact point relative to the centers of mass.
+        let rotation1 = integrate_rotation(prev_rot1, ang_vel1, toi);
+        let rotation2 = integrate_rotation(*body2.rot, ang_vel2, toi);
+        let normal = rotation1 * Vector::from(hit.normal1);
+        let anchor1 = rotation1 * (Vector::from(hit.witness1) - com1);
+        let anchor2 = rotation2 * (Vector::from(hit.witness2) - body2.com.0);
+
+        // Compute the impulse that makes the contact point of the body move along the normal
+        // at least as fast as the kinematic body. The kinematic body has infinite mass.
+        // Only push the body if the kinematic body is catching up with it.
+        let inv_mass2 = inertia2.effective_inv_mass();
+        let inv_angular_inertia2 = inertia2.effective_inv_angular_inertia();
+        let impulse = -compute_ccd_impulse(
+            velocity_at_point(lin_vel1, ang_vel1, anchor1)
+                - velocity_at_point(lin_vel2, ang_vel2, anchor2),
+            normal,
+            [anchor1, anchor2],
+            [Vector::ZERO, inv_mass2],
+            [
+                &SolverBodyInertia::DUMMY.effective_inv_angular_inertia(),
+                &inv_angular_inertia2,
+            ],
+            0.0,
+            0.0,
+        );
+        if impulse == Vector::ZERO {
+            continue;
+        }
+
+        // The body moves with its old velocity until the time of impact,
+        // and with the pushed velocity for the rest of the time step.
+        let lin_vel_change2 = inv_mass2 * impulse;
+        let ang_vel_change2 = inv_angular_inertia2 * cross(anchor2, impulse);
+        let remaining = delta_secs - toi;
+        solver_body2.linear_velocity += lin_vel_change2;
+        solver_body2.angular_velocity += ang_vel_change2;
+        solver_body2.delta_position += lin_vel_change2 * remaining;
+        solver_body2.delta_rotation =
+            integrate_rotation(solver_body2.delta_rotation, ang_vel_change2, remaining);
+    }
+}
+
+/// Returns `true` if both the relative linear and relative angular velocity
+/// are below the velocity thresholds of the given [`SweptCcd`].
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn is_below_velocity_threshold(
+    ccd: &SweptCcd,
+    relative_lin_vel: Vector,
+    relative_ang_vel: AngularVector,
+) -> bool {
+    #[cfg(feature = "2d")]
+    let ang_vel_below_threshold = relative_ang_vel.abs() < ccd.angular_threshold;
+    #[cfg(feature = "3d")]
+    let ang_vel_below_threshold = relative_ang_vel.length_squared() < ccd.angular_threshold.powi(2);
+
+    ang_vel_below_threshold && relative_lin_vel.length_squared() < ccd.linear_threshold.powi(2)
+}
+
+/// Computes the velocity of a point at the given `anchor` relative to the center of mass of a body.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn velocity_at_point(lin_vel: Vector, ang_vel: AngularVector, anchor: Vector) -> Vector {
+    #[cfg(feature = "2d")]
+    {
+        lin_vel + ang_vel * anchor.perp()
+    }
+    #[cfg(feature = "3d")]
+    {
+        lin_vel + ang_vel.cross(anchor)
+    }
+}
+
+/// Computes the impulse applied to the swept body at the contact point to resolve an impact
+/// with the given relative velocity at the contact point and contact normal pointing towards the other body.
+/// The opposite impulse is applied to the other body.
+///
+/// The `anchors` are the contact point relative to the centers of mass of the bodies,
+/// and the effective inverse mass includes both the linear and angular terms.
+///
+/// The normal velocity is reflected based on the `restitution` coefficient,
+/// and the tangential velocity is reduced based on the `friction` coefficient.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn compute_ccd_impulse(
+    relative_velocity: Vector,
+    normal: Vector,
+    anchors: [Vector; 2],
+    inv_masses: [Vector; 2],
+    inv_angular_inertias: [&SymmetricTensor; 2],
+    friction: Scalar,
+    restitution: Scalar,
+) -> Vector {
+    let normal_speed = relative_velocity.dot(normal);
+
+    // The bodies are already separating.
+    if normal_speed <= 0.0 {
+        return Vector::ZERO;
+    }
+
+    // K = 1/m1 + 1/m2 + dot(r1 x d, I1^-1 * (r1 x d)) + dot(r2 x d, I2^-1 * (r2 x d))
+    let inv_mass_sum = |direction: Vector| {
+        let mut sum = 0.0;
+        for ((anchor, inv_mass), inv_angular_inertia) in
+            anchors.iter().zip(inv_masses).zip(inv_angular_inertias)
+        {
+            let r_cross_d = cross(*anchor, direction);
+            sum += direction.dot(inv_mass * direction);
+            #[cfg(feature = "2d")]
+            {
+                sum += r_cross_d * inv_angular_inertia * r_cross_d;
+            }
+            #[cfg(feature = "3d")]
+            {
+                sum += r_cross_d.dot(*inv_angular_inertia * r_cross_d);
+            }
+        }
+        sum
+    };
+
+    let normal_inv_mass = inv_mass_sum(normal);
+    if normal_inv_mass <= Scalar::EPSILON {
+        return Vector::ZERO;
+    }
+
+    let normal_impulse = (1.0 + restitution) * normal_speed / normal_inv_mass;
+
+    // Coulomb friction, clamped by the normal impulse.
+    let tangent_velocity = relative_velocity - normal_speed * normal;
+    let tangent_impulse = tangent_velocity
+        .try_normalize()
+        .map_or(Vector::ZERO, |tangent| {
+            let tangent_inv_mass = inv_mass_sum(tangent).max(Scalar::EPSILON);
+            let max_impulse = friction * normal_impulse;
+            tangent * (tangent_velocity.length() / tangent_inv_mass).min(max_impulse)
+        });
+
+    -normal_impulse * normal - tangent_impulse
+}
+
+/// Integrates the given rotation with the angular velocity over the time step `delta_secs`.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn integrate_rotation(rotation: Rotation, ang_vel: AngularVector, delta_secs: Scalar) -> Rotation {
+    #[cfg(feature = "2d")]
+    {
+        Rotation::radians(ang_vel * delta_secs) * rotation
+    }
+    #[cfg(feature = "3d")]
+    {
+        Rotation((Quaternion::from_scaled_axis(ang_vel * delta_secs) * rotation.0).normalize())
+    }
 }
 
 /// Computes the time of impact for the motion of two objects for Continuous Collision Detection.
@@ -697,7 +1315,7 @@ fn compute_ccd_toi(
     collider2: &Collider,
     min_toi: Scalar,
     prediction_distance: Scalar,
-) -> Option<Scalar> {
+) -> Option<ShapeCastHit> {
     if mode == SweepMode::Linear {
         let hit = cast_shapes(
             &motion1.start,
@@ -716,7 +1334,7 @@ fn compute_ccd_toi(
         let toi = hit.time_of_impact;
         if toi > 0.0 && toi < min_toi {
             // New smaller time of impact found
-            return Some(toi);
+            return Some(hit);
         } else if toi == 0.0 {
             // If the time of impact is zero, fall back to computing the TOI of a small circle
             // around the centroid of the first body.
@@ -737,13 +1355,10 @@ fn compute_ccd_toi(
             let toi = hit.time_of_impact;
             if toi > 0.0 && toi < min_toi {
                 // New smaller time of impact found
-                return Some(toi);
+                return Some(hit);
             }
         }
-    } else if let Ok(Some(ShapeCastHit {
-        time_of_impact: toi,
-        ..
-    })) = cast_shapes_nonlinear(
+    } else if let Ok(Some(hit)) = cast_shapes_nonlinear(
         motion1,
         collider1.shape_scaled().as_ref(),
         motion2,
@@ -752,9 +1367,10 @@ fn compute_ccd_toi(
         min_toi,
         false,
     ) {
+        let toi = hit.time_of_impact;
         if toi > 0.0 && toi < min_toi {
             // New smaller time of impact found
-            return Some(toi);
+            return Some(hit);
         } else if toi == 0.0 {
             // If the time of impact is zero, fall back to computing the TOI of a small circle
             // around the centroid of the first body.
@@ -771,10 +1387,232 @@ fn compute_ccd_toi(
             let toi = hit.time_of_impact;
             if toi > 0.0 && toi < min_toi {
                 // New smaller time of impact found
-                return Some(toi);
+                return Some(hit);
             }
         }
     }
 
     None
 }
+
+#[cfg(all(test, any(feature = "parry-f32", feature = "parry-f64")))]
+mod tests {
+    use super::*;
+    use crate::tests::create_test_app;
+    use approx::assert_relative_eq;
+
+    fn create_app() -> App {
+        let mut app = create_test_app(());
+        app.insert_resource(Gravity(Vector::ZERO));
+        app
+    }
+
+    /// Spawns a small ball moving along the x-axis at 600 m/s, which is 10 m per time step.
+    /// Speculative contacts are disabled so that only swept CCD can stop the ball.
+    fn spawn_fast_ball(app: &mut App, max_iterations: u32, restitution: Scalar) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.1),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.1),
+                SweptCcd::LINEAR.with_max_iterations(max_iterations),
+                SpeculativeMargin(0.0),
+                LinearVelocity(Vector::X * 600.0),
+                Restitution::new(restitution),
+                Friction::ZERO,
+            ))
+            .id()
+    }
+
+    fn cuboid(half_extents: Vector) -> Collider {
+        #[cfg(feature = "2d")]
+        {
+            Collider::rectangle(2.0 * half_extents.x, 2.0 * half_extents.y)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::cuboid(
+                2.0 * half_extents.x,
+                2.0 * half_extents.y,
+                2.0 * half_extents.z,
+            )
+        }
+    }
+
+    #[cfg(feature = "2d")]
+    fn unit_inv_angular_inertia() -> SymmetricTensor {
+        1.0
+    }
+
+    #[cfg(feature = "3d")]
+    fn unit_inv_angular_inertia() -> SymmetricTensor {
+        SymmetricTensor::from_diagonal(Vector::ONE)
+    }
+
+    #[test]
+    fn ccd_impulse_includes_angular_term() {
+        let inv_angular_inertia = unit_inv_angular_inertia();
+        let static_inv_angular_inertia = SolverBodyInertia::DUMMY.effective_inv_angular_inertia();
+
+        // A body with unit mass and angular inertia hits a static wall off-center.
+        let lin_vel = Vector::X;
+        let normal = Vector::X;
+        let anchor = Vector::Y;
+        let impulse = compute_ccd_impulse(
+            lin_vel,
+            normal,
+            [anchor, Vector::ZERO],
+            [Vector::ONE, Vector::ZERO],
+            [&inv_angular_inertia, &static_inv_angular_inertia],
+            0.0,
+            0.0,
+        );
+
+        // Half of the impulse goes into rotating the body, so the linear impulse is halved.
+        assert_relative_eq!(impulse, Vector::NEG_X * 0.5, epsilon = 1e-6);
+
+        // Without restitution, the contact point stops moving along the normal.
+        let new_lin_vel = lin_vel + impulse;
+        let new_ang_vel = inv_angular_inertia * cross(anchor, impulse);
+        let point_vel = velocity_at_point(new_lin_vel, new_ang_vel, anchor);
+        assert_relative_eq!(point_vel.dot(normal), 0.0, epsilon = 1e-6);
+
+        // With full restitution, the normal velocity of the contact point is reflected.
+        let impulse = compute_ccd_impulse(
+            lin_vel,
+            normal,
+            [anchor, Vector::ZERO],
+            [Vector::ONE, Vector::ZERO],
+            [&inv_angular_inertia, &static_inv_angular_inertia],
+            0.0,
+            1.0,
+        );
+        let new_lin_vel = lin_vel + impulse;
+        let new_ang_vel = inv_angular_inertia * cross(anchor, impulse);
+        let point_vel = velocity_at_point(new_lin_vel, new_ang_vel, anchor);
+        assert_relative_eq!(point_vel.dot(normal), -1.0, epsilon = 1e-6);
+
+        // Separating bodies receive no impulse.
+        let impulse = compute_ccd_impulse(
+            -lin_vel,
+            normal,
+            [anchor, Vector::ZERO],
+            [Vector::ONE, Vector::ZERO],
+            [&inv_angular_inertia, &static_inv_angular_inertia],
+            0.5,
+            1.0,
+        );
+        assert_eq!(impulse, Vector::ZERO);
+    }
+
+    #[test]
+    fn ccd_iterations_bounce_off_wall() {
+        let mut app = create_app();
+        app.world_mut().spawn((
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            cuboid(Vector::splat(2.0).with_x(0.05)),
+            Restitution::new(1.0),
+            Friction::ZERO,
+        ));
+        let ball = spawn_fast_ball(&mut app, 4, 1.0);
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // The ball bounces back instead of tunneling through the wall or losing time at the impact.
+        let position = app.world().get::<Position>(ball).unwrap().0;
+        let velocity = app.world().get::<LinearVelocity>(ball).unwrap().0;
+        assert!(position.x < 0.0);
+        assert_relative_eq!(velocity.x, -600.0, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn ccd_iterations_push_hit_body() {
+        let mut app = create_app();
+
+        // The ball hits a resting box above its center of mass.
+        let ball = spawn_fast_ball(&mut app, 4, 0.0);
+        app.world_mut()
+            .entity_mut(ball)
+            .insert(Position(Vector::Y * 0.4));
+        let box_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * 5.0),
+                cuboid(Vector::splat(0.5)),
+                Friction::ZERO,
+            ))
+            .id();
+
+        let mut box_velocity = Vector::ZERO;
+        for _ in 0..5 {
+            app.update();
+            box_velocity = app.world().get::<LinearVelocity>(box_entity).unwrap().0;
+            if box_velocity.x > 0.0 {
+                break;
+            }
+        }
+
+        // The box is pushed by the ball and advanced with its new velocity after the time of impact.
+        let ball_position = app.world().get::<Position>(ball).unwrap().0;
+        let box_position = app.world().get::<Position>(box_entity).unwrap().0;
+        assert!(box_velocity.x > 1.0);
+        assert!(box_position.x > 5.0);
+        assert!(ball_position.x < box_position.x);
+
+        // The off-center hit also makes the box rotate.
+        let angular_velocity = app.world().get::<AngularVelocity>(box_entity).unwrap().0;
+        #[cfg(feature = "2d")]
+        assert!(angular_velocity < -1.0);
+        #[cfg(feature = "3d")]
+        assert!(angular_velocity.z < -1.0);
+    }
+
+    #[test]
+    fn kinematic_push_moves_resting_body() {
+        let mut app = create_app();
+
+        // A fast kinematic box moves 10 m per time step towards a resting dynamic box.
+        let kinematic = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                cuboid(Vector::splat(0.5)),
+                SweptCcd::LINEAR.push_dynamic(true),
+                SpeculativeMargin(0.0),
+                LinearVelocity(Vector::X * 600.0),
+            ))
+            .id();
+        let dynamic = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * 5.0),
+                cuboid(Vector::splat(0.5)),
+                Friction::ZERO,
+            ))
+            .id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // The dynamic box is pushed ahead of the kinematic box instead of being tunneled through,
+        // and keeps moving at the speed of the kinematic box without rotating.
+        let kinematic_position = app.world().get::<Position>(kinematic).unwrap().0;
+        let dynamic_position = app.world().get::<Position>(dynamic).unwrap().0;
+        let velocity = app.world().get::<LinearVelocity>(dynamic).unwrap().0;
+        let angular_velocity = app.world().get::<AngularVelocity>(dynamic).unwrap().0;
+        assert!(dynamic_position.x - kinematic_position.x > 0.9);
+        assert!(velocity.x > 500.0);
+        #[cfg(feature = "2d")]
+        assert_relative_eq!(angular_velocity, 0.0, epsilon = 1e-3);
+        #[cfg(feature = "3d")]
+        assert_relative_eq!(angular_velocity, Vector::ZERO, epsilon = 1e-3);
+    }
+}
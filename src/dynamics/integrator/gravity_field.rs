@@ -0,0 +1,479 @@
+//! Local and regional gravity using [`GravityOverride`] and [`GravityField`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Overrides the global [`Gravity`] for a specific [rigid body](RigidBody).
+///
+/// The overridden gravity is still affected by the [`GravityScale`] of the body,
+/// and [`GravityField`]s that the body is inside of are added on top of it.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// // Spawn a dynamic body that falls to the right instead of down.
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((RigidBody::Dynamic, GravityOverride(Vector::X * 9.81)));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Deref, DerefMut, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct GravityOverride(pub Vector);
+
+impl GravityOverride {
+    /// Zero gravity.
+    pub const ZERO: GravityOverride = GravityOverride(Vector::ZERO);
+}
+
+/// A region of space that applies gravitational acceleration to dynamic [rigid bodies](RigidBody) inside of it.
+///
+/// The field is positioned and oriented using the [`Position`] and [`Rotation`] of its entity.
+/// It can be attached to a rigid body such as a planet, or spawned as a standalone entity.
+///
+/// A body is considered to be inside of a field if its global center of mass is inside the field's volume.
+/// The accelerations of all fields that a body is inside of are summed together, and added
+/// to the body's base gravity, which is either the global [`Gravity`] or the body's [`GravityOverride`].
+/// If a field uses [`GravityFieldMode::Replace`], the base gravity is ignored for bodies inside of it.
+///
+/// The total gravity is scaled by the [`GravityScale`] of each body.
+///
+/// # Field Shapes
+///
+/// - [`GravityFieldShape::Point`]: Pulls bodies towards the center of the field, like a planet or a black hole.
+/// - [`GravityFieldShape::Directional`]: Applies a constant acceleration in a box-shaped volume.
+/// - [`GravityFieldShape::Cylindrical`]: Pulls bodies towards an axis, like a space station or a tube.
+///
+/// The strength of point and cylindrical fields can additionally be attenuated with a [`GravityFalloff`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Disable global gravity.
+///     commands.insert_resource(Gravity::ZERO);
+///
+///     // Spawn a planet that attracts bodies within a radius of 50 units.
+///     // The gravitational acceleration is 9.81 at the surface, and follows the inverse-square law.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "         Collider::circle(10.0),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::sphere(10.0),")]
+///         GravityField::point(9.81, 50.0)
+///             .with_falloff(GravityFalloff::InverseSquare { reference_distance: 10.0 }),
+///     ));
+/// }
+/// ```
+///
+/// # Performance
+///
+/// With the default collider backend, the bounding volumes of gravity fields are stored
+/// in a bounding volume hierarchy once per time step, so each dynamic body is only tested
+/// against the fields near its center of mass.
+/// Fields are still intended to be used in moderate amounts, not for simulating gravity
+/// between large numbers of bodies.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+#[require(Position, Rotation)]
+pub struct GravityField {
+    /// The shape and strength of the field.
+    pub shape: GravityFieldShape,
+    /// Determines how the strength of the field is attenuated based on distance.
+    ///
+    /// Only affects [`GravityFieldShape::Point`] and [`GravityFieldShape::Cylindrical`].
+    pub falloff: GravityFalloff,
+    /// Determines how the field is combined with the base gravity of bodies inside of it.
+    pub mode: GravityFieldMode,
+}
+
+/// The shape of a [`GravityField`].
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum GravityFieldShape {
+    /// Pulls bodies within the given `radius` towards the center of the field.
+    Point {
+        /// The magnitude of the gravitational acceleration before [falloff](GravityFalloff) is applied.
+        strength: Scalar,
+        /// The radius of the field.
+        radius: Scalar,
+    },
+    /// Applies a constant `acceleration` to bodies inside a box-shaped volume.
+    Directional {
+        /// The gravitational acceleration in the local space of the field.
+        acceleration: Vector,
+        /// The half-extents of the box-shaped volume in the local space of the field.
+        half_extents: Vector,
+    },
+    /// Pulls bodies within the given `radius` towards the closest point
+    /// on a line segment along the local `axis` of the field.
+    ///
+    /// In 2D, this pulls bodies towards a line segment, like gravity near a long wall.
+    Cylindrical {
+        /// The axis of the field in local space.
+        axis: Dir,
+        /// The magnitude of the gravitational acceleration before [falloff](GravityFalloff) is applied.
+        strength: Scalar,
+        /// The radius of the field, measured from the axis.
+        radius: Scalar,
+        /// Half of the length of the field along the axis.
+        half_length: Scalar,
+    },
+}
+
+/// Determines how the strength of a [`GravityField`] is attenuated based on the distance
+/// from the center or axis of the field.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum GravityFalloff {
+    /// The strength of the field is constant within the field's volume.
+    #[default]
+    Constant,
+    /// The strength of the field decreases linearly from full strength at the center
+    /// to zero at the edge of the field.
+    Linear,
+    /// The strength of the field follows the inverse-square law,
+    /// like gravity caused by a real-world planet.
+    ///
+    /// The strength of the field is reached at the `reference_distance`,
+    /// such as the radius of a planet. Within the reference distance,
+    /// the strength is constant to avoid the singularity at the center.
+    InverseSquare {
+        /// The distance at which the field has its full strength.
+        reference_distance: Scalar,
+    },
+}
+
+impl GravityFalloff {
+    /// Computes the multiplier for the field strength at the given `distance`
+    /// for a field with the given `radius`.
+    #[inline]
+    pub fn factor(&self, distance: Scalar, radius: Scalar) -> Scalar {
+        match *self {
+            Self::Constant => 1.0,
+            Self::Linear => {
+                if radius <= Scalar::EPSILON {
+                    0.0
+                } else {
+                    (1.0 - distance / radius).max(0.0)
+                }
+            }
+            Self::InverseSquare { reference_distance } => {
+                if distance <= reference_distance {
+                    1.0
+                } else {
+                    let ratio = reference_distance / distance;
+                    ratio * ratio
+                }
+            }
+        }
+    }
+}
+
+/// Determines how a [`GravityField`] is combined with the base gravity of bodies inside of it.
+///
+/// The base gravity is either the global [`Gravity`] or the [`GravityOverride`] of a body.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum GravityFieldMode {
+    /// The acceleration of the field is added to the base gravity.
+    #[default]
+    Add,
+    /// The base gravity is ignored for bodies inside of the field.
+    /// Accelerations of overlapping fields are still summed together.
+    Replace,
+}
+
+impl GravityField {
+    /// Creates a [`GravityField`] that pulls bodies within the given `radius` towards its center.
+    ///
+    /// The field has no [falloff](GravityFalloff) by default.
+    pub const fn point(strength: Scalar, radius: Scalar) -> Self {
+        Self {
+            shape: GravityFieldShape::Point { strength, radius },
+            falloff: GravityFalloff::Constant,
+            mode: GravityFieldMode::Add,
+        }
+    }
+
+    /// Creates a [`GravityField`] that applies a constant `acceleration`
+    /// to bodies inside a box-shaped volume with the given `half_extents`.
+    ///
+    /// Both the acceleration and the half-extents are in the local space of the field.
+    pub const fn directional(acceleration: Vector, half_extents: Vector) -> Self {
+        Self {
+            shape: GravityFieldShape::Directional {
+                acceleration,
+                half_extents,
+            },
+            falloff: GravityFalloff::Constant,
+            mode: GravityFieldMode::Add,
+        }
+    }
+
+    /// Creates a [`GravityField`] that pulls bodies within the given `radius`
+    /// towards the closest point on a line segment along the local `axis`.
+    ///
+    /// The field has no [falloff](GravityFalloff) by default.
+    pub const fn cylindrical(
+        axis: Dir,
+        strength: Scalar,
+        radius: Scalar,
+        half_length: Scalar,
+    ) -> Self {
+        Self {
+            shape: GravityFieldShape::Cylindrical {
+                axis,
+                strength,
+                radius,
+                half_length,
+            },
+            falloff: GravityFalloff::Constant,
+            mode: GravityFieldMode::Add,
+        }
+    }
+
+    /// Sets the [`GravityFalloff`] of the field.
+    pub const fn with_falloff(mut self, falloff: GravityFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Sets the [`GravityFieldMode`] of the field.
+    pub const fn with_mode(mut self, mode: GravityFieldMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Computes the gravitational acceleration at the given world-space `point`
+    /// for a field with the given `position` and `rotation`.
+    ///
+    /// Returns `None` if the point is outside of the field.
+    pub fn acceleration_at(
+        &self,
+        position: Vector,
+        rotation: &Rotation,
+        point: Vector,
+    ) -> Option<Vector> {
+        match self.shape {
+            GravityFieldShape::Point { strength, radius } => {
+                let offset = position - point;
+                let distance_squared = offset.length_squared();
+                if distance_squared > radius * radius {
+                    return None;
+                }
+                let distance = distance_squared.sqrt();
+                if distance <= Scalar::EPSILON {
+                    // The point is at the center, and there is no well-defined direction.
+                    return Some(Vector::ZERO);
+                }
+                let factor = self.falloff.factor(distance, radius);
+                Some(offset / distance * strength * factor)
+            }
+            GravityFieldShape::Directional {
+                acceleration,
+                half_extents,
+            } => {
+                let local_point = rotation.inverse() * (point - position);
+                if local_point.abs().cmpgt(half_extents).any() {
+                    return None;
+                }
+                Some(*rotation * acceleration)
+            }
+            GravityFieldShape::Cylindrical {
+                axis,
+                strength,
+                radius,
+                half_length,
+            } => {
+                let local_point = rotation.inverse() * (point - position);
+                let along_axis = local_point.dot(axis.adjust_precision());
+                if along_axis.abs() > half_length {
+                    return None;
+                }
+                let local_offset = axis.adjust_precision() * along_axis - local_point;
+                let distance_squared = local_offset.length_squared();
+                if distance_squared > radius * radius {
+                    return None;
+                }
+                let distance = distance_squared.sqrt();
+                if distance <= Scalar::EPSILON {
+                    // The point is on the axis, and there is no well-defined direction.
+                    return Some(Vector::ZERO);
+                }
+                let factor = self.falloff.factor(distance, radius);
+                Some(*rotation * (local_offset / distance * strength * factor))
+            }
+        }
+    }
+
+    /// Computes a world-space [`ColliderAabb`] that contains the volume of the field
+    /// for a field with the given `position` and `rotation`.
+    pub fn aabb(&self, position: Vector, rotation: &Rotation) -> ColliderAabb {
+        match self.shape {
+            GravityFieldShape::Point { radius, .. } => {
+                ColliderAabb::new(position, Vector::splat(radius))
+            }
+            GravityFieldShape::Directional { half_extents, .. } => {
+                // Use the bounding sphere of the box, which is independent of the rotation.
+                ColliderAabb::new(position, Vector::splat(half_extents.length()))
+            }
+            GravityFieldShape::Cylindrical {
+                axis,
+                radius,
+                half_length,
+                ..
+            } => {
+                let half_segment = (*rotation * axis.adjust_precision() * half_length).abs();
+                ColliderAabb::new(position, half_segment + Vector::splat(radius))
+            }
+        }
+    }
+}
+
+/// An acceleration structure for finding the [`GravityField`]s that contain a given point.
+///
+/// The bounding volumes of the fields are stored in a bounding volume hierarchy when a collision
+/// backend is available, so that points are only tested against the fields near them.
+/// Used both for applying gravity in the integrator and for waking up sleeping bodies
+/// in changed fields, so that both test the same global center of mass against the same volumes.
+#[derive(Clone)]
+pub(crate) struct GravityFieldTree {
+    /// The fields with their positions and rotations.
+    fields: Vec<(GravityField, Vector, Rotation)>,
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    bvh: parry::partitioning::Bvh,
+}
+
+impl Default for GravityFieldTree {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            #[cfg(all(
+                feature = "default-collider",
+                any(feature = "parry-f32", feature = "parry-f64")
+            ))]
+            bvh: parry::partitioning::Bvh::new(),
+        }
+    }
+}
+
+impl GravityFieldTree {
+    /// Rebuilds the tree from the given fields and their positions and rotations.
+    pub(crate) fn rebuild(
+        &mut self,
+        fields: impl IntoIterator<Item = (GravityField, Vector, Rotation)>,
+    ) {
+        self.fields.clear();
+        self.fields.extend(fields);
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        {
+            use parry::{
+                bounding_volume::Aabb,
+                partitioning::{Bvh, BvhBuildStrategy},
+            };
+
+            let aabbs = self
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, (field, position, rotation))| {
+                    let aabb = field.aabb(*position, rotation);
+                    (i, Aabb::new(aabb.min.into(), aabb.max.into()))
+                });
+            self.bvh = Bvh::from_iter(BvhBuildStrategy::Binned, aabbs);
+        }
+    }
+
+    /// Returns `true` if the tree contains no fields.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Calls `f` with each field that contains the given world-space `point`,
+    /// along with the acceleration of the field at that point.
+    pub(crate) fn for_each_field_at(
+        &self,
+        point: Vector,
+        mut f: impl FnMut(&GravityField, Vector),
+    ) {
+        let mut test_field = |(field, position, rotation): &(GravityField, Vector, Rotation)| {
+            if let Some(acceleration) = field.acceleration_at(*position, rotation, point) {
+                f(field, acceleration);
+            }
+        };
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        {
+            use parry::partitioning::BvhNode;
+
+            let local_point = point.into();
+            let leaves = self
+                .bvh
+                .leaves(|node: &BvhNode| node.aabb().contains_local_point(&local_point));
+            for leaf in leaves {
+                if let Some(field) = self.fields.get(leaf as usize) {
+                    test_field(field);
+                }
+            }
+        }
+
+        #[cfg(not(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        )))]
+        self.fields.iter().for_each(&mut test_field);
+    }
+
+    /// Returns `true` if the given world-space `point` is inside of any of the fields.
+    pub(crate) fn contains_point(&self, point: Vector) -> bool {
+        let mut is_inside = false;
+        self.for_each_field_at(point, |_, _| is_inside = true);
+        is_inside
+    }
+
+    /// Sums the accelerations of all fields that contain the given world-space `point`.
+    ///
+    /// The `base_gravity` is included unless one of the fields uses [`GravityFieldMode::Replace`].
+    pub(crate) fn gravity_at(&self, base_gravity: Vector, point: Vector) -> Vector {
+        let mut field_gravity = Vector::ZERO;
+        let mut replace_base = false;
+
+        self.for_each_field_at(point, |field, acceleration| {
+            field_gravity += acceleration;
+            replace_base |= field.mode == GravityFieldMode::Replace;
+        });
+
+        if replace_base {
+            field_gravity
+        } else {
+            base_gravity + field_gravity
+        }
+    }
+}
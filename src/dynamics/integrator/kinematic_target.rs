@@ -0,0 +1,139 @@
+//! Moving [kinematic](RigidBody::Kinematic) bodies to target poses using [`KinematicTarget`].
+
+use crate::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+
+/// A target pose for a [kinematic](RigidBody::Kinematic) rigid body to move to during the next physics step.
+///
+/// Instead of teleporting the body by modifying its [`Position`] and [`Rotation`] directly,
+/// the [`LinearVelocity`] and [`AngularVelocity`] are set to the exact velocities required
+/// for the body to arrive at the target by the end of the time step, taking [substepping](SubstepCount)
+/// into account. This way, dynamic bodies that are in contact with the kinematic body,
+/// such as characters standing on a moving platform, get a proper contact response.
+///
+/// The component is removed once the velocities have been computed, and the velocities
+/// are reset to zero at the next time step unless a new target has been set.
+///
+/// The target has no effect on bodies that are not kinematic.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Elevator;
+///
+/// // Move the elevator up and down along a sine wave.
+/// fn move_elevator(
+///     mut commands: Commands,
+///     query: Query<(Entity, &Rotation), With<Elevator>>,
+///     time: Res<Time>,
+/// ) {
+///     for (entity, rotation) in &query {
+///         let height = 5.0 * ops::sin(time.elapsed_secs()) as Scalar;
+///         commands
+///             .entity(entity)
+///             .insert(KinematicTarget::new(Vector::Y * height, *rotation));
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct KinematicTarget {
+    /// The target [`Position`] of the body.
+    pub position: Vector,
+    /// The target [`Rotation`] of the body.
+    pub rotation: Rotation,
+}
+
+impl KinematicTarget {
+    /// Creates a new [`KinematicTarget`] with the given target position and rotation.
+    pub const fn new(position: Vector, rotation: Rotation) -> Self {
+        Self { position, rotation }
+    }
+}
+
+/// Computes the velocities required for [kinematic](RigidBody::Kinematic) bodies
+/// to reach their [`KinematicTarget`] by the end of the time step, and removes the targets.
+///
+/// Bodies whose target was consumed during the previous time step are stopped.
+/// The consumed targets are tracked in a [`Local`] set instead of with [`RemovedComponents`],
+/// since removal events can be missed when the physics schedule does not run every frame.
+/// A set is also used instead of a marker component to avoid moving the bodies between archetypes.
+#[allow(clippy::type_complexity)]
+pub(super) fn apply_kinematic_targets(
+    mut commands: Commands,
+    mut bodies: Query<(
+        Entity,
+        &RigidBody,
+        &KinematicTarget,
+        &Position,
+        &Rotation,
+        Option<&ComputedCenterOfMass>,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    mut reached_bodies: Query<
+        (&RigidBody, &mut LinearVelocity, &mut AngularVelocity),
+        Without<KinematicTarget>,
+    >,
+    mut consumed_targets: Local<EntityHashSet>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    // Stop bodies that reached their target during the previous time step.
+    for entity in consumed_targets.drain() {
+        // Bodies with a new target are handled below.
+        let Ok((rb, mut lin_vel, mut ang_vel)) = reached_bodies.get_mut(entity) else {
+            continue;
+        };
+
+        if rb.is_kinematic() {
+            lin_vel.0 = Vector::ZERO;
+            ang_vel.0 = AngularVector::ZERO;
+        }
+    }
+
+    for (entity, rb, target, position, rotation, center_of_mass, mut lin_vel, mut ang_vel) in
+        &mut bodies
+    {
+        commands.entity(entity).try_remove::<KinematicTarget>();
+        consumed_targets.insert(entity);
+
+        if !rb.is_kinematic() {
+            continue;
+        }
+
+        // The solver moves the center of mass and rotates the body around it,
+        // so the linear velocity is computed for the center of mass.
+        let local_center_of_mass = center_of_mass.map_or(Vector::ZERO, |com| com.0);
+        let current_center_of_mass = position.0 + *rotation * local_center_of_mass;
+        let target_center_of_mass = target.position + target.rotation * local_center_of_mass;
+
+        lin_vel.0 = (target_center_of_mass - current_center_of_mass) / delta_secs;
+
+        #[cfg(feature = "2d")]
+        {
+            ang_vel.0 = rotation.angle_between(target.rotation) / delta_secs;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let mut delta_rotation = target.rotation.0 * rotation.0.inverse();
+            // Take the shortest path to the target.
+            if delta_rotation.w < 0.0 {
+                delta_rotation = -delta_rotation;
+            }
+            ang_vel.0 = delta_rotation.to_scaled_axis() / delta_secs;
+        }
+    }
+}
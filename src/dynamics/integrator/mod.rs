@@ -3,6 +3,11 @@
 //!
 //! See [`IntegratorPlugin`].
 
+mod gravity_field;
+mod kinematic_target;
+pub use gravity_field::*;
+pub use kinematic_target::*;
+
 use crate::prelude::*;
 use bevy::{
     ecs::{intern::Interned, query::QueryData, schedule::ScheduleLabel},
@@ -52,6 +57,9 @@ impl Plugin for IntegratorPlugin {
         app.configure_sets(
             PhysicsSchedule,
             (
+                IntegrationSystems::KinematicTargets
+                    .after(PhysicsStepSystems::First)
+                    .before(PhysicsStepSystems::BroadPhase),
                 IntegrationSystems::UpdateVelocityIncrements
                     .in_set(SolverSystems::PreSubstep)
                     .before(IntegrationSystems::Velocity),
@@ -64,6 +72,8 @@ impl Plugin for IntegratorPlugin {
         app.add_systems(
             PhysicsSchedule,
             (
+                kinematic_target::apply_kinematic_targets
+                    .in_set(IntegrationSystems::KinematicTargets),
                 pre_process_velocity_increments
                     .in_set(IntegrationSystems::UpdateVelocityIncrements),
                 clear_velocity_increments.in_set(IntegrationSystems::ClearVelocityIncrements),
@@ -91,6 +101,11 @@ impl Plugin for IntegratorPlugin {
 /// applying forces and moving bodies based on velocity.
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum IntegrationSystems {
+    /// Computes the velocities required for kinematic bodies to reach their [`KinematicTarget`]
+    /// by the end of the time step.
+    ///
+    /// Runs in the [`PhysicsSchedule`], between [`PhysicsStepSystems::First`] and [`PhysicsStepSystems::BroadPhase`].
+    KinematicTargets,
     /// Applies gravity and locked axes to the linear and angular velocity increments of bodies,
     /// and multiplies them by the substep delta time to get the final per-substep increments.
     ///
@@ -123,6 +138,8 @@ pub type IntegrationSet = IntegrationSystems;
 /// You can also control how gravity affects a specific [rigid body](RigidBody) using the [`GravityScale`]
 /// component. The magnitude of the gravity will be multiplied by this scaling factor.
 ///
+/// For per-body or regional gravity, see [`GravityOverride`] and [`GravityField`].
+///
 /// # Example
 ///
 /// ```no_run
@@ -170,7 +187,7 @@ impl Gravity {
 ///
 /// This includes:
 ///
-/// - Velocity increments for [`Gravity`].
+/// - Velocity increments for [`Gravity`], [`GravityOverride`], and [`GravityField`]s.
 /// - Velocity increments for [`ConstantForce`], [`ConstantTorque`], [`ConstantLinearAcceleration`], and [`ConstantAngularAcceleration`].
 /// - Velocity increments for forces, torques, and accelerations applied using [`Forces`].
 /// - Cached operands for applying linear and angular velocity damping.
@@ -233,11 +250,17 @@ pub fn pre_process_velocity_increments(
     mut bodies: Query<(
         &RigidBody,
         &mut VelocityIntegrationData,
+        &Position,
+        &Rotation,
+        &ComputedCenterOfMass,
         Option<&LinearDamping>,
         Option<&AngularDamping>,
         Option<&GravityScale>,
+        Option<&GravityOverride>,
         Option<&LockedAxes>,
     )>,
+    gravity_fields: Query<(&GravityField, &Position, &Rotation)>,
+    mut gravity_field_tree: Local<GravityFieldTree>,
     gravity: Res<Gravity>,
     time: Res<Time<Substeps>>,
     mut diagnostics: ResMut<SolverDiagnostics>,
@@ -246,9 +269,27 @@ pub fn pre_process_velocity_increments(
 
     let delta_secs = time.delta_secs_f64() as Scalar;
 
+    gravity_field_tree.rebuild(
+        gravity_fields
+            .iter()
+            .map(|(field, position, rotation)| (*field, position.0, *rotation)),
+    );
+    let gravity_field_tree = &*gravity_field_tree;
+
     // TODO: Do we want to skip kinematic bodies here?
     bodies.par_iter_mut().for_each(
-        |(rb, mut integration, lin_damping, ang_damping, gravity_scale, locked_axes)| {
+        |(
+            rb,
+            mut integration,
+            position,
+            rotation,
+            center_of_mass,
+            lin_damping,
+            ang_damping,
+            gravity_scale,
+            gravity_override,
+            locked_axes,
+        )| {
             if !rb.is_dynamic() {
                 // Skip non-dynamic bodies.
                 return;
@@ -266,8 +307,16 @@ pub fn pre_process_velocity_increments(
             // NOTE: The `ForcePlugin` handles the application of external forces and torques.
             // NOTE: The velocity increments are treated as accelerations at this point.
 
-            // Apply gravity.
-            integration.linear_increment += gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
+            // Apply gravity. Gravity fields are sampled at the global center of mass.
+            let base_gravity = gravity_override.map_or(gravity.0, |gravity| gravity.0);
+            let total_gravity = if gravity_field_tree.is_empty() {
+                base_gravity
+            } else {
+                let global_center_of_mass = position.0 + *rotation * center_of_mass.0;
+                gravity_field_tree.gravity_at(base_gravity, global_center_of_mass)
+            };
+            integration.linear_increment +=
+                total_gravity * gravity_scale.map_or(1.0, |scale| scale.0);
 
             // Apply locked axes.
             integration.linear_increment = locked_axes.apply_to_vec(integration.linear_increment);
@@ -596,4 +645,350 @@ mod tests {
         #[cfg(feature = "3d")]
         assert_relative_eq!(angular_velocity, Vector::Z * 2.0, epsilon = 0.00001);
     }
+
+    #[test]
+    fn gravity_override_and_fields() {
+        let mut app = create_app();
+        app.insert_resource(SubstepCount(1));
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        #[cfg(feature = "2d")]
+        let shape = Rectangle::from_length(1.0);
+        #[cfg(feature = "3d")]
+        let shape = Cuboid::from_length(1.0);
+
+        // A body with a gravity override, far away from the field.
+        let override_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::from_shape(&shape, 1.0),
+                Position(Vector::X * 100.0),
+                GravityOverride(Vector::X * 5.0),
+            ))
+            .id();
+
+        // A body inside a point field.
+        let field_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::from_shape(&shape, 1.0),
+                Position(Vector::Y * 5.0),
+            ))
+            .id();
+
+        // A body outside of the point field.
+        let outside_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::from_shape(&shape, 1.0),
+                Position(Vector::NEG_Y * 20.0),
+            ))
+            .id();
+
+        app.world_mut().spawn(GravityField::point(10.0, 10.0));
+
+        app.insert_resource(Time::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 10.0,
+        )));
+
+        // Initialize the app.
+        app.update();
+
+        // Step once.
+        app.update();
+
+        let world = app.world();
+        let override_velocity = world.get::<LinearVelocity>(override_entity).unwrap().0;
+        let field_velocity = world.get::<LinearVelocity>(field_entity).unwrap().0;
+        let outside_velocity = world.get::<LinearVelocity>(outside_entity).unwrap().0;
+
+        assert_relative_eq!(override_velocity, Vector::X * 0.5, epsilon = 0.0001);
+        assert_relative_eq!(field_velocity, Vector::NEG_Y, epsilon = 0.0001);
+        assert_relative_eq!(outside_velocity, Vector::ZERO, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn moving_gravity_field_wakes_bodies() {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        #[cfg(feature = "2d")]
+        let shape = Rectangle::from_length(1.0);
+        #[cfg(feature = "3d")]
+        let shape = Cuboid::from_length(1.0);
+
+        let body_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::from_shape(&shape, 1.0),
+                Position(Vector::X),
+            ))
+            .id();
+
+        // A field far away from the body.
+        let field_entity = app
+            .world_mut()
+            .spawn((GravityField::point(10.0, 5.0), Position(Vector::X * 100.0)))
+            .id();
+
+        app.insert_resource(Time::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 10.0,
+        )));
+
+        // Initialize the app.
+        app.update();
+
+        // Let the body fall asleep.
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world().entity(body_entity).contains::<Sleeping>());
+
+        // Move the field on top of the body. The body should wake up and start falling towards it.
+        app.world_mut().get_mut::<Position>(field_entity).unwrap().0 = Vector::ZERO;
+        app.update();
+
+        assert!(!app.world().entity(body_entity).contains::<Sleeping>());
+
+        app.update();
+
+        let velocity = app.world().get::<LinearVelocity>(body_entity).unwrap().0;
+        assert!(velocity.x < 0.0);
+    }
+
+    #[test]
+    fn changed_gravity_field_wakes_only_bodies_inside() {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        #[cfg(feature = "2d")]
+        let shape = Rectangle::from_length(1.0);
+        #[cfg(feature = "3d")]
+        let shape = Cuboid::from_length(1.0);
+
+        let inside_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::from_shape(&shape, 1.0),
+                Position(Vector::X),
+            ))
+            .id();
+        let outside_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::from_shape(&shape, 1.0),
+                Position(Vector::X * 100.0),
+            ))
+            .id();
+
+        // A field with no strength, so that the bodies can fall asleep.
+        let field_entity = app.world_mut().spawn(GravityField::point(0.0, 5.0)).id();
+
+        app.insert_resource(Time::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 10.0,
+        )));
+
+        // Initialize the app.
+        app.update();
+
+        // Let the bodies fall asleep.
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world().entity(inside_entity).contains::<Sleeping>());
+        assert!(app.world().entity(outside_entity).contains::<Sleeping>());
+
+        // Strengthen the field. Only the body inside of it should wake up.
+        *app.world_mut()
+            .get_mut::<GravityField>(field_entity)
+            .unwrap() = GravityField::point(10.0, 5.0);
+        app.update();
+
+        assert!(!app.world().entity(inside_entity).contains::<Sleeping>());
+        assert!(app.world().entity(outside_entity).contains::<Sleeping>());
+
+        // Removing the field should wake up the body that was inside of it again.
+        for _ in 0..40 {
+            app.update();
+        }
+        app.world_mut()
+            .entity_mut(field_entity)
+            .remove::<GravityField>();
+        app.update();
+
+        assert!(!app.world().entity(inside_entity).contains::<Sleeping>());
+        assert!(app.world().entity(outside_entity).contains::<Sleeping>());
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    fn changed_gravity_field_wakes_body_by_center_of_mass() {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+        app.finish();
+
+        // A body whose center of mass is far outside of its collider.
+        let body_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                #[cfg(feature = "2d")]
+                CenterOfMass::new(20.0, 0.0),
+                #[cfg(feature = "3d")]
+                CenterOfMass::new(20.0, 0.0, 0.0),
+            ))
+            .id();
+
+        app.insert_resource(Time::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 10.0,
+        )));
+
+        // Initialize the app.
+        app.update();
+
+        // Let the body fall asleep.
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world().entity(body_entity).contains::<Sleeping>());
+
+        // Spawn a field that contains the center of mass but not the collider.
+        app.world_mut()
+            .spawn((GravityField::point(10.0, 5.0), Position(Vector::X * 20.0)));
+        app.update();
+
+        assert!(!app.world().entity(body_entity).contains::<Sleeping>());
+    }
+
+    #[test]
+    fn kinematic_target_stops_with_fewer_physics_steps_than_frames() {
+        let mut app = create_app();
+        app.finish();
+
+        let body_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                KinematicTarget::new(Vector::X * 2.0, Rotation::default()),
+            ))
+            .id();
+
+        // Run the physics schedule once every six frames.
+        app.insert_resource(Time::<Fixed>::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )));
+
+        // Run several physics steps. The body should stop after reaching the target,
+        // even though the frames in between have no physics steps.
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let entity_ref = app.world().entity(body_entity);
+        assert_relative_eq!(
+            entity_ref.get::<Position>().unwrap().0,
+            Vector::X * 2.0,
+            epsilon = 0.0001
+        );
+        assert_relative_eq!(
+            entity_ref.get::<LinearVelocity>().unwrap().0,
+            Vector::ZERO,
+            epsilon = 0.0001
+        );
+    }
+
+    #[test]
+    fn kinematic_target() {
+        let mut app = create_app();
+        app.insert_resource(SubstepCount(4));
+        app.finish();
+
+        #[cfg(feature = "2d")]
+        let target_rotation = Rotation::radians(0.5);
+        #[cfg(feature = "3d")]
+        let target_rotation = Rotation(Quaternion::from_rotation_y(0.5));
+
+        let body_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                KinematicTarget::new(Vector::X * 2.0, target_rotation),
+            ))
+            .id();
+
+        app.insert_resource(Time::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 10.0,
+        )));
+
+        // Initialize the app.
+        app.update();
+
+        // Step once. The body should arrive at the target.
+        app.update();
+
+        let entity_ref = app.world().entity(body_entity);
+        assert!(!entity_ref.contains::<KinematicTarget>());
+        assert_relative_eq!(
+            entity_ref.get::<Position>().unwrap().0,
+            Vector::X * 2.0,
+            epsilon = 0.0001
+        );
+        assert_relative_eq!(
+            entity_ref.get::<LinearVelocity>().unwrap().0,
+            Vector::X * 20.0,
+            epsilon = 0.0001
+        );
+        #[cfg(feature = "2d")]
+        assert_relative_eq!(
+            entity_ref.get::<Rotation>().unwrap().as_radians(),
+            0.5,
+            epsilon = 0.0001
+        );
+        #[cfg(feature = "3d")]
+        assert_relative_eq!(
+            entity_ref.get::<Rotation>().unwrap().0,
+            target_rotation.0,
+            epsilon = 0.0001
+        );
+
+        // Step again without a new target. The body should stop.
+        app.update();
+
+        let entity_ref = app.world().entity(body_entity);
+        assert_relative_eq!(
+            entity_ref.get::<Position>().unwrap().0,
+            Vector::X * 2.0,
+            epsilon = 0.0001
+        );
+        assert_relative_eq!(
+            entity_ref.get::<LinearVelocity>().unwrap().0,
+            Vector::ZERO,
+            epsilon = 0.0001
+        );
+    }
 }
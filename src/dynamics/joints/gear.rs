@@ -0,0 +1,154 @@
+use crate::{dynamics::joints::EntityConstraint, prelude::*};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A gear [joint](dynamics::joints) couples the rotation of two [`RevoluteJoint`]s with a gear ratio.
+///
+/// Each gear is a body that is attached to another body, such as the ground or a chassis, using a [`RevoluteJoint`].
+/// The gear joint links the angles of the two revolute joints such that
+///
+/// `angle1 + ratio * angle2 = constant`,
+///
+/// where each angle is the rotation of the gear relative to the other body of its revolute joint.
+/// The constant is determined by the configuration of the gears when the joint is first simulated.
+///
+/// For two meshing gears with radii `r1` and `r2`, the ratio should be `r2 / r1`.
+/// A positive ratio makes the gears rotate in opposite directions, like meshing gears,
+/// while a negative ratio makes them rotate in the same direction, like gears connected by a belt.
+///
+/// The gear joint does not keep the gears in place, and it must be used together with the two revolute joints.
+/// It is solved after the revolute joints by the [`XpbdSolverPlugin`], and a warning is logged
+/// if the joint is used without it.
+///
+/// The joint only applies torques to the gears. Rotation of the other bodies of the revolute joints,
+/// for example when the gears are mounted on a moving vehicle, is taken into account at the start of each time step,
+/// but no reaction torque is applied to those bodies. If they are dynamic, angular momentum is not conserved,
+/// and driving the gears does not make the carriers spin the other way.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ground = commands.spawn(RigidBody::Static).id();
+///     let gear1 = commands.spawn(RigidBody::Dynamic).id();
+///     let gear2 = commands
+///         .spawn((RigidBody::Dynamic, Transform::from_xyz(3.0, 0.0, 0.0)))
+///         .id();
+///
+///     // Attach the gears to the ground.
+///     let joint1 = commands.spawn(RevoluteJoint::new(ground, gear1)).id();
+///     let joint2 = commands
+///         .spawn(RevoluteJoint::new(ground, gear2).with_local_anchor1(Vector::X * 3.0))
+///         .id();
+///
+///     // Couple the rotation of the gears. The second gear is twice as large as the first one.
+///     commands.spawn(GearJoint::new(gear1, gear2, joint1, joint2).with_ratio(2.0));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, MapEntities, PartialEq)]
+pub struct GearJoint {
+    /// The first gear body. This must be one of the bodies of [`joint1`](Self::joint1).
+    pub body1: Entity,
+    /// The second gear body. This must be one of the bodies of [`joint2`](Self::joint2).
+    pub body2: Entity,
+    /// The [`RevoluteJoint`] entity attaching the first gear.
+    pub joint1: Entity,
+    /// The [`RevoluteJoint`] entity attaching the second gear.
+    pub joint2: Entity,
+    /// The gear ratio. The joint maintains `angle1 + ratio * angle2 = constant`.
+    ///
+    /// By default, this is `1.0`.
+    pub ratio: Scalar,
+    /// The compliance of the joint (inverse of stiffness, N * m / rad).
+    pub compliance: Scalar,
+}
+
+impl EntityConstraint<2> for GearJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.body1, self.body2]
+    }
+}
+
+impl GearJoint {
+    /// Creates a new [`GearJoint`] between two gear bodies, coupling the given [`RevoluteJoint`]s.
+    ///
+    /// `body1` must be one of the bodies of `joint1`, and `body2` must be one of the bodies of `joint2`.
+    #[inline]
+    pub const fn new(body1: Entity, body2: Entity, joint1: Entity, joint2: Entity) -> Self {
+        Self {
+            body1,
+            body2,
+            joint1,
+            joint2,
+            ratio: 1.0,
+            compliance: 0.0,
+        }
+    }
+
+    /// Sets the gear ratio. The joint maintains `angle1 + ratio * angle2 = constant`.
+    #[inline]
+    pub const fn with_ratio(mut self, ratio: Scalar) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, N * m / rad).
+    #[inline]
+    pub const fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+}
+
+impl MapEntities for GearJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.body1 = entity_mapper.get_mapped(self.body1);
+        self.body2 = entity_mapper.get_mapped(self.body2);
+        self.joint1 = entity_mapper.get_mapped(self.joint1);
+        self.joint2 = entity_mapper.get_mapped(self.joint2);
+    }
+}
+
+#[cfg(feature = "debug-plugin")]
+impl DebugRenderConstraint<2> for GearJoint {
+    type Context = ();
+
+    fn debug_render(
+        &self,
+        positions: [Vector; 2],
+        rotations: [Rotation; 2],
+        _context: &mut Self::Context,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        config: &PhysicsGizmos,
+    ) {
+        let [pos1, pos2] = positions;
+        let [rot1, rot2] = rotations;
+
+        if let Some(color) = config.joint_separation_color {
+            gizmos.draw_line(pos1, pos2, color);
+        }
+
+        // Draw the orientation of each gear, with lengths matching meshing gears
+        // whose radii follow the gear ratio.
+        if let Some(anchor_color) = config.joint_anchor_color {
+            let distance = pos1.distance(pos2);
+            let ratio = self.ratio.abs();
+            let radius1 = distance / (1.0 + ratio);
+            let radius2 = distance * ratio / (1.0 + ratio);
+            gizmos.draw_line(pos1, pos1 + rot1 * (Vector::X * radius1), anchor_color);
+            gizmos.draw_line(pos2, pos2 + rot2 * (Vector::X * radius2), anchor_color);
+        }
+    }
+}
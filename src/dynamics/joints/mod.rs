@@ -34,6 +34,16 @@ Therefore, they have 3 translational DOF and 3 rotational DOF, a total of 6 DOF.
     doc = "| [`SphericalJoint`] | -                         | 3 Rotations                 |"
 )]
 //!
+//! There are also coupling joints that relate the motion of bodies without otherwise restricting their
+//! degrees of freedom:
+//!
+//! - [`GearJoint`]: Couples the angles of two [`RevoluteJoint`]s with a gear ratio.
+//! - [`RackAndPinionJoint`]: Couples the angle of a [`RevoluteJoint`] to the translation of a [`PrismaticJoint`].
+//! - [`PulleyJoint`]: Connects two bodies with a rope that runs over two fixed pulleys.
+//!
+//! A [`TargetJoint`] pulls a single body towards a target point in world space with a soft spring,
+//! which is useful for grabbing and dragging bodies.
+//!
 //! # Using Joints
 //!
 //! In Avian, joints are modeled as components. Each joint is spawned as its own entity,
@@ -133,6 +143,46 @@ Therefore, they have 3 translational DOF and 3 rotational DOF, a total of 6 DOF.
 //! # }
 //! ```
 //!
+//! ## Softness
+//!
+//! By default, joints are solved as rigid constraints, and only a small amount of softness is used
+//! to stabilize drift. The global stabilization can be tuned with [`SolverConfig::joint_damping_ratio`]
+//! and [`SolverConfig::joint_frequency_factor`].
+//!
+//! Individual joints can be made springy with the [`JointSoftness`] component, which configures
+//! the stiffness of the joint using a frequency in Hertz and a damping ratio. Alternatively,
+//! joints with non-zero compliance are solved by the [`XpbdSolverPlugin`] if the `xpbd_joints` feature is enabled.
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+#![cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+//! # use bevy::prelude::*;
+//! #
+//! # fn setup(mut commands: Commands) {
+//! #     let body1 = commands.spawn(RigidBody::Dynamic).id();
+//! #     let body2 = commands.spawn(RigidBody::Dynamic).id();
+//! #
+//! // Connect two bodies with a springy fixed joint.
+//! commands.spawn((
+//!     FixedJoint::new(body1, body2),
+//!     JointSoftness::new(5.0, 0.5),
+//! ));
+//! # }
+//! ```
+//!
+//! ## Orientation Drives
+//!
+#![cfg_attr(
+    feature = "2d",
+    doc = "[`RevoluteJoint`]s can be given an [`OrientationDrive`] that pulls the relative"
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "[`RevoluteJoint`]s and [`SphericalJoint`]s can be given an [`OrientationDrive`] that pulls the relative"
+)]
+//! orientation of the joint frames towards a target orientation with a given stiffness and damping.
+//! This can be used for things like powered ragdolls that follow an animation while still reacting to hits.
+//!
 //! ## Reading Joint Forces
 //!
 //! Joints apply forces and torques to constrain the bodies they are attached to.
@@ -214,17 +264,25 @@ Therefore, they have 3 translational DOF and 3 rotational DOF, a total of 6 DOF.
 
 mod distance;
 mod fixed;
+mod gear;
 mod prismatic;
+mod pulley;
+mod rack_and_pinion;
 mod revolute;
 #[cfg(feature = "3d")]
 mod spherical;
+mod target;
 
 pub use distance::DistanceJoint;
 pub use fixed::FixedJoint;
+pub use gear::GearJoint;
 pub use prismatic::PrismaticJoint;
+pub use pulley::PulleyJoint;
+pub use rack_and_pinion::RackAndPinionJoint;
 pub use revolute::RevoluteJoint;
 #[cfg(feature = "3d")]
 pub use spherical::SphericalJoint;
+pub use target::TargetJoint;
 
 use crate::{dynamics::solver::joint_graph::JointGraph, prelude::*};
 use bevy::{
@@ -235,7 +293,8 @@ use bevy::{
 /// A plugin for managing and initializing [joints](self).
 ///
 /// Note that this does *not* include the actual joint constraint solver.
-/// For a built-in joint solver, enable the `xpbd_joints` feature, and use the [`XpbdSolverPlugin`].
+/// Joints are solved by the [`ImpulseJointSolverPlugin`] together with contacts,
+/// or alternatively by the [`XpbdSolverPlugin`] if the `xpbd_joints` feature is enabled.
 pub struct JointPlugin;
 
 impl Plugin for JointPlugin {
@@ -247,6 +306,8 @@ impl Plugin for JointPlugin {
             revolute::plugin,
             #[cfg(feature = "3d")]
             spherical::plugin,
+            pulley::plugin,
+            target::plugin,
         ));
 
         app.configure_sets(
@@ -256,6 +317,37 @@ impl Plugin for JointPlugin {
                 .before(SolverSystems::PrepareJoints),
         );
     }
+
+    fn finish(&self, app: &mut App) {
+        // Coupling joints are only solved by the XPBD solver.
+        #[cfg(feature = "xpbd_joints")]
+        if app.is_plugin_added::<crate::dynamics::solver::xpbd::XpbdSolverPlugin>() {
+            return;
+        }
+
+        app.add_systems(
+            PhysicsSchedule,
+            (
+                warn_unsolved_joints::<PulleyJoint>,
+                warn_unsolved_joints::<GearJoint>,
+                warn_unsolved_joints::<RackAndPinionJoint>,
+            ),
+        );
+    }
+}
+
+/// Warns once if a joint that is only supported by the [`XpbdSolverPlugin`] is spawned without it.
+fn warn_unsolved_joints<J: Component>(joints: Query<(), Added<J>>, mut warned: Local<bool>) {
+    if *warned || joints.is_empty() {
+        return;
+    }
+
+    warn!(
+        "{} is only solved by the `XpbdSolverPlugin`, which is not enabled. \
+        Enable the `xpbd_joints` feature and add the `XpbdSolverPlugin` to simulate it.",
+        disqualified::ShortName::of::<J>()
+    );
+    *warned = true;
 }
 
 /// System sets for [joints](dynamics::joints).
@@ -473,6 +565,108 @@ impl AngleLimit {
     }
 }
 
+/// A drive that pulls the relative orientation of the frames of a [joint](self) towards a target orientation,
+/// like a spring and damper acting on the relative rotation of the bodies.
+///
+/// The drive is a proportional-derivative (PD) controller: the [`stiffness`](Self::stiffness) determines
+/// how strongly the rotation error is corrected, and the [`damping`](Self::damping) determines how strongly
+/// the relative angular velocity is resisted. It is solved implicitly inside the joint solver,
+/// so it remains stable even with large stiffness and damping values.
+///
+#[cfg_attr(
+    feature = "2d",
+    doc = "Orientation drives are supported by [`RevoluteJoint`]."
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "Orientation drives are supported by [`RevoluteJoint`] and [`SphericalJoint`]."
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "For a [`RevoluteJoint`], the target should be a rotation around the [hinge axis](RevoluteJoint::hinge_axis)."
+)]
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+/// # use bevy::prelude::*;
+/// #
+/// # fn setup(mut commands: Commands) {
+/// #     let body1 = commands.spawn(RigidBody::Dynamic).id();
+/// #     let body2 = commands.spawn(RigidBody::Dynamic).id();
+/// #
+/// // Pull the second body towards a 45 degree angle relative to the first body.
+/// commands.spawn(
+///     RevoluteJoint::new(body1, body2).with_orientation_drive(OrientationDrive::new(
+#[cfg_attr(
+    feature = "2d",
+    doc = "        core::f32::consts::FRAC_PI_4 as Scalar,"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        Quaternion::from_rotation_z(core::f32::consts::FRAC_PI_4 as Scalar),"
+)]
+///         500.0, // Stiffness
+///         50.0,  // Damping
+///     )),
+/// );
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct OrientationDrive {
+    /// The target rotation angle of the second joint frame relative to the first joint frame.
+    #[cfg(feature = "2d")]
+    pub target: Scalar,
+    /// The target rotation of the second joint frame relative to the first joint frame.
+    #[cfg(feature = "3d")]
+    pub target: Quaternion,
+    /// The stiffness of the drive (N * m / rad).
+    pub stiffness: Scalar,
+    /// The damping of the drive (N * m * s / rad).
+    pub damping: Scalar,
+}
+
+impl OrientationDrive {
+    /// Creates a new [`OrientationDrive`] with the given target orientation, stiffness, and damping.
+    #[cfg(feature = "2d")]
+    pub const fn new(target: Scalar, stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            target,
+            stiffness,
+            damping,
+        }
+    }
+
+    /// Creates a new [`OrientationDrive`] with the given target orientation, stiffness, and damping.
+    #[cfg(feature = "3d")]
+    pub const fn new(target: Quaternion, stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            target,
+            stiffness,
+            damping,
+        }
+    }
+
+    /// Sets the target rotation angle of the second joint frame relative to the first joint frame.
+    #[cfg(feature = "2d")]
+    pub const fn with_target(mut self, target: Scalar) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets the target rotation of the second joint frame relative to the first joint frame.
+    #[cfg(feature = "3d")]
+    pub const fn with_target(mut self, target: Quaternion) -> Self {
+        self.target = target;
+        self
+    }
+}
+
 /// A marker component that indicates that a [joint](self) is disabled and should not constrain the bodies it is attached to.
 /// Must be on the same entity as the joint.
 ///
@@ -611,6 +805,57 @@ pub struct JointDamping {
     pub angular: Scalar,
 }
 
+/// A component for configuring the stiffness of a [joint](self) as a spring
+/// with a given [`frequency`](Self::frequency) and [`damping_ratio`](Self::damping_ratio).
+///
+/// Without this component, joints are rigid, and only the default stabilization configured by
+/// [`SolverConfig::joint_damping_ratio`] and [`SolverConfig::joint_frequency_factor`] is used.
+///
+/// This is only used by the [`ImpulseJointSolverPlugin`]. The [`XpbdSolverPlugin`] uses
+/// the compliance parameters of each joint instead, and joints with non-zero compliance
+/// are solved by the [`XpbdSolverPlugin`] if it is enabled.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+/// # use bevy::prelude::*;
+/// #
+/// # fn setup(mut commands: Commands) {
+/// #     let body1 = commands.spawn(RigidBody::Dynamic).id();
+/// #     let body2 = commands.spawn(RigidBody::Dynamic).id();
+/// #
+/// // Connect two bodies with a revolute joint that acts like a soft spring.
+/// commands.spawn((
+///     RevoluteJoint::new(body1, body2),
+///     JointSoftness::new(2.0, 0.7),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct JointSoftness {
+    /// The frequency of the spring in Hertz. Higher values make the joint stiffer.
+    pub frequency: Scalar,
+    /// The damping ratio of the spring. A value of `1.0` is critically damped,
+    /// and lower values let the bodies oscillate.
+    pub damping_ratio: Scalar,
+}
+
+impl JointSoftness {
+    /// Creates a new [`JointSoftness`] with the given frequency in Hertz and damping ratio.
+    #[inline]
+    pub const fn new(frequency: Scalar, damping_ratio: Scalar) -> Self {
+        Self {
+            frequency,
+            damping_ratio,
+        }
+    }
+}
+
 /// A component for reading the force and torque exerted by a [joint](self).
 ///
 /// This is not inserted automatically for joints, and must be added manually.
@@ -1129,3 +1374,532 @@ impl From<JointBasis> for JointFrame {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, tests::create_test_app};
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    fn create_app() -> App {
+        let mut app = create_test_app(());
+        app.insert_resource(Gravity(Vector::ZERO));
+        app
+    }
+
+    /// Spawns a static body and a dynamic body with unit mass and angular inertia, both at the origin.
+    fn spawn_bodies(app: &mut App) -> (Entity, Entity) {
+        let body1 = app.world_mut().spawn(RigidBody::Static).id();
+        let body2 = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Mass(1.0),
+                #[cfg(feature = "2d")]
+                AngularInertia(1.0),
+                #[cfg(feature = "3d")]
+                AngularInertia::new(Vec3::ONE),
+            ))
+            .id();
+        (body1, body2)
+    }
+
+    /// Spawns a dynamic body with unit mass and angular inertia at the given position,
+    /// rotating around the z-axis with the given angular velocity.
+    fn spawn_dynamic_body(app: &mut App, position: Vector, angular_velocity: Scalar) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(position),
+                Mass(1.0),
+                #[cfg(feature = "2d")]
+                AngularInertia(1.0),
+                #[cfg(feature = "3d")]
+                AngularInertia::new(Vec3::ONE),
+                #[cfg(feature = "2d")]
+                AngularVelocity(angular_velocity),
+                #[cfg(feature = "3d")]
+                AngularVelocity(Vector::Z * angular_velocity),
+            ))
+            .id()
+    }
+
+    /// Steps the app for the given number of seconds, returning the rotations of `body` after each step.
+    fn simulate(app: &mut App, body: Entity, seconds: Scalar) -> Vec<Rotation> {
+        let steps = (seconds * 60.0) as usize;
+        (0..steps)
+            .map(|_| {
+                app.update();
+                *app.world().get::<Rotation>(body).unwrap()
+            })
+            .collect()
+    }
+
+    /// Returns the rotation angle around the z-axis.
+    fn hinge_angle(rotation: &Rotation) -> Scalar {
+        #[cfg(feature = "2d")]
+        {
+            rotation.as_radians()
+        }
+        #[cfg(feature = "3d")]
+        {
+            let (axis, angle) = rotation.0.to_axis_angle();
+            angle * axis.z.signum()
+        }
+    }
+
+    /// Creates a [`RevoluteJoint`] with an [`OrientationDrive`] towards the given angle around the z-axis.
+    fn revolute_drive(
+        body1: Entity,
+        body2: Entity,
+        target_angle: Scalar,
+        stiffness: Scalar,
+        damping: Scalar,
+    ) -> RevoluteJoint {
+        #[cfg(feature = "2d")]
+        let target = target_angle;
+        #[cfg(feature = "3d")]
+        let target = Quaternion::from_rotation_z(target_angle);
+        RevoluteJoint::new(body1, body2)
+            .with_orientation_drive(OrientationDrive::new(target, stiffness, damping))
+    }
+
+    #[test]
+    fn revolute_orientation_drive_converges_to_target() {
+        let mut app = create_app();
+        let (body1, body2) = spawn_bodies(&mut app);
+
+        // A critically damped drive with a natural frequency of 10 rad/s.
+        let target_angle = 0.8;
+        app.world_mut()
+            .spawn(revolute_drive(body1, body2, target_angle, 100.0, 20.0));
+
+        let angles: Vec<Scalar> = simulate(&mut app, body2, 2.0)
+            .iter()
+            .map(hinge_angle)
+            .collect();
+
+        // The drive is a spring, so the body doesn't snap to the target immediately.
+        assert!(angles[2] < 0.5 * target_angle);
+
+        // A critically damped drive approaches the target without overshooting noticeably.
+        for angle in &angles {
+            assert!(*angle <= target_angle * 1.02);
+        }
+
+        assert_relative_eq!(*angles.last().unwrap(), target_angle, epsilon = 1e-3);
+        let angular_velocity = app.world().get::<AngularVelocity>(body2).unwrap();
+        #[cfg(feature = "2d")]
+        assert_relative_eq!(angular_velocity.0, 0.0, epsilon = 1e-2);
+        #[cfg(feature = "3d")]
+        assert!(angular_velocity.0.length() < 1e-2);
+    }
+
+    #[test]
+    fn orientation_drive_stiffness_and_damping_determine_convergence() {
+        let angle_after_quarter_second = |stiffness: Scalar, damping: Scalar| {
+            let mut app = create_app();
+            let (body1, body2) = spawn_bodies(&mut app);
+            app.world_mut()
+                .spawn(revolute_drive(body1, body2, 1.0, stiffness, damping));
+            hinge_angle(simulate(&mut app, body2, 0.25).last().unwrap())
+        };
+
+        // A stiffer drive converges faster than a softer one with the same damping ratio.
+        let soft = angle_after_quarter_second(25.0, 10.0);
+        let stiff = angle_after_quarter_second(400.0, 40.0);
+        assert!(soft < 0.5);
+        assert!(stiff > 0.8);
+
+        // Without stiffness, the drive doesn't pull the body towards the target.
+        let undriven = angle_after_quarter_second(0.0, 40.0);
+        assert_relative_eq!(undriven, 0.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn distance_joint_holds_distance() {
+        let mut app = create_app();
+        let ground = app.world_mut().spawn(RigidBody::Static).id();
+        let body = spawn_dynamic_body(&mut app, Vector::X * 2.0, 0.0);
+        app.world_mut()
+            .entity_mut(body)
+            .insert(LinearVelocity(Vector::Y * 3.0));
+        app.world_mut()
+            .spawn(DistanceJoint::new(ground, body).with_limits(2.0, 2.0));
+
+        // The body swings around the ground at a fixed distance.
+        for _ in 0..120 {
+            app.update();
+            let distance = app.world().get::<Position>(body).unwrap().length();
+            assert_relative_eq!(distance, 2.0, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn distance_joint_limits_distance() {
+        let mut app = create_app();
+        let ground = app.world_mut().spawn(RigidBody::Static).id();
+        let body = spawn_dynamic_body(&mut app, Vector::X * 1.5, 0.0);
+        app.world_mut()
+            .entity_mut(body)
+            .insert(LinearVelocity(Vector::X * 3.0));
+        app.world_mut()
+            .spawn(DistanceJoint::new(ground, body).with_limits(1.0, 2.0));
+
+        for _ in 0..60 {
+            app.update();
+            let distance = app.world().get::<Position>(body).unwrap().length();
+            assert!(distance <= 2.0 + 1e-2);
+        }
+
+        // The body stops at the upper limit.
+        let distance = app.world().get::<Position>(body).unwrap().length();
+        assert_relative_eq!(distance, 2.0, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn revolute_joint_limits_angle() {
+        let mut app = create_app();
+        let ground = app.world_mut().spawn(RigidBody::Static).id();
+        let body = spawn_dynamic_body(&mut app, Vector::X, 3.0);
+        app.world_mut().spawn(
+            RevoluteJoint::new(ground, body)
+                .with_local_anchor1(Vector::X)
+                .with_angle_limits(-0.5, 0.5),
+        );
+
+        for _ in 0..60 {
+            app.update();
+            let angle = hinge_angle(app.world().get::<Rotation>(body).unwrap());
+            let position = app.world().get::<Position>(body).unwrap();
+            assert!(angle <= 0.5 + 1e-2);
+            assert!(position.distance(Vector::X) < 1e-2);
+        }
+
+        // The body stops at the upper limit.
+        let angle = hinge_angle(app.world().get::<Rotation>(body).unwrap());
+        assert_relative_eq!(angle, 0.5, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn prismatic_joint_limits_translation() {
+        let mut app = create_app();
+        let ground = app.world_mut().spawn(RigidBody::Static).id();
+        let body = spawn_dynamic_body(&mut app, Vector::ZERO, 0.0);
+
+        // The velocity perpendicular to the slider axis is removed by the joint.
+        app.world_mut()
+            .entity_mut(body)
+            .insert(LinearVelocity(Vector::X * 3.0 + Vector::Y * 2.0));
+        app.world_mut()
+            .spawn(PrismaticJoint::new(ground, body).with_limits(-1.0, 1.0));
+
+        for _ in 0..60 {
+            app.update();
+            let position = app.world().get::<Position>(body).unwrap();
+            assert!(position.x <= 1.0 + 1e-2);
+            assert!(position.y.abs() < 1e-2);
+        }
+
+        // The body stops at the upper limit.
+        let position = app.world().get::<Position>(body).unwrap();
+        assert_relative_eq!(position.x, 1.0, epsilon = 1e-2);
+    }
+
+    #[cfg(feature = "xpbd_joints")]
+    #[test]
+    fn compliant_joint_is_soft() {
+        use crate::dynamics::solver::joint::ImpulseJointSolverData;
+
+        // Returns the largest stretch of a distance joint holding a hanging body, and whether
+        // the joint was solved by the impulse-based joint solver.
+        let max_stretch = |compliance: Scalar| {
+            let mut app = create_app();
+            app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+            let ground = app.world_mut().spawn(RigidBody::Static).id();
+            let body = spawn_dynamic_body(&mut app, Vector::NEG_Y, 0.0);
+            let joint = app
+                .world_mut()
+                .spawn(
+                    DistanceJoint::new(ground, body)
+                        .with_limits(1.0, 1.0)
+                        .with_compliance(compliance),
+                )
+                .id();
+
+            let mut max_stretch: Scalar = 0.0;
+            for _ in 0..120 {
+                app.update();
+                let distance = app.world().get::<Position>(body).unwrap().length();
+                max_stretch = max_stretch.max(distance - 1.0);
+            }
+
+            let is_impulse_joint = app.world().get::<ImpulseJointSolverData>(joint).is_some();
+            (max_stretch, is_impulse_joint)
+        };
+
+        // Rigid joints are solved by the impulse-based joint solver.
+        let (rigid_stretch, is_impulse_joint) = max_stretch(0.0);
+        assert!(is_impulse_joint);
+        assert!(rigid_stretch < 5e-3);
+
+        // Compliant joints are solved with XPBD, and act like a spring with a stiffness of 400 N/m.
+        // The body oscillates around the equilibrium stretch of 9.81 / 400 = 0.0245 m.
+        let (soft_stretch, is_impulse_joint) = max_stretch(1.0 / 400.0);
+        assert!(!is_impulse_joint);
+        assert!(soft_stretch > 0.015);
+        assert!(soft_stretch < 0.06);
+    }
+
+    #[cfg(feature = "xpbd_joints")]
+    #[test]
+    fn overflow_xpbd_joints_are_solved() {
+        use crate::dynamics::solver::constraint_graph::GRAPH_COLOR_COUNT;
+
+        let mut app = create_app();
+        let hub = spawn_dynamic_body(&mut app, Vector::ZERO, 0.0);
+
+        // More joints share the hub than there are graph colors, so some of them overflow
+        // and are solved serially, while the rest are solved in parallel within their colors.
+        // The compliance routes the joints to the XPBD solver.
+        let count = GRAPH_COLOR_COUNT + 8;
+        let bodies: Vec<Entity> = (0..count)
+            .map(|i| {
+                let angle = i as Scalar / count as Scalar * 2.0 * PI;
+                let direction = Vector::X * angle.cos() + Vector::Y * angle.sin();
+                let body = spawn_dynamic_body(&mut app, direction, 0.0);
+                app.world_mut()
+                    .entity_mut(body)
+                    .insert(LinearVelocity(direction));
+                app.world_mut().spawn(
+                    DistanceJoint::new(hub, body)
+                        .with_limits(1.0, 1.0)
+                        .with_compliance(1e-8),
+                );
+                body
+            })
+            .collect();
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        // Every joint holds its distance despite the bodies moving outwards.
+        let hub_position = app.world().get::<Position>(hub).unwrap().0;
+        for body in bodies {
+            let position = app.world().get::<Position>(body).unwrap().0;
+            assert_relative_eq!(position.distance(hub_position), 1.0, epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn gear_joint_holds_ratio() {
+        let mut app = create_app();
+        let ground = app.world_mut().spawn(RigidBody::Static).id();
+        let gear1 = spawn_dynamic_body(&mut app, Vector::ZERO, 2.0);
+        let gear2 = spawn_dynamic_body(&mut app, Vector::X * 3.0, 0.0);
+
+        // The second gear is the first body of its revolute joint to test both joint orientations.
+        let joint1 = app
+            .world_mut()
+            .spawn(RevoluteJoint::new(ground, gear1))
+            .id();
+        let joint2 = app
+            .world_mut()
+            .spawn(RevoluteJoint::new(gear2, ground).with_local_anchor2(Vector::X * 3.0))
+            .id();
+        let ratio = 2.0;
+        app.world_mut()
+            .spawn(GearJoint::new(gear1, gear2, joint1, joint2).with_ratio(ratio));
+
+        for _ in 0..60 {
+            app.update();
+            let angle1 = hinge_angle(app.world().get::<Rotation>(gear1).unwrap());
+            let angle2 = hinge_angle(app.world().get::<Rotation>(gear2).unwrap());
+            assert_relative_eq!(angle1 + ratio * angle2, 0.0, epsilon = 1e-3);
+        }
+
+        // The gears keep rotating, with the second gear rotating in the opposite direction.
+        let angle1 = hinge_angle(app.world().get::<Rotation>(gear1).unwrap());
+        assert!(angle1 > 1.0);
+    }
+
+    #[test]
+    fn rack_and_pinion_joint_holds_ratio() {
+        let mut app = create_app();
+        let ground = app.world_mut().spawn(RigidBody::Static).id();
+        let pinion = spawn_dynamic_body(&mut app, Vector::ZERO, 1.0);
+        let rack = spawn_dynamic_body(&mut app, Vector::NEG_Y, 0.0);
+
+        let revolute = app
+            .world_mut()
+            .spawn(RevoluteJoint::new(ground, pinion))
+            .id();
+        let prismatic = app
+            .world_mut()
+            .spawn(PrismaticJoint::new(ground, rack).with_local_anchor1(Vector::NEG_Y))
+            .id();
+        let ratio = 0.5;
+        app.world_mut()
+            .spawn(RackAndPinionJoint::new(pinion, rack, revolute, prismatic).with_ratio(ratio));
+
+        for _ in 0..60 {
+            app.update();
+            let angle = hinge_angle(app.world().get::<Rotation>(pinion).unwrap());
+            let translation = app.world().get::<Position>(rack).unwrap().x;
+            assert_relative_eq!(translation, ratio * angle, epsilon = 1e-3);
+        }
+
+        // The pinion keeps rotating and drives the rack along the x-axis.
+        let translation = app.world().get::<Position>(rack).unwrap().x;
+        assert!(translation > 0.2);
+    }
+
+    #[test]
+    fn pulley_joint_holds_ratio() {
+        let mut app = create_app();
+        app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+        let body1 = spawn_dynamic_body(&mut app, Vector::NEG_X * 2.0, 0.0);
+        let body2 = spawn_dynamic_body(&mut app, Vector::X * 2.0, 0.0);
+
+        let ground_anchor1 = Vector::NEG_X * 2.0 + Vector::Y * 5.0;
+        let ground_anchor2 = Vector::X * 2.0 + Vector::Y * 5.0;
+        let ratio = 2.0;
+        app.world_mut().spawn(
+            PulleyJoint::new(body1, body2)
+                .with_ground_anchors(ground_anchor1, ground_anchor2)
+                .with_ratio(ratio),
+        );
+
+        // The rope length is computed from the initial configuration.
+        let length = 5.0 + ratio * 5.0;
+
+        for _ in 0..60 {
+            app.update();
+            let length1 = app
+                .world()
+                .get::<Position>(body1)
+                .unwrap()
+                .distance(ground_anchor1);
+            let length2 = app
+                .world()
+                .get::<Position>(body2)
+                .unwrap()
+                .distance(ground_anchor2);
+            assert_relative_eq!(length1 + ratio * length2, length, epsilon = 1e-2);
+        }
+
+        // The rope pulls the second body with twice the force, so the first body falls
+        // and lifts the second body.
+        let position1 = app.world().get::<Position>(body1).unwrap();
+        let position2 = app.world().get::<Position>(body2).unwrap();
+        assert!(position1.y < -0.5);
+        assert!(position2.y > 0.25);
+    }
+
+    #[test]
+    fn pulley_joint_allows_slack() {
+        let mut app = create_app();
+        let body1 = spawn_dynamic_body(&mut app, Vector::NEG_X * 2.0, 0.0);
+        let body2 = spawn_dynamic_body(&mut app, Vector::X * 2.0, 0.0);
+
+        app.world_mut()
+            .spawn(PulleyJoint::new(body1, body2).with_ground_anchors(
+                Vector::NEG_X * 2.0 + Vector::Y * 5.0,
+                Vector::X * 2.0 + Vector::Y * 5.0,
+            ));
+
+        // Moving the first body towards its pulley makes the rope go slack.
+        app.world_mut()
+            .entity_mut(body1)
+            .insert(LinearVelocity(Vector::Y));
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        // The slack rope neither slows down the first body nor pushes the second body.
+        let velocity1 = app.world().get::<LinearVelocity>(body1).unwrap();
+        let position2 = app.world().get::<Position>(body2).unwrap();
+        assert_relative_eq!(velocity1.0, Vector::Y, epsilon = 1e-4);
+        assert_relative_eq!(position2.0, Vector::X * 2.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn target_joint_drags_body_to_target() {
+        let mut app = create_app();
+        let body = spawn_dynamic_body(&mut app, Vector::ZERO, 0.0);
+
+        let target = Vector::X * 2.0;
+        app.world_mut().spawn(
+            TargetJoint::new(body)
+                .with_target(target)
+                .with_frequency(5.0)
+                .with_damping_ratio(1.0),
+        );
+
+        // The joint is a spring, so the body doesn't snap to the target immediately.
+        app.update();
+        assert!(app.world().get::<Position>(body).unwrap().x < 0.5 * target.x);
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        let position = app.world().get::<Position>(body).unwrap();
+        let velocity = app.world().get::<LinearVelocity>(body).unwrap();
+        assert!(position.distance(target) < 1e-2);
+        assert!(velocity.length() < 1e-2);
+    }
+
+    #[test]
+    fn target_joint_force_is_clamped() {
+        let mut app = create_app();
+        let body = spawn_dynamic_body(&mut app, Vector::ZERO, 0.0);
+
+        // A stiff joint with a distant target would accelerate the body much faster without a force limit.
+        let max_force = 2.0;
+        let joint = app
+            .world_mut()
+            .spawn((
+                TargetJoint::new(body)
+                    .with_target(Vector::X * 100.0)
+                    .with_frequency(30.0)
+                    .with_max_force(max_force),
+                JointForces::default(),
+            ))
+            .id();
+
+        for _ in 0..60 {
+            app.update();
+            let forces = app.world().get::<JointForces>(joint).unwrap();
+            assert!(forces.force().length() <= max_force + 1e-4);
+        }
+
+        // The body has unit mass, so it accelerates at the maximum force for one second.
+        let velocity = app.world().get::<LinearVelocity>(body).unwrap();
+        assert_relative_eq!(velocity.x, max_force, epsilon = 1e-2);
+        let forces = app.world().get::<JointForces>(joint).unwrap();
+        assert_relative_eq!(forces.force().length(), max_force, epsilon = 1e-4);
+    }
+
+    #[cfg(feature = "3d")]
+    #[test]
+    fn spherical_orientation_drive_converges_to_target() {
+        let mut app = create_app();
+        let (body1, body2) = spawn_bodies(&mut app);
+
+        let target = Quaternion::from_euler(EulerRot::YXZ, 0.6, -0.4, 0.3);
+        app.world_mut().spawn(
+            SphericalJoint::new(body1, body2)
+                .with_orientation_drive(OrientationDrive::new(target, 100.0, 20.0)),
+        );
+
+        let rotations = simulate(&mut app, body2, 2.0);
+
+        assert!(
+            rotations[2].0.angle_between(target) > 0.5 * target.angle_between(Quaternion::IDENTITY)
+        );
+        assert!(rotations.last().unwrap().0.angle_between(target) < 1e-3);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{
+    dynamics::joints::{EntityConstraint, JointSystems},
+    prelude::*,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A pulley [joint](dynamics::joints) connects two bodies with a rope that runs over two fixed pulleys.
+///
+/// Each body is connected to a ground anchor, a fixed point in world space representing a pulley.
+/// The joint limits the lengths of the two rope segments from the ground anchors to the anchor points
+/// on the bodies such that
+///
+/// `length1 + ratio * length2 <= length`.
+///
+/// When one body moves away from its pulley, the other body is pulled towards its own pulley.
+/// Like a real rope, the joint only resists stretching, so the rope can go slack.
+///
+/// The ratio can be used to simulate a block and tackle. For example, a ratio of `2.0` means that
+/// the second body moves half the distance of the first body, but the rope pulls it with twice the force.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let body1 = commands
+///         .spawn((RigidBody::Dynamic, Transform::from_xyz(-2.0, 0.0, 0.0)))
+///         .id();
+///     let body2 = commands
+///         .spawn((RigidBody::Dynamic, Transform::from_xyz(2.0, 0.0, 0.0)))
+///         .id();
+///
+///     // Hang the bodies from two pulleys above them.
+///     // The length of the rope is determined by the initial configuration.
+///     commands.spawn(
+///         PulleyJoint::new(body1, body2)
+#[cfg_attr(
+    feature = "2d",
+    doc = "            .with_ground_anchors(Vector::new(-2.0, 5.0), Vector::new(2.0, 5.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            .with_ground_anchors(Vector::new(-2.0, 5.0, 0.0), Vector::new(2.0, 5.0, 0.0)),"
+)]
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, MapEntities, PartialEq)]
+pub struct PulleyJoint {
+    /// The first body constrained by the joint.
+    pub body1: Entity,
+    /// The second body constrained by the joint.
+    pub body2: Entity,
+    /// The joint anchor point on the first body.
+    pub anchor1: JointAnchor,
+    /// The joint anchor point on the second body.
+    pub anchor2: JointAnchor,
+    /// The fixed world-space point that the rope of the first body runs over.
+    pub ground_anchor1: Vector,
+    /// The fixed world-space point that the rope of the second body runs over.
+    pub ground_anchor2: Vector,
+    /// The pulley ratio. The joint maintains `length1 + ratio * length2 <= length`.
+    ///
+    /// By default, this is `1.0`.
+    pub ratio: Scalar,
+    /// The total length of the rope, `length1 + ratio * length2`.
+    ///
+    /// If `None`, the length is computed from the configuration of the bodies when the joint is first simulated.
+    pub length: Option<Scalar>,
+    /// The joint's compliance, the inverse of stiffness (m / N).
+    pub compliance: Scalar,
+}
+
+impl EntityConstraint<2> for PulleyJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.body1, self.body2]
+    }
+}
+
+impl PulleyJoint {
+    /// Creates a new [`PulleyJoint`] between two entities.
+    ///
+    /// The ground anchors default to the origin, and should be configured using
+    /// [`with_ground_anchors`](Self::with_ground_anchors).
+    #[inline]
+    pub const fn new(body1: Entity, body2: Entity) -> Self {
+        Self {
+            body1,
+            body2,
+            anchor1: JointAnchor::ZERO,
+            anchor2: JointAnchor::ZERO,
+            ground_anchor1: Vector::ZERO,
+            ground_anchor2: Vector::ZERO,
+            ratio: 1.0,
+            length: None,
+            compliance: 0.0,
+        }
+    }
+
+    /// Sets the fixed world-space points that the ropes of the first and second body run over.
+    #[inline]
+    pub const fn with_ground_anchors(mut self, anchor1: Vector, anchor2: Vector) -> Self {
+        self.ground_anchor1 = anchor1;
+        self.ground_anchor2 = anchor2;
+        self
+    }
+
+    /// Sets the local anchor point on the first body.
+    ///
+    /// This configures the [`JointAnchor`] of the first body.
+    #[inline]
+    pub const fn with_local_anchor1(mut self, anchor: Vector) -> Self {
+        self.anchor1 = JointAnchor::Local(anchor);
+        self
+    }
+
+    /// Sets the local anchor point on the second body.
+    ///
+    /// This configures the [`JointAnchor`] of the second body.
+    #[inline]
+    pub const fn with_local_anchor2(mut self, anchor: Vector) -> Self {
+        self.anchor2 = JointAnchor::Local(anchor);
+        self
+    }
+
+    /// Sets the pulley ratio. The joint maintains `length1 + ratio * length2 <= length`.
+    #[inline]
+    pub const fn with_ratio(mut self, ratio: Scalar) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the total length of the rope, `length1 + ratio * length2`.
+    #[inline]
+    pub const fn with_length(mut self, length: Scalar) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, m / N).
+    #[inline]
+    pub const fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+
+    /// Returns the local anchor point on the first body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, this will return `None`.
+    #[inline]
+    pub const fn local_anchor1(&self) -> Option<Vector> {
+        match self.anchor1 {
+            JointAnchor::Local(anchor) => Some(anchor),
+            _ => None,
+        }
+    }
+
+    /// Returns the local anchor point on the second body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, this will return `None`.
+    #[inline]
+    pub const fn local_anchor2(&self) -> Option<Vector> {
+        match self.anchor2 {
+            JointAnchor::Local(anchor) => Some(anchor),
+            _ => None,
+        }
+    }
+}
+
+impl MapEntities for PulleyJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.body1 = entity_mapper.get_mapped(self.body1);
+        self.body2 = entity_mapper.get_mapped(self.body2);
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        update_local_anchors.in_set(JointSystems::PrepareLocalFrames),
+    );
+}
+
+fn update_local_anchors(
+    mut joints: Query<&mut PulleyJoint, Changed<PulleyJoint>>,
+    bodies: Query<(&Position, &Rotation)>,
+) {
+    for mut joint in &mut joints {
+        if matches!(joint.anchor1, JointAnchor::Local(_))
+            && matches!(joint.anchor2, JointAnchor::Local(_))
+        {
+            continue;
+        }
+
+        let Ok([(pos1, rot1), (pos2, rot2)]) = bodies.get_many(joint.entities()) else {
+            continue;
+        };
+
+        let [anchor1, anchor2] =
+            JointAnchor::compute_local(joint.anchor1, joint.anchor2, pos1.0, pos2.0, rot1, rot2);
+        joint.anchor1 = anchor1;
+        joint.anchor2 = anchor2;
+    }
+}
+
+#[cfg(feature = "debug-plugin")]
+impl DebugRenderConstraint<2> for PulleyJoint {
+    type Context = ();
+
+    fn debug_render(
+        &self,
+        positions: [Vector; 2],
+        rotations: [Rotation; 2],
+        _context: &mut Self::Context,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        config: &PhysicsGizmos,
+    ) {
+        let [pos1, pos2] = positions;
+        let [rot1, rot2] = rotations;
+
+        let JointAnchor::Local(local_anchor1) = self.anchor1 else {
+            return;
+        };
+        let JointAnchor::Local(local_anchor2) = self.anchor2 else {
+            return;
+        };
+
+        let anchor1 = pos1 + rot1 * local_anchor1;
+        let anchor2 = pos2 + rot2 * local_anchor2;
+
+        if let Some(anchor_color) = config.joint_anchor_color {
+            gizmos.draw_line(pos1, anchor1, anchor_color);
+            gizmos.draw_line(pos2, anchor2, anchor_color);
+        }
+
+        if let Some(color) = config.joint_separation_color {
+            gizmos.draw_line(anchor1, self.ground_anchor1, color);
+            gizmos.draw_line(self.ground_anchor1, self.ground_anchor2, color);
+            gizmos.draw_line(self.ground_anchor2, anchor2, color);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{dynamics::joints::EntityConstraint, prelude::*};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A rack-and-pinion [joint](dynamics::joints) couples the rotation of a [`RevoluteJoint`]
+/// to the translation of a [`PrismaticJoint`].
+///
+/// The pinion is a body that is attached to another body using a [`RevoluteJoint`],
+/// and the rack is a body that is attached to another body using a [`PrismaticJoint`].
+/// The rack-and-pinion joint links the angle of the pinion to the translation of the rack such that
+///
+/// `translation = ratio * angle + constant`,
+///
+/// where the angle and translation are measured relative to the other bodies of the joints.
+/// The constant is determined by the configuration of the bodies when the joint is first simulated.
+///
+/// The ratio is the distance that the rack moves per radian of rotation of the pinion,
+/// which is typically the radius of the pinion. A negative ratio flips the direction of the rack.
+///
+/// The rack-and-pinion joint does not keep the bodies in place, and it must be used together
+/// with the revolute and prismatic joints. It is solved after them by the [`XpbdSolverPlugin`],
+/// and a warning is logged if the joint is used without it.
+///
+/// The joint only applies a torque to the pinion and a force to the rack. Motion of the other bodies
+/// of the revolute and prismatic joints is taken into account at the start of each time step,
+/// but no reaction is applied to those bodies. If they are dynamic, momentum is not conserved.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ground = commands.spawn(RigidBody::Static).id();
+///     let pinion = commands.spawn(RigidBody::Dynamic).id();
+///     let rack = commands
+///         .spawn((RigidBody::Dynamic, Transform::from_xyz(0.0, -1.0, 0.0)))
+///         .id();
+///
+///     // Attach the pinion and the rack to the ground.
+///     let revolute = commands.spawn(RevoluteJoint::new(ground, pinion)).id();
+///     let prismatic = commands
+///         .spawn(PrismaticJoint::new(ground, rack).with_local_anchor1(Vector::NEG_Y))
+///         .id();
+///
+///     // Couple the rotation of the pinion to the translation of the rack.
+///     commands.spawn(
+///         RackAndPinionJoint::new(pinion, rack, revolute, prismatic).with_ratio(0.5),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, MapEntities, PartialEq)]
+pub struct RackAndPinionJoint {
+    /// The pinion body. This must be one of the bodies of the [`revolute_joint`](Self::revolute_joint).
+    pub pinion: Entity,
+    /// The rack body. This must be one of the bodies of the [`prismatic_joint`](Self::prismatic_joint).
+    pub rack: Entity,
+    /// The [`RevoluteJoint`] entity attaching the pinion.
+    pub revolute_joint: Entity,
+    /// The [`PrismaticJoint`] entity attaching the rack.
+    pub prismatic_joint: Entity,
+    /// The distance that the rack moves per radian of rotation of the pinion.
+    ///
+    /// By default, this is `1.0`.
+    pub ratio: Scalar,
+    /// The compliance of the joint (inverse of stiffness, m / N).
+    pub compliance: Scalar,
+}
+
+impl EntityConstraint<2> for RackAndPinionJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.pinion, self.rack]
+    }
+}
+
+impl RackAndPinionJoint {
+    /// Creates a new [`RackAndPinionJoint`] between a pinion and a rack,
+    /// coupling the given [`RevoluteJoint`] and [`PrismaticJoint`].
+    ///
+    /// `pinion` must be one of the bodies of `revolute_joint`, and `rack` must be one of the bodies of `prismatic_joint`.
+    #[inline]
+    pub const fn new(
+        pinion: Entity,
+        rack: Entity,
+        revolute_joint: Entity,
+        prismatic_joint: Entity,
+    ) -> Self {
+        Self {
+            pinion,
+            rack,
+            revolute_joint,
+            prismatic_joint,
+            ratio: 1.0,
+            compliance: 0.0,
+        }
+    }
+
+    /// Sets the distance that the rack moves per radian of rotation of the pinion.
+    #[inline]
+    pub const fn with_ratio(mut self, ratio: Scalar) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, m / N).
+    #[inline]
+    pub const fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+}
+
+impl MapEntities for RackAndPinionJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.pinion = entity_mapper.get_mapped(self.pinion);
+        self.rack = entity_mapper.get_mapped(self.rack);
+        self.revolute_joint = entity_mapper.get_mapped(self.revolute_joint);
+        self.prismatic_joint = entity_mapper.get_mapped(self.prismatic_joint);
+    }
+}
+
+#[cfg(feature = "debug-plugin")]
+impl DebugRenderConstraint<2> for RackAndPinionJoint {
+    type Context = ();
+
+    fn debug_render(
+        &self,
+        positions: [Vector; 2],
+        rotations: [Rotation; 2],
+        _context: &mut Self::Context,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        config: &PhysicsGizmos,
+    ) {
+        let [pinion_pos, rack_pos] = positions;
+        let [pinion_rot, _] = rotations;
+
+        if let Some(color) = config.joint_separation_color {
+            gizmos.draw_line(pinion_pos, rack_pos, color);
+        }
+
+        // The ratio is typically the radius of the pinion.
+        if let Some(anchor_color) = config.joint_anchor_color {
+            let radius = self.ratio.abs();
+            gizmos.draw_line(
+                pinion_pos,
+                pinion_pos + pinion_rot * (Vector::X * radius),
+                anchor_color,
+            );
+        }
+    }
+}
@@ -67,6 +67,9 @@ pub struct RevoluteJoint {
     pub align_compliance: Scalar,
     /// The compliance of the angle limit (inverse of stiffness, N * m / rad).
     pub limit_compliance: Scalar,
+    /// An optional [`OrientationDrive`] that pulls the relative rotation of the bodies
+    /// towards a target orientation.
+    pub orientation_drive: Option<OrientationDrive>,
 }
 
 impl EntityConstraint<2> for RevoluteJoint {
@@ -95,6 +98,7 @@ impl RevoluteJoint {
             #[cfg(feature = "3d")]
             align_compliance: 0.0,
             limit_compliance: 0.0,
+            orientation_drive: None,
         }
     }
 
@@ -337,6 +341,13 @@ impl RevoluteJoint {
         self.limit_compliance = compliance;
         self
     }
+
+    /// Sets the [`OrientationDrive`] that pulls the relative rotation of the bodies towards a target orientation.
+    #[inline]
+    pub const fn with_orientation_drive(mut self, drive: OrientationDrive) -> Self {
+        self.orientation_drive = Some(drive);
+        self
+    }
 }
 
 impl MapEntities for RevoluteJoint {
@@ -58,6 +58,9 @@ pub struct SphericalJoint {
     pub swing_compliance: Scalar,
     /// The compliance for twist (inverse of stiffness, N * m / rad).
     pub twist_compliance: Scalar,
+    /// An optional [`OrientationDrive`] that pulls the relative rotation of the bodies
+    /// towards a target orientation.
+    pub orientation_drive: Option<OrientationDrive>,
 }
 
 impl EntityConstraint<2> for SphericalJoint {
@@ -84,6 +87,7 @@ impl SphericalJoint {
             point_compliance: 0.0,
             swing_compliance: 0.0,
             twist_compliance: 0.0,
+            orientation_drive: None,
         }
     }
 
@@ -317,6 +321,13 @@ impl SphericalJoint {
         self.twist_compliance = compliance;
         self
     }
+
+    /// Sets the [`OrientationDrive`] that pulls the relative rotation of the bodies towards a target orientation.
+    #[inline]
+    pub const fn with_orientation_drive(mut self, drive: OrientationDrive) -> Self {
+        self.orientation_drive = Some(drive);
+        self
+    }
 }
 
 impl MapEntities for SphericalJoint {
@@ -0,0 +1,245 @@
+use crate::{
+    dynamics::joints::{EntityConstraint, JointSystems},
+    prelude::*,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A target [joint](dynamics::joints) pulls an anchor point on a body towards a target point in world space
+/// using a soft spring with a maximum force.
+///
+/// This is commonly known as a mouse joint, and it is useful for grabbing and dragging bodies
+/// in editors and physics toys. The target can be moved every frame, and the body follows it
+/// physically, still colliding with other bodies and reacting to other joints.
+///
+/// The stiffness of the spring is configured using a [`frequency`](Self::frequency) in Hertz
+/// and a [`damping_ratio`](Self::damping_ratio). The [`max_force`](Self::max_force) limits the force
+/// that the joint can apply, so that a dragged body can't be pushed through heavy obstacles.
+///
+/// Unlike other joints, a target joint only constrains a single body.
+/// It is solved as a soft constraint by the [`SolverPlugin`], so it works even without the `xpbd_joints` feature.
+///
+#[cfg_attr(
+    feature = "bevy_picking",
+    doc = "If the [`PhysicsPickingPlugin`] is enabled, bodies can also be dragged with the pointer
+using target joints by enabling [`PhysicsPickingSettings::drag_bodies`]."
+)]
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Grab;
+///
+/// fn setup(mut commands: Commands) {
+///     let body = commands
+///         .spawn((RigidBody::Dynamic, Transform::from_xyz(1.0, 2.0, 0.0)))
+///         .id();
+///
+///     // Grab the body at its current position.
+///     let grab_point = Vector::X * 1.0 + Vector::Y * 2.0;
+///     commands.spawn((
+///         TargetJoint::new(body)
+///             .with_anchor(grab_point)
+///             .with_target(grab_point)
+///             .with_max_force(1000.0),
+///         Grab,
+///     ));
+/// }
+///
+/// fn move_target(mut joints: Query<&mut TargetJoint, With<Grab>>, time: Res<Time>) {
+///     for mut joint in &mut joints {
+///         // Drag the body to the right.
+///         joint.target += Vector::X * time.delta_secs() as Scalar;
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, MapEntities, PartialEq)]
+pub struct TargetJoint {
+    /// The body constrained by the joint.
+    pub body: Entity,
+    /// The anchor point on the body that is pulled towards the target.
+    pub anchor: JointAnchor,
+    /// The target point in world space.
+    pub target: Vector,
+    /// The frequency of the spring in Hertz. Higher values make the joint stiffer.
+    ///
+    /// By default, this is `5.0`.
+    pub frequency: Scalar,
+    /// The damping ratio of the spring. A value of `1.0` is critically damped,
+    /// and lower values let the body oscillate around the target.
+    ///
+    /// By default, this is `0.7`.
+    pub damping_ratio: Scalar,
+    /// The maximum force that the joint can apply to pull the body towards the target.
+    ///
+    /// By default, this is unlimited.
+    pub max_force: Scalar,
+}
+
+impl EntityConstraint<1> for TargetJoint {
+    fn entities(&self) -> [Entity; 1] {
+        [self.body]
+    }
+}
+
+impl TargetJoint {
+    /// Creates a new [`TargetJoint`] for the given body.
+    ///
+    /// By default, the anchor is at the origin of the body, and the target is at the world origin.
+    #[inline]
+    pub const fn new(body: Entity) -> Self {
+        Self {
+            body,
+            anchor: JointAnchor::ZERO,
+            target: Vector::ZERO,
+            frequency: 5.0,
+            damping_ratio: 0.7,
+            max_force: Scalar::MAX,
+        }
+    }
+
+    /// Sets the global anchor point on the body.
+    ///
+    /// This is typically the point where the body was grabbed.
+    #[inline]
+    pub const fn with_anchor(mut self, anchor: Vector) -> Self {
+        self.anchor = JointAnchor::FromGlobal(anchor);
+        self
+    }
+
+    /// Sets the local anchor point on the body.
+    #[inline]
+    pub const fn with_local_anchor(mut self, anchor: Vector) -> Self {
+        self.anchor = JointAnchor::Local(anchor);
+        self
+    }
+
+    /// Sets the target point in world space.
+    #[inline]
+    pub const fn with_target(mut self, target: Vector) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Sets the frequency of the spring in Hertz.
+    #[inline]
+    pub const fn with_frequency(mut self, frequency: Scalar) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Sets the damping ratio of the spring.
+    #[inline]
+    pub const fn with_damping_ratio(mut self, damping_ratio: Scalar) -> Self {
+        self.damping_ratio = damping_ratio;
+        self
+    }
+
+    /// Sets the maximum force that the joint can apply.
+    #[inline]
+    pub const fn with_max_force(mut self, max_force: Scalar) -> Self {
+        self.max_force = max_force;
+        self
+    }
+
+    /// Returns the local anchor point on the body.
+    ///
+    /// If the [`JointAnchor`] is set to [`FromGlobal`](JointAnchor::FromGlobal),
+    /// and the local anchor has not yet been computed, this will return `None`.
+    #[inline]
+    pub const fn local_anchor(&self) -> Option<Vector> {
+        match self.anchor {
+            JointAnchor::Local(anchor) => Some(anchor),
+            _ => None,
+        }
+    }
+}
+
+impl MapEntities for TargetJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.body = entity_mapper.get_mapped(self.body);
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        (update_local_anchors, wake_targeted_bodies)
+            .chain()
+            .in_set(JointSystems::PrepareLocalFrames),
+    );
+}
+
+fn update_local_anchors(
+    mut joints: Query<&mut TargetJoint, Changed<TargetJoint>>,
+    bodies: Query<(&Position, &Rotation)>,
+) {
+    for mut joint in &mut joints {
+        let JointAnchor::FromGlobal(anchor) = joint.anchor else {
+            continue;
+        };
+
+        let Ok((pos, rot)) = bodies.get(joint.body) else {
+            continue;
+        };
+
+        joint.anchor = JointAnchor::Local(rot.inverse() * (anchor - pos.0));
+    }
+}
+
+/// Wakes up sleeping bodies when their target joint is moved.
+fn wake_targeted_bodies(
+    joints: Query<&TargetJoint, (Changed<TargetJoint>, Without<JointDisabled>)>,
+    bodies: Query<(), With<Sleeping>>,
+    mut commands: Commands,
+) {
+    for joint in &joints {
+        if bodies.contains(joint.body) {
+            commands.queue(WakeBody(joint.body));
+        }
+    }
+}
+
+#[cfg(feature = "debug-plugin")]
+impl DebugRenderConstraint<1> for TargetJoint {
+    type Context = ();
+
+    fn debug_render(
+        &self,
+        positions: [Vector; 1],
+        rotations: [Rotation; 1],
+        _context: &mut Self::Context,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        config: &PhysicsGizmos,
+    ) {
+        let [pos] = positions;
+        let [rot] = rotations;
+
+        let JointAnchor::Local(local_anchor) = self.anchor else {
+            return;
+        };
+
+        let anchor = pos + rot * local_anchor;
+
+        if let Some(anchor_color) = config.joint_anchor_color {
+            gizmos.draw_line(pos, anchor, anchor_color);
+        }
+
+        if let Some(color) = config.joint_separation_color {
+            gizmos.draw_line(anchor, self.target, color);
+        }
+    }
+}
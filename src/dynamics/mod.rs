@@ -11,6 +11,18 @@
 //! - Collision response, preventing objects from overlapping each other,
 //!   considering properties such as [`Friction`] and [`Restitution`].
 //! - [Joints](joints) connecting rigid bodies to each other.
+//! - [Articulations](articulation) simulated in reduced coordinates.
+//! - [Carrying bodies](platform) on moving platforms.
+//! - [Soft bodies, cloth, and ropes](soft_body) simulated using particles.
+//! - [Raycast vehicles](vehicle) with suspension and tire friction.
+#![cfg_attr(
+    all(
+        feature = "3d",
+        feature = "collider-from-mesh",
+        feature = "bevy_animation"
+    ),
+    doc = "- [Ragdolls](ragdoll) generated from skinned meshes."
+)]
 //! - Everything else related to the physical behavior and properties of rigid bodies.
 //!
 //! Rigid body dynamics does *not* include:
@@ -61,25 +73,99 @@
 //! [Gauss-Seidel]: https://en.wikipedia.org/wiki/Gauss%E2%80%93Seidel_method
 //! [Semi-implicit Euler]: https://en.wikipedia.org/wiki/Semi-implicit_Euler_method
 
+pub mod articulation;
 pub mod ccd;
 pub mod integrator;
 pub mod joints;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod platform;
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    feature = "collider-from-mesh",
+    feature = "bevy_animation"
+))]
+pub mod ragdoll;
 pub mod rigid_body;
+#[cfg(all(
+    feature = "xpbd_joints",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod soft_body;
 pub mod solver;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod vehicle;
 
 /// Re-exports common types related to the rigid body dynamics functionality.
 pub mod prelude {
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::platform::{
+        GroundedOn, PlatformRider, PlatformRiderPlugin, PlatformRiderSystems, PlatformRiders,
+        PlatformVelocity,
+    };
+    #[cfg(all(
+        feature = "3d",
+        feature = "default-collider",
+        feature = "collider-from-mesh",
+        feature = "bevy_animation"
+    ))]
+    pub use super::ragdoll::{
+        Ragdoll, RagdollBone, RagdollConstructor, RagdollJoint, RagdollMode, RagdollPlugin,
+        RagdollPoseTracking, RagdollReady,
+    };
     pub(crate) use super::rigid_body::mass_properties::{ComputeMassProperties, MassProperties};
+    #[cfg(all(
+        feature = "xpbd_joints",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::soft_body::{
+        ParticleAttachment, ParticleBendingConstraint, ParticleDistanceConstraint,
+        ParticleVolumeConstraint, Rope, RopeTorn, SoftBody, SoftBodyConfig, SoftBodyParticle,
+        SoftBodyPlugin, SoftBodySystems,
+    };
     #[cfg(feature = "xpbd_joints")]
     pub use super::solver::xpbd::XpbdSolverPlugin;
+    #[cfg(all(
+        feature = "3d",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::vehicle::{AntiRollBar, Suspension, WheelCast};
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::vehicle::{
+        TireFriction, Vehicle, VehicleControls, VehiclePlugin, VehicleSystems, VehicleWheels,
+        Wheel, WheelOf, WheelState,
+    };
     #[expect(deprecated)]
     pub use super::{
+        articulation::{
+            Articulation, ArticulationDrive, ArticulationJoint, ArticulationJointKind,
+            ArticulationLimit, ArticulationPlugin, ArticulationSystems,
+        },
         ccd::{CcdPlugin, SpeculativeMargin, SweepMode, SweptCcd},
-        integrator::{Gravity, IntegratorPlugin},
+        integrator::{
+            Gravity, GravityFalloff, GravityField, GravityFieldMode, GravityFieldShape,
+            GravityOverride, IntegratorPlugin, KinematicTarget,
+        },
         joints::{
-            AngleLimit, DistanceJoint, DistanceLimit, FixedJoint, JointAnchor, JointBasis,
-            JointCollisionDisabled, JointDamping, JointDisabled, JointForces, JointFrame,
-            JointPlugin, PrismaticJoint, RevoluteJoint,
+            AngleLimit, DistanceJoint, DistanceLimit, FixedJoint, GearJoint, JointAnchor,
+            JointBasis, JointCollisionDisabled, JointDamping, JointDisabled, JointForces,
+            JointFrame, JointPlugin, JointSoftness, OrientationDrive, PrismaticJoint, PulleyJoint,
+            RackAndPinionJoint, RevoluteJoint, TargetJoint,
         },
         rigid_body::{
             forces::{
@@ -111,6 +197,7 @@ pub mod prelude {
                 IslandPlugin, IslandSleepingPlugin, SleepBody, SleepIslands, WakeBody, WakeIslands,
                 WakeUpBody,
             },
+            joint::ImpulseJointSolverPlugin,
             schedule::{
                 SolverSchedulePlugin, SolverSet, SolverSystems, SubstepCount, SubstepSchedule,
             },
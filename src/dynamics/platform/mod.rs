@@ -0,0 +1,546 @@
+//! Carrying bodies on moving platforms.
+//!
+//! See [`PlatformRiderPlugin`].
+//!
+//! # Overview
+//!
+//! Bodies standing on a moving [kinematic](RigidBody::Kinematic) platform, such as an elevator
+//! or the deck of a ship, are only moved by friction. This can make them slide off or jitter,
+//! and [kinematic](RigidBody::Kinematic) characters, which never receive contact impulses,
+//! are not carried at all.
+//!
+//! Adding the [`PlatformRider`] component to a rigid body makes it inherit the motion of the body it is standing on.
+//! The supporting body is detected through the contacts in the [`ContactGraph`], with an optional
+//! ground [shape cast](SpatialQuery::cast_shape) as a fallback for kinematic riders that are not in contact
+//! with anything. The support is exposed as a [`GroundedOn`] relationship, and platforms have
+//! a [`PlatformRiders`] component listing the bodies standing on them.
+//!
+//! While a rider is grounded, the velocity of the platform at the rider's center of mass
+//! is added to the [`LinearVelocity`] of the rider for the duration of the physics step,
+//! along with the [`AngularVelocity`] of the platform. This inherited velocity is stored in
+//! the [`PlatformVelocity`] component. After the step, the inherited velocity is removed again,
+//! so that the velocity of the rider remains relative to the platform and can still be controlled directly.
+//! When the rider leaves the platform, for example by jumping off, it keeps the momentum of the platform.
+//!
+//! # Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     // A moving platform.
+//!     commands.spawn((
+//!         RigidBody::Kinematic,
+#![cfg_attr(feature = "2d", doc = "        Collider::rectangle(4.0, 0.5),")]
+#![cfg_attr(feature = "3d", doc = "        Collider::cuboid(4.0, 0.5, 4.0),")]
+//!         LinearVelocity(Vector::X * 2.0),
+//!     ));
+//!
+//!     // A kinematic character that is carried by the platform.
+//!     commands.spawn((
+//!         RigidBody::Kinematic,
+//!         Collider::capsule(0.4, 1.0),
+//!         Transform::from_xyz(0.0, 1.0, 0.0),
+//!         PlatformRider::default().with_ground_cast_distance(0.1),
+//!     ));
+//! }
+//! ```
+
+use crate::{dynamics::solver::solver_body::SolverBody, prelude::*};
+use bevy::prelude::*;
+
+/// A plugin for carrying [`PlatformRider`]s on moving platforms.
+///
+/// The plugin is not included in [`PhysicsPlugins`] by default, and must be added manually.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct PlatformRiderPlugin;
+
+impl Plugin for PlatformRiderPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            PhysicsSchedule,
+            (
+                (
+                    PlatformRiderSystems::DetectSupports,
+                    PlatformRiderSystems::Carry,
+                )
+                    .chain()
+                    .after(PhysicsStepSystems::First)
+                    .after(IntegrationSystems::KinematicTargets)
+                    .before(PhysicsStepSystems::BroadPhase),
+                PlatformRiderSystems::Release
+                    .after(PhysicsStepSystems::Sleeping)
+                    .before(PhysicsStepSystems::SpatialQuery),
+            ),
+        );
+
+        app.add_systems(
+            PhysicsSchedule,
+            (
+                detect_platform_supports.in_set(PlatformRiderSystems::DetectSupports),
+                carry_platform_riders.in_set(PlatformRiderSystems::Carry),
+                release_platform_riders.in_set(PlatformRiderSystems::Release),
+            ),
+        );
+    }
+}
+
+/// System sets for carrying [`PlatformRider`]s on moving platforms.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlatformRiderSystems {
+    /// Detects the bodies that riders are standing on, and updates the [`GroundedOn`] relationships.
+    ///
+    /// Runs in the [`PhysicsSchedule`], between [`PhysicsStepSystems::First`] and [`PhysicsStepSystems::BroadPhase`].
+    DetectSupports,
+    /// Adds the velocity of the platform to the velocity of each rider.
+    ///
+    /// Runs in the [`PhysicsSchedule`], between [`PhysicsStepSystems::First`] and [`PhysicsStepSystems::BroadPhase`].
+    Carry,
+    /// Removes the velocity of the platform from the velocity of each rider after the solver has run.
+    ///
+    /// Runs in the [`PhysicsSchedule`], between [`PhysicsStepSystems::Sleeping`] and [`PhysicsStepSystems::SpatialQuery`].
+    Release,
+}
+
+/// A component that makes a [rigid body](RigidBody) inherit the motion of the body it is standing on.
+///
+/// The supporting body is stored in the [`GroundedOn`] relationship.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[require(PlatformVelocity)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct PlatformRider {
+    /// The unit up direction in world space. Contacts whose normal is within
+    /// [`max_slope_angle`](Self::max_slope_angle) of this direction are considered to be ground.
+    ///
+    /// Default: `Vector::Y`
+    pub up: Vector,
+    /// The maximum angle in radians between the [`up`](Self::up) direction and the normal
+    /// of a surface for it to be considered as ground.
+    ///
+    /// Default: `PI / 4` (45 degrees)
+    pub max_slope_angle: Scalar,
+    /// The maximum distance of the ground [shape cast](SpatialQuery::cast_shape) used for finding
+    /// the supporting body when the rider is not in contact with any ground.
+    ///
+    /// The [`Collider`] of the rider entity is cast along the negative [`up`](Self::up) direction.
+    /// This is primarily useful for [kinematic](RigidBody::Kinematic) riders, which do not have
+    /// contacts with other kinematic or static bodies.
+    ///
+    /// If `None`, only contacts are used for finding the support.
+    ///
+    /// Default: `None`
+    pub ground_cast_distance: Option<Scalar>,
+    /// If `true`, the rider also inherits the [`AngularVelocity`] of the platform, rotating along with it.
+    ///
+    /// Default: `true`
+    pub inherit_rotation: bool,
+}
+
+impl Default for PlatformRider {
+    fn default() -> Self {
+        Self {
+            up: Vector::Y,
+            max_slope_angle: PI / 4.0,
+            ground_cast_distance: None,
+            inherit_rotation: true,
+        }
+    }
+}
+
+impl PlatformRider {
+    /// Sets the unit up direction in world space.
+    pub fn with_up(mut self, up: Vector) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// Sets the maximum angle in radians between the up direction and the normal
+    /// of a surface for it to be considered as ground.
+    pub fn with_max_slope_angle(mut self, max_slope_angle: Scalar) -> Self {
+        self.max_slope_angle = max_slope_angle;
+        self
+    }
+
+    /// Sets the maximum distance of the ground shape cast used for finding
+    /// the supporting body when the rider is not in contact with any ground.
+    pub fn with_ground_cast_distance(mut self, distance: Scalar) -> Self {
+        self.ground_cast_distance = Some(distance);
+        self
+    }
+
+    /// Sets whether the rider inherits the [`AngularVelocity`] of the platform.
+    pub fn with_inherit_rotation(mut self, inherit_rotation: bool) -> Self {
+        self.inherit_rotation = inherit_rotation;
+        self
+    }
+}
+
+/// The velocity that a [`PlatformRider`] inherited from the platform it is [grounded on](GroundedOn)
+/// during the last physics step.
+///
+/// This is added to the velocity of the rider before the step and removed after it,
+/// so that the [`LinearVelocity`] and [`AngularVelocity`] of the rider remain relative to the platform.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct PlatformVelocity {
+    /// The inherited linear velocity.
+    pub linear: Vector,
+    /// The inherited angular velocity.
+    #[cfg(feature = "2d")]
+    pub angular: Scalar,
+    /// The inherited angular velocity.
+    #[cfg(feature = "3d")]
+    pub angular: Vector,
+}
+
+/// A [`Relationship`](bevy::ecs::relationship::Relationship) component that stores the rigid body
+/// that a [`PlatformRider`] is standing on.
+///
+/// This is automatically inserted and removed for entities with the [`PlatformRider`] component.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[relationship(relationship_target = PlatformRiders)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct GroundedOn(pub Entity);
+
+/// A [`RelationshipTarget`](bevy::ecs::relationship::RelationshipTarget) component that tracks
+/// which [`PlatformRider`]s are standing on a rigid body.
+///
+/// This is automatically inserted and populated with entities that have the [`GroundedOn`] component.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[relationship_target(relationship = GroundedOn)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct PlatformRiders(Vec<Entity>);
+
+/// Detects the bodies that [`PlatformRider`]s are standing on, and updates their [`GroundedOn`] relationships.
+#[allow(clippy::type_complexity)]
+fn detect_platform_supports(
+    mut commands: Commands,
+    riders: Query<(
+        Entity,
+        &PlatformRider,
+        &Position,
+        &Rotation,
+        Option<&Collider>,
+        Option<&RigidBodyColliders>,
+        Option<&GroundedOn>,
+    )>,
+    contact_graph: Res<ContactGraph>,
+    spatial_query: SpatialQuery,
+) {
+    for (entity, rider, position, rotation, collider, rider_colliders, grounded_on) in &riders {
+        let min_normal_dot = rider.max_slope_angle.cos();
+
+        // Find the contact whose normal is closest to the up direction.
+        let mut support: Option<(Entity, Scalar)> = None;
+        let colliders = rider_colliders.map_or(&[entity][..], |colliders| &colliders[..]);
+
+        for &collider_entity in colliders {
+            for pair in contact_graph.contact_pairs_with(collider_entity) {
+                if !pair.is_touching() {
+                    continue;
+                }
+
+                // Make the normal point from the other body towards the rider.
+                let (other_body, sign) = if pair.collider1 == collider_entity {
+                    (pair.body2, -1.0)
+                } else {
+                    (pair.body1, 1.0)
+                };
+
+                let Some(other_body) = other_body.filter(|&body| body != entity) else {
+                    continue;
+                };
+
+                for manifold in pair.manifolds.iter() {
+                    let normal_dot = (sign * manifold.normal).dot(rider.up);
+                    if normal_dot >= min_normal_dot
+                        && support.is_none_or(|(_, best_dot)| normal_dot > best_dot)
+                    {
+                        support = Some((other_body, normal_dot));
+                    }
+                }
+            }
+        }
+
+        // Fall back to a ground shape cast.
+        if support.is_none()
+            && let (Some(distance), Some(collider)) = (rider.ground_cast_distance, collider)
+        {
+            let down = Dir::new_unchecked((-rider.up).f32());
+            let filter = SpatialQueryFilter::from_excluded_entities(colliders.iter().copied());
+
+            #[cfg(feature = "2d")]
+            let shape_rotation = rotation.as_radians();
+            #[cfg(feature = "3d")]
+            let shape_rotation = rotation.0;
+
+            if let Some(hit) = spatial_query.cast_shape(
+                collider,
+                position.0,
+                shape_rotation,
+                down,
+                &ShapeCastConfig::from_max_distance(distance),
+                &filter,
+            ) {
+                let normal_dot = hit.normal1.dot(rider.up);
+                if normal_dot >= min_normal_dot {
+                    let body = spatial_query
+                        .collider_of
+                        .get(hit.entity)
+                        .map_or(hit.entity, |collider_of| collider_of.body);
+                    support = Some((body, normal_dot));
+                }
+            }
+        }
+
+        // Update the relationship.
+        match (support, grounded_on) {
+            (Some((body, _)), Some(grounded_on)) if grounded_on.0 == body => {}
+            (Some((body, _)), _) => {
+                commands.entity(entity).try_insert(GroundedOn(body));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).try_remove::<GroundedOn>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Adds the velocity of the platform at the center of mass of each [`PlatformRider`] to the velocity of the rider.
+#[allow(clippy::type_complexity)]
+fn carry_platform_riders(
+    mut riders: Query<(
+        Entity,
+        &PlatformRider,
+        Option<&GroundedOn>,
+        &mut PlatformVelocity,
+    )>,
+    mut bodies: Query<(
+        &Position,
+        &Rotation,
+        &ComputedCenterOfMass,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+) {
+    for (entity, rider, grounded_on, mut platform_velocity) in &mut riders {
+        let Ok((position, rotation, center_of_mass, ..)) = bodies.get(entity) else {
+            continue;
+        };
+        let rider_center_of_mass = position.0 + *rotation * center_of_mass.0;
+
+        let platform = grounded_on.and_then(|grounded_on| bodies.get(grounded_on.0).ok());
+
+        let Some((platform_pos, platform_rot, platform_com, platform_lin_vel, platform_ang_vel)) =
+            platform
+        else {
+            // The rider left the platform. Keep the momentum of the platform.
+            let inherited = core::mem::take(&mut *platform_velocity);
+            if let Ok((.., mut lin_vel, mut ang_vel)) = bodies.get_mut(entity) {
+                lin_vel.0 += inherited.linear;
+                ang_vel.0 += inherited.angular;
+            }
+            continue;
+        };
+
+        // Compute the velocity of the platform at the center of mass of the rider.
+        let platform_center_of_mass = platform_pos.0 + *platform_rot * platform_com.0;
+        let offset = rider_center_of_mass - platform_center_of_mass;
+        let platform_body = SolverBody {
+            linear_velocity: platform_lin_vel.0,
+            angular_velocity: platform_ang_vel.0,
+            ..SolverBody::DUMMY
+        };
+        let linear = platform_body.velocity_at_point(offset);
+        let angular = if rider.inherit_rotation {
+            platform_ang_vel.0
+        } else {
+            AngularVelocity::default().0
+        };
+
+        *platform_velocity = PlatformVelocity { linear, angular };
+
+        if let Ok((.., mut lin_vel, mut ang_vel)) = bodies.get_mut(entity) {
+            lin_vel.0 += linear;
+            ang_vel.0 += angular;
+        }
+    }
+}
+
+/// Removes the velocity inherited from the platform from the velocity of each [`PlatformRider`].
+fn release_platform_riders(
+    mut riders: Query<
+        (&PlatformVelocity, &mut LinearVelocity, &mut AngularVelocity),
+        With<GroundedOn>,
+    >,
+) {
+    for (platform_velocity, mut lin_vel, mut ang_vel) in &mut riders {
+        lin_vel.0 -= platform_velocity.linear;
+        ang_vel.0 -= platform_velocity.angular;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::create_test_app;
+
+    /// Spawns a kinematic platform with a thickness of `0.5` at the origin.
+    fn spawn_platform(app: &mut App, lin_vel: LinearVelocity, ang_vel: AngularVelocity) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(6.0, 0.5),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(6.0, 0.5, 6.0),
+                lin_vel,
+                ang_vel,
+            ))
+            .id()
+    }
+
+    /// Returns the position and rotation of the `rider` in the local space of the `platform`.
+    fn local_pose(app: &App, rider: Entity, platform: Entity) -> (Vector, Rotation) {
+        let world = app.world();
+        let platform_position = world.get::<Position>(platform).unwrap().0;
+        let platform_rotation = *world.get::<Rotation>(platform).unwrap();
+        let rider_position = world.get::<Position>(rider).unwrap().0;
+        let rider_rotation = *world.get::<Rotation>(rider).unwrap();
+        (
+            platform_rotation.inverse() * (rider_position - platform_position),
+            platform_rotation.inverse() * rider_rotation,
+        )
+    }
+
+    fn assert_rider_keeps_pose(app: &mut App, rider: Entity, platform: Entity) {
+        // Let the rider find the platform.
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(
+            app.world().get::<GroundedOn>(rider),
+            Some(&GroundedOn(platform))
+        );
+        let (initial_offset, initial_rotation) = local_pose(app, rider, platform);
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        assert_eq!(
+            app.world().get::<GroundedOn>(rider),
+            Some(&GroundedOn(platform))
+        );
+        let (offset, rotation) = local_pose(app, rider, platform);
+        assert_relative_eq!(offset, initial_offset, epsilon = 1e-2);
+        #[cfg(feature = "2d")]
+        assert_relative_eq!(
+            rotation.as_radians(),
+            initial_rotation.as_radians(),
+            epsilon = 1e-3
+        );
+        #[cfg(feature = "3d")]
+        assert!(rotation.0.angle_between(initial_rotation.0) < 1e-3);
+
+        // The inherited velocity is not added to the velocity of the rider permanently.
+        let lin_vel = app.world().get::<LinearVelocity>(rider).unwrap();
+        assert_relative_eq!(lin_vel.0, Vector::ZERO, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn kinematic_rider_keeps_offset_on_moving_platform() {
+        let mut app = create_test_app(PlatformRiderPlugin);
+        app.insert_resource(Gravity(Vector::ZERO));
+
+        let platform = spawn_platform(
+            &mut app,
+            LinearVelocity(Vector::X * 2.0),
+            AngularVelocity::ZERO,
+        );
+        let rider = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Transform::from_xyz(1.0, 0.8, 0.0),
+                PlatformRider::default().with_ground_cast_distance(0.1),
+            ))
+            .id();
+
+        assert_rider_keeps_pose(&mut app, rider, platform);
+    }
+
+    #[test]
+    fn kinematic_rider_keeps_offset_on_rotating_platform() {
+        let mut app = create_test_app(PlatformRiderPlugin);
+        app.insert_resource(Gravity(Vector::ZERO));
+
+        // In 3D, the platform spins around the up axis like a carousel.
+        // In 2D, the platform tilts, so the rider is cast further to find the ground.
+        #[cfg(feature = "2d")]
+        let ang_vel = AngularVelocity(0.5);
+        #[cfg(feature = "3d")]
+        let ang_vel = AngularVelocity(Vector::Y);
+
+        let platform = spawn_platform(&mut app, LinearVelocity::ZERO, ang_vel);
+        let rider = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Transform::from_xyz(1.5, 0.8, 0.0),
+                PlatformRider::default().with_ground_cast_distance(0.5),
+            ))
+            .id();
+
+        assert_rider_keeps_pose(&mut app, rider, platform);
+    }
+
+    #[test]
+    fn dynamic_rider_keeps_offset_on_moving_platform() {
+        let mut app = create_test_app(PlatformRiderPlugin);
+
+        let platform = spawn_platform(
+            &mut app,
+            LinearVelocity(Vector::X * 2.0),
+            AngularVelocity::ZERO,
+        );
+        let rider = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Transform::from_xyz(1.0, 0.75, 0.0),
+                PlatformRider::default(),
+            ))
+            .id();
+
+        assert_rider_keeps_pose(&mut app, rider, platform);
+    }
+}
@@ -0,0 +1,1277 @@
+//! Ragdolls generated automatically from skinned meshes.
+//!
+//! See [`RagdollPlugin`] and [`RagdollConstructor`].
+//!
+//! # Overview
+//!
+//! Adding a [`RagdollConstructor`] to the root of a hierarchy containing [`SkinnedMesh`]es,
+//! such as a glTF scene, generates a ragdoll for the skeleton once the scene and its meshes have loaded.
+//!
+//! For each bone that influences the mesh, the constructor creates a capsule [`Collider`] that is sized
+//! based on the vertices the bone influences, and turns the bone into a [rigid body](RigidBody).
+//! Each bone is connected to its closest ancestor bone with a [`SphericalJoint`] or a [`RevoluteJoint`],
+//! configured using a [`RagdollJoint`].
+//!
+//! Collisions between bones connected by a joint are disabled using [`JointCollisionDisabled`].
+//! Collisions between other bones of the same ragdoll are disabled by placing the bones
+//! in a [`SelfCollisionGroup`] keyed on the ragdoll, unless enabled with
+//! [`RagdollConstructor::with_self_collision`].
+//!
+//! Once the ragdoll has been generated, the [`RagdollConstructor`] is replaced with a [`Ragdoll`] component,
+//! each bone receives a [`RagdollBone`] component, and a [`RagdollReady`] event is triggered.
+//!
+//! # Animated and Simulated Modes
+//!
+//! A ragdoll is either [animated](RagdollMode::Animated) or [simulated](RagdollMode::Simulated).
+//!
+//! - In the animated mode, the bones are [kinematic](RigidBody::Kinematic) and follow the animation
+//!   without being affected by other bodies. The bones are moved to the animated pose directly
+//!   instead of with velocities, so dynamic bodies are pushed out of overlap with them,
+//!   but don't receive the momentum of the animation.
+//! - In the simulated mode, the bones are [dynamic](RigidBody::Dynamic), and the pose of the skeleton
+//!   is driven by the physics simulation, overriding any animation.
+//!
+//! The mode can be changed at any time by modifying [`Ragdoll::mode`]. When switching back to the animated mode,
+//! the pose of the skeleton is blended from the simulated pose back to the animation over [`Ragdoll::blend_duration`].
+//!
+//! # Powered Ragdolls
+//!
+//! A [powered](RagdollMode::Powered) ragdoll is simulated by physics, but its joints are driven towards
+//! an animated pose using [`OrientationDrive`]s. This allows the character to follow its animation
+//! while still reacting to hits and other external forces.
+//!
+//! The target pose is sampled from a *shadow skeleton*: a second, typically invisible copy of the character
+//! that is animated with an [`AnimationPlayer`] as usual. The shadow skeleton is configured by adding
+//! a [`RagdollPoseTracking`] component to the ragdoll. Bones are matched with the bones of the shadow
+//! skeleton by their `Name`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use avian3d::prelude::*;
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands, assets: Res<AssetServer>) {
+//!     commands.spawn((
+//!         SceneRoot(assets.load("character.glb#Scene0")),
+//!         RagdollConstructor::default()
+//!             // Knees and elbows are hinges.
+//!             .with_joint_for_name("LeftLeg", RagdollJoint::hinge(Vector::X, -2.4, 0.0))
+//!             .with_joint_for_name("RightLeg", RagdollJoint::hinge(Vector::X, -2.4, 0.0))
+//!             .with_joint_for_name("LeftForeArm", RagdollJoint::hinge(Vector::Z, 0.0, 2.4))
+//!             .with_joint_for_name("RightForeArm", RagdollJoint::hinge(Vector::Z, -2.4, 0.0))
+//!             // Fingers are not simulated.
+//!             .without_bone_for_name("LeftHandIndex1")
+//!             .without_bone_for_name("RightHandIndex1"),
+//!     ));
+//! }
+//!
+//! // Go limp when hit.
+//! fn on_hit(mut ragdolls: Query<&mut Ragdoll>) {
+//!     for mut ragdoll in &mut ragdolls {
+//!         ragdoll.mode = RagdollMode::Simulated;
+//!     }
+//! }
+//! ```
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{hash_map::EntityHashMap, hash_set::EntityHashSet},
+    mesh::{
+        VertexAttributeValues,
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+    },
+    platform::collections::HashMap,
+    prelude::*,
+    transform::{TransformSystems, helper::TransformHelper},
+};
+
+/// A plugin for generating and managing [ragdolls](self) for skinned meshes.
+///
+/// The plugin is not included in [`PhysicsPlugins`] by default, and must be added manually.
+pub struct RagdollPlugin;
+
+impl Plugin for RagdollPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (init_ragdolls, update_ragdoll_modes).chain());
+
+        app.add_systems(
+            PostUpdate,
+            (
+                apply_ragdoll_poses
+                    .after(bevy::animation::AnimationSystems)
+                    .before(TransformSystems::Propagate),
+                track_ragdoll_poses.after(TransformSystems::Propagate),
+            ),
+        );
+    }
+}
+
+/// Generates a [ragdoll](self) for the skinned meshes in the hierarchy of the entity.
+///
+/// If the entity has a `SceneInstance`, the ragdoll is only generated once the scene is ready
+/// and the meshes have loaded. The constructor is then replaced with a [`Ragdoll`].
+///
+/// Bones are configured by their `Name`. Bones that do not influence any vertices
+/// are skipped, and their descendants are connected to the closest simulated ancestor instead.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq, Default)]
+pub struct RagdollConstructor {
+    /// The joint used for bones that are not included in [`bones`](Self::bones).
+    pub default_joint: RagdollJoint,
+    /// Specifies the joint used for bones by `Name`. Entries with a `None` value are not simulated.
+    pub bones: HashMap<String, Option<RagdollJoint>>,
+    /// The minimum skinning weight for a vertex to be considered part of a bone.
+    ///
+    /// `0.5` by default.
+    pub weight_threshold: f32,
+    /// A multiplier for the radius of the generated capsules.
+    ///
+    /// The radius is computed as the average distance of the vertices influenced by a bone
+    /// from the axis of the bone. `1.0` by default.
+    pub radius_scale: Scalar,
+    /// The minimum radius of the generated capsules.
+    ///
+    /// `0.01` by default.
+    pub min_radius: Scalar,
+    /// The [`CollisionLayers`] used for the colliders of the bones.
+    pub layers: CollisionLayers,
+    /// The [`ColliderDensity`] used for the colliders of the bones.
+    pub density: ColliderDensity,
+    /// If `true`, bones that are not connected by a joint can collide with each other.
+    ///
+    /// `false` by default.
+    pub self_collision: bool,
+    /// The initial [`RagdollMode`] of the ragdoll.
+    pub mode: RagdollMode,
+    /// The duration of the blend from the simulated pose back to the animation, in seconds.
+    ///
+    /// `0.3` by default.
+    pub blend_duration: Scalar,
+}
+
+impl Default for RagdollConstructor {
+    fn default() -> Self {
+        Self {
+            default_joint: RagdollJoint::default(),
+            bones: HashMap::default(),
+            weight_threshold: 0.5,
+            radius_scale: 1.0,
+            min_radius: 0.01,
+            layers: CollisionLayers::default(),
+            density: ColliderDensity(1.0),
+            self_collision: false,
+            mode: RagdollMode::Animated,
+            blend_duration: 0.3,
+        }
+    }
+}
+
+impl RagdollConstructor {
+    /// Sets the joint used for bones not included in [`RagdollConstructor::bones`].
+    pub fn with_default_joint(mut self, joint: RagdollJoint) -> Self {
+        self.default_joint = joint;
+        self
+    }
+
+    /// Specifies the joint used for the bone with the given `name`.
+    pub fn with_joint_for_name(mut self, name: &str, joint: RagdollJoint) -> Self {
+        self.bones.insert(name.to_string(), Some(joint));
+        self
+    }
+
+    /// Excludes the bone with the given `name` from the ragdoll.
+    ///
+    /// Its descendants are connected to the closest simulated ancestor instead.
+    pub fn without_bone_for_name(mut self, name: &str) -> Self {
+        self.bones.insert(name.to_string(), None);
+        self
+    }
+
+    /// Sets the minimum skinning weight for a vertex to be considered part of a bone.
+    pub fn with_weight_threshold(mut self, threshold: f32) -> Self {
+        self.weight_threshold = threshold;
+        self
+    }
+
+    /// Sets the multiplier for the radius of the generated capsules.
+    pub fn with_radius_scale(mut self, scale: Scalar) -> Self {
+        self.radius_scale = scale;
+        self
+    }
+
+    /// Sets the minimum radius of the generated capsules.
+    pub fn with_min_radius(mut self, radius: Scalar) -> Self {
+        self.min_radius = radius;
+        self
+    }
+
+    /// Sets the [`CollisionLayers`] used for the colliders of the bones.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Sets the [`ColliderDensity`] used for the colliders of the bones.
+    pub fn with_density(mut self, density: impl Into<ColliderDensity>) -> Self {
+        self.density = density.into();
+        self
+    }
+
+    /// Sets whether bones that are not connected by a joint can collide with each other.
+    pub fn with_self_collision(mut self, self_collision: bool) -> Self {
+        self.self_collision = self_collision;
+        self
+    }
+
+    /// Sets the initial [`RagdollMode`] of the ragdoll.
+    pub fn with_mode(mut self, mode: RagdollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the duration of the blend from the simulated pose back to the animation, in seconds.
+    pub fn with_blend_duration(mut self, duration: Scalar) -> Self {
+        self.blend_duration = duration;
+        self
+    }
+}
+
+/// The type and limits of the joint connecting a bone of a [ragdoll](self) to its parent bone.
+///
+/// Angles are in radians, and axes are in the local space of the child bone.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum RagdollJoint {
+    /// A [`SphericalJoint`] twisting around the axis of the bone, like a shoulder or a hip.
+    Spherical {
+        /// The maximum swing angle away from the axis of the bone.
+        swing_limit: Scalar,
+        /// The maximum twist angle around the axis of the bone in either direction.
+        twist_limit: Scalar,
+    },
+    /// A [`RevoluteJoint`] rotating around a fixed axis, like a knee or an elbow.
+    Revolute {
+        /// The hinge axis in the local space of the bone.
+        axis: Vector,
+        /// The minimum angle around the hinge axis.
+        min_angle: Scalar,
+        /// The maximum angle around the hinge axis.
+        max_angle: Scalar,
+    },
+}
+
+impl Default for RagdollJoint {
+    fn default() -> Self {
+        Self::Spherical {
+            swing_limit: 0.8,
+            twist_limit: 0.5,
+        }
+    }
+}
+
+impl RagdollJoint {
+    /// Creates a [`RagdollJoint::Spherical`] with the given swing and twist limits.
+    pub const fn spherical(swing_limit: Scalar, twist_limit: Scalar) -> Self {
+        Self::Spherical {
+            swing_limit,
+            twist_limit,
+        }
+    }
+
+    /// Creates a [`RagdollJoint::Revolute`] with the given hinge `axis` and angle limits.
+    pub const fn hinge(axis: Vector, min_angle: Scalar, max_angle: Scalar) -> Self {
+        Self::Revolute {
+            axis,
+            min_angle,
+            max_angle,
+        }
+    }
+}
+
+/// Determines whether a [ragdoll](self) follows its animation or is simulated by physics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum RagdollMode {
+    /// The bones are [kinematic](RigidBody::Kinematic) and follow the animation.
+    #[default]
+    Animated,
+    /// The bones are [dynamic](RigidBody::Dynamic) and simulated by physics.
+    Simulated,
+    /// The bones are [dynamic](RigidBody::Dynamic) and simulated by physics, but the joints
+    /// are driven towards the pose of a shadow skeleton using [`OrientationDrive`]s.
+    ///
+    /// Requires the [`RagdollPoseTracking`] component. Otherwise, this behaves like [`RagdollMode::Simulated`].
+    Powered,
+}
+
+/// A [ragdoll](self) generated by a [`RagdollConstructor`].
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct Ragdoll {
+    /// The current mode of the ragdoll. Can be modified to switch between animation and simulation.
+    pub mode: RagdollMode,
+    /// The duration of the blend from the simulated pose back to the animation, in seconds.
+    pub blend_duration: Scalar,
+    /// The bones of the ragdoll, ordered so that parents come before their children.
+    bones: Vec<Entity>,
+    /// The joints connecting the bones.
+    joints: Vec<Entity>,
+    /// The mode that has been applied to the bones.
+    applied_mode: RagdollMode,
+    /// The time elapsed since the blend back to the animation started, if blending.
+    blend_elapsed: Option<Scalar>,
+}
+
+impl Ragdoll {
+    /// Returns the bone entities of the ragdoll, ordered so that parents come before their children.
+    pub fn bones(&self) -> &[Entity] {
+        &self.bones
+    }
+
+    /// Returns the joint entities connecting the bones of the ragdoll.
+    pub fn joints(&self) -> &[Entity] {
+        &self.joints
+    }
+
+    /// Returns `true` if the ragdoll is currently blending from the simulated pose back to the animation.
+    pub fn is_blending(&self) -> bool {
+        self.blend_elapsed.is_some()
+    }
+}
+
+/// Drives the joints of a [powered](RagdollMode::Powered) [`Ragdoll`] towards the pose of a shadow skeleton.
+///
+/// The shadow skeleton is a separate hierarchy with the same bone names as the ragdoll,
+/// typically an invisible copy of the same scene that is animated with an [`AnimationPlayer`].
+/// Each frame, the relative rotations of the bones in the shadow skeleton are sampled
+/// and used as the targets of the [`OrientationDrive`]s of the ragdoll's joints.
+///
+/// # Example
+///
+/// ```no_run
+/// use avian3d::prelude::*;
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands, assets: Res<AssetServer>) {
+///     let scene = assets.load("character.glb#Scene0");
+///
+///     // The shadow skeleton is animated, but not rendered.
+///     let shadow = commands.spawn((SceneRoot(scene.clone()), Visibility::Hidden)).id();
+///
+///     commands.spawn((
+///         SceneRoot(scene),
+///         RagdollConstructor::default().with_mode(RagdollMode::Powered),
+///         RagdollPoseTracking::new(shadow, 2000.0, 100.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct RagdollPoseTracking {
+    /// The root entity of the shadow skeleton.
+    pub shadow: Entity,
+    /// The stiffness of the joint drives (N * m / rad).
+    pub stiffness: Scalar,
+    /// The damping of the joint drives (N * m * s / rad).
+    pub damping: Scalar,
+}
+
+impl RagdollPoseTracking {
+    /// Creates a new [`RagdollPoseTracking`] following the shadow skeleton with the given root entity,
+    /// using joint drives with the given `stiffness` and `damping`.
+    pub const fn new(shadow: Entity, stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            shadow,
+            stiffness,
+            damping,
+        }
+    }
+}
+
+/// A bone of a [`Ragdoll`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct RagdollBone {
+    /// The entity of the [`Ragdoll`] that the bone belongs to.
+    pub ragdoll: Entity,
+    /// The closest ancestor bone that the bone is connected to with a joint, if any.
+    pub parent: Option<Entity>,
+    /// The joint entity connecting the bone to its [`parent`](Self::parent), if any.
+    pub joint: Option<Entity>,
+    /// The local transform of the bone at the end of the last simulated frame,
+    /// used for blending back to the animation.
+    pub simulated_pose: Transform,
+}
+
+/// Triggered when a [`RagdollConstructor`] finished generating a [`Ragdoll`].
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RagdollReady {
+    /// The entity that held the [`RagdollConstructor`].
+    pub entity: Entity,
+}
+
+/// The vertices influenced by a bone, in the local space of the bone.
+#[derive(Default)]
+struct BoneVertices(Vec<Vector>);
+
+/// Generates [`Ragdoll`]s for entities with a [`RagdollConstructor`].
+#[allow(clippy::too_many_arguments)]
+fn init_ragdolls(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    #[cfg(feature = "bevy_scene")] scene_spawner: Res<SceneSpawner>,
+    #[cfg(feature = "bevy_scene")] scenes: Query<&SceneRoot>,
+    #[cfg(feature = "bevy_scene")] scene_instances: Query<&SceneInstance>,
+    constructors: Query<(Entity, &RagdollConstructor)>,
+    children: Query<&Children>,
+    parents: Query<&ChildOf>,
+    skinned_meshes: Query<(&SkinnedMesh, &Mesh3d)>,
+    bone_query: Query<(Option<&Name>, &Transform)>,
+    transform_helper: TransformHelper,
+) {
+    'constructors: for (root, constructor) in constructors.iter() {
+        #[cfg(feature = "bevy_scene")]
+        {
+            if scenes.contains(root) {
+                if let Ok(scene_instance) = scene_instances.get(root) {
+                    if !scene_spawner.instance_is_ready(**scene_instance) {
+                        // Wait for the scene to be ready
+                        continue;
+                    }
+                } else {
+                    // SceneInstance is added in the SpawnScene schedule, so it might not be available yet
+                    continue;
+                }
+            }
+        }
+
+        // Collect the vertices influenced by each bone in the local space of the bone.
+        let mut bone_vertices = EntityHashMap::<BoneVertices>::default();
+        let mut skeleton = EntityHashSet::default();
+
+        for entity in children.iter_descendants(root) {
+            let Ok((skinned_mesh, mesh_handle)) = skinned_meshes.get(entity) else {
+                continue;
+            };
+            let (Some(mesh), Some(bindposes)) = (
+                meshes.get(mesh_handle),
+                inverse_bindposes.get(&skinned_mesh.inverse_bindposes),
+            ) else {
+                // Mesh required, but not loaded yet
+                continue 'constructors;
+            };
+
+            skeleton.extend(skinned_mesh.joints.iter().copied());
+
+            let (
+                Some(VertexAttributeValues::Float32x3(positions)),
+                Some(VertexAttributeValues::Uint16x4(joint_indices)),
+                Some(VertexAttributeValues::Float32x4(joint_weights)),
+            ) = (
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+                mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX),
+                mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT),
+            )
+            else {
+                continue;
+            };
+
+            for ((position, indices), weights) in
+                positions.iter().zip(joint_indices).zip(joint_weights)
+            {
+                for (&index, &weight) in indices.iter().zip(weights) {
+                    if weight < constructor.weight_threshold {
+                        continue;
+                    }
+                    let (Some(&bone), Some(bindpose)) = (
+                        skinned_mesh.joints.get(index as usize),
+                        bindposes.get(index as usize),
+                    ) else {
+                        continue;
+                    };
+                    let local_position = bindpose.transform_point3(Vec3::from(*position));
+                    bone_vertices
+                        .entry(bone)
+                        .or_default()
+                        .0
+                        .push(local_position.adjust_precision());
+                }
+            }
+        }
+
+        // Determine the bones of the ragdoll. Descendants are iterated breadth-first,
+        // so parents always come before their children.
+        let bones: Vec<Entity> = children
+            .iter_descendants(root)
+            .filter(|entity| {
+                bone_vertices.contains_key(entity)
+                    && !bone_query.get(*entity).is_ok_and(|(name, _)| {
+                        name.is_some_and(|name| {
+                            constructor
+                                .bones
+                                .get(name.as_str())
+                                .is_some_and(|joint| joint.is_none())
+                        })
+                    })
+            })
+            .collect();
+        let bone_set: EntityHashSet = bones.iter().copied().collect();
+
+        let mut joints = Vec::with_capacity(bones.len());
+
+        for &bone in bones.iter() {
+            let Ok((name, _)) = bone_query.get(bone) else {
+                continue;
+            };
+            let vertices = &bone_vertices[&bone];
+
+            // The axis of the bone points towards its child joint furthest away, if any.
+            let axis = children
+                .get(bone)
+                .into_iter()
+                .flatten()
+                .filter(|child| skeleton.contains(*child))
+                .filter_map(|child| bone_query.get(*child).ok())
+                .map(|(_, transform)| transform.translation.adjust_precision())
+                .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+                .or_else(|| {
+                    // Leaf bones point towards the center of their vertices.
+                    let sum: Vector = vertices.0.iter().sum();
+                    Some(sum / vertices.0.len() as Scalar)
+                })
+                .and_then(|axis| axis.try_normalize())
+                .unwrap_or(Vector::Y);
+
+            let collider = bone_capsule(
+                &vertices.0,
+                axis,
+                constructor.radius_scale,
+                constructor.min_radius,
+            );
+
+            // Find the closest ancestor bone.
+            let parent = parents
+                .iter_ancestors(bone)
+                .take_while(|&ancestor| ancestor != root)
+                .find(|ancestor| bone_set.contains(ancestor));
+
+            let initial_body = match constructor.mode {
+                RagdollMode::Animated => RigidBody::Kinematic,
+                RagdollMode::Simulated | RagdollMode::Powered => RigidBody::Dynamic,
+            };
+
+            commands.entity(bone).insert((
+                initial_body,
+                collider,
+                constructor.layers,
+                constructor.density,
+            ));
+            if !constructor.self_collision {
+                commands.entity(bone).insert(SelfCollisionGroup(root));
+            }
+
+            let joint = parent.and_then(|parent| {
+                let (Ok(parent_transform), Ok(bone_transform)) = (
+                    transform_helper.compute_global_transform(parent),
+                    transform_helper.compute_global_transform(bone),
+                ) else {
+                    return None;
+                };
+
+                let (_, parent_rotation, parent_translation) =
+                    parent_transform.to_scale_rotation_translation();
+                let (_, bone_rotation, bone_translation) =
+                    bone_transform.to_scale_rotation_translation();
+                let parent_rotation = parent_rotation.adjust_precision();
+                let bone_rotation = bone_rotation.adjust_precision();
+
+                // The anchor is at the origin of the child bone.
+                let local_anchor1 = parent_rotation.inverse()
+                    * (bone_translation - parent_translation).adjust_precision();
+                let local_basis1 = parent_rotation.inverse() * bone_rotation;
+
+                let joint_config = name
+                    .and_then(|name| constructor.bones.get(name.as_str()).copied().flatten())
+                    .unwrap_or(constructor.default_joint);
+
+                let mut joint_commands = match joint_config {
+                    RagdollJoint::Spherical {
+                        swing_limit,
+                        twist_limit,
+                    } => commands.spawn(
+                        SphericalJoint::new(parent, bone)
+                            .with_local_anchor1(local_anchor1)
+                            .with_local_basis1(local_basis1)
+                            .with_twist_axis(axis)
+                            .with_swing_limits(-swing_limit, swing_limit)
+                            .with_twist_limits(-twist_limit, twist_limit),
+                    ),
+                    RagdollJoint::Revolute {
+                        axis,
+                        min_angle,
+                        max_angle,
+                    } => commands.spawn(
+                        RevoluteJoint::new(parent, bone)
+                            .with_local_anchor1(local_anchor1)
+                            .with_local_basis1(local_basis1)
+                            .with_hinge_axis(axis)
+                            .with_angle_limits(min_angle, max_angle),
+                    ),
+                };
+                joint_commands.insert(JointCollisionDisabled);
+                Some(joint_commands.id())
+            });
+
+            joints.extend(joint);
+
+            commands.entity(bone).insert(RagdollBone {
+                ragdoll: root,
+                parent,
+                joint,
+                simulated_pose: Transform::IDENTITY,
+            });
+        }
+
+        commands
+            .entity(root)
+            .remove::<RagdollConstructor>()
+            .insert(Ragdoll {
+                mode: constructor.mode,
+                blend_duration: constructor.blend_duration,
+                bones,
+                joints,
+                applied_mode: constructor.mode,
+                blend_elapsed: None,
+            });
+        commands.trigger(RagdollReady { entity: root });
+    }
+}
+
+/// Creates a capsule collider for a bone with the given `axis`, fitted to the given vertices.
+fn bone_capsule(
+    vertices: &[Vector],
+    axis: Vector,
+    radius_scale: Scalar,
+    min_radius: Scalar,
+) -> Collider {
+    let mut min_extent = Scalar::MAX;
+    let mut max_extent = Scalar::MIN;
+    let mut distance_sum = 0.0;
+
+    for vertex in vertices {
+        let along_axis = vertex.dot(axis);
+        min_extent = min_extent.min(along_axis);
+        max_extent = max_extent.max(along_axis);
+        distance_sum += (*vertex - axis * along_axis).length();
+    }
+
+    // The vertices are on the surface of the mesh, so their average distance
+    // from the axis approximates the thickness of the limb.
+    let radius = (distance_sum / vertices.len() as Scalar * radius_scale).max(min_radius);
+
+    // Shrink the segment so that the capsule stays within the extents of the vertices.
+    let (a, b) = if max_extent - min_extent > 2.0 * radius {
+        (min_extent + radius, max_extent - radius)
+    } else {
+        let center = 0.5 * (min_extent + max_extent);
+        (center, center)
+    };
+
+    Collider::capsule_endpoints(radius, axis * a, axis * b)
+}
+
+/// Switches the bones of [`Ragdoll`]s between kinematic and dynamic rigid bodies when the mode changes.
+fn update_ragdoll_modes(
+    mut commands: Commands,
+    mut ragdolls: Query<&mut Ragdoll, Changed<Ragdoll>>,
+) {
+    for mut ragdoll in &mut ragdolls {
+        if ragdoll.mode == ragdoll.applied_mode {
+            continue;
+        }
+
+        let (body, blend_elapsed) = match ragdoll.mode {
+            RagdollMode::Animated => (
+                RigidBody::Kinematic,
+                (ragdoll.blend_duration > 0.0).then_some(0.0),
+            ),
+            RagdollMode::Simulated | RagdollMode::Powered => (RigidBody::Dynamic, None),
+        };
+
+        for &bone in ragdoll.bones.iter() {
+            commands.entity(bone).insert(body);
+            if ragdoll.mode == RagdollMode::Animated {
+                commands
+                    .entity(bone)
+                    .insert((LinearVelocity::ZERO, AngularVelocity::ZERO));
+            }
+        }
+
+        ragdoll.applied_mode = ragdoll.mode;
+        ragdoll.blend_elapsed = blend_elapsed;
+    }
+}
+
+/// Overrides the animated pose of simulated [`Ragdoll`]s with the physics pose,
+/// and blends the pose back to the animation after switching to [`RagdollMode::Animated`].
+fn apply_ragdoll_poses(
+    mut ragdolls: Query<(Entity, &mut Ragdoll)>,
+    mut bones: Query<(&mut RagdollBone, &mut Transform, &Position, &Rotation)>,
+    transforms: Query<&Transform, Without<RagdollBone>>,
+    parents: Query<&ChildOf>,
+    global_transforms: Query<&GlobalTransform>,
+    time: Res<Time>,
+) {
+    // The world transforms of the entities in the skeleton computed so far.
+    let mut world_transforms = EntityHashMap::<GlobalTransform>::default();
+
+    for (root, mut ragdoll) in &mut ragdolls {
+        let blend_weight = match (ragdoll.applied_mode, ragdoll.blend_elapsed) {
+            (RagdollMode::Simulated | RagdollMode::Powered, _) => 1.0,
+            (RagdollMode::Animated, Some(elapsed)) => {
+                1.0 - (elapsed / ragdoll.blend_duration).clamp(0.0, 1.0)
+            }
+            (RagdollMode::Animated, None) => continue,
+        };
+
+        world_transforms.clear();
+        if let Ok(root_transform) = global_transforms.get(root) {
+            world_transforms.insert(root, *root_transform);
+        }
+
+        for &bone in ragdoll.bones.iter() {
+            // Compute the world transform of the parent entity from the poses written so far.
+            let Ok(&ChildOf(parent)) = parents.get(bone) else {
+                continue;
+            };
+            let parent_transform = world_transform(
+                parent,
+                &mut world_transforms,
+                &transforms,
+                &parents,
+                &global_transforms,
+            );
+
+            let Ok((mut bone_data, mut transform, position, rotation)) = bones.get_mut(bone) else {
+                continue;
+            };
+
+            if ragdoll.applied_mode != RagdollMode::Animated {
+                // Compute the local transform from the physics pose.
+                let world = Transform {
+                    translation: position.0.f32(),
+                    rotation: rotation.0.f32(),
+                    scale: global_transforms
+                        .get(bone)
+                        .map_or(Vec3::ONE, |transform| transform.scale()),
+                };
+                bone_data.simulated_pose =
+                    GlobalTransform::from(world).reparented_to(&parent_transform);
+            }
+
+            // Blend from the simulated pose to the animated pose.
+            let blend_weight = blend_weight as f32;
+            let simulated_pose = bone_data.simulated_pose;
+            transform.translation = transform
+                .translation
+                .lerp(simulated_pose.translation, blend_weight);
+            transform.rotation = transform
+                .rotation
+                .slerp(simulated_pose.rotation, blend_weight);
+
+            world_transforms.insert(bone, parent_transform.mul_transform(*transform));
+        }
+
+        if let Some(elapsed) = ragdoll.blend_elapsed.as_mut() {
+            *elapsed += time.delta_secs_f64() as Scalar;
+            if *elapsed >= ragdoll.blend_duration {
+                ragdoll.blend_elapsed = None;
+            }
+        }
+    }
+}
+
+/// Computes the world transform of an entity in the skeleton of a [`Ragdoll`],
+/// using the poses that have already been written for this frame.
+fn world_transform(
+    entity: Entity,
+    world_transforms: &mut EntityHashMap<GlobalTransform>,
+    transforms: &Query<&Transform, Without<RagdollBone>>,
+    parents: &Query<&ChildOf>,
+    global_transforms: &Query<&GlobalTransform>,
+) -> GlobalTransform {
+    if let Some(transform) = world_transforms.get(&entity) {
+        return *transform;
+    }
+
+    // Entities between the bones are not simulated, so they follow their parent.
+    let (Ok(&ChildOf(parent)), Ok(transform)) = (parents.get(entity), transforms.get(entity))
+    else {
+        return global_transforms.get(entity).copied().unwrap_or_default();
+    };
+    let parent_transform = world_transform(
+        parent,
+        world_transforms,
+        transforms,
+        parents,
+        global_transforms,
+    );
+    let transform = parent_transform.mul_transform(*transform);
+    world_transforms.insert(entity, transform);
+    transform
+}
+
+/// Samples the pose of the shadow skeleton of [powered](RagdollMode::Powered) [`Ragdoll`]s
+/// and updates the targets of the [`OrientationDrive`]s of their joints.
+///
+/// The drives are removed when the ragdoll is not powered.
+fn track_ragdoll_poses(
+    ragdolls: Query<(&Ragdoll, Option<&RagdollPoseTracking>)>,
+    bones: Query<(&RagdollBone, &Name)>,
+    shadow_bones: Query<(&Name, &GlobalTransform)>,
+    children: Query<&Children>,
+    mut spherical_joints: Query<&mut SphericalJoint>,
+    mut revolute_joints: Query<&mut RevoluteJoint>,
+    mut shadow_rotations: Local<HashMap<Name, Quaternion>>,
+) {
+    for (ragdoll, tracking) in &ragdolls {
+        let tracking = tracking.filter(|_| ragdoll.applied_mode == RagdollMode::Powered);
+
+        let Some(tracking) = tracking else {
+            // Remove the drives of ragdolls that are not powered.
+            for &joint in ragdoll.joints.iter() {
+                if let Ok(mut joint) = spherical_joints.get_mut(joint)
+                    && joint.orientation_drive.is_some()
+                {
+                    joint.orientation_drive = None;
+                }
+                if let Ok(mut joint) = revolute_joints.get_mut(joint)
+                    && joint.orientation_drive.is_some()
+                {
+                    joint.orientation_drive = None;
+                }
+            }
+            continue;
+        };
+
+        // Sample the world-space rotations of the bones in the shadow skeleton.
+        shadow_rotations.clear();
+        for entity in children.iter_descendants(tracking.shadow) {
+            if let Ok((name, transform)) = shadow_bones.get(entity) {
+                let (_, rotation, _) = transform.to_scale_rotation_translation();
+                shadow_rotations.insert(name.clone(), rotation.adjust_precision());
+            }
+        }
+
+        for &bone in ragdoll.bones.iter() {
+            let Ok((bone_data, name)) = bones.get(bone) else {
+                continue;
+            };
+            let (Some(parent), Some(joint)) = (bone_data.parent, bone_data.joint) else {
+                continue;
+            };
+            let Ok((_, parent_name)) = bones.get(parent) else {
+                continue;
+            };
+            let (Some(&parent_rotation), Some(&bone_rotation)) = (
+                shadow_rotations.get(parent_name),
+                shadow_rotations.get(name),
+            ) else {
+                continue;
+            };
+
+            if let Ok(joint) = spherical_joints.get_mut(joint) {
+                let (Some(basis1), Some(basis2)) = (joint.local_basis1(), joint.local_basis2())
+                else {
+                    continue;
+                };
+                // The target is the rotation of the second frame relative to the first frame.
+                let target = (parent_rotation * basis1).inverse() * (bone_rotation * basis2);
+                let drive =
+                    OrientationDrive::new(target.normalize(), tracking.stiffness, tracking.damping);
+
+                // Only write the drive when it changes to avoid triggering change detection.
+                joint
+                    .map_unchanged(|joint| &mut joint.orientation_drive)
+                    .set_if_neq(Some(drive));
+            } else if let Ok(joint) = revolute_joints.get_mut(joint) {
+                let (Some(basis1), Some(basis2)) = (joint.local_basis1(), joint.local_basis2())
+                else {
+                    continue;
+                };
+                let target = (parent_rotation * basis1).inverse() * (bone_rotation * basis2);
+
+                // Only keep the part of the rotation around the hinge axis.
+                let axis = joint.hinge_axis;
+                let twist = Quaternion::from_xyzw(
+                    axis.x * target.xyz().dot(axis),
+                    axis.y * target.xyz().dot(axis),
+                    axis.z * target.xyz().dot(axis),
+                    target.w,
+                );
+                let target = twist.try_normalize().unwrap_or(Quaternion::IDENTITY);
+
+                let drive = OrientationDrive::new(target, tracking.stiffness, tracking.damping);
+                joint
+                    .map_unchanged(|joint| &mut joint.orientation_drive)
+                    .set_if_neq(Some(drive));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use bevy::{asset::RenderAssetUsages, mesh::PrimitiveTopology};
+
+    use super::*;
+    use crate::tests::create_test_app;
+
+    fn create_app() -> App {
+        let mut app = create_test_app(RagdollPlugin);
+        app.insert_resource(Gravity(Vector::ZERO));
+        app
+    }
+
+    /// Spawns a skinned mesh with a chain of three bones stacked along the y-axis,
+    /// each influencing a cylinder of vertices with a length of `1.0` and a radius of `0.1`.
+    fn spawn_skinned_chain(
+        app: &mut App,
+        constructor: RagdollConstructor,
+    ) -> (Entity, [Entity; 3]) {
+        let world = app.world_mut();
+
+        let root = world.spawn((Transform::default(), constructor)).id();
+        let bone1 = world
+            .spawn((Name::new("Bone1"), Transform::default(), ChildOf(root)))
+            .id();
+        let bone2 = world
+            .spawn((
+                Name::new("Bone2"),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                ChildOf(bone1),
+            ))
+            .id();
+        let bone3 = world
+            .spawn((
+                Name::new("Bone3"),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                ChildOf(bone2),
+            ))
+            .id();
+
+        let mut positions = Vec::new();
+        let mut joint_indices = Vec::new();
+        let mut joint_weights = Vec::new();
+        for bone in 0..3_u16 {
+            for y in [0.2, 0.8] {
+                for i in 0..8 {
+                    let angle = i as f32 * core::f32::consts::TAU / 8.0;
+                    positions.push([
+                        0.1 * ops::cos(angle),
+                        bone as f32 + y,
+                        0.1 * ops::sin(angle),
+                    ]);
+                    joint_indices.push([bone, 0, 0, 0]);
+                    joint_weights.push([1.0, 0.0, 0.0, 0.0]);
+                }
+            }
+        }
+        let mesh = Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_JOINT_INDEX,
+                VertexAttributeValues::Uint16x4(joint_indices),
+            )
+            .with_inserted_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, joint_weights);
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(mesh);
+
+        // The bones are at the origin of each cylinder in the bind pose.
+        let inverse_bindposes = world
+            .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+            .add(SkinnedMeshInverseBindposes::from(vec![
+                Mat4::IDENTITY,
+                Mat4::from_translation(-Vec3::Y),
+                Mat4::from_translation(-2.0 * Vec3::Y),
+            ]));
+
+        world.spawn((
+            Mesh3d(mesh),
+            SkinnedMesh {
+                inverse_bindposes,
+                joints: vec![bone1, bone2, bone3],
+            },
+            ChildOf(root),
+        ));
+
+        (root, [bone1, bone2, bone3])
+    }
+
+    #[test]
+    fn ragdoll_is_generated_for_skinned_mesh() {
+        let mut app = create_app();
+        let (root, [bone1, bone2, bone3]) =
+            spawn_skinned_chain(&mut app, RagdollConstructor::default());
+
+        app.update();
+
+        let world = app.world();
+        let ragdoll = world
+            .get::<Ragdoll>(root)
+            .expect("ragdoll should be generated");
+        assert_eq!(ragdoll.bones(), &[bone1, bone2, bone3]);
+        assert_eq!(ragdoll.joints().len(), 2);
+        assert!(!world.entity(root).contains::<RagdollConstructor>());
+
+        // Each bone is connected to its parent bone.
+        assert_eq!(world.get::<RagdollBone>(bone1).unwrap().parent, None);
+        assert_eq!(world.get::<RagdollBone>(bone2).unwrap().parent, Some(bone1));
+        assert_eq!(world.get::<RagdollBone>(bone3).unwrap().parent, Some(bone2));
+
+        for bone in [bone1, bone2, bone3] {
+            assert_eq!(world.get::<RigidBody>(bone), Some(&RigidBody::Kinematic));
+            assert_eq!(
+                world.get::<SelfCollisionGroup>(bone),
+                Some(&SelfCollisionGroup(root))
+            );
+
+            // The capsule is fitted to the cylinder of vertices along the bone.
+            let capsule = world
+                .get::<Collider>(bone)
+                .unwrap()
+                .shape()
+                .as_capsule()
+                .expect("bone collider should be a capsule");
+            assert_relative_eq!(capsule.radius, 0.1, epsilon = 1e-4);
+            assert_relative_eq!(capsule.height(), 0.4, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn switching_ragdoll_modes() {
+        let mut app = create_app();
+        let (root, bones) = spawn_skinned_chain(&mut app, RagdollConstructor::default());
+
+        app.update();
+
+        let assert_bodies = |app: &App, body: RigidBody| {
+            for bone in bones {
+                assert_eq!(app.world().get::<RigidBody>(bone), Some(&body));
+            }
+        };
+        let set_mode = |app: &mut App, mode: RagdollMode| {
+            app.world_mut().get_mut::<Ragdoll>(root).unwrap().mode = mode;
+            app.update();
+        };
+
+        assert_bodies(&app, RigidBody::Kinematic);
+
+        set_mode(&mut app, RagdollMode::Simulated);
+        assert_bodies(&app, RigidBody::Dynamic);
+        assert!(!app.world().get::<Ragdoll>(root).unwrap().is_blending());
+
+        set_mode(&mut app, RagdollMode::Powered);
+        assert_bodies(&app, RigidBody::Dynamic);
+
+        // Switching back to the animation starts blending, and stops the bones.
+        set_mode(&mut app, RagdollMode::Animated);
+        assert_bodies(&app, RigidBody::Kinematic);
+        assert!(app.world().get::<Ragdoll>(root).unwrap().is_blending());
+        for bone in bones {
+            assert_eq!(
+                app.world().get::<LinearVelocity>(bone),
+                Some(&LinearVelocity::ZERO)
+            );
+        }
+
+        // Without a blend duration, the ragdoll switches to the animation immediately.
+        set_mode(&mut app, RagdollMode::Simulated);
+        app.world_mut()
+            .get_mut::<Ragdoll>(root)
+            .unwrap()
+            .blend_duration = 0.0;
+        set_mode(&mut app, RagdollMode::Animated);
+        assert_bodies(&app, RigidBody::Kinematic);
+        assert!(!app.world().get::<Ragdoll>(root).unwrap().is_blending());
+    }
+
+    #[test]
+    fn ragdoll_blends_back_to_animation() {
+        /// The pose that a bone is animated to.
+        #[derive(Component)]
+        struct AnimatedPose(Transform);
+
+        let mut app = create_app();
+        app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+
+        // Act as the animation by writing the animated pose every frame.
+        app.add_systems(
+            PostUpdate,
+            (|mut bones: Query<(&mut Transform, &AnimatedPose)>| {
+                for (mut transform, pose) in &mut bones {
+                    *transform = pose.0;
+                }
+            })
+            .before(apply_ragdoll_poses),
+        );
+
+        let constructor = RagdollConstructor::default()
+            .with_mode(RagdollMode::Simulated)
+            .with_blend_duration(0.5);
+        let (root, [bone1, ..]) = spawn_skinned_chain(&mut app, constructor);
+        app.world_mut()
+            .entity_mut(bone1)
+            .insert(AnimatedPose(Transform::default()));
+
+        // Let the ragdoll fall.
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let simulated_y = app.world().get::<Transform>(bone1).unwrap().translation.y;
+        assert!(simulated_y < -0.5);
+
+        // Switch back to the animation. The bone starts at the simulated pose.
+        app.world_mut().get_mut::<Ragdoll>(root).unwrap().mode = RagdollMode::Animated;
+        app.update();
+
+        let y = app.world().get::<Transform>(bone1).unwrap().translation.y;
+        assert_relative_eq!(y, simulated_y, epsilon = 1e-3);
+        assert!(app.world().get::<Ragdoll>(root).unwrap().is_blending());
+
+        // Halfway through the blend, the bone is between the simulated and animated poses.
+        for _ in 0..15 {
+            app.update();
+        }
+        let y = app.world().get::<Transform>(bone1).unwrap().translation.y;
+        assert!(y > simulated_y + 0.1 && y < -0.1);
+
+        // After the blend, the bone follows the animation.
+        for _ in 0..20 {
+            app.update();
+        }
+        assert!(!app.world().get::<Ragdoll>(root).unwrap().is_blending());
+        let transform = app.world().get::<Transform>(bone1).unwrap();
+        assert_relative_eq!(transform.translation.y, 0.0, epsilon = 1e-4);
+        assert!(transform.rotation.angle_between(Quat::IDENTITY) < 1e-3);
+    }
+
+    #[test]
+    fn powered_ragdoll_tracks_shadow_pose() {
+        let mut app = create_app();
+
+        // The shadow skeleton has the second bone bent around the z-axis.
+        let target = Quaternion::from_rotation_z(0.5);
+        let world = app.world_mut();
+        let shadow = world.spawn(Transform::default()).id();
+        let shadow1 = world
+            .spawn((Name::new("Bone1"), Transform::default(), ChildOf(shadow)))
+            .id();
+        let shadow2 = world
+            .spawn((
+                Name::new("Bone2"),
+                Transform::from_xyz(0.0, 1.0, 0.0).with_rotation(target.f32()),
+                ChildOf(shadow1),
+            ))
+            .id();
+        world.spawn((
+            Name::new("Bone3"),
+            Transform::from_xyz(0.0, 1.0, 0.0),
+            ChildOf(shadow2),
+        ));
+
+        let constructor = RagdollConstructor::default()
+            .with_mode(RagdollMode::Powered)
+            .with_density(1000.0);
+        let (root, [bone1, bone2, _]) = spawn_skinned_chain(&mut app, constructor);
+        app.world_mut()
+            .entity_mut(root)
+            .insert(RagdollPoseTracking::new(shadow, 50.0, 10.0));
+
+        let relative_rotation = |app: &App| {
+            let rotation1 = app.world().get::<Rotation>(bone1).unwrap().0;
+            let rotation2 = app.world().get::<Rotation>(bone2).unwrap().0;
+            rotation1.inverse() * rotation2
+        };
+
+        app.update();
+        assert!(relative_rotation(&app).angle_between(target) > 0.4);
+
+        // The drive targets are sampled from the shadow skeleton.
+        let joint = app
+            .world()
+            .get::<RagdollBone>(bone2)
+            .unwrap()
+            .joint
+            .unwrap();
+        let drive = app
+            .world()
+            .get::<SphericalJoint>(joint)
+            .unwrap()
+            .orientation_drive
+            .expect("powered ragdoll joints should have orientation drives");
+        assert!(drive.target.angle_between(target) < 1e-3);
+
+        for _ in 0..180 {
+            app.update();
+        }
+
+        // The bone has converged to the pose of the shadow skeleton.
+        assert!(relative_rotation(&app).angle_between(target) < 0.05);
+
+        // Switching to simulation removes the drives.
+        app.world_mut().get_mut::<Ragdoll>(root).unwrap().mode = RagdollMode::Simulated;
+        app.update();
+        app.update();
+        let joint = app.world().get::<SphericalJoint>(joint).unwrap();
+        assert!(joint.orientation_drive.is_none());
+    }
+
+    #[test]
+    fn ragdoll_bones_do_not_collide() {
+        // The capsules are large enough for all bones to overlap.
+        let constructor = RagdollConstructor::default()
+            .with_min_radius(1.2)
+            .with_mode(RagdollMode::Simulated);
+
+        let mut app = create_app();
+        let (_, [bone1, bone2, bone3]) = spawn_skinned_chain(&mut app, constructor.clone());
+        let mut self_colliding_app = create_app();
+        let (_, [other1, other2, other3]) = spawn_skinned_chain(
+            &mut self_colliding_app,
+            constructor.with_self_collision(true),
+        );
+
+        for _ in 0..3 {
+            app.update();
+            self_colliding_app.update();
+        }
+
+        let contact_graph = app.world().resource::<ContactGraph>();
+        assert!(!contact_graph.contains(bone1, bone2));
+        assert!(!contact_graph.contains(bone2, bone3));
+        assert!(!contact_graph.contains(bone1, bone3));
+
+        // With self-collision enabled, only bones connected by a joint are excluded.
+        let contact_graph = self_colliding_app.world().resource::<ContactGraph>();
+        assert!(!contact_graph.contains(other1, other2));
+        assert!(!contact_graph.contains(other2, other3));
+        assert!(contact_graph.contains(other1, other3));
+    }
+}
@@ -302,6 +302,8 @@ pub enum RigidBody {
     /// Unlike static bodies, kinematic bodies can have velocity.
     /// The engine doesn't modify the values of a kinematic body's components,
     /// so you have full control of them.
+    ///
+    /// To move a kinematic body to a specific pose, use a [`KinematicTarget`].
     Kinematic,
 }
 
@@ -25,6 +25,8 @@ use bevy::prelude::*;
 /// - A [constant force component](super::forces#constant-forces) of a sleeping body is modified.
 /// - A force, impulse, or acceleration is applied via [`Forces`], without using [`non_waking`].
 /// - The [`Gravity`] resource or [`GravityScale`] component is modified.
+/// - The [`GravityOverride`] of a sleeping body is modified, or a [`GravityField`] is modified or removed.
+/// - A [`GravityField`] is moved or rotated, waking up the bodies in the regions it moved out of and into.
 ///
 /// A body and all bodies connected to it can also be forced to sleep or wake up
 /// by manually adding or removing the [`Sleeping`] component, or by using
@@ -39,6 +41,8 @@ use bevy::prelude::*;
 /// [`non_waking`]: super::forces::ForcesItem::non_waking
 /// [`Gravity`]: super::Gravity
 /// [`GravityScale`]: super::GravityScale
+/// [`GravityOverride`]: crate::dynamics::integrator::GravityOverride
+/// [`GravityField`]: crate::dynamics::integrator::GravityField
 /// [`SleepBody`]: crate::dynamics::solver::islands::SleepBody
 /// [`WakeBody`]: crate::dynamics::solver::islands::WakeBody
 ///
@@ -0,0 +1,275 @@
+//! Particle constraints for [soft bodies](super).
+
+use crate::{
+    dynamics::solver::xpbd::{compute_lagrange_update, compute_lagrange_update_with_gradients},
+    prelude::*,
+};
+use bevy::prelude::*;
+
+use super::SoftBodyParticle;
+
+/// A constraint that keeps two [particles](SoftBodyParticle) of a [`SoftBody`] at a given distance from each other.
+///
+/// Distance constraints are used for the edges of a soft body, resisting stretching and compression.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleDistanceConstraint {
+    /// The indices of the constrained particles.
+    pub particles: [usize; 2],
+    /// The distance that the particles are kept at.
+    pub rest_length: Scalar,
+    /// The compliance of the constraint (inverse of stiffness, m / N).
+    pub compliance: Scalar,
+}
+
+impl ParticleDistanceConstraint {
+    /// Creates a new [`ParticleDistanceConstraint`] between the given particles,
+    /// using their current distance as the rest length.
+    pub fn new(particles: &[SoftBodyParticle], indices: [usize; 2], compliance: Scalar) -> Self {
+        Self {
+            particles: indices,
+            rest_length: particles[indices[0]]
+                .position
+                .distance(particles[indices[1]].position),
+            compliance,
+        }
+    }
+
+    /// Solves the constraint for the given particles.
+    ///
+    /// Returns the Lagrange multiplier update, which is negative when the particles are pulled together.
+    /// Dividing it by the squared time step gives the force applied by the constraint.
+    pub fn solve(&self, particles: &mut [SoftBodyParticle], dt: Scalar) -> Scalar {
+        let [i1, i2] = self.particles;
+        let (p1, p2) = (particles[i1], particles[i2]);
+
+        let delta = p1.position - p2.position;
+        let length = delta.length();
+
+        if length <= Scalar::EPSILON {
+            return 0.0;
+        }
+
+        let normal = delta / length;
+        let c = length - self.rest_length;
+
+        let delta_lagrange = compute_lagrange_update(
+            0.0,
+            c,
+            &[p1.inverse_mass, p2.inverse_mass],
+            self.compliance,
+            dt,
+        );
+
+        particles[i1].position += delta_lagrange * p1.inverse_mass * normal;
+        particles[i2].position -= delta_lagrange * p2.inverse_mass * normal;
+
+        delta_lagrange
+    }
+}
+
+/// A constraint that resists bending of a [`SoftBody`] across the edge shared by two adjacent triangles.
+///
+/// The bending is limited by maintaining the distance between the two vertices opposite of the shared edge.
+/// This is cheaper and more robust than constraining the dihedral angle directly.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleBendingConstraint {
+    /// The indices of the particles opposite of the shared edge.
+    pub particles: [usize; 2],
+    /// The distance between the particles in the rest pose.
+    pub rest_length: Scalar,
+    /// The compliance of the constraint (inverse of stiffness, m / N).
+    pub compliance: Scalar,
+}
+
+impl ParticleBendingConstraint {
+    /// Creates a new [`ParticleBendingConstraint`] between the given particles opposite of a shared edge,
+    /// using their current distance as the rest length.
+    pub fn new(particles: &[SoftBodyParticle], indices: [usize; 2], compliance: Scalar) -> Self {
+        Self {
+            particles: indices,
+            rest_length: particles[indices[0]]
+                .position
+                .distance(particles[indices[1]].position),
+            compliance,
+        }
+    }
+
+    /// Solves the constraint for the given particles.
+    pub fn solve(&self, particles: &mut [SoftBodyParticle], dt: Scalar) {
+        ParticleDistanceConstraint {
+            particles: self.particles,
+            rest_length: self.rest_length,
+            compliance: self.compliance,
+        }
+        .solve(particles, dt);
+    }
+}
+
+/// A constraint that preserves the volume of a tetrahedron formed by four [particles](SoftBodyParticle)
+/// of a [`SoftBody`].
+#[cfg(feature = "3d")]
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleVolumeConstraint {
+    /// The indices of the particles forming the tetrahedron.
+    pub particles: [usize; 4],
+    /// The signed volume of the tetrahedron in the rest pose.
+    pub rest_volume: Scalar,
+    /// The compliance of the constraint (inverse of stiffness, m^3 / N).
+    pub compliance: Scalar,
+}
+
+/// A constraint that preserves the area of a triangle formed by three [particles](SoftBodyParticle)
+/// of a [`SoftBody`].
+#[cfg(feature = "2d")]
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleVolumeConstraint {
+    /// The indices of the particles forming the triangle.
+    pub particles: [usize; 3],
+    /// The signed area of the triangle in the rest pose.
+    pub rest_volume: Scalar,
+    /// The compliance of the constraint (inverse of stiffness, m^2 / N).
+    pub compliance: Scalar,
+}
+
+impl ParticleVolumeConstraint {
+    /// Creates a new [`ParticleVolumeConstraint`] for the given particles,
+    /// using their current volume as the rest volume.
+    #[cfg(feature = "2d")]
+    pub fn new(particles: &[SoftBodyParticle], indices: [usize; 3], compliance: Scalar) -> Self {
+        let positions = indices.map(|i| particles[i].position);
+        Self {
+            particles: indices,
+            rest_volume: Self::signed_volume(positions),
+            compliance,
+        }
+    }
+
+    /// Creates a new [`ParticleVolumeConstraint`] for the given particles,
+    /// using their current volume as the rest volume.
+    #[cfg(feature = "3d")]
+    pub fn new(particles: &[SoftBodyParticle], indices: [usize; 4], compliance: Scalar) -> Self {
+        let positions = indices.map(|i| particles[i].position);
+        Self {
+            particles: indices,
+            rest_volume: Self::signed_volume(positions),
+            compliance,
+        }
+    }
+
+    /// Computes the signed area of the triangle formed by the given points.
+    #[cfg(feature = "2d")]
+    fn signed_volume([a, b, c]: [Vector; 3]) -> Scalar {
+        0.5 * (b - a).perp_dot(c - a)
+    }
+
+    /// Computes the signed volume of the tetrahedron formed by the given points.
+    #[cfg(feature = "3d")]
+    fn signed_volume([a, b, c, d]: [Vector; 4]) -> Scalar {
+        (b - a).cross(c - a).dot(d - a) / 6.0
+    }
+
+    /// Solves the constraint for the given particles.
+    pub fn solve(&self, particles: &mut [SoftBodyParticle], dt: Scalar) {
+        let positions = self.particles.map(|i| particles[i].position);
+        let inverse_masses = self.particles.map(|i| particles[i].inverse_mass);
+
+        let c = Self::signed_volume(positions) - self.rest_volume;
+
+        #[cfg(feature = "2d")]
+        let gradients = {
+            let [a, b, c] = positions;
+            [
+                0.5 * (c - b).perp(),
+                0.5 * (a - c).perp(),
+                0.5 * (b - a).perp(),
+            ]
+        };
+        #[cfg(feature = "3d")]
+        let gradients = {
+            let [a, b, c, d] = positions;
+            [
+                (d - b).cross(c - b) / 6.0,
+                (c - a).cross(d - a) / 6.0,
+                (d - a).cross(b - a) / 6.0,
+                (b - a).cross(c - a) / 6.0,
+            ]
+        };
+
+        let delta_lagrange = compute_lagrange_update_with_gradients(
+            0.0,
+            c,
+            &gradients,
+            &inverse_masses,
+            self.compliance,
+            dt,
+        );
+
+        for (i, &index) in self.particles.iter().enumerate() {
+            particles[index].position += delta_lagrange * inverse_masses[i] * gradients[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn distance_constraint_restores_rest_length() {
+        let mut particles = vec![
+            SoftBodyParticle::new(Vector::ZERO, 1.0),
+            SoftBodyParticle::new(Vector::X, 1.0),
+        ];
+        let constraint = ParticleDistanceConstraint::new(&particles, [0, 1], 0.0);
+
+        particles[1].position = Vector::X * 2.0;
+        constraint.solve(&mut particles, 1.0 / 60.0);
+
+        // Both particles have the same mass, so they should move the same amount.
+        assert_relative_eq!(particles[0].position, Vector::X * 0.5, epsilon = 1e-5);
+        assert_relative_eq!(particles[1].position, Vector::X * 1.5, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn volume_constraint_restores_rest_volume() {
+        #[cfg(feature = "2d")]
+        let (positions, indices) = (vec![Vector::ZERO, Vector::X, Vector::Y], [0, 1, 2]);
+        #[cfg(feature = "3d")]
+        let (positions, indices) = (
+            vec![Vector::ZERO, Vector::X, Vector::Y, Vector::Z],
+            [0, 1, 2, 3],
+        );
+
+        let mut particles: Vec<_> = positions
+            .into_iter()
+            .map(|position| SoftBodyParticle::new(position, 1.0))
+            .collect();
+        let constraint = ParticleVolumeConstraint::new(&particles, indices, 0.0);
+
+        // Squash the shape and iterate the constraint until it converges.
+        particles[1].position *= 0.5;
+        for _ in 0..20 {
+            constraint.solve(&mut particles, 1.0 / 60.0);
+        }
+
+        let positions = indices.map(|i| particles[i].position);
+        assert_relative_eq!(
+            ParticleVolumeConstraint::signed_volume(positions),
+            constraint.rest_volume,
+            epsilon = 1e-4
+        );
+    }
+}
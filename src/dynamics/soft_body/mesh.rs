@@ -0,0 +1,237 @@
+//! Constructing [soft bodies](super) from Bevy [`Mesh`]es.
+
+use crate::prelude::*;
+use bevy::{
+    mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use super::{
+    ParticleBendingConstraint, ParticleDistanceConstraint, ParticleVolumeConstraint, SoftBody,
+    SoftBodyConfig, SoftBodyParticle,
+};
+
+impl SoftBody {
+    /// Creates a [`SoftBody`] from the triangles of the given [`Mesh`].
+    ///
+    /// Vertices at the same position are welded into a single particle, so meshes
+    /// with UV seams or split normals stay connected. Each triangle edge is turned into
+    /// a [`ParticleDistanceConstraint`], and each pair of adjacent triangles into
+    /// a [`ParticleBendingConstraint`].
+    #[cfg_attr(
+        feature = "2d",
+        doc = "\nIn 2D, the `Z` coordinates of the vertices are ignored, and each triangle additionally
+gets a [`ParticleVolumeConstraint`] preserving its area if [`SoftBodyConfig::volume_compliance`] is set."
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "\nIn 3D, the mesh is treated as a surface, which is suitable for cloth.
+For volumetric soft bodies, use [`SoftBody::from_tetrahedra`]."
+    )]
+    ///
+    /// The mass is distributed evenly between the particles.
+    ///
+    /// Returns `None` if the mesh does not use [`PrimitiveTopology::TriangleList`],
+    /// if it has no vertex positions or no triangle indices, or if an index is out of bounds.
+    pub fn from_mesh(mesh: &Mesh, config: &SoftBodyConfig) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+
+        let positions: Vec<Vector> = match mesh.attribute(Mesh::ATTRIBUTE_POSITION)? {
+            #[cfg(feature = "2d")]
+            VertexAttributeValues::Float32x3(positions) => positions
+                .iter()
+                .map(|p| Vector::new(p[0] as Scalar, p[1] as Scalar))
+                .collect(),
+            #[cfg(feature = "3d")]
+            VertexAttributeValues::Float32x3(positions) => positions
+                .iter()
+                .map(|p| Vector::new(p[0] as Scalar, p[1] as Scalar, p[2] as Scalar))
+                .collect(),
+            _ => return None,
+        };
+
+        let triangles: Vec<[usize; 3]> = match mesh.indices()? {
+            Indices::U16(indices) => indices
+                .chunks_exact(3)
+                .map(|i| [i[0] as usize, i[1] as usize, i[2] as usize])
+                .collect(),
+            Indices::U32(indices) => indices
+                .chunks_exact(3)
+                .map(|i| [i[0] as usize, i[1] as usize, i[2] as usize])
+                .collect(),
+        };
+
+        if triangles.iter().flatten().any(|&i| i >= positions.len()) {
+            return None;
+        }
+
+        // Weld vertices that share the same position.
+        let mut welded = HashMap::default();
+        let mut particle_positions = Vec::new();
+        let vertex_particles: Vec<usize> = positions
+            .iter()
+            .map(|position| {
+                let key = position.to_array().map(Scalar::to_bits);
+                *welded.entry(key).or_insert_with(|| {
+                    particle_positions.push(*position);
+                    particle_positions.len() - 1
+                })
+            })
+            .collect();
+
+        let triangles: Vec<[usize; 3]> = triangles
+            .iter()
+            .map(|triangle| triangle.map(|i| vertex_particles[i]))
+            .filter(|[a, b, c]| a != b && b != c && a != c)
+            .collect();
+
+        let mut soft_body = Self::from_positions(particle_positions, config);
+        soft_body.mesh_vertex_particles = vertex_particles;
+
+        // Find the edges and the vertices opposite of them.
+        let mut edges = HashMap::<[usize; 2], Vec<usize>>::default();
+        for &[a, b, c] in &triangles {
+            for (edge, opposite) in [([a, b], c), ([b, c], a), ([c, a], b)] {
+                let key = [edge[0].min(edge[1]), edge[0].max(edge[1])];
+                edges.entry(key).or_default().push(opposite);
+            }
+        }
+
+        // Sort the edges for deterministic constraint ordering.
+        let mut edges: Vec<_> = edges.into_iter().collect();
+        edges.sort_unstable_by_key(|(edge, _)| *edge);
+
+        for (edge, opposite) in edges.iter() {
+            soft_body
+                .distance_constraints
+                .push(ParticleDistanceConstraint::new(
+                    &soft_body.particles,
+                    *edge,
+                    config.stretch_compliance,
+                ));
+
+            if let Some(compliance) = config.bend_compliance
+                && let [o1, o2] = opposite[..]
+            {
+                soft_body
+                    .bending_constraints
+                    .push(ParticleBendingConstraint::new(
+                        &soft_body.particles,
+                        [o1, o2],
+                        compliance,
+                    ));
+            }
+        }
+
+        #[cfg(feature = "2d")]
+        if let Some(compliance) = config.volume_compliance {
+            for triangle in triangles {
+                soft_body
+                    .volume_constraints
+                    .push(ParticleVolumeConstraint::new(
+                        &soft_body.particles,
+                        triangle,
+                        compliance,
+                    ));
+            }
+        }
+
+        Some(soft_body)
+    }
+
+    /// Creates a volumetric [`SoftBody`] from the given vertices and tetrahedra.
+    ///
+    /// Each tetrahedron is given a [`ParticleVolumeConstraint`] if [`SoftBodyConfig::volume_compliance`] is set,
+    /// and each unique tetrahedron edge is turned into a [`ParticleDistanceConstraint`].
+    ///
+    /// The mass is distributed evenly between the particles.
+    #[cfg(feature = "3d")]
+    pub fn from_tetrahedra(
+        vertices: Vec<Vector>,
+        tetrahedra: &[[usize; 4]],
+        config: &SoftBodyConfig,
+    ) -> Self {
+        let mut soft_body = Self::from_positions(vertices, config);
+
+        let mut edges = bevy::platform::collections::HashSet::<[usize; 2]>::default();
+        for &[a, b, c, d] in tetrahedra {
+            for [i, j] in [[a, b], [a, c], [a, d], [b, c], [b, d], [c, d]] {
+                edges.insert([i.min(j), i.max(j)]);
+            }
+        }
+
+        // Sort the edges for deterministic constraint ordering.
+        let mut edges: Vec<_> = edges.into_iter().collect();
+        edges.sort_unstable();
+
+        for edge in edges {
+            soft_body
+                .distance_constraints
+                .push(ParticleDistanceConstraint::new(
+                    &soft_body.particles,
+                    edge,
+                    config.stretch_compliance,
+                ));
+        }
+
+        if let Some(compliance) = config.volume_compliance {
+            for &tetrahedron in tetrahedra {
+                soft_body
+                    .volume_constraints
+                    .push(ParticleVolumeConstraint::new(
+                        &soft_body.particles,
+                        tetrahedron,
+                        compliance,
+                    ));
+            }
+        }
+
+        soft_body
+    }
+
+    /// Creates a [`SoftBody`] with particles at the given positions and no constraints.
+    ///
+    /// The mass is distributed evenly between the particles.
+    pub fn from_positions(positions: Vec<Vector>, config: &SoftBodyConfig) -> Self {
+        let particle_mass = config.mass / positions.len().max(1) as Scalar;
+        Self {
+            particles: positions
+                .into_iter()
+                .map(|position| SoftBodyParticle::new(position, particle_mass))
+                .collect(),
+            particle_radius: config.particle_radius,
+            ..default()
+        }
+    }
+
+    /// Writes the current particle positions to the vertex positions of the given [`Mesh`].
+    ///
+    /// The mesh should be the one that the soft body was created from using [`SoftBody::from_mesh`].
+    /// The positions are transformed into the local space described by `transform`,
+    /// which is typically the [`GlobalTransform`] of the entity rendering the mesh.
+    ///
+    /// Normals are recomputed if the mesh has a normal attribute.
+    pub fn write_mesh_positions(&self, mesh: &mut Mesh, transform: &GlobalTransform) {
+        let inverse = transform.affine().inverse();
+
+        let positions: Vec<[f32; 3]> = self
+            .mesh_vertex_particles
+            .iter()
+            .map(|&particle| {
+                let position = self.particles[particle].position.f32();
+                #[cfg(feature = "2d")]
+                let position = position.extend(0.0);
+                inverse.transform_point3(position).to_array()
+            })
+            .collect();
+
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+
+        if mesh.contains_attribute(Mesh::ATTRIBUTE_NORMAL) {
+            mesh.compute_normals();
+        }
+    }
+}
@@ -0,0 +1,1134 @@
+//! Position-based soft bodies, cloth, and ropes simulated using particles.
+//!
+//! See [`SoftBodyPlugin`].
+//!
+//! # Overview
+//!
+//! A [`SoftBody`] is a collection of [particles](SoftBodyParticle) connected by constraints
+//! that are solved using [Extended Position-Based Dynamics (XPBD)](crate::dynamics::solver::xpbd).
+//! The following constraints are supported:
+//!
+//! - [`ParticleDistanceConstraint`]: Resists stretching and compression along the edges of the soft body.
+//! - [`ParticleBendingConstraint`]: Resists bending across the edges shared by adjacent triangles.
+#![cfg_attr(
+    feature = "2d",
+    doc = "- [`ParticleVolumeConstraint`]: Preserves the area of triangles."
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "- [`ParticleVolumeConstraint`]: Preserves the volume of tetrahedra."
+)]
+//!
+//! Each constraint has a *compliance*, the inverse of its stiffness. A compliance of zero
+//! corresponds to an infinitely stiff constraint.
+//!
+//! Soft bodies are typically created from a Bevy [`Mesh`] using [`SoftBody::from_mesh`].
+#![cfg_attr(
+    feature = "3d",
+    doc = "In 3D, meshes are treated as surfaces, which is suitable for cloth. Volumetric soft bodies
+can be created from tetrahedra using [`SoftBody::from_tetrahedra`]."
+)]
+//! The particles are stored in world space, and the [`Transform`] of the soft body entity
+//! is only used for placing the particles when the soft body is spawned.
+//!
+#![cfg_attr(
+    feature = "2d",
+    doc = "If the soft body entity has a [`Mesh2d`] with the mesh the soft body was created from,"
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "If the `collider-from-mesh` feature is enabled and the soft body entity has a [`Mesh3d`]
+with the mesh the soft body was created from,"
+)]
+//! the particle positions are written back to the vertices of the mesh after each physics step.
+//!
+//! # Coupling With Rigid Bodies
+//!
+//! Particles collide with [colliders](Collider) using contacts that are generated once per physics step,
+//! like the speculative contacts of rigid bodies, and solved at each substep. The contacts
+//! push the particles and the rigid bodies apart based on their masses, so soft bodies and
+//! rigid bodies affect each other. Contact filtering uses the [`CollisionLayers`] of the soft body entity.
+//! Sleeping rigid bodies are woken up when they are touched by moving particles.
+//!
+//! Particle contacts are generated by the [`SoftBodyPlugin`] using [spatial queries](crate::spatial_query)
+//! instead of the [narrow phase](crate::collision::narrow_phase). They are not stored in the [`ContactGraph`],
+//! do not trigger collision events like [`CollisionStart`], are not passed to [`CollisionHooks`],
+//! and are not included in [`CollidingEntities`].
+//!
+//! Particles can also be attached to rigid bodies using [`ParticleAttachment`]s, or pinned in place
+//! by giving them an inverse mass of zero with [`SoftBody::pin`].
+//!
+//! Particles do not currently collide with each other or with other soft bodies.
+//!
+//! # Ropes
+//!
+//! Ropes and cables are represented by the [`Rope`] component, a chain of particles with
+//! tethers that prevent stretching, capsule collisions for each segment, reeling, and tearing.
+//! See its documentation for more information.
+//!
+//! # Example
+//!
+//! ```no_run
+#![cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+#![cfg_attr(
+    feature = "2d",
+    doc = "    let mesh = Mesh::from(Rectangle::new(2.0, 2.0));"
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "    let mesh = Plane3d::default().mesh().size(2.0, 2.0).subdivisions(16).build();"
+)]
+//!     let config = SoftBodyConfig::default().with_mass(0.5);
+//!
+//!     if let Some(soft_body) = SoftBody::from_mesh(&mesh, &config) {
+//!         // Pin the first particle in place.
+//!         commands.spawn((soft_body.pin(0), Transform::from_xyz(0.0, 3.0, 0.0)));
+//!     }
+//! }
+//! ```
+
+mod constraints;
+mod mesh;
+mod rope;
+
+pub use constraints::*;
+pub use rope::{Rope, RopeTorn};
+
+use crate::{
+    dynamics::solver::{
+        solver_body::{SolverBody, SolverBodyInertia},
+        xpbd::{PositionConstraint, XpbdSolverSystems, compute_lagrange_update},
+    },
+    prelude::*,
+};
+use bevy::{
+    ecs::{entity::hash_set::EntityHashSet, system::SystemParam},
+    prelude::*,
+};
+
+/// A plugin for simulating [soft bodies](self) and [ropes](Rope) using particles and XPBD constraints.
+///
+/// The plugin is not included in [`PhysicsPlugins`] by default, and must be added manually.
+/// It also requires the [`XpbdSolverPlugin`].
+///
+/// Contacts and attachments are prepared in [`SolverSystems::PreSubstep`],
+/// and the particles are simulated in the [`SubstepSchedule`] in [`SoftBodySystems`].
+/// Ropes that exceed their tear tension are torn in [`SolverSystems::Finalize`].
+pub struct SoftBodyPlugin;
+
+impl Plugin for SoftBodyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PhysicsSchedule,
+            (
+                (init_soft_bodies, rope::init_ropes).in_set(PhysicsStepSystems::First),
+                (prepare_soft_bodies, rope::prepare_ropes).in_set(SolverSystems::PreSubstep),
+                rope::tear_ropes.in_set(SolverSystems::Finalize),
+            ),
+        );
+
+        app.configure_sets(
+            SubstepSchedule,
+            SoftBodySystems
+                .after(XpbdSolverSystems::SolveUserConstraints)
+                .before(XpbdSolverSystems::VelocityProjection),
+        );
+
+        app.add_systems(
+            SubstepSchedule,
+            (
+                (
+                    integrate_particles,
+                    solve_soft_body_constraints,
+                    update_particle_velocities,
+                )
+                    .chain(),
+                (
+                    rope::integrate_rope_particles,
+                    rope::solve_ropes,
+                    rope::update_rope_velocities,
+                )
+                    .chain(),
+            )
+                .chain()
+                .in_set(SoftBodySystems),
+        );
+
+        #[cfg(feature = "2d")]
+        app.add_systems(PostUpdate, update_soft_body_meshes::<Mesh2d>);
+        #[cfg(feature = "collider-from-mesh")]
+        app.add_systems(PostUpdate, update_soft_body_meshes::<Mesh3d>);
+    }
+}
+
+/// A system set for simulating [soft bodies](self) and [ropes](Rope) in the [`SubstepSchedule`].
+///
+/// Runs after [`XpbdSolverSystems::SolveUserConstraints`] and before [`XpbdSolverSystems::VelocityProjection`],
+/// so that the corrections applied to rigid bodies by contacts and attachments also update their velocities.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoftBodySystems;
+
+/// A soft body consisting of [particles](SoftBodyParticle) connected by XPBD constraints.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[require(Transform)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct SoftBody {
+    /// The particles of the soft body, in world space.
+    pub particles: Vec<SoftBodyParticle>,
+    /// Constraints resisting stretching and compression.
+    pub distance_constraints: Vec<ParticleDistanceConstraint>,
+    /// Constraints resisting bending.
+    pub bending_constraints: Vec<ParticleBendingConstraint>,
+    #[cfg_attr(feature = "2d", doc = "Constraints preserving the area of triangles.")]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "Constraints preserving the volume of tetrahedra."
+    )]
+    pub volume_constraints: Vec<ParticleVolumeConstraint>,
+    /// Attachments connecting particles to rigid bodies.
+    pub attachments: Vec<ParticleAttachment>,
+    /// The radius of each particle used for collisions.
+    pub particle_radius: Scalar,
+    /// The coefficient of friction between the particles and colliders.
+    pub friction: Scalar,
+    /// The linear damping applied to the velocities of the particles.
+    pub linear_damping: Scalar,
+    /// Maps each vertex of the source mesh to a particle.
+    mesh_vertex_particles: Vec<usize>,
+    /// Contacts between particles and colliders for the current time step.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    contacts: Vec<ParticleContact>,
+}
+
+impl Default for SoftBody {
+    fn default() -> Self {
+        Self {
+            particles: Vec::new(),
+            distance_constraints: Vec::new(),
+            bending_constraints: Vec::new(),
+            volume_constraints: Vec::new(),
+            attachments: Vec::new(),
+            particle_radius: 0.05,
+            friction: 0.5,
+            linear_damping: 0.0,
+            mesh_vertex_particles: Vec::new(),
+            contacts: Vec::new(),
+        }
+    }
+}
+
+impl SoftBody {
+    /// Pins the particle at the given index in place by giving it an inverse mass of zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `particle` is not a valid index into [`SoftBody::particles`].
+    pub fn pin(mut self, particle: usize) -> Self {
+        self.particles[particle].inverse_mass = 0.0;
+        self
+    }
+
+    /// Attaches the particle at the given index to a rigid body at the given local anchor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `particle` is not a valid index into [`SoftBody::particles`].
+    pub fn with_attachment(mut self, particle: usize, body: Entity, local_anchor: Vector) -> Self {
+        assert!(
+            particle < self.particles.len(),
+            "attachment particle index {particle} is out of bounds for a soft body with {} particles",
+            self.particles.len()
+        );
+        self.attachments
+            .push(ParticleAttachment::new(particle, body, local_anchor));
+        self
+    }
+
+    /// Sets the coefficient of friction between the particles and colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_linear_damping(mut self, damping: Scalar) -> Self {
+        self.linear_damping = damping;
+        self
+    }
+}
+
+/// Configuration for constructing a [`SoftBody`], used by methods such as [`SoftBody::from_mesh`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct SoftBodyConfig {
+    /// The total mass of the soft body, distributed evenly between the particles.
+    ///
+    /// Default: `1.0`
+    pub mass: Scalar,
+    /// The radius of each particle used for collisions.
+    ///
+    /// Default: `0.05`
+    pub particle_radius: Scalar,
+    /// The compliance of the [`ParticleDistanceConstraint`]s (inverse of stiffness, m / N).
+    ///
+    /// Default: `0.0`
+    pub stretch_compliance: Scalar,
+    /// The compliance of the [`ParticleBendingConstraint`]s (inverse of stiffness, m / N).
+    /// If `None`, no bending constraints are created.
+    ///
+    /// Default: `Some(0.01)`
+    pub bend_compliance: Option<Scalar>,
+    /// The compliance of the [`ParticleVolumeConstraint`]s. If `None`, no volume constraints are created.
+    ///
+    /// Default: `Some(0.0)`
+    pub volume_compliance: Option<Scalar>,
+}
+
+impl Default for SoftBodyConfig {
+    fn default() -> Self {
+        Self {
+            mass: 1.0,
+            particle_radius: 0.05,
+            stretch_compliance: 0.0,
+            bend_compliance: Some(0.01),
+            volume_compliance: Some(0.0),
+        }
+    }
+}
+
+impl SoftBodyConfig {
+    /// Sets the total mass of the soft body.
+    pub fn with_mass(mut self, mass: Scalar) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets the radius of each particle used for collisions.
+    pub fn with_particle_radius(mut self, radius: Scalar) -> Self {
+        self.particle_radius = radius;
+        self
+    }
+
+    /// Sets the compliance of the [`ParticleDistanceConstraint`]s.
+    pub fn with_stretch_compliance(mut self, compliance: Scalar) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the [`ParticleBendingConstraint`]s. If `None`, no bending constraints are created.
+    pub fn with_bend_compliance(mut self, compliance: Option<Scalar>) -> Self {
+        self.bend_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the [`ParticleVolumeConstraint`]s. If `None`, no volume constraints are created.
+    pub fn with_volume_compliance(mut self, compliance: Option<Scalar>) -> Self {
+        self.volume_compliance = compliance;
+        self
+    }
+}
+
+/// A particle of a [`SoftBody`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct SoftBodyParticle {
+    /// The position of the particle in world space.
+    pub position: Vector,
+    /// The velocity of the particle.
+    pub velocity: Vector,
+    /// The inverse mass of the particle. Zero means that the particle is pinned in place.
+    pub inverse_mass: Scalar,
+    /// The position of the particle at the start of the current substep.
+    previous_position: Vector,
+}
+
+impl SoftBodyParticle {
+    /// Creates a new [`SoftBodyParticle`] at the given position with the given mass.
+    ///
+    /// A mass of zero or less pins the particle in place.
+    pub fn new(position: Vector, mass: Scalar) -> Self {
+        Self {
+            position,
+            velocity: Vector::ZERO,
+            inverse_mass: if mass > 0.0 { 1.0 / mass } else { 0.0 },
+            previous_position: position,
+        }
+    }
+}
+
+/// Attaches a [particle](SoftBodyParticle) of a [`SoftBody`] to a point on a rigid body.
+///
+/// The attachment is two-way: the rigid body is also pulled by the particle.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleAttachment {
+    /// The index of the attached particle.
+    pub particle: usize,
+    /// The rigid body that the particle is attached to.
+    pub body: Entity,
+    /// The attachment point in the local space of the body.
+    pub local_anchor: Vector,
+    /// The compliance of the attachment (inverse of stiffness, m / N).
+    pub compliance: Scalar,
+    /// The world-space center of mass of the body at the start of the time step.
+    center_of_mass: Vector,
+    /// The world-space offset from the center of mass to the anchor at the start of the time step.
+    world_anchor: Vector,
+}
+
+impl ParticleAttachment {
+    /// Creates a new rigid [`ParticleAttachment`] between a particle and a point on a rigid body.
+    pub const fn new(particle: usize, body: Entity, local_anchor: Vector) -> Self {
+        Self {
+            particle,
+            body,
+            local_anchor,
+            compliance: 0.0,
+            center_of_mass: Vector::ZERO,
+            world_anchor: Vector::ZERO,
+        }
+    }
+
+    /// Sets the compliance of the attachment (inverse of stiffness, m / N).
+    pub const fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+}
+
+/// A contact between a [particle](SoftBodyParticle) and a collider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ParticleContact {
+    /// The index of the particle.
+    particle: usize,
+    /// The rigid body that the collider is attached to, if any.
+    body: Option<Entity>,
+    /// The world-space contact point on the collider at the start of the time step.
+    point: Vector,
+    /// The offset from the center of mass of the body to the contact point at the start of the time step.
+    anchor: Vector,
+    /// The world-space contact normal pointing towards the particle at the start of the time step.
+    normal: Vector,
+    /// The contact point at the end of the previous substep, used for friction.
+    previous_point: Vector,
+}
+
+/// A helper for applying positional corrections between particles and rigid bodies.
+struct ParticleBodyConstraint;
+
+impl PositionConstraint for ParticleBodyConstraint {}
+
+impl ParticleBodyConstraint {
+    /// Computes the generalized inverse mass of a body when applying a correction at `r` along `n`.
+    fn inverse_mass(inertia: &SolverBodyInertia, r: Vector, n: Vector) -> Scalar {
+        ParticleBodyConstraint.compute_generalized_inverse_mass(
+            inertia.effective_inv_mass().max_element(), // TODO: Do this properly.
+            inertia.effective_inv_angular_inertia(),
+            r,
+            n,
+        )
+    }
+
+    /// Applies a positional impulse to a body at the world-space offset `r` from its center of mass.
+    fn apply_impulse(
+        body: &mut SolverBody,
+        inertia: &SolverBodyInertia,
+        impulse: Vector,
+        r: Vector,
+    ) {
+        body.delta_position += impulse * inertia.effective_inv_mass();
+
+        let delta_rotation =
+            Self::get_delta_rot(inertia.effective_inv_angular_inertia(), r, impulse);
+        #[cfg(feature = "2d")]
+        {
+            body.delta_rotation = body.delta_rotation.add_angle_fast(delta_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            body.delta_rotation.0 = delta_rotation * body.delta_rotation.0;
+        }
+    }
+}
+
+/// A [`SystemParam`] for waking up sleeping rigid bodies that are touched by moving particles.
+///
+/// Sleeping bodies are not simulated by the solver, so contacts and attachments could not move them otherwise.
+/// A body is woken up if a particle in contact with it or attached to it moves faster than its [`SleepThreshold`].
+#[derive(SystemParam)]
+pub(super) struct ParticleBodyWaker<'w, 's> {
+    commands: Commands<'w, 's>,
+    sleeping_bodies: Query<'w, 's, Option<&'static SleepThreshold>, With<Sleeping>>,
+    length_unit: Res<'w, PhysicsLengthUnit>,
+    woken_bodies: Local<'s, EntityHashSet>,
+}
+
+impl ParticleBodyWaker<'_, '_> {
+    /// Clears the bodies woken up so far. Should be called at the start of each time step.
+    fn clear(&mut self) {
+        self.woken_bodies.clear();
+    }
+
+    /// Wakes up the given body if it is sleeping and the particle touching it moves with the given velocity.
+    fn wake_if_moving(&mut self, body: Entity, particle_velocity: Vector) {
+        let Ok(threshold) = self.sleeping_bodies.get(body) else {
+            return;
+        };
+
+        let threshold =
+            threshold.copied().unwrap_or_default().linear as Scalar * self.length_unit.0;
+        if particle_velocity.length_squared() > threshold * threshold
+            && self.woken_bodies.insert(body)
+        {
+            self.commands.queue(WakeBody(body));
+        }
+    }
+
+    /// Wakes up the sleeping bodies that are attached to moving particles.
+    fn wake_attached(
+        &mut self,
+        particles: &[SoftBodyParticle],
+        attachments: &[ParticleAttachment],
+    ) {
+        for attachment in attachments.iter() {
+            if let Some(particle) = particles.get(attachment.particle) {
+                self.wake_if_moving(attachment.body, particle.velocity);
+            }
+        }
+    }
+}
+
+/// Moves the particles of newly added soft bodies to the space of their [`Transform`].
+fn init_soft_bodies(mut soft_bodies: Query<(&mut SoftBody, &Transform), Added<SoftBody>>) {
+    for (mut soft_body, transform) in &mut soft_bodies {
+        transform_particles(&mut soft_body.particles, transform);
+    }
+}
+
+/// Transforms the given particles from the local space of the given [`Transform`] to world space.
+fn transform_particles(particles: &mut [SoftBodyParticle], transform: &Transform) {
+    if *transform == Transform::IDENTITY {
+        return;
+    }
+
+    for particle in particles.iter_mut() {
+        #[cfg(feature = "2d")]
+        let position = transform
+            .transform_point(particle.position.f32().extend(0.0))
+            .truncate()
+            .adjust_precision();
+        #[cfg(feature = "3d")]
+        let position = transform
+            .transform_point(particle.position.f32())
+            .adjust_precision();
+        particle.position = position;
+        particle.previous_position = position;
+    }
+}
+
+/// Generates contacts between particles and colliders, and prepares attachments for the substepping loop.
+#[allow(clippy::type_complexity)]
+fn prepare_soft_bodies(
+    mut soft_bodies: Query<(&mut SoftBody, Option<&CollisionLayers>)>,
+    colliders: Query<
+        (
+            &Collider,
+            &Position,
+            &Rotation,
+            Option<&ColliderOf>,
+            Option<&CollisionLayers>,
+        ),
+        (Without<Sensor>, Without<ColliderDisabled>),
+    >,
+    bodies: Query<(&Position, &Rotation, &ComputedCenterOfMass), With<RigidBody>>,
+    spatial_query: SpatialQuery,
+    mut waker: ParticleBodyWaker,
+    mut particle_aabbs: Local<Vec<ColliderAabb>>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    waker.clear();
+
+    for (mut soft_body, layers) in &mut soft_bodies {
+        let soft_body = &mut *soft_body;
+        let layers = layers.copied().unwrap_or_default();
+        let radius = soft_body.particle_radius;
+
+        prepare_attachments(&mut soft_body.attachments, &bodies);
+        waker.wake_attached(&soft_body.particles, &soft_body.attachments);
+
+        soft_body.contacts.clear();
+
+        if soft_body.particles.is_empty() {
+            continue;
+        }
+
+        // Compute the AABB of the region that each particle could reach during the time step.
+        particle_aabbs.clear();
+        particle_aabbs.extend(soft_body.particles.iter().map(|particle| {
+            let margin = radius + particle.velocity.length() * delta_secs;
+            ColliderAabb::new(particle.position, Vector::splat(margin))
+        }));
+        let aabb = particle_aabbs
+            .iter()
+            .fold(ColliderAabb::INVALID, |aabb, particle_aabb| {
+                aabb.merged(*particle_aabb)
+            });
+
+        // Generate contacts with all colliders that could be hit during the time step.
+        for collider_entity in spatial_query.aabb_intersections_with_aabb(aabb) {
+            let Ok((collider, position, rotation, collider_of, collider_layers)) =
+                colliders.get(collider_entity)
+            else {
+                continue;
+            };
+
+            if !layers.interacts_with(collider_layers.copied().unwrap_or_default()) {
+                continue;
+            }
+
+            let collider_aabb = collider.aabb(position.0, *rotation);
+
+            let body = collider_of.and_then(|collider_of| {
+                bodies
+                    .get(collider_of.body)
+                    .ok()
+                    .map(|body| (collider_of.body, body))
+            });
+
+            for (index, particle) in soft_body.particles.iter().enumerate() {
+                // Only project particles that could reach the collider.
+                if !particle_aabbs[index].intersects(&collider_aabb) {
+                    continue;
+                }
+
+                let (point, is_inside) =
+                    collider.project_point(*position, *rotation, particle.position, false);
+
+                let offset = particle.position - point;
+                let distance = offset.length();
+
+                if distance <= Scalar::EPSILON {
+                    continue;
+                }
+
+                let (normal, separation) = if is_inside {
+                    (-offset / distance, -distance)
+                } else {
+                    (offset / distance, distance)
+                };
+
+                // Include contacts that could be reached during the time step, like speculative contacts.
+                let margin = radius + particle.velocity.length() * delta_secs;
+                if separation > margin {
+                    continue;
+                }
+
+                let (body_entity, anchor) = match body {
+                    Some((entity, (body_position, body_rotation, center_of_mass))) => (
+                        Some(entity),
+                        point - (body_position.0 + *body_rotation * center_of_mass.0),
+                    ),
+                    None => (None, Vector::ZERO),
+                };
+
+                if let Some(entity) = body_entity {
+                    waker.wake_if_moving(entity, particle.velocity);
+                }
+
+                soft_body.contacts.push(ParticleContact {
+                    particle: index,
+                    body: body_entity,
+                    point,
+                    anchor,
+                    normal,
+                    previous_point: point,
+                });
+            }
+        }
+    }
+}
+
+/// Applies gravity and damping to the particles of soft bodies, and moves them based on their velocities.
+fn integrate_particles(
+    mut soft_bodies: Query<(
+        &mut SoftBody,
+        Option<&GravityScale>,
+        Option<&GravityOverride>,
+    )>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut soft_body, gravity_scale, gravity_override) in &mut soft_bodies {
+        let gravity = gravity_override.map_or(gravity.0, |gravity| gravity.0)
+            * gravity_scale.map_or(1.0, |scale| scale.0);
+        let linear_damping = soft_body.linear_damping;
+
+        integrate(
+            &mut soft_body.particles,
+            gravity,
+            linear_damping,
+            delta_secs,
+        );
+    }
+}
+
+/// Solves the constraints, attachments, and contacts of soft bodies.
+fn solve_soft_body_constraints(
+    mut soft_bodies: Query<&mut SoftBody>,
+    mut bodies: Query<(&mut SolverBody, &SolverBodyInertia)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut soft_body in &mut soft_bodies {
+        let SoftBody {
+            particles,
+            distance_constraints,
+            bending_constraints,
+            volume_constraints,
+            attachments,
+            particle_radius,
+            friction,
+            contacts,
+            ..
+        } = &mut *soft_body;
+
+        for constraint in distance_constraints.iter() {
+            constraint.solve(particles, delta_secs);
+        }
+        for constraint in bending_constraints.iter() {
+            constraint.solve(particles, delta_secs);
+        }
+        for constraint in volume_constraints.iter() {
+            constraint.solve(particles, delta_secs);
+        }
+
+        solve_attachments(particles, attachments, &mut bodies, delta_secs);
+
+        // Solve contacts.
+        for contact in contacts.iter_mut() {
+            let particle = &mut particles[contact.particle];
+            let mut body = contact.body.and_then(|entity| bodies.get_mut(entity).ok());
+
+            let (delta_position, delta_rotation) = body
+                .as_ref()
+                .map_or((Vector::ZERO, Rotation::IDENTITY), |(body, _)| {
+                    (body.delta_position, body.delta_rotation)
+                });
+
+            let r = delta_rotation * contact.anchor;
+            let point = contact.point + delta_position + (r - contact.anchor);
+            let normal = delta_rotation * contact.normal;
+            let previous_point = contact.previous_point;
+            contact.previous_point = point;
+
+            let separation = (particle.position - point).dot(normal) - *particle_radius;
+
+            if separation >= 0.0 {
+                continue;
+            }
+
+            let w1 = particle.inverse_mass;
+            let w2 = body.as_ref().map_or(0.0, |(_, inertia)| {
+                ParticleBodyConstraint::inverse_mass(inertia, r, normal)
+            });
+            let w_sum = w1 + w2;
+
+            if w_sum <= Scalar::EPSILON {
+                continue;
+            }
+
+            // Push the particle out of the collider.
+            let normal_lagrange = -separation / w_sum;
+            let mut impulse = normal_lagrange * normal;
+
+            // Apply positional friction based on the relative tangential motion during the substep.
+            let relative_motion =
+                (particle.position - particle.previous_position) - (point - previous_point);
+            let tangential_motion = relative_motion - relative_motion.dot(normal) * normal;
+            let tangential_distance = tangential_motion.length();
+
+            if tangential_distance > Scalar::EPSILON {
+                let tangent = tangential_motion / tangential_distance;
+                let tangent_lagrange =
+                    (tangential_distance / w_sum).min(*friction * normal_lagrange);
+                impulse -= tangent_lagrange * tangent;
+            }
+
+            particle.position += impulse * w1;
+            if let Some((body, inertia)) = body.as_mut() {
+                ParticleBodyConstraint::apply_impulse(body, inertia, -impulse, r);
+            }
+        }
+    }
+}
+
+/// Updates the velocities of the particles of soft bodies based on how much they moved during the substep.
+fn update_particle_velocities(mut soft_bodies: Query<&mut SoftBody>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for mut soft_body in &mut soft_bodies {
+        update_velocities(&mut soft_body.particles, delta_secs);
+    }
+}
+
+/// Computes the world-space anchors of the given attachments at the start of the time step.
+fn prepare_attachments(
+    attachments: &mut [ParticleAttachment],
+    bodies: &Query<(&Position, &Rotation, &ComputedCenterOfMass), With<RigidBody>>,
+) {
+    for attachment in attachments.iter_mut() {
+        if let Ok((position, rotation, center_of_mass)) = bodies.get(attachment.body) {
+            attachment.center_of_mass = position.0 + *rotation * center_of_mass.0;
+            attachment.world_anchor = *rotation * (attachment.local_anchor - center_of_mass.0);
+        } else {
+            attachment.center_of_mass = attachment.local_anchor;
+            attachment.world_anchor = Vector::ZERO;
+        }
+    }
+}
+
+/// Applies gravity and damping to the given particles, and moves them based on their velocities.
+fn integrate(
+    particles: &mut [SoftBodyParticle],
+    gravity: Vector,
+    linear_damping: Scalar,
+    delta_secs: Scalar,
+) {
+    let damping = 1.0 / (1.0 + delta_secs * linear_damping);
+
+    for particle in particles.iter_mut() {
+        particle.previous_position = particle.position;
+
+        if particle.inverse_mass == 0.0 {
+            continue;
+        }
+
+        particle.velocity = (particle.velocity + gravity * delta_secs) * damping;
+        particle.position += particle.velocity * delta_secs;
+    }
+}
+
+/// Solves the given attachments between particles and rigid bodies.
+fn solve_attachments(
+    particles: &mut [SoftBodyParticle],
+    attachments: &[ParticleAttachment],
+    bodies: &mut Query<(&mut SolverBody, &SolverBodyInertia)>,
+    delta_secs: Scalar,
+) {
+    for attachment in attachments.iter() {
+        // Attachments can be pushed to `SoftBody::attachments` directly, so skip invalid indices.
+        let Some(particle) = particles.get_mut(attachment.particle) else {
+            continue;
+        };
+        let mut body = bodies.get_mut(attachment.body).ok();
+
+        let (delta_position, delta_rotation) = body
+            .as_ref()
+            .map_or((Vector::ZERO, Rotation::IDENTITY), |(body, _)| {
+                (body.delta_position, body.delta_rotation)
+            });
+
+        let r = delta_rotation * attachment.world_anchor;
+        let target = attachment.center_of_mass + delta_position + r;
+        let separation = particle.position - target;
+        let distance = separation.length();
+
+        if distance <= Scalar::EPSILON {
+            continue;
+        }
+
+        let normal = separation / distance;
+        let w1 = particle.inverse_mass;
+        let w2 = body.as_ref().map_or(0.0, |(_, inertia)| {
+            ParticleBodyConstraint::inverse_mass(inertia, r, normal)
+        });
+
+        let delta_lagrange =
+            compute_lagrange_update(0.0, distance, &[w1, w2], attachment.compliance, delta_secs);
+
+        particle.position += delta_lagrange * w1 * normal;
+        if let Some((body, inertia)) = body.as_mut() {
+            ParticleBodyConstraint::apply_impulse(body, inertia, -delta_lagrange * normal, r);
+        }
+    }
+}
+
+/// Updates the velocities of the given particles based on how much they moved during the substep.
+fn update_velocities(particles: &mut [SoftBodyParticle], delta_secs: Scalar) {
+    for particle in particles.iter_mut() {
+        particle.velocity = (particle.position - particle.previous_position) / delta_secs;
+    }
+}
+
+/// Writes the particle positions of soft bodies created from meshes back to their mesh component,
+/// which is either a [`Mesh2d`] or a [`Mesh3d`].
+#[cfg(any(feature = "2d", feature = "collider-from-mesh"))]
+fn update_soft_body_meshes<M: Component + bevy::asset::AsAssetId<Asset = Mesh>>(
+    soft_bodies: Query<(&SoftBody, &M, &GlobalTransform), Changed<SoftBody>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+) {
+    // Mesh assets are not available without the `AssetPlugin`.
+    let Some(mut meshes) = meshes else {
+        return;
+    };
+
+    for (soft_body, mesh, global_transform) in &soft_bodies {
+        if soft_body.mesh_vertex_particles.is_empty() {
+            continue;
+        }
+
+        if let Some(mesh) = meshes.get_mut(mesh.as_asset_id()) {
+            soft_body.write_mesh_positions(mesh, global_transform);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::create_test_app;
+
+    fn create_app() -> App {
+        // Soft body meshes are stored as assets, which requires the `MeshPlugin`.
+        create_test_app((
+            SoftBodyPlugin,
+            #[cfg(feature = "2d")]
+            bevy::mesh::MeshPlugin,
+        ))
+    }
+
+    /// Computes the signed area or volume of the given volume constraint.
+    fn constraint_volume(
+        particles: &[SoftBodyParticle],
+        constraint: &ParticleVolumeConstraint,
+    ) -> Scalar {
+        let positions = constraint.particles.map(|i| particles[i].position);
+        #[cfg(feature = "2d")]
+        {
+            let [a, b, c] = positions;
+            0.5 * (b - a).perp_dot(c - a)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let [a, b, c, d] = positions;
+            (b - a).cross(c - a).dot(d - a) / 6.0
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn pin_invalid_particle_panics() {
+        let soft_body = SoftBody::from_positions(vec![Vector::ZERO], &SoftBodyConfig::default());
+        let _ = soft_body.pin(1);
+    }
+
+    #[cfg(any(feature = "2d", feature = "collider-from-mesh"))]
+    #[test]
+    fn soft_body_mesh_follows_particles() {
+        use bevy::mesh::VertexAttributeValues;
+
+        let mut app = create_app();
+
+        #[cfg(feature = "2d")]
+        let mesh = Mesh::from(Rectangle::new(2.0, 2.0));
+        #[cfg(feature = "3d")]
+        let mesh = Plane3d::default().mesh().size(2.0, 2.0).build();
+        let soft_body = SoftBody::from_mesh(&mesh, &SoftBodyConfig::default()).unwrap();
+        let handle = app.world_mut().resource_mut::<Assets<Mesh>>().add(mesh);
+
+        // The soft body falls freely, and the mesh is offset by the transform of the entity.
+        let entity = app
+            .world_mut()
+            .spawn((
+                soft_body,
+                #[cfg(feature = "2d")]
+                Mesh2d(handle.clone()),
+                #[cfg(feature = "3d")]
+                Mesh3d(handle.clone()),
+                Transform::from_xyz(0.0, 5.0, 0.0),
+            ))
+            .id();
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let world = app.world();
+        let soft_body = world.get::<SoftBody>(entity).unwrap();
+        let mesh = world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh should have vertex positions");
+        };
+
+        // The vertices are in the local space of the entity, which has not moved.
+        for (vertex, &particle) in positions.iter().zip(&soft_body.mesh_vertex_particles) {
+            let particle_position = soft_body.particles[particle].position.f32();
+            assert!(particle_position.y < 4.5);
+            assert!((vertex[1] + 5.0 - particle_position.y).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn cloth_rests_on_collider() {
+        let mut app = create_app();
+
+        // The top of the ground is at `y = 0.0`.
+        app.world_mut().spawn((
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(10.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 1.0, 10.0),
+            Transform::from_xyz(0.0, -0.5, 0.0),
+        ));
+
+        #[cfg(feature = "2d")]
+        let mesh = Mesh::from(Rectangle::new(2.0, 0.5));
+        #[cfg(feature = "3d")]
+        let mesh = Plane3d::default()
+            .mesh()
+            .size(2.0, 2.0)
+            .subdivisions(4)
+            .build();
+        let config = SoftBodyConfig::default();
+        let soft_body = SoftBody::from_mesh(&mesh, &config).unwrap();
+        let cloth = app
+            .world_mut()
+            .spawn((soft_body, Transform::from_xyz(0.0, 1.0, 0.0)))
+            .id();
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        // The cloth has landed on the ground, and the particles rest on its surface.
+        let soft_body = app.world().get::<SoftBody>(cloth).unwrap();
+        let lowest = soft_body
+            .particles
+            .iter()
+            .map(|particle| particle.position.y)
+            .fold(Scalar::MAX, Scalar::min);
+        assert_relative_eq!(lowest, config.particle_radius, epsilon = 0.02);
+        for particle in soft_body.particles.iter() {
+            assert!(particle.velocity.length() < 0.1);
+        }
+    }
+
+    #[test]
+    fn particles_push_rigid_body() {
+        let mut app = create_app();
+        app.insert_resource(Gravity(Vector::ZERO));
+
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ))
+            .id();
+
+        // A cluster of particles to the left of the body, moving towards it.
+        let mut soft_body = SoftBody::from_positions(
+            vec![
+                Vector::NEG_X,
+                Vector::NEG_X + Vector::Y * 0.2,
+                Vector::NEG_X - Vector::Y * 0.2,
+            ],
+            &SoftBodyConfig::default(),
+        );
+        for particle in soft_body.particles.iter_mut() {
+            particle.velocity = Vector::X * 5.0;
+        }
+        let particles = app.world_mut().spawn(soft_body).id();
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        // Momentum is transferred from the particles to the rigid body.
+        let world = app.world();
+        assert!(world.get::<LinearVelocity>(body).unwrap().x > 0.5);
+        assert!(world.get::<Position>(body).unwrap().x > 0.0);
+        for particle in world.get::<SoftBody>(particles).unwrap().particles.iter() {
+            assert!(particle.velocity.x < 5.0);
+        }
+    }
+
+    #[test]
+    fn constraints_restore_rest_shape() {
+        let mut app = create_app();
+        app.insert_resource(Gravity(Vector::ZERO));
+
+        #[cfg(feature = "2d")]
+        let mut soft_body = SoftBody::from_mesh(
+            &Mesh::from(Rectangle::new(1.0, 1.0)),
+            &SoftBodyConfig::default(),
+        )
+        .unwrap();
+        #[cfg(feature = "3d")]
+        let mut soft_body = SoftBody::from_tetrahedra(
+            vec![Vector::ZERO, Vector::X, Vector::Y, Vector::Z],
+            &[[0, 1, 2, 3]],
+            &SoftBodyConfig::default(),
+        );
+        assert!(!soft_body.distance_constraints.is_empty());
+        assert!(!soft_body.volume_constraints.is_empty());
+
+        // Stretch the soft body along the x-axis.
+        for particle in soft_body.particles.iter_mut() {
+            particle.position.x *= 1.5;
+            particle.previous_position = particle.position;
+        }
+        let entity = app.world_mut().spawn(soft_body).id();
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        let soft_body = app.world().get::<SoftBody>(entity).unwrap();
+        let particles = &soft_body.particles;
+        for constraint in soft_body.distance_constraints.iter() {
+            let [a, b] = constraint.particles;
+            let length = particles[a].position.distance(particles[b].position);
+            assert_relative_eq!(length, constraint.rest_length, epsilon = 1e-3);
+        }
+        for constraint in soft_body.volume_constraints.iter() {
+            assert_relative_eq!(
+                constraint_volume(particles, constraint),
+                constraint.rest_volume,
+                epsilon = 1e-3
+            );
+        }
+    }
+}
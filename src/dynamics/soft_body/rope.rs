@@ -0,0 +1,1030 @@
+//! Ropes and cables simulated using particles. See [`Rope`].
+
+use crate::{
+    collision::collider::contact_query::contact,
+    dynamics::solver::solver_body::{SolverBody, SolverBodyInertia},
+    prelude::*,
+};
+use bevy::prelude::*;
+
+use super::{
+    ParticleAttachment, ParticleBodyConstraint, ParticleDistanceConstraint, SoftBodyParticle,
+    integrate, prepare_attachments, solve_attachments, transform_particles, update_velocities,
+};
+
+/// A rope or cable simulated as a chain of [particles](SoftBodyParticle) connected by XPBD distance constraints.
+///
+/// Compared to building a chain out of rigid bodies connected by [`DistanceJoint`]s, a rope is much cheaper
+/// to simulate, and it does not stretch noticeably under load. Stretching is prevented using *tethers*,
+/// also known as long-range attachments, that limit the distance of each particle from the anchored ends
+/// of the rope to its distance along the rope.
+///
+/// Ropes are simulated by the [`SoftBodyPlugin`]. Like for [soft bodies](super), the particles are stored
+/// in world space, and the [`Transform`] of the rope entity is only used for placing the particles
+/// when the rope is spawned.
+///
+/// # Attachments
+///
+/// The ends of the rope can be attached to rigid bodies using [`Rope::attach_start`] and [`Rope::attach_end`].
+/// Attachments are two-way, so a rope can be used for hanging, pulling, or swinging rigid bodies.
+/// Particles can also be pinned in place using [`Rope::pin`].
+///
+/// A rope only uses tethers for ends that are attached or pinned. Tethers can be disabled using [`Rope::with_tethers`].
+///
+/// # Collisions
+///
+/// Each segment of the rope collides with [colliders](Collider) as a capsule with the [radius](Rope::radius)
+/// of the rope. Contacts are generated once per physics step, like the speculative contacts of rigid bodies.
+/// The colliders are searched within the region swept by each segment during the time step, and contacts
+/// are created for colliders within a speculative margin based on the velocities of the particles.
+/// This makes fast-moving ropes less likely to tunnel through thin geometry, but it is not
+/// full continuous collision detection. Contact filtering uses the [`CollisionLayers`] of the rope entity.
+///
+/// Ropes do not currently collide with themselves or with other ropes.
+///
+/// # Reeling
+///
+/// The rope can be reeled in and out by changing the rest length of its segments at runtime,
+/// either directly through [`Rope::segment_length`] or using [`Rope::set_length`].
+/// The masses of the particles are updated automatically based on the [`mass_per_length`](Rope::mass_per_length).
+///
+/// # Tearing
+///
+/// If a [tear tension](Rope::tear_tension) is set, the rope tears at the segment with the highest tension
+/// once the tension exceeds the threshold. The segment is removed, the part of the rope after it
+/// is moved to a new entity, and a [`RopeTorn`] event is triggered. The new entity inherits
+/// the [`CollisionLayers`], [`GravityScale`], and [`GravityOverride`] of the rope.
+///
+/// The tension of each segment is estimated from the forces applied by the distance constraints and tethers,
+/// and can be read using [`Rope::tensions`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ceiling = commands.spawn((RigidBody::Static, Transform::from_xyz(0.0, 5.0, 0.0))).id();
+///     let weight = commands
+///         .spawn((
+///             RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cuboid(1.0, 1.0, 1.0),")]
+///         ))
+///         .id();
+///
+///     // A rope from the ceiling to the weight that tears under a tension of 500 N.
+///     commands
+///         .spawn(
+///             Rope::new(Vector::Y * 5.0, Vector::Y * 0.5, 20)
+///                 .with_mass_per_length(0.1)
+///                 .attach_start(ceiling, Vector::ZERO)
+///                 .attach_end(weight, Vector::Y * 0.5)
+///                 .with_tear_tension(500.0),
+///         )
+///         .observe(|torn: On<RopeTorn>| {
+///             info!("Rope torn at segment {}", torn.segment);
+///         });
+/// }
+///
+/// // Reel the rope in over time.
+/// fn reel_in(mut ropes: Query<&mut Rope>, time: Res<Time>) {
+///     for mut rope in &mut ropes {
+///         let length = rope.length() - 0.5 * time.delta_secs() as Scalar;
+///         rope.set_length(length.max(1.0));
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[require(Transform)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Rope {
+    /// The particles of the rope, in world space. Each pair of consecutive particles forms a segment.
+    pub particles: Vec<SoftBodyParticle>,
+    /// The rest length of each segment.
+    pub segment_length: Scalar,
+    /// The mass of the rope per unit of length, in kilograms per meter.
+    ///
+    /// Default: `1.0`
+    pub mass_per_length: Scalar,
+    /// The compliance of the segments (inverse of stiffness, m / N).
+    ///
+    /// Default: `0.0`
+    pub compliance: Scalar,
+    /// The compliance of the constraints resisting bending (inverse of stiffness, m / N).
+    /// If `None`, the rope bends freely.
+    ///
+    /// Default: `None`
+    pub bend_compliance: Option<Scalar>,
+    /// If `true`, tethers are used to prevent the rope from stretching beyond its length
+    /// when one or both of its ends are attached or pinned.
+    ///
+    /// Default: `true`
+    pub tethers: bool,
+    /// Attachments connecting particles to rigid bodies.
+    pub attachments: Vec<ParticleAttachment>,
+    /// The radius of the rope used for collisions.
+    ///
+    /// Default: `0.05`
+    pub radius: Scalar,
+    /// The coefficient of friction between the rope and colliders.
+    ///
+    /// Default: `0.5`
+    pub friction: Scalar,
+    /// The linear damping applied to the velocities of the particles.
+    ///
+    /// Default: `0.0`
+    pub linear_damping: Scalar,
+    /// The tension at which the rope tears, in Newtons. If `None`, the rope never tears.
+    ///
+    /// Default: `None`
+    pub tear_tension: Option<Scalar>,
+    /// The estimated tension of each segment during the last physics step.
+    tensions: Vec<Scalar>,
+    /// Contacts between segments and colliders for the current time step.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    contacts: Vec<RopeContact>,
+}
+
+impl Rope {
+    /// Creates a new straight [`Rope`] from `start` to `end` with the given number of segments.
+    ///
+    /// The points are in the local space of the [`Transform`] of the rope entity.
+    pub fn new(start: Vector, end: Vector, segment_count: usize) -> Self {
+        let segment_count = segment_count.max(1);
+        let particles = (0..=segment_count)
+            .map(|i| {
+                let position = start.lerp(end, i as Scalar / segment_count as Scalar);
+                SoftBodyParticle::new(position, 1.0)
+            })
+            .collect();
+
+        Self {
+            particles,
+            segment_length: start.distance(end) / segment_count as Scalar,
+            mass_per_length: 1.0,
+            compliance: 0.0,
+            bend_compliance: None,
+            tethers: true,
+            attachments: Vec::new(),
+            radius: 0.05,
+            friction: 0.5,
+            linear_damping: 0.0,
+            tear_tension: None,
+            tensions: Vec::new(),
+            contacts: Vec::new(),
+        }
+    }
+
+    /// Returns the number of segments in the rope.
+    pub fn segment_count(&self) -> usize {
+        self.particles.len().saturating_sub(1)
+    }
+
+    /// Returns the rest length of the rope.
+    pub fn length(&self) -> Scalar {
+        self.segment_count() as Scalar * self.segment_length
+    }
+
+    /// Sets the rest length of the rope by changing the rest length of each segment.
+    ///
+    /// This can be used for reeling the rope in and out.
+    pub fn set_length(&mut self, length: Scalar) {
+        if self.segment_count() > 0 {
+            self.segment_length = length.max(0.0) / self.segment_count() as Scalar;
+        }
+    }
+
+    /// Returns the estimated tension of each segment during the last physics step, in Newtons.
+    pub fn tensions(&self) -> &[Scalar] {
+        &self.tensions
+    }
+
+    /// Returns the highest estimated tension of the segments during the last physics step, in Newtons.
+    pub fn max_tension(&self) -> Scalar {
+        self.tensions.iter().copied().fold(0.0, Scalar::max)
+    }
+
+    /// Sets the rest length of the rope by changing the rest length of each segment.
+    pub fn with_length(mut self, length: Scalar) -> Self {
+        self.set_length(length);
+        self
+    }
+
+    /// Sets the mass of the rope per unit of length, in kilograms per meter.
+    pub fn with_mass_per_length(mut self, mass_per_length: Scalar) -> Self {
+        self.mass_per_length = mass_per_length;
+        self
+    }
+
+    /// Sets the compliance of the segments (inverse of stiffness, m / N).
+    pub fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the constraints resisting bending. If `None`, the rope bends freely.
+    pub fn with_bend_compliance(mut self, compliance: Option<Scalar>) -> Self {
+        self.bend_compliance = compliance;
+        self
+    }
+
+    /// Sets whether tethers are used to prevent the rope from stretching.
+    pub fn with_tethers(mut self, tethers: bool) -> Self {
+        self.tethers = tethers;
+        self
+    }
+
+    /// Sets the radius of the rope used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the coefficient of friction between the rope and colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping applied to the velocities of the particles.
+    pub fn with_linear_damping(mut self, damping: Scalar) -> Self {
+        self.linear_damping = damping;
+        self
+    }
+
+    /// Sets the tension at which the rope tears, in Newtons.
+    pub fn with_tear_tension(mut self, tension: Scalar) -> Self {
+        self.tear_tension = Some(tension);
+        self
+    }
+
+    /// Pins the particle at the given index in place by giving it an inverse mass of zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `particle` is not a valid index into [`Rope::particles`].
+    pub fn pin(mut self, particle: usize) -> Self {
+        self.particles[particle].inverse_mass = 0.0;
+        self
+    }
+
+    /// Attaches the start of the rope to a rigid body at the given local anchor.
+    pub fn attach_start(mut self, body: Entity, local_anchor: Vector) -> Self {
+        self.attachments
+            .push(ParticleAttachment::new(0, body, local_anchor));
+        self
+    }
+
+    /// Attaches the end of the rope to a rigid body at the given local anchor.
+    pub fn attach_end(mut self, body: Entity, local_anchor: Vector) -> Self {
+        let last = self.particles.len().saturating_sub(1);
+        self.attachments
+            .push(ParticleAttachment::new(last, body, local_anchor));
+        self
+    }
+
+    /// Splits the rope at the given segment, removing the segment.
+    ///
+    /// The rope keeps the part before the segment, and the part after it is returned as a new [`Rope`]
+    /// with the same properties. Attachments are moved to the part that contains their particle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment` is not less than the [segment count](Rope::segment_count).
+    pub fn split(&mut self, segment: usize) -> Rope {
+        assert!(
+            segment < self.segment_count(),
+            "segment index {segment} out of bounds for rope with {} segments",
+            self.segment_count()
+        );
+
+        let particles = self.particles.split_off(segment + 1);
+        let (attachments, kept_attachments): (Vec<_>, Vec<_>) =
+            core::mem::take(&mut self.attachments)
+                .into_iter()
+                .partition(|attachment| attachment.particle > segment);
+        self.attachments = kept_attachments;
+
+        let attachments = attachments
+            .into_iter()
+            .map(|mut attachment| {
+                attachment.particle -= segment + 1;
+                attachment
+            })
+            .collect();
+
+        self.tensions.truncate(segment);
+        self.contacts.clear();
+
+        Rope {
+            particles,
+            attachments,
+            tensions: Vec::new(),
+            contacts: Vec::new(),
+            ..*self
+        }
+    }
+
+    /// Returns `true` if the particle at the given index is pinned or attached to a body.
+    fn is_anchored(&self, particle: usize) -> bool {
+        self.particles[particle].inverse_mass == 0.0
+            || self
+                .attachments
+                .iter()
+                .any(|attachment| attachment.particle == particle)
+    }
+}
+
+/// An event triggered when a [`Rope`] tears after exceeding its [tear tension](Rope::tear_tension).
+///
+/// The rope entity keeps the part of the rope before the torn segment,
+/// and the part after it is moved to [`new_rope`](Self::new_rope).
+#[derive(EntityEvent, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RopeTorn {
+    /// The rope entity that was torn.
+    pub entity: Entity,
+    /// The new rope entity containing the part of the rope after the torn segment.
+    pub new_rope: Entity,
+    /// The index of the segment that was torn.
+    pub segment: usize,
+    /// The tension of the segment when it was torn, in Newtons.
+    pub tension: Scalar,
+}
+
+/// A contact between a segment of a [`Rope`] and a collider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct RopeContact {
+    /// The index of the segment.
+    segment: usize,
+    /// The position of the contact along the segment, from `0.0` at the first particle to `1.0` at the second.
+    t: Scalar,
+    /// The rigid body that the collider is attached to, if any.
+    body: Option<Entity>,
+    /// The world-space contact point on the collider at the start of the time step.
+    point: Vector,
+    /// The offset from the center of mass of the body to the contact point at the start of the time step.
+    anchor: Vector,
+    /// The world-space contact normal pointing towards the rope at the start of the time step.
+    normal: Vector,
+    /// The contact point at the end of the previous substep, used for friction.
+    previous_point: Vector,
+}
+
+/// Moves the particles of newly added ropes to the space of their [`Transform`].
+pub(super) fn init_ropes(mut ropes: Query<(&mut Rope, &Transform), Added<Rope>>) {
+    for (mut rope, transform) in &mut ropes {
+        transform_particles(&mut rope.particles, transform);
+    }
+}
+
+/// Updates the masses of rope particles, generates contacts between segments and colliders,
+/// and prepares attachments for the substepping loop.
+#[allow(clippy::type_complexity)]
+pub(super) fn prepare_ropes(
+    mut ropes: Query<(&mut Rope, Option<&CollisionLayers>)>,
+    colliders: Query<
+        (
+            &Collider,
+            &ColliderAabb,
+            &Position,
+            &Rotation,
+            Option<&ColliderOf>,
+            Option<&CollisionLayers>,
+        ),
+        (Without<Sensor>, Without<ColliderDisabled>),
+    >,
+    bodies: Query<(&Position, &Rotation, &ComputedCenterOfMass), With<RigidBody>>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut rope, layers) in &mut ropes {
+        let rope = &mut *rope;
+        let layers = layers.copied().unwrap_or_default();
+        let radius = rope.radius;
+        let segment_count = rope.segment_count();
+
+        // Update the masses of the particles, as the rope may have been reeled in or out.
+        // The end particles only carry half of a segment.
+        let particle_mass = rope.mass_per_length * rope.segment_length;
+        if particle_mass > 0.0 {
+            for (i, particle) in rope.particles.iter_mut().enumerate() {
+                if particle.inverse_mass == 0.0 {
+                    continue;
+                }
+                let mass = if i == 0 || i == segment_count {
+                    0.5 * particle_mass
+                } else {
+                    particle_mass
+                };
+                particle.inverse_mass = 1.0 / mass;
+            }
+        }
+
+        prepare_attachments(&mut rope.attachments, &bodies);
+
+        rope.tensions.clear();
+        rope.tensions.resize(segment_count, 0.0);
+        rope.contacts.clear();
+
+        if segment_count == 0 {
+            continue;
+        }
+
+        // All segments share a single capsule that is long enough to cover the longest segment.
+        // Each segment only provides the pose of the capsule.
+        let capsule_length = rope
+            .particles
+            .windows(2)
+            .map(|particles| particles[0].position.distance(particles[1].position))
+            .fold(0.0, Scalar::max);
+        let capsule = Collider::capsule(radius, capsule_length);
+
+        // Compute the poses of the segment capsules, the AABBs swept by them during the time step,
+        // and the speculative margins used for finding contacts.
+        let segments: Vec<(Vector, Rotation, ColliderAabb, Scalar)> = rope
+            .particles
+            .windows(2)
+            .map(|particles| {
+                let [p1, p2] = [particles[0], particles[1]];
+                let margin = p1.velocity.length().max(p2.velocity.length()) * delta_secs;
+                let (predicted1, predicted2) = (
+                    p1.position + p1.velocity * delta_secs,
+                    p2.position + p2.velocity * delta_secs,
+                );
+                let min = p1.position.min(p2.position).min(predicted1).min(predicted2);
+                let max = p1.position.max(p2.position).max(predicted1).max(predicted2);
+                (
+                    0.5 * (p1.position + p2.position),
+                    segment_rotation(p2.position - p1.position),
+                    ColliderAabb::from_min_max(min - radius, max + radius),
+                    margin,
+                )
+            })
+            .collect();
+
+        let aabb = segments
+            .iter()
+            .map(|(_, _, aabb, _)| *aabb)
+            .reduce(ColliderAabb::merged)
+            .unwrap();
+
+        // Generate contacts with all colliders that could be hit during the time step.
+        for collider_entity in spatial_query.aabb_intersections_with_aabb(aabb) {
+            let Ok((collider, collider_aabb, position, rotation, collider_of, collider_layers)) =
+                colliders.get(collider_entity)
+            else {
+                continue;
+            };
+
+            if !layers.interacts_with(collider_layers.copied().unwrap_or_default()) {
+                continue;
+            }
+
+            let body = collider_of.and_then(|collider_of| {
+                bodies
+                    .get(collider_of.body)
+                    .ok()
+                    .map(|body| (collider_of.body, body))
+            });
+
+            for (segment, (capsule_position, capsule_rotation, segment_aabb, margin)) in
+                segments.iter().enumerate()
+            {
+                if !segment_aabb.intersects(collider_aabb) {
+                    continue;
+                }
+
+                let Ok(Some(contact)) = contact(
+                    &capsule,
+                    *capsule_position,
+                    *capsule_rotation,
+                    collider,
+                    *position,
+                    *rotation,
+                    *margin,
+                ) else {
+                    continue;
+                };
+
+                // Find the position of the contact along the segment.
+                let (p1, p2) = (
+                    rope.particles[segment].position,
+                    rope.particles[segment + 1].position,
+                );
+                let axis = p2 - p1;
+                let rope_point = *capsule_position + *capsule_rotation * contact.local_point1;
+                let t = if axis.length_squared() > Scalar::EPSILON {
+                    ((rope_point - p1).dot(axis) / axis.length_squared()).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+
+                let point = position.0 + *rotation * contact.local_point2;
+
+                let (body_entity, anchor) = match body {
+                    Some((entity, (body_position, body_rotation, center_of_mass))) => (
+                        Some(entity),
+                        point - (body_position.0 + *body_rotation * center_of_mass.0),
+                    ),
+                    None => (None, Vector::ZERO),
+                };
+
+                rope.contacts.push(RopeContact {
+                    segment,
+                    t,
+                    body: body_entity,
+                    point,
+                    anchor,
+                    normal: -(*capsule_rotation * contact.local_normal1),
+                    previous_point: point,
+                });
+            }
+        }
+    }
+}
+
+/// Returns the rotation that aligns the local `Y` axis of a capsule with the given segment `axis`.
+fn segment_rotation(axis: Vector) -> Rotation {
+    let direction = axis.try_normalize().unwrap_or(Vector::Y);
+    #[cfg(feature = "2d")]
+    {
+        Rotation::from_sin_cos(-direction.x, direction.y)
+    }
+    #[cfg(feature = "3d")]
+    {
+        Rotation(Quaternion::from_rotation_arc(Vector::Y, direction))
+    }
+}
+
+/// Applies gravity and damping to the particles of ropes, and moves them based on their velocities.
+pub(super) fn integrate_rope_particles(
+    mut ropes: Query<(&mut Rope, Option<&GravityScale>, Option<&GravityOverride>)>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut rope, gravity_scale, gravity_override) in &mut ropes {
+        let gravity = gravity_override.map_or(gravity.0, |gravity| gravity.0)
+            * gravity_scale.map_or(1.0, |scale| scale.0);
+        let linear_damping = rope.linear_damping;
+
+        integrate(&mut rope.particles, gravity, linear_damping, delta_secs);
+    }
+}
+
+/// Solves the segments, tethers, attachments, and contacts of ropes, and estimates the tensions of the segments.
+pub(super) fn solve_ropes(
+    mut ropes: Query<&mut Rope>,
+    mut bodies: Query<(&mut SolverBody, &SolverBodyInertia)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    let inv_delta_secs_squared = 1.0 / (delta_secs * delta_secs);
+
+    for mut rope in &mut ropes {
+        let segment_count = rope.segment_count();
+
+        if segment_count == 0 {
+            continue;
+        }
+
+        let anchored_ends = [
+            (0, rope.tethers && rope.is_anchored(0)),
+            (
+                segment_count,
+                rope.tethers && rope.is_anchored(segment_count),
+            ),
+        ];
+
+        let Rope {
+            particles,
+            segment_length,
+            compliance,
+            bend_compliance,
+            attachments,
+            radius,
+            friction,
+            tensions,
+            contacts,
+            ..
+        } = &mut *rope;
+
+        // The tension of each segment during this substep.
+        let mut substep_tensions = vec![0.0; segment_count];
+
+        // Solve segments.
+        for (segment, tension) in substep_tensions.iter_mut().enumerate() {
+            let delta_lagrange = ParticleDistanceConstraint {
+                particles: [segment, segment + 1],
+                rest_length: *segment_length,
+                compliance: *compliance,
+            }
+            .solve(particles, delta_secs);
+
+            // The Lagrange multiplier is negative when the segment is stretched.
+            *tension = (-delta_lagrange).max(0.0) * inv_delta_secs_squared;
+        }
+
+        // Resist bending by keeping every other particle apart.
+        if let Some(bend_compliance) = *bend_compliance {
+            for i in 0..segment_count.saturating_sub(1) {
+                ParticleDistanceConstraint {
+                    particles: [i, i + 2],
+                    rest_length: 2.0 * *segment_length,
+                    compliance: bend_compliance,
+                }
+                .solve(particles, delta_secs);
+            }
+        }
+
+        solve_attachments(particles, attachments, &mut bodies, delta_secs);
+
+        // Solve tethers, limiting the distance of each particle from the anchored ends
+        // to its distance along the rope. The tethers are one-way, only moving the particles.
+        // The tether force on a particle is transmitted through all segments between it and the anchor,
+        // so it is accumulated for a range of segments using a difference array.
+        let mut tether_tensions = vec![0.0; segment_count + 1];
+        for (anchor, is_anchored) in anchored_ends {
+            if !is_anchored {
+                continue;
+            }
+
+            let anchor_position = particles[anchor].position;
+
+            for (i, particle) in particles.iter_mut().enumerate() {
+                if i == anchor || particle.inverse_mass == 0.0 {
+                    continue;
+                }
+
+                let max_distance = anchor.abs_diff(i) as Scalar * *segment_length;
+                let offset = particle.position - anchor_position;
+                let distance = offset.length();
+
+                if distance <= max_distance {
+                    continue;
+                }
+
+                let correction = distance - max_distance;
+                particle.position -= offset / distance * correction;
+
+                let force = correction / particle.inverse_mass * inv_delta_secs_squared;
+                tether_tensions[anchor.min(i)] += force;
+                tether_tensions[anchor.max(i)] -= force;
+            }
+        }
+
+        let mut tether_tension = 0.0;
+        for (tension, delta) in substep_tensions.iter_mut().zip(tether_tensions) {
+            tether_tension += delta;
+            *tension += tether_tension;
+        }
+
+        for (tension, substep_tension) in tensions.iter_mut().zip(substep_tensions) {
+            *tension = tension.max(substep_tension);
+        }
+
+        // Solve contacts.
+        for contact in contacts.iter_mut() {
+            let [i1, i2] = [contact.segment, contact.segment + 1];
+            let (p1, p2) = (particles[i1], particles[i2]);
+            let (b1, b2) = (1.0 - contact.t, contact.t);
+            let mut body = contact.body.and_then(|entity| bodies.get_mut(entity).ok());
+
+            let (delta_position, delta_rotation) = body
+                .as_ref()
+                .map_or((Vector::ZERO, Rotation::IDENTITY), |(body, _)| {
+                    (body.delta_position, body.delta_rotation)
+                });
+
+            let r = delta_rotation * contact.anchor;
+            let point = contact.point + delta_position + (r - contact.anchor);
+            let normal = delta_rotation * contact.normal;
+            let previous_point = contact.previous_point;
+            contact.previous_point = point;
+
+            let rope_point = b1 * p1.position + b2 * p2.position;
+            let separation = (rope_point - point).dot(normal) - *radius;
+
+            if separation >= 0.0 {
+                continue;
+            }
+
+            // The correction is distributed between the particles based on the position along the segment.
+            let w1 = b1 * b1 * p1.inverse_mass + b2 * b2 * p2.inverse_mass;
+            let w2 = body.as_ref().map_or(0.0, |(_, inertia)| {
+                ParticleBodyConstraint::inverse_mass(inertia, r, normal)
+            });
+            let w_sum = w1 + w2;
+
+            if w_sum <= Scalar::EPSILON {
+                continue;
+            }
+
+            // Push the segment out of the collider.
+            let normal_lagrange = -separation / w_sum;
+            let mut impulse = normal_lagrange * normal;
+
+            // Apply positional friction based on the relative tangential motion during the substep.
+            let previous_rope_point = b1 * p1.previous_position + b2 * p2.previous_position;
+            let relative_motion = (rope_point - previous_rope_point) - (point - previous_point);
+            let tangential_motion = relative_motion - relative_motion.dot(normal) * normal;
+            let tangential_distance = tangential_motion.length();
+
+            if tangential_distance > Scalar::EPSILON {
+                let tangent = tangential_motion / tangential_distance;
+                let tangent_lagrange =
+                    (tangential_distance / w_sum).min(*friction * normal_lagrange);
+                impulse -= tangent_lagrange * tangent;
+            }
+
+            particles[i1].position += impulse * b1 * p1.inverse_mass;
+            particles[i2].position += impulse * b2 * p2.inverse_mass;
+            if let Some((body, inertia)) = body.as_mut() {
+                ParticleBodyConstraint::apply_impulse(body, inertia, -impulse, r);
+            }
+        }
+    }
+}
+
+/// Updates the velocities of the particles of ropes based on how much they moved during the substep.
+pub(super) fn update_rope_velocities(mut ropes: Query<&mut Rope>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for mut rope in &mut ropes {
+        update_velocities(&mut rope.particles, delta_secs);
+    }
+}
+
+/// Tears ropes whose tension exceeds their [tear tension](Rope::tear_tension),
+/// moving the part after the torn segment to a new entity and triggering a [`RopeTorn`] event.
+pub(super) fn tear_ropes(
+    mut commands: Commands,
+    mut ropes: Query<(
+        Entity,
+        &mut Rope,
+        Option<&CollisionLayers>,
+        Option<&GravityScale>,
+        Option<&GravityOverride>,
+    )>,
+) {
+    for (entity, mut rope, layers, gravity_scale, gravity_override) in &mut ropes {
+        let Some(tear_tension) = rope.tear_tension else {
+            continue;
+        };
+
+        // Tear the segment with the highest tension.
+        let Some((segment, tension)) = rope
+            .tensions
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, tension)| *tension > tear_tension)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            continue;
+        };
+
+        let new_rope = rope.split(segment);
+
+        let mut entity_commands = commands.spawn(new_rope);
+        if let Some(layers) = layers {
+            entity_commands.insert(*layers);
+        }
+        if let Some(gravity_scale) = gravity_scale {
+            entity_commands.insert(*gravity_scale);
+        }
+        if let Some(gravity_override) = gravity_override {
+            entity_commands.insert(*gravity_override);
+        }
+        let new_rope = entity_commands.id();
+
+        commands.trigger(RopeTorn {
+            entity,
+            new_rope,
+            segment,
+            tension,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::create_test_app;
+    use approx::assert_relative_eq;
+
+    /// Spawns the given rope and runs the app for the given number of updates.
+    fn simulate(app: &mut App, rope: Rope, updates: usize) -> Entity {
+        let rope = app.world_mut().spawn(rope).id();
+        for _ in 0..updates {
+            app.update();
+        }
+        rope
+    }
+
+    /// Returns the distance between the first and last particle of the rope.
+    fn end_to_end_distance(app: &App, rope: Entity) -> Scalar {
+        let rope = app.world().get::<Rope>(rope).unwrap();
+        let (first, last) = (
+            rope.particles.first().unwrap(),
+            rope.particles.last().unwrap(),
+        );
+        first.position.distance(last.position)
+    }
+
+    #[test]
+    fn split_rope() {
+        let mut world = World::new();
+        let body1 = world.spawn_empty().id();
+        let body2 = world.spawn_empty().id();
+        let mut rope = Rope::new(Vector::ZERO, Vector::X * 4.0, 4)
+            .attach_start(body1, Vector::ZERO)
+            .attach_end(body2, Vector::ZERO);
+
+        let new_rope = rope.split(1);
+
+        assert_eq!(rope.segment_count(), 1);
+        assert_eq!(new_rope.segment_count(), 2);
+        assert_eq!(new_rope.particles[0].position, Vector::X * 2.0);
+        assert_eq!(rope.attachments.len(), 1);
+        assert_eq!(rope.attachments[0].particle, 0);
+        assert_eq!(new_rope.attachments.len(), 1);
+        assert_eq!(new_rope.attachments[0].body, body2);
+        assert_eq!(new_rope.attachments[0].particle, 2);
+    }
+
+    #[test]
+    fn torn_rope_keeps_settings() {
+        let mut app = create_test_app(SoftBodyPlugin);
+
+        // A pinned rope hanging under its own weight that tears immediately.
+        let layers = CollisionLayers::new(0b10, 0b01);
+        let gravity_scale = GravityScale(0.5);
+        let gravity_override = GravityOverride(Vector::NEG_Y * 20.0);
+        let rope = app
+            .world_mut()
+            .spawn((
+                Rope::new(Vector::ZERO, Vector::NEG_Y * 4.0, 4)
+                    .pin(0)
+                    .with_tear_tension(1e-3),
+                layers,
+                gravity_scale,
+                gravity_override,
+            ))
+            .id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let world = app.world_mut();
+        let new_ropes: Vec<Entity> = world
+            .query_filtered::<Entity, With<Rope>>()
+            .iter(world)
+            .filter(|&entity| entity != rope)
+            .collect();
+        assert!(!new_ropes.is_empty());
+
+        for new_rope in new_ropes {
+            let entity_ref = world.entity(new_rope);
+            assert_eq!(entity_ref.get::<CollisionLayers>(), Some(&layers));
+            assert_eq!(entity_ref.get::<GravityScale>(), Some(&gravity_scale));
+            assert_eq!(entity_ref.get::<GravityOverride>(), Some(&gravity_override));
+        }
+    }
+
+    #[test]
+    fn tethers_limit_stretching() {
+        // A soft rope hanging from a pinned particle stretches under its own weight without tethers.
+        let rope = || {
+            Rope::new(Vector::ZERO, Vector::NEG_Y * 4.0, 8)
+                .with_compliance(1e-2)
+                .with_linear_damping(1.0)
+                .pin(0)
+        };
+
+        let mut app = create_test_app(SoftBodyPlugin);
+        let tethered = simulate(&mut app, rope(), 120);
+        let tethered_length = end_to_end_distance(&app, tethered);
+
+        let mut app = create_test_app(SoftBodyPlugin);
+        let untethered = simulate(&mut app, rope().with_tethers(false), 120);
+        let untethered_length = end_to_end_distance(&app, untethered);
+
+        // The tethers keep the rope from stretching beyond its length.
+        assert!(tethered_length <= 4.0 + 1e-3);
+        assert!(untethered_length > 4.1);
+    }
+
+    #[test]
+    fn set_length_reels_rope() {
+        let mut app = create_test_app(SoftBodyPlugin);
+        let rope = simulate(
+            &mut app,
+            Rope::new(Vector::ZERO, Vector::NEG_Y * 4.0, 8)
+                .with_linear_damping(1.0)
+                .pin(0),
+            10,
+        );
+
+        // Reel the rope in to half of its length.
+        app.world_mut()
+            .get_mut::<Rope>(rope)
+            .unwrap()
+            .set_length(2.0);
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        let rope_component = app.world().get::<Rope>(rope).unwrap();
+        assert_relative_eq!(rope_component.length(), 2.0);
+        assert_relative_eq!(rope_component.segment_length, 0.25);
+        assert_relative_eq!(end_to_end_distance(&app, rope), 2.0, epsilon = 0.02);
+    }
+
+    #[test]
+    fn rope_tears_past_threshold() {
+        #[derive(Resource, Default)]
+        struct TornRopes(Vec<RopeTorn>);
+
+        // The top segment of the hanging rope carries almost all of its weight of about 40 N.
+        for (tear_tension, should_tear) in [(10.0, true), (500.0, false)] {
+            let mut app = create_test_app(SoftBodyPlugin);
+            app.init_resource::<TornRopes>();
+            app.add_observer(|torn: On<RopeTorn>, mut torn_ropes: ResMut<TornRopes>| {
+                torn_ropes.0.push(*torn);
+            });
+
+            let rope = simulate(
+                &mut app,
+                Rope::new(Vector::ZERO, Vector::NEG_Y * 4.0, 8)
+                    .pin(0)
+                    .with_tear_tension(tear_tension),
+                10,
+            );
+
+            let torn_ropes = &app.world().resource::<TornRopes>().0;
+            assert_eq!(!torn_ropes.is_empty(), should_tear);
+
+            if let Some(torn) = torn_ropes.first() {
+                assert_eq!(torn.entity, rope);
+                assert!(torn.tension > tear_tension);
+
+                // The torn segment is removed, and the rest of the rope is moved to the new entity.
+                let segment_count = app.world().get::<Rope>(rope).unwrap().segment_count();
+                let new_rope = app.world().get::<Rope>(torn.new_rope).unwrap();
+                assert!(segment_count <= torn.segment);
+                assert!(new_rope.segment_count() < 8);
+            }
+        }
+    }
+
+    #[test]
+    fn rope_rests_on_collider() {
+        let mut app = create_test_app(SoftBodyPlugin);
+
+        // The top of the ground is at `y = 0.0`.
+        app.world_mut().spawn((
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(10.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 1.0, 10.0),
+            Transform::from_xyz(0.0, -0.5, 0.0),
+        ));
+
+        let rope = Rope::new(
+            Vector::NEG_X * 2.0 + Vector::Y * 0.5,
+            Vector::X * 2.0 + Vector::Y * 0.5,
+            8,
+        );
+        let rope = simulate(&mut app, rope, 120);
+
+        // The rope has landed on the ground, and rests on its surface.
+        let rope = app.world().get::<Rope>(rope).unwrap();
+        for particle in rope.particles.iter() {
+            assert_relative_eq!(particle.position.y, rope.radius, epsilon = 0.02);
+            assert!(particle.velocity.length() < 0.1);
+        }
+    }
+}
@@ -31,7 +31,7 @@ use crate::{
     prelude::{ContactPair, ContactPairFlags},
 };
 
-use super::contact::ContactConstraint;
+use super::{contact::ContactConstraint, joint::JointConstraint};
 
 /// The maximum number of [`GraphColor`]s in the [`ConstraintGraph`].
 /// Constraints that cannot find a color are added to the overflow set,
@@ -76,7 +76,11 @@ pub struct GraphColor {
     pub manifold_handles: Vec<ContactManifoldHandle>,
     /// The contact constraints in this color.
     pub contact_constraints: Vec<ContactConstraint>,
-    // TODO: Joints
+    /// The joint constraints in this color.
+    ///
+    /// Unlike contacts, joints are added to the graph before each time step
+    /// and removed after the time step with [`ConstraintGraph::clear_joints`].
+    pub joint_constraints: Vec<JointConstraint>,
 }
 
 /// A handle to a contact manifold in the [`ContactGraph`].
@@ -153,6 +157,7 @@ impl ConstraintGraph {
                 // TODO: What's a good initial capacity for contacts and constraints?
                 manifold_handles: Vec::with_capacity(bit_capacity),
                 contact_constraints: Vec::with_capacity(bit_capacity),
+                joint_constraints: Vec::new(),
             });
         }
 
@@ -172,51 +177,14 @@ impl ConstraintGraph {
         debug_assert!(!contact_pair.manifolds.is_empty());
         debug_assert!(!is_static1 || !is_static2);
 
-        let mut color_index = COLOR_OVERFLOW_INDEX;
-
-        // TODO: We could allow forcing overflow by making this optional.
-        if !is_static1 && !is_static2 {
-            // Constraints involving only non-static bodies cannot be in colors reserved
-            // for constraints involving static bodies. This helps reduce tunneling through
-            // static geometry by solving static contacts last.
-            for i in 0..DYNAMIC_COLOR_COUNT {
-                let color = &mut self.colors[i];
-                if color.body_set.get(body1.index() as usize)
-                    || color.body_set.get(body2.index() as usize)
-                {
-                    continue;
-                }
-
-                color.body_set.set_and_grow(body1.index() as usize);
-                color.body_set.set_and_grow(body2.index() as usize);
-                color_index = i;
-                break;
-            }
-        } else if !is_static1 {
-            // Build static colors from the end to give them higher priority.
-            for i in (1..COLOR_OVERFLOW_INDEX).rev() {
-                let color = &mut self.colors[i];
-                if color.body_set.get(body1.index() as usize) {
-                    continue;
-                }
-
-                color.body_set.set_and_grow(body1.index() as usize);
-                color_index = i;
-                break;
-            }
-        } else if !is_static2 {
-            // Build static colors from the end to give them higher priority.
-            for i in (1..COLOR_OVERFLOW_INDEX).rev() {
-                let color = &mut self.colors[i];
-                if color.body_set.get(body2.index() as usize) {
-                    continue;
-                }
-
-                color.body_set.set_and_grow(body2.index() as usize);
-                color_index = i;
-                break;
-            }
-        }
+        let color_index = assign_color(
+            &mut self.colors,
+            |color| &mut color.body_set,
+            body1,
+            body2,
+            is_static1,
+            is_static2,
+        );
 
         // Add a constraint handle to the contact edge.
         let color = &mut self.colors[color_index];
@@ -235,6 +203,42 @@ impl ConstraintGraph {
         });
     }
 
+    /// Adds a [`JointConstraint`] to the graph, assigning it to a color
+    /// that does not contain either of its non-static bodies.
+    pub fn push_joint(&mut self, constraint: JointConstraint) {
+        debug_assert!(!constraint.is_static1 || !constraint.is_static2);
+
+        let color_index = assign_color(
+            &mut self.colors,
+            |color| &mut color.body_set,
+            constraint.body1,
+            constraint.body2,
+            constraint.is_static1,
+            constraint.is_static2,
+        );
+
+        self.colors[color_index].joint_constraints.push(constraint);
+    }
+
+    /// Removes all [`JointConstraint`]s from the graph, updating the body sets of the colors accordingly.
+    pub fn clear_joints(&mut self) {
+        for (color_index, color) in self.colors.iter_mut().enumerate() {
+            if color_index != COLOR_OVERFLOW_INDEX {
+                // Remove the bodies from the color's body set.
+                for constraint in &color.joint_constraints {
+                    if !constraint.is_static1 {
+                        color.body_set.unset(constraint.body1.index() as usize);
+                    }
+                    if !constraint.is_static2 {
+                        color.body_set.unset(constraint.body2.index() as usize);
+                    }
+                }
+            }
+
+            color.joint_constraints.clear();
+        }
+    }
+
     /// Removes a [`ContactConstraintHandle`] corresponding to a [`ContactManifold`]
     /// from the end of the vector stored in the [`ContactEdge`], updating the color's
     /// body set and manifold handles accordingly.
@@ -309,6 +313,71 @@ impl ConstraintGraph {
             color.body_set.clear();
             color.manifold_handles.clear();
             color.contact_constraints.clear();
+            color.joint_constraints.clear();
         }
     }
 }
+
+/// Finds a color for a constraint between the given bodies, and adds the non-static bodies
+/// to the body set of the color.
+///
+/// The `body_set` function returns the body set of a color. This allows the same coloring
+/// to be used for both the [`ConstraintGraph`] and the [`JointGraph`].
+///
+/// Returns the index of the color, or [`COLOR_OVERFLOW_INDEX`] if no color was found.
+///
+/// [`JointGraph`]: super::joint_graph::JointGraph
+pub(crate) fn assign_color<T>(
+    colors: &mut [T],
+    body_set: fn(&mut T) -> &mut BitVec,
+    body1: Entity,
+    body2: Entity,
+    is_static1: bool,
+    is_static2: bool,
+) -> usize {
+    let mut color_index = COLOR_OVERFLOW_INDEX;
+
+    // TODO: We could allow forcing overflow by making this optional.
+    if !is_static1 && !is_static2 {
+        // Constraints involving only non-static bodies cannot be in colors reserved
+        // for constraints involving static bodies. This helps reduce tunneling through
+        // static geometry by solving static contacts last.
+        for i in 0..DYNAMIC_COLOR_COUNT {
+            let set = body_set(&mut colors[i]);
+            if set.get(body1.index() as usize) || set.get(body2.index() as usize) {
+                continue;
+            }
+
+            set.set_and_grow(body1.index() as usize);
+            set.set_and_grow(body2.index() as usize);
+            color_index = i;
+            break;
+        }
+    } else if !is_static1 {
+        // Build static colors from the end to give them higher priority.
+        for i in (1..COLOR_OVERFLOW_INDEX).rev() {
+            let set = body_set(&mut colors[i]);
+            if set.get(body1.index() as usize) {
+                continue;
+            }
+
+            set.set_and_grow(body1.index() as usize);
+            color_index = i;
+            break;
+        }
+    } else if !is_static2 {
+        // Build static colors from the end to give them higher priority.
+        for i in (1..COLOR_OVERFLOW_INDEX).rev() {
+            let set = body_set(&mut colors[i]);
+            if set.get(body2.index() as usize) {
+                continue;
+            }
+
+            set.set_and_grow(body2.index() as usize);
+            color_index = i;
+            break;
+        }
+    }
+
+    color_index
+}
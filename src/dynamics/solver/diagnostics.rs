@@ -37,6 +37,16 @@ pub struct SolverDiagnostics {
     pub swept_ccd: Duration,
     /// The number of contact constraints generated.
     pub contact_constraint_count: u32,
+    /// The number of joint constraints solved by the impulse-based joint solver.
+    pub joint_constraint_count: u32,
+    /// The number of time-of-impact iterations performed by swept CCD.
+    ///
+    /// Each [`SweptCcd`] body performs at least one iteration per time step,
+    /// and up to [`SweptCcd::max_iterations`] iterations.
+    ///
+    /// [`SweptCcd`]: crate::dynamics::ccd::SweptCcd
+    /// [`SweptCcd::max_iterations`]: crate::dynamics::ccd::SweptCcd::max_iterations
+    pub swept_ccd_iterations: u32,
 }
 
 impl PhysicsDiagnostics for SolverDiagnostics {
@@ -60,10 +70,14 @@ impl PhysicsDiagnostics for SolverDiagnostics {
     }
 
     fn counter_paths(&self) -> Vec<(&'static DiagnosticPath, u32)> {
-        vec![(
-            Self::CONTACT_CONSTRAINT_COUNT,
-            self.contact_constraint_count,
-        )]
+        vec![
+            (
+                Self::CONTACT_CONSTRAINT_COUNT,
+                self.contact_constraint_count,
+            ),
+            (Self::JOINT_CONSTRAINT_COUNT, self.joint_constraint_count),
+            (Self::SWEPT_CCD_ITERATIONS, self.swept_ccd_iterations),
+        ]
     }
 }
 
@@ -81,5 +95,7 @@ impl_diagnostic_paths! {
         STORE_IMPULSES: "avian/solver/store_impulses",
         SWEPT_CCD: "avian/solver/swept_ccd",
         CONTACT_CONSTRAINT_COUNT: "avian/solver/contact_constraint_count",
+        JOINT_CONSTRAINT_COUNT: "avian/solver/joint_constraint_count",
+        SWEPT_CCD_ITERATIONS: "avian/solver/swept_ccd_iterations",
     }
 }
@@ -5,9 +5,9 @@
 use bevy::{
     app::{App, Plugin},
     ecs::{
-        entity::Entity,
+        entity::{Entity, EntityHashMap},
         entity_disabling::Disabled,
-        lifecycle::{HookContext, Insert, Replace},
+        lifecycle::{HookContext, Insert, RemovedComponents, Replace},
         observer::On,
         query::{Changed, Has, Or, With, Without},
         resource::Resource,
@@ -28,11 +28,14 @@ use bevy::{
 
 use crate::{
     data_structures::bit_vec::BitVec,
-    dynamics::solver::{
-        constraint_graph::ConstraintGraph,
-        islands::{BodyIslandNode, IslandId, PhysicsIslands},
-        joint_graph::JointGraph,
-        solver_body::SolverBody,
+    dynamics::{
+        integrator::GravityFieldTree,
+        solver::{
+            constraint_graph::ConstraintGraph,
+            islands::{BodyIslandNode, IslandId, PhysicsIslands},
+            joint_graph::JointGraph,
+            solver_body::SolverBody,
+        },
     },
     prelude::*,
     schedule::{LastPhysicsTick, is_changed_after_tick},
@@ -74,6 +77,7 @@ impl Plugin for IslandSleepingPlugin {
                 update_sleeping_states,
                 wake_islands_with_sleeping_disabled,
                 wake_on_changed,
+                wake_in_changed_gravity_fields,
                 wake_all_islands.run_if(resource_changed::<Gravity>),
                 sleep_islands,
             )
@@ -581,7 +585,14 @@ fn wake_on_changed(
         >,
         // These are not modified by the physics engine
         // and don't need special handling.
-        Query<&BodyIslandNode, Or<(ConstantForceChanges, Changed<GravityScale>)>>,
+        Query<
+            &BodyIslandNode,
+            Or<(
+                ConstantForceChanges,
+                Changed<GravityScale>,
+                Changed<GravityOverride>,
+            )>,
+        >,
     )>,
     mut awake_island_bit_vec: ResMut<AwakeIslandBitVec>,
     last_physics_tick: Res<LastPhysicsTick>,
@@ -605,6 +616,53 @@ fn wake_on_changed(
     }
 }
 
+/// The state of a [`GravityField`] when it was last seen by [`wake_in_changed_gravity_fields`].
+type GravityFieldState = (GravityField, Vector, Rotation);
+
+/// Wakes up sleeping bodies affected by [`GravityField`]s that have been added, changed, moved, or removed.
+///
+/// Bodies are woken up both in the volume that the field occupied before the change and in the volume
+/// that it occupies after it, since the gravity acting on both of them has changed. Bodies are tested
+/// at their global center of mass against a [`GravityFieldTree`] of the changed volumes,
+/// using the same criterion as the integrator.
+fn wake_in_changed_gravity_fields(
+    fields: Query<
+        (Entity, &GravityField, &Position, &Rotation),
+        Or<(Changed<GravityField>, Changed<Position>, Changed<Rotation>)>,
+    >,
+    mut removed_fields: RemovedComponents<GravityField>,
+    bodies: Query<(&Position, &Rotation, &ComputedCenterOfMass, &BodyIslandNode), With<Sleeping>>,
+    mut previous_fields: Local<EntityHashMap<GravityFieldState>>,
+    mut changed_volumes: Local<GravityFieldTree>,
+    mut awake_island_bit_vec: ResMut<AwakeIslandBitVec>,
+) {
+    // The field volumes in which bodies should be woken up.
+    let mut volumes: Vec<GravityFieldState> = Vec::new();
+
+    for entity in removed_fields.read() {
+        volumes.extend(previous_fields.remove(&entity));
+    }
+
+    for (entity, field, position, rotation) in &fields {
+        let state = (*field, position.0, *rotation);
+        volumes.extend(previous_fields.insert(entity, state));
+        volumes.push(state);
+    }
+
+    if volumes.is_empty() {
+        return;
+    }
+
+    changed_volumes.rebuild(volumes);
+
+    for (position, rotation, center_of_mass, body_island) in &bodies {
+        let global_center_of_mass = position.0 + *rotation * center_of_mass.0;
+        if changed_volumes.contains_point(global_center_of_mass) {
+            awake_island_bit_vec.set_and_grow(body_island.island_id.0 as usize);
+        }
+    }
+}
+
 /// Wakes up all sleeping [`PhysicsIsland`](super::PhysicsIsland)s. Triggered automatically when [`Gravity`] is changed.
 fn wake_all_islands(mut commands: Commands, islands: Res<PhysicsIslands>) {
     let sleeping_islands: Vec<IslandId> = islands
@@ -0,0 +1,121 @@
+use super::{
+    ImpulseJoint, Jacobian, JointBodies, JointBodyQueryItem, JointConstraintData, JointFrames,
+    JointSolveContext, solve_limits,
+};
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+impl ImpulseJoint for DistanceJoint {
+    fn constraint_data(
+        &self,
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+    ) -> Option<JointConstraintData> {
+        let frames = JointFrames::new(
+            body1,
+            body2,
+            self.local_anchor1()?,
+            self.local_anchor2()?,
+            Rot::IDENTITY,
+            Rot::IDENTITY,
+        );
+        Some(JointConstraintData::Distance(DistanceJointConstraint::new(
+            self, frames,
+        )))
+    }
+
+    fn is_compliant(&self) -> bool {
+        self.compliance != 0.0
+    }
+}
+
+/// Constraint data for solving a [`DistanceJoint`] with impulses.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct DistanceJointConstraint {
+    /// The frames of the joint at the start of the time step.
+    ///
+    /// Only the anchors are used.
+    pub frames: JointFrames,
+    /// The limits of the distance between the anchors.
+    pub limits: DistanceLimit,
+    /// The accumulated impulse of the constraint when the distance is fixed.
+    pub impulse: Scalar,
+    /// The accumulated impulse of the lower distance limit.
+    pub lower_impulse: Scalar,
+    /// The accumulated impulse of the upper distance limit.
+    pub upper_impulse: Scalar,
+}
+
+impl DistanceJointConstraint {
+    /// Creates a new [`DistanceJointConstraint`] for the given joint and frames.
+    pub fn new(joint: &DistanceJoint, frames: JointFrames) -> Self {
+        Self {
+            frames,
+            limits: joint.limits,
+            impulse: 0.0,
+            lower_impulse: 0.0,
+            upper_impulse: 0.0,
+        }
+    }
+
+    /// Copies the accumulated impulses from the previous time step for warm starting.
+    pub fn copy_impulses(&mut self, previous: &Self) {
+        self.impulse = previous.impulse;
+        self.lower_impulse = previous.lower_impulse;
+        self.upper_impulse = previous.upper_impulse;
+    }
+
+    /// Returns the linear impulse applied to the second body.
+    pub fn linear_impulse(&self) -> Vector {
+        let separation = self.frames.center_difference + self.frames.anchor2 - self.frames.anchor1;
+        self.total_impulse() * separation.normalize_or_zero()
+    }
+
+    /// Returns the total impulse along the line between the anchors.
+    fn total_impulse(&self) -> Scalar {
+        self.impulse + self.lower_impulse - self.upper_impulse
+    }
+
+    pub(super) fn warm_start(&self, bodies: &mut JointBodies, coefficient: Scalar) {
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+        let impulse = self.total_impulse() * separation.normalize_or_zero();
+        bodies.apply_linear_impulse(r1, r2, coefficient * impulse);
+    }
+
+    pub(super) fn solve(&mut self, bodies: &mut JointBodies, context: &JointSolveContext) {
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+
+        let (direction, distance) = separation.normalize_and_length();
+        if distance <= Scalar::EPSILON {
+            return;
+        }
+
+        let jacobian = Jacobian::linear(direction, r1, r2);
+
+        if self.limits.min == self.limits.max {
+            jacobian.solve_equality(
+                bodies,
+                context,
+                distance - self.limits.min,
+                &mut self.impulse,
+            );
+        } else {
+            solve_limits(
+                bodies,
+                context,
+                jacobian,
+                distance,
+                (self.limits.min, self.limits.max),
+                &mut self.lower_impulse,
+                &mut self.upper_impulse,
+            );
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::{
+    ImpulseJoint, JointBodies, JointBodyQueryItem, JointConstraintData, JointFrames,
+    JointSolveContext, angular_error, solve_angular_lock, solve_point_constraint,
+};
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+impl ImpulseJoint for FixedJoint {
+    fn constraint_data(
+        &self,
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+    ) -> Option<JointConstraintData> {
+        let frames = JointFrames::new(
+            body1,
+            body2,
+            self.local_anchor1()?,
+            self.local_anchor2()?,
+            self.local_basis1()?,
+            self.local_basis2()?,
+        );
+        Some(JointConstraintData::Fixed(FixedJointConstraint::new(
+            frames,
+        )))
+    }
+
+    fn is_compliant(&self) -> bool {
+        self.point_compliance != 0.0 || self.angle_compliance != 0.0
+    }
+}
+
+/// Constraint data for solving a [`FixedJoint`] with impulses.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct FixedJointConstraint {
+    /// The frames of the joint at the start of the time step.
+    pub frames: JointFrames,
+    /// The accumulated impulse of the point constraint.
+    pub point_impulse: Vector,
+    /// The accumulated impulse of the angular constraint.
+    pub angle_impulse: AngularVector,
+}
+
+impl FixedJointConstraint {
+    /// Creates a new [`FixedJointConstraint`] with the given frames.
+    pub fn new(frames: JointFrames) -> Self {
+        Self {
+            frames,
+            point_impulse: Vector::ZERO,
+            angle_impulse: AngularVector::ZERO,
+        }
+    }
+
+    /// Copies the accumulated impulses from the previous time step for warm starting.
+    pub fn copy_impulses(&mut self, previous: &Self) {
+        self.point_impulse = previous.point_impulse;
+        self.angle_impulse = previous.angle_impulse;
+    }
+
+    /// Returns the linear impulse applied to the second body.
+    pub fn linear_impulse(&self) -> Vector {
+        self.point_impulse
+    }
+
+    /// Returns the angular impulse applied to the second body.
+    pub fn angular_impulse(&self) -> AngularVector {
+        self.angle_impulse
+    }
+
+    pub(super) fn warm_start(&self, bodies: &mut JointBodies, coefficient: Scalar) {
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        bodies.apply_linear_impulse(r1, r2, coefficient * self.point_impulse);
+        bodies.apply_angular_impulse(coefficient * self.angle_impulse);
+    }
+
+    pub(super) fn solve(&mut self, bodies: &mut JointBodies, context: &JointSolveContext) {
+        // Lock the relative rotation.
+        let (basis1, basis2) = self.frames.bases(bodies.body1, bodies.body2);
+        solve_angular_lock(
+            bodies,
+            context,
+            angular_error(basis1, basis2),
+            &mut self.angle_impulse,
+        );
+
+        // Keep the anchors together.
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+        solve_point_constraint(bodies, context, r1, r2, separation, &mut self.point_impulse);
+    }
+}
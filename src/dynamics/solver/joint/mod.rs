@@ -0,0 +1,698 @@
+//! Constraints and other types used for solving [joints](dynamics::joints) with impulses.
+//!
+//! The [`ImpulseJointSolverPlugin`] prepares a [`JointConstraint`] for each joint before the substepping loop
+//! and adds it to the [`ConstraintGraph`]. The constraints are then warm started and solved by the [`SolverPlugin`]
+//! in the same graph-colored loop as contacts, using the same [soft constraint](super::softness_parameters) formulation.
+//!
+//! Each joint is made up of a few simple parts:
+//!
+//! - A point-to-point constraint that keeps the anchors of the bodies coincident.
+//! - Angular constraints that lock or align the bases of the bodies.
+//! - One-dimensional limits that are solved speculatively, so that bodies approaching
+//!   a limit are slowed down before they reach it instead of bouncing off of it.
+//! - Orientation drives that are solved as implicit springs.
+//!
+//! By default, joints are rigid, and softness is only used to stabilize drift.
+//! The stabilization is configured globally using [`SolverConfig::joint_damping_ratio`]
+//! and [`SolverConfig::joint_frequency_factor`], and individual joints can be made springy
+//! using the [`JointSoftness`] component.
+//!
+//! [`ConstraintGraph`]: super::constraint_graph::ConstraintGraph
+
+mod distance;
+mod fixed;
+mod plugin;
+mod prismatic;
+mod revolute;
+#[cfg(feature = "3d")]
+mod spherical;
+
+pub use distance::DistanceJointConstraint;
+pub use fixed::FixedJointConstraint;
+pub use plugin::{
+    ImpulseJoint, ImpulseJointSolverData, ImpulseJointSolverPlugin, JointBodyQuery,
+    JointBodyQueryItem, JointSoftnessCoefficients,
+};
+pub use prismatic::PrismaticJointConstraint;
+pub use revolute::RevoluteJointConstraint;
+#[cfg(feature = "3d")]
+pub use spherical::SphericalJointConstraint;
+
+use super::{
+    softness_parameters::SoftnessCoefficients,
+    solver_body::{SolverBody, SolverBodyInertia},
+};
+use crate::prelude::*;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy::{ecs::entity::Entity, reflect::Reflect};
+
+/// A joint constraint solved by the impulse-based [`SolverPlugin`].
+///
+/// Joint constraints are stored in the [`GraphColor`]s of the [`ConstraintGraph`]
+/// alongside contact constraints, and solved in the same substepping loop.
+///
+/// [`GraphColor`]: super::constraint_graph::GraphColor
+/// [`ConstraintGraph`]: super::constraint_graph::ConstraintGraph
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct JointConstraint {
+    /// The joint entity.
+    pub entity: Entity,
+    /// The first body constrained by the joint.
+    pub body1: Entity,
+    /// The second body constrained by the joint.
+    pub body2: Entity,
+    /// Whether the first body is static or sleeping, and is not part of the graph coloring.
+    pub is_static1: bool,
+    /// Whether the second body is static or sleeping, and is not part of the graph coloring.
+    pub is_static2: bool,
+    /// The soft constraint coefficients used for the joint.
+    pub softness: SoftnessCoefficients,
+    /// Whether the joint is always soft, even when relaxing velocities.
+    ///
+    /// This is `true` for joints with a custom [`JointSoftness`].
+    pub always_soft: bool,
+    /// The constraint data specific to the joint type.
+    pub data: JointConstraintData,
+}
+
+impl JointConstraint {
+    /// Warm starts the joint constraint by applying the impulses from the previous frame or substep.
+    pub fn warm_start(
+        &self,
+        body1: &mut SolverBody,
+        body2: &mut SolverBody,
+        inertia1: &SolverBodyInertia,
+        inertia2: &SolverBodyInertia,
+        warm_start_coefficient: Scalar,
+    ) {
+        let mut bodies = JointBodies {
+            body1,
+            body2,
+            inertia1,
+            inertia2,
+        };
+
+        match &self.data {
+            JointConstraintData::Fixed(data) => {
+                data.warm_start(&mut bodies, warm_start_coefficient)
+            }
+            JointConstraintData::Revolute(data) => {
+                data.warm_start(&mut bodies, warm_start_coefficient)
+            }
+            JointConstraintData::Prismatic(data) => {
+                data.warm_start(&mut bodies, warm_start_coefficient)
+            }
+            JointConstraintData::Distance(data) => {
+                data.warm_start(&mut bodies, warm_start_coefficient)
+            }
+            #[cfg(feature = "3d")]
+            JointConstraintData::Spherical(data) => {
+                data.warm_start(&mut bodies, warm_start_coefficient)
+            }
+        }
+    }
+
+    /// Solves the joint constraint, applying impulses to the bodies.
+    ///
+    /// If `use_bias` is `true`, the impulses are boosted to correct positional drift.
+    /// Otherwise, the constraint is only solved at the velocity level to relax velocities,
+    /// unless the joint is [always soft](Self::always_soft).
+    pub fn solve(
+        &mut self,
+        body1: &mut SolverBody,
+        body2: &mut SolverBody,
+        inertia1: &SolverBodyInertia,
+        inertia2: &SolverBodyInertia,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let mut bodies = JointBodies {
+            body1,
+            body2,
+            inertia1,
+            inertia2,
+        };
+
+        let context = JointSolveContext {
+            softness: self.softness,
+            use_bias: use_bias || self.always_soft,
+            delta_secs,
+        };
+
+        match &mut self.data {
+            JointConstraintData::Fixed(data) => data.solve(&mut bodies, &context),
+            JointConstraintData::Revolute(data) => data.solve(&mut bodies, &context),
+            JointConstraintData::Prismatic(data) => data.solve(&mut bodies, &context),
+            JointConstraintData::Distance(data) => data.solve(&mut bodies, &context),
+            #[cfg(feature = "3d")]
+            JointConstraintData::Spherical(data) => data.solve(&mut bodies, &context),
+        }
+    }
+
+    /// Returns the linear impulse applied by the joint to the second body during the last substep.
+    pub fn linear_impulse(&self) -> Vector {
+        match &self.data {
+            JointConstraintData::Fixed(data) => data.linear_impulse(),
+            JointConstraintData::Revolute(data) => data.linear_impulse(),
+            JointConstraintData::Prismatic(data) => data.linear_impulse(),
+            JointConstraintData::Distance(data) => data.linear_impulse(),
+            #[cfg(feature = "3d")]
+            JointConstraintData::Spherical(data) => data.linear_impulse(),
+        }
+    }
+
+    /// Returns the angular impulse applied by the joint to the second body during the last substep.
+    pub fn angular_impulse(&self) -> AngularVector {
+        match &self.data {
+            JointConstraintData::Fixed(data) => data.angular_impulse(),
+            JointConstraintData::Revolute(data) => data.angular_impulse(),
+            JointConstraintData::Prismatic(data) => data.angular_impulse(),
+            JointConstraintData::Distance(_) => AngularVector::ZERO,
+            #[cfg(feature = "3d")]
+            JointConstraintData::Spherical(data) => data.angular_impulse(),
+        }
+    }
+}
+
+/// The constraint data of a [`JointConstraint`], specific to the joint type.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum JointConstraintData {
+    /// The constraint data of a [`FixedJoint`].
+    Fixed(FixedJointConstraint),
+    /// The constraint data of a [`RevoluteJoint`].
+    Revolute(RevoluteJointConstraint),
+    /// The constraint data of a [`PrismaticJoint`].
+    Prismatic(PrismaticJointConstraint),
+    /// The constraint data of a [`DistanceJoint`].
+    Distance(DistanceJointConstraint),
+    /// The constraint data of a [`SphericalJoint`].
+    #[cfg(feature = "3d")]
+    Spherical(SphericalJointConstraint),
+}
+
+impl JointConstraintData {
+    /// Copies the accumulated impulses from the constraint data of the previous time step,
+    /// if the joint type has not changed.
+    pub fn copy_impulses(&mut self, previous: &Self) {
+        match (self, previous) {
+            (Self::Fixed(data), Self::Fixed(previous)) => data.copy_impulses(previous),
+            (Self::Revolute(data), Self::Revolute(previous)) => data.copy_impulses(previous),
+            (Self::Prismatic(data), Self::Prismatic(previous)) => data.copy_impulses(previous),
+            (Self::Distance(data), Self::Distance(previous)) => data.copy_impulses(previous),
+            #[cfg(feature = "3d")]
+            (Self::Spherical(data), Self::Spherical(previous)) => data.copy_impulses(previous),
+            _ => {}
+        }
+    }
+}
+
+/// The frames of a joint at the start of the time step.
+///
+/// The current anchors and bases are computed in the substepping loop
+/// by applying the position and rotation deltas of the [`SolverBody`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct JointFrames {
+    /// The world-space anchor of the first body relative to its center of mass.
+    pub anchor1: Vector,
+    /// The world-space anchor of the second body relative to its center of mass.
+    pub anchor2: Vector,
+    /// The difference between the world-space centers of mass of the bodies,
+    /// `center_of_mass2 - center_of_mass1`.
+    pub center_difference: Vector,
+    /// The world-space basis of the first joint frame.
+    pub basis1: Rot,
+    /// The world-space basis of the second joint frame.
+    pub basis2: Rot,
+}
+
+impl JointFrames {
+    /// Computes the world-space [`JointFrames`] for the given bodies and local joint frames.
+    pub fn new(
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+        local_anchor1: Vector,
+        local_anchor2: Vector,
+        local_basis1: Rot,
+        local_basis2: Rot,
+    ) -> Self {
+        let rotation1 = *body1.rotation;
+        let rotation2 = *body2.rotation;
+
+        let center_of_mass1 = body1.position.0 + rotation1 * body1.center_of_mass.0;
+        let center_of_mass2 = body2.position.0 + rotation2 * body2.center_of_mass.0;
+
+        Self {
+            anchor1: rotation1 * (local_anchor1 - body1.center_of_mass.0),
+            anchor2: rotation2 * (local_anchor2 - body2.center_of_mass.0),
+            center_difference: center_of_mass2 - center_of_mass1,
+            #[cfg(feature = "2d")]
+            basis1: rotation1 * local_basis1,
+            #[cfg(feature = "2d")]
+            basis2: rotation2 * local_basis2,
+            #[cfg(feature = "3d")]
+            basis1: rotation1.0 * local_basis1,
+            #[cfg(feature = "3d")]
+            basis2: rotation2.0 * local_basis2,
+        }
+    }
+
+    /// Returns the current world-space anchors of the bodies relative to their centers of mass.
+    #[inline]
+    pub fn anchors(&self, body1: &SolverBody, body2: &SolverBody) -> (Vector, Vector) {
+        (
+            body1.delta_rotation * self.anchor1,
+            body2.delta_rotation * self.anchor2,
+        )
+    }
+
+    /// Returns the current separation between the anchors, `anchor2 - anchor1` in world space,
+    /// given the current anchors relative to the centers of mass.
+    #[inline]
+    pub fn separation(
+        &self,
+        body1: &SolverBody,
+        body2: &SolverBody,
+        r1: Vector,
+        r2: Vector,
+    ) -> Vector {
+        (body2.delta_position - body1.delta_position) + self.center_difference + (r2 - r1)
+    }
+
+    /// Returns the current world-space bases of the joint frames.
+    #[inline]
+    pub fn bases(&self, body1: &SolverBody, body2: &SolverBody) -> (Rot, Rot) {
+        #[cfg(feature = "2d")]
+        {
+            (
+                body1.delta_rotation * self.basis1,
+                body2.delta_rotation * self.basis2,
+            )
+        }
+        #[cfg(feature = "3d")]
+        {
+            (
+                body1.delta_rotation.0 * self.basis1,
+                body2.delta_rotation.0 * self.basis2,
+            )
+        }
+    }
+}
+
+/// The bodies of a joint, along with their inertial properties.
+pub(super) struct JointBodies<'a> {
+    pub body1: &'a mut SolverBody,
+    pub body2: &'a mut SolverBody,
+    pub inertia1: &'a SolverBodyInertia,
+    pub inertia2: &'a SolverBodyInertia,
+}
+
+impl JointBodies<'_> {
+    /// Applies a linear impulse at the given anchors, pushing the second body
+    /// along the impulse and the first body in the opposite direction.
+    #[inline]
+    fn apply_linear_impulse(&mut self, r1: Vector, r2: Vector, impulse: Vector) {
+        self.body1.linear_velocity -= impulse * self.inertia1.effective_inv_mass();
+        self.body1.angular_velocity -=
+            self.inertia1.effective_inv_angular_inertia() * cross(r1, impulse);
+
+        self.body2.linear_velocity += impulse * self.inertia2.effective_inv_mass();
+        self.body2.angular_velocity +=
+            self.inertia2.effective_inv_angular_inertia() * cross(r2, impulse);
+    }
+
+    /// Applies an angular impulse, rotating the second body along the impulse
+    /// and the first body in the opposite direction.
+    #[inline]
+    fn apply_angular_impulse(&mut self, impulse: AngularVector) {
+        self.body1.angular_velocity -= self.inertia1.effective_inv_angular_inertia() * impulse;
+        self.body2.angular_velocity += self.inertia2.effective_inv_angular_inertia() * impulse;
+    }
+
+    /// Returns the relative angular velocity of the bodies, `angular_velocity2 - angular_velocity1`.
+    #[inline]
+    fn relative_angular_velocity(&self) -> AngularVector {
+        self.body2.angular_velocity - self.body1.angular_velocity
+    }
+}
+
+/// Shared state for solving the parts of a joint.
+pub(super) struct JointSolveContext {
+    /// The soft constraint coefficients of the joint.
+    pub softness: SoftnessCoefficients,
+    /// Whether to use a position bias to correct drift.
+    pub use_bias: bool,
+    /// The substep time step.
+    pub delta_secs: Scalar,
+}
+
+impl JointSolveContext {
+    /// Returns the bias, mass scale, and impulse scale for a constraint with the given position error.
+    #[inline]
+    fn soft_terms<T: core::ops::Mul<Scalar, Output = T> + Default>(
+        &self,
+        error: T,
+    ) -> (T, Scalar, Scalar) {
+        if self.use_bias {
+            (
+                error * self.softness.bias,
+                self.softness.mass_scale,
+                self.softness.impulse_scale,
+            )
+        } else {
+            (T::default(), 1.0, 0.0)
+        }
+    }
+}
+
+/// Computes the dot product of two angular vectors.
+#[inline]
+fn angular_dot(a: AngularVector, b: AngularVector) -> Scalar {
+    #[cfg(feature = "2d")]
+    {
+        a * b
+    }
+    #[cfg(feature = "3d")]
+    {
+        a.dot(b)
+    }
+}
+
+/// Returns the rotation error from `basis1` to `basis2` as an angular vector.
+///
+/// The time derivative of the error is the relative angular velocity of the bodies.
+#[inline]
+fn angular_error(basis1: Rot, basis2: Rot) -> AngularVector {
+    #[cfg(feature = "2d")]
+    {
+        basis1.angle_between(basis2)
+    }
+    #[cfg(feature = "3d")]
+    {
+        let mut difference = basis2 * basis1.inverse();
+        // Take the shortest path.
+        if difference.w < 0.0 {
+            difference = -difference;
+        }
+        2.0 * difference.xyz()
+    }
+}
+
+/// The Jacobian of a one-dimensional constraint between two bodies.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Jacobian {
+    /// The linear part of the Jacobian for the second body. The first body uses the negation.
+    linear: Vector,
+    /// The angular part of the Jacobian for the first body, with the sign flipped.
+    angular1: AngularVector,
+    /// The angular part of the Jacobian for the second body.
+    angular2: AngularVector,
+}
+
+impl Jacobian {
+    /// Creates a Jacobian for a linear constraint along the given `axis`
+    /// acting at the anchors `r1` and `r2`.
+    #[inline]
+    fn linear(axis: Vector, r1: Vector, r2: Vector) -> Self {
+        Self {
+            linear: axis,
+            angular1: cross(r1, axis),
+            angular2: cross(r2, axis),
+        }
+    }
+
+    /// Creates a Jacobian for an angular constraint about the given `axis`.
+    #[inline]
+    fn angular(axis: AngularVector) -> Self {
+        Self {
+            linear: Vector::ZERO,
+            angular1: axis,
+            angular2: axis,
+        }
+    }
+
+    /// Computes the effective mass of the constraint.
+    #[inline]
+    fn effective_mass(&self, bodies: &JointBodies) -> Scalar {
+        let inv_mass1 = bodies.inertia1.effective_inv_mass();
+        let inv_mass2 = bodies.inertia2.effective_inv_mass();
+        let inv_inertia1 = bodies.inertia1.effective_inv_angular_inertia();
+        let inv_inertia2 = bodies.inertia2.effective_inv_angular_inertia();
+
+        let k = self.linear.dot((inv_mass1 + inv_mass2) * self.linear)
+            + angular_dot(self.angular1, inv_inertia1 * self.angular1)
+            + angular_dot(self.angular2, inv_inertia2 * self.angular2);
+
+        k.recip_or_zero()
+    }
+
+    /// Computes the time derivative of the constraint.
+    #[inline]
+    fn velocity(&self, bodies: &JointBodies) -> Scalar {
+        self.linear
+            .dot(bodies.body2.linear_velocity - bodies.body1.linear_velocity)
+            + angular_dot(self.angular2, bodies.body2.angular_velocity)
+            - angular_dot(self.angular1, bodies.body1.angular_velocity)
+    }
+
+    /// Applies the given impulse along the Jacobian.
+    #[inline]
+    fn apply_impulse(&self, bodies: &mut JointBodies, impulse: Scalar) {
+        let linear = impulse * self.linear;
+        bodies.body1.linear_velocity -= linear * bodies.inertia1.effective_inv_mass();
+        bodies.body1.angular_velocity -=
+            bodies.inertia1.effective_inv_angular_inertia() * (impulse * self.angular1);
+
+        bodies.body2.linear_velocity += linear * bodies.inertia2.effective_inv_mass();
+        bodies.body2.angular_velocity +=
+            bodies.inertia2.effective_inv_angular_inertia() * (impulse * self.angular2);
+    }
+
+    /// Solves the equality constraint `C = 0` along the Jacobian, where `c` is the current position error.
+    fn solve_equality(
+        &self,
+        bodies: &mut JointBodies,
+        context: &JointSolveContext,
+        c: Scalar,
+        accumulated_impulse: &mut Scalar,
+    ) {
+        let (bias, mass_scale, impulse_scale) = context.soft_terms(c);
+
+        let effective_mass = self.effective_mass(bodies);
+        let velocity = self.velocity(bodies);
+
+        let impulse =
+            -effective_mass * mass_scale * (velocity + bias) - impulse_scale * *accumulated_impulse;
+        *accumulated_impulse += impulse;
+
+        self.apply_impulse(bodies, impulse);
+    }
+
+    /// Solves the inequality constraint `C >= 0` along the Jacobian, where `c` is the current position error.
+    ///
+    /// If the constraint is not yet violated, it is solved speculatively, only removing
+    /// the part of the velocity that would cause the limit to be exceeded within the substep.
+    fn solve_inequality(
+        &self,
+        bodies: &mut JointBodies,
+        context: &JointSolveContext,
+        c: Scalar,
+        accumulated_impulse: &mut Scalar,
+    ) {
+        let (bias, mass_scale, impulse_scale) = if c > 0.0 {
+            // Speculative: Allow approaching the limit, but not exceeding it.
+            (c / context.delta_secs, 1.0, 0.0)
+        } else {
+            context.soft_terms(c)
+        };
+
+        let effective_mass = self.effective_mass(bodies);
+        let velocity = self.velocity(bodies);
+
+        let impulse =
+            -effective_mass * mass_scale * (velocity + bias) - impulse_scale * *accumulated_impulse;
+
+        // Clamp the accumulated impulse so that the limit can only push.
+        let new_impulse = (*accumulated_impulse + impulse).max(0.0);
+        let impulse = new_impulse - *accumulated_impulse;
+        *accumulated_impulse = new_impulse;
+
+        self.apply_impulse(bodies, impulse);
+    }
+}
+
+impl core::ops::Neg for Jacobian {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            linear: -self.linear,
+            angular1: -self.angular1,
+            angular2: -self.angular2,
+        }
+    }
+}
+
+/// Solves a lower and upper limit for a coordinate with the given Jacobian.
+///
+/// Limits set to infinity are ignored.
+fn solve_limits(
+    bodies: &mut JointBodies,
+    context: &JointSolveContext,
+    jacobian: Jacobian,
+    value: Scalar,
+    (min, max): (Scalar, Scalar),
+    lower_impulse: &mut Scalar,
+    upper_impulse: &mut Scalar,
+) {
+    if min.is_finite() {
+        jacobian.solve_inequality(bodies, context, value - min, lower_impulse);
+    }
+    if max.is_finite() {
+        (-jacobian).solve_inequality(bodies, context, max - value, upper_impulse);
+    }
+}
+
+/// Solves a point-to-point constraint that keeps the anchors `r1` and `r2` of the bodies coincident.
+fn solve_point_constraint(
+    bodies: &mut JointBodies,
+    context: &JointSolveContext,
+    r1: Vector,
+    r2: Vector,
+    separation: Vector,
+    accumulated_impulse: &mut Vector,
+) {
+    let inv_mass_sum = bodies.inertia1.effective_inv_mass() + bodies.inertia2.effective_inv_mass();
+    let inv_inertia1 = bodies.inertia1.effective_inv_angular_inertia();
+    let inv_inertia2 = bodies.inertia2.effective_inv_angular_inertia();
+
+    // The velocity at the anchor caused by a unit impulse along the given axis.
+    let angular_response = |inv_inertia: SymmetricTensor, r: Vector, axis: Vector| {
+        #[cfg(feature = "2d")]
+        {
+            (inv_inertia * r.perp_dot(axis)) * r.perp()
+        }
+        #[cfg(feature = "3d")]
+        {
+            (inv_inertia * r.cross(axis)).cross(r)
+        }
+    };
+
+    // The effective mass matrix `K = M⁻¹ + [r1]ᵀ I1⁻¹ [r1] + [r2]ᵀ I2⁻¹ [r2]`.
+    let column = |axis: Vector| {
+        inv_mass_sum * axis
+            + angular_response(inv_inertia1, r1, axis)
+            + angular_response(inv_inertia2, r2, axis)
+    };
+    #[cfg(feature = "2d")]
+    let k = Matrix::from_cols(column(Vector::X), column(Vector::Y));
+    #[cfg(feature = "3d")]
+    let k = Matrix::from_cols(column(Vector::X), column(Vector::Y), column(Vector::Z));
+
+    let (bias, mass_scale, impulse_scale) = context.soft_terms(separation);
+
+    let velocity = bodies.body2.velocity_at_point(r2) - bodies.body1.velocity_at_point(r1);
+
+    let impulse = -mass_scale * (k.inverse_or_zero() * (velocity + bias))
+        - impulse_scale * *accumulated_impulse;
+    *accumulated_impulse += impulse;
+
+    bodies.apply_linear_impulse(r1, r2, impulse);
+}
+
+/// Returns the inverse of the angular effective mass `K = I1⁻¹ + I2⁻¹`.
+#[inline]
+fn angular_effective_mass(bodies: &JointBodies) -> AngularMatrix {
+    let inv_inertia1 = bodies.inertia1.effective_inv_angular_inertia();
+    let inv_inertia2 = bodies.inertia2.effective_inv_angular_inertia();
+
+    #[cfg(feature = "2d")]
+    {
+        (inv_inertia1 + inv_inertia2).recip_or_zero()
+    }
+    #[cfg(feature = "3d")]
+    {
+        let column = |axis: Vector| inv_inertia1 * axis + inv_inertia2 * axis;
+        Matrix::from_cols(column(Vector::X), column(Vector::Y), column(Vector::Z)).inverse_or_zero()
+    }
+}
+
+/// A matrix acting on angular vectors.
+#[cfg(feature = "2d")]
+type AngularMatrix = Scalar;
+
+/// A matrix acting on angular vectors.
+#[cfg(feature = "3d")]
+type AngularMatrix = Matrix;
+
+/// Solves an angular constraint that locks the relative rotation of the bodies,
+/// where `error` is the current rotation error.
+fn solve_angular_lock(
+    bodies: &mut JointBodies,
+    context: &JointSolveContext,
+    error: AngularVector,
+    accumulated_impulse: &mut AngularVector,
+) {
+    let (bias, mass_scale, impulse_scale) = context.soft_terms(error);
+
+    let mass = angular_effective_mass(bodies);
+    let velocity = bodies.relative_angular_velocity();
+
+    let impulse = -mass_scale * (mass * (velocity + bias)) - impulse_scale * *accumulated_impulse;
+    *accumulated_impulse += impulse;
+
+    bodies.apply_angular_impulse(impulse);
+}
+
+/// Solves an [`OrientationDrive`] as an implicit spring and damper acting on the relative rotation of the bodies,
+/// where `error` is the rotation error from the target orientation to the current orientation.
+fn solve_orientation_drive(
+    bodies: &mut JointBodies,
+    context: &JointSolveContext,
+    drive: &OrientationDrive,
+    error: AngularVector,
+    accumulated_impulse: &mut AngularVector,
+) {
+    let h = context.delta_secs;
+
+    // The implicit damping term `h * (c + h * k)`.
+    let gamma = h * (drive.damping + h * drive.stiffness);
+
+    if gamma <= 0.0 {
+        return;
+    }
+
+    // Solve `(I + gamma * K) * impulse = -(gamma * velocity + h * k * error + accumulated_impulse)`,
+    // which is the soft constraint formulation expressed with stiffness and damping coefficients.
+    let velocity = bodies.relative_angular_velocity();
+    let rhs = gamma * velocity + h * drive.stiffness * error + *accumulated_impulse;
+
+    #[cfg(feature = "2d")]
+    let impulse = {
+        let k = bodies.inertia1.effective_inv_angular_inertia()
+            + bodies.inertia2.effective_inv_angular_inertia();
+        -rhs / (1.0 + gamma * k)
+    };
+    #[cfg(feature = "3d")]
+    let impulse = {
+        let inv_inertia1 = bodies.inertia1.effective_inv_angular_inertia();
+        let inv_inertia2 = bodies.inertia2.effective_inv_angular_inertia();
+        let column = |axis: Vector| axis + gamma * (inv_inertia1 * axis + inv_inertia2 * axis);
+        let m = Matrix::from_cols(column(Vector::X), column(Vector::Y), column(Vector::Z));
+        -(m.inverse_or_zero() * rhs)
+    };
+
+    *accumulated_impulse += impulse;
+
+    bodies.apply_angular_impulse(impulse);
+}
@@ -0,0 +1,344 @@
+use super::{JointConstraint, JointConstraintData};
+use crate::{
+    dynamics::{
+        joints::EntityConstraint,
+        solver::{
+            SolverDiagnostics,
+            constraint_graph::ConstraintGraph,
+            softness_parameters::{SoftnessCoefficients, SoftnessParameters},
+            solver_body::SolverBody,
+        },
+    },
+    prelude::*,
+};
+use bevy::{
+    ecs::{query::QueryData, system::lifetimeless::Read},
+    prelude::*,
+};
+
+/// A plugin for solving [joints](dynamics::joints) with impulses in the same substepping loop as contacts.
+///
+/// Each frame, a [`JointConstraint`] is prepared for every [`FixedJoint`], [`RevoluteJoint`],
+#[cfg_attr(
+    feature = "3d",
+    doc = "[`PrismaticJoint`], [`DistanceJoint`], and [`SphericalJoint`],"
+)]
+#[cfg_attr(feature = "2d", doc = "[`PrismaticJoint`], and [`DistanceJoint`],")]
+/// and added to the [`ConstraintGraph`]. The [`SolverPlugin`] then warm starts and solves
+/// the joint constraints together with contacts, using graph coloring and
+/// [soft constraints](crate::dynamics::solver::softness_parameters).
+///
+/// The stiffness of joints is configured with [`SolverConfig::joint_damping_ratio`]
+/// and [`SolverConfig::joint_frequency_factor`], or per joint with the [`JointSoftness`] component.
+/// Compliance is not supported by this plugin. If the [`XpbdSolverPlugin`] is enabled, joints with
+/// non-zero compliance are solved by it instead, so that they keep their springiness.
+/// Otherwise, the compliance is ignored, the joints are rigid, and a warning is logged once per joint type.
+///
+/// Coupling joints such as the [`GearJoint`] are not supported by this plugin,
+/// and are still solved by the [`XpbdSolverPlugin`] if it is enabled.
+///
+/// # Migration
+///
+/// Previously, all joints were solved by the [`XpbdSolverPlugin`]. This plugin is included
+/// in the [`SolverPlugins`] by default, so joints are now solved with impulses unless they are compliant.
+/// Joints with a non-zero compliance behave the same as before, but rigid joints now use
+/// [soft constraints](crate::dynamics::solver::softness_parameters) configured by the [`SolverConfig`]
+/// or [`JointSoftness`], and their [`JointForces`] are computed from the solved impulses.
+///
+/// To keep solving all joints with XPBD, disable this plugin:
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default()
+///                 .build()
+///                 .disable::<ImpulseJointSolverPlugin>(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct ImpulseJointSolverPlugin;
+
+impl Plugin for ImpulseJointSolverPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_required_components::<FixedJoint, ImpulseJointSolverData>();
+        app.register_required_components::<RevoluteJoint, ImpulseJointSolverData>();
+        app.register_required_components::<PrismaticJoint, ImpulseJointSolverData>();
+        app.register_required_components::<DistanceJoint, ImpulseJointSolverData>();
+        #[cfg(feature = "3d")]
+        app.register_required_components::<SphericalJoint, ImpulseJointSolverData>();
+
+        app.init_resource::<JointSoftnessCoefficients>();
+
+        app.add_systems(
+            PhysicsSchedule,
+            update_joint_softness.before(SolverSystems::PrepareJoints),
+        );
+
+        // Prepare joint constraints and add them to the constraint graph before the substepping loop.
+        let prepare_joints = (
+            prepare_impulse_joints::<FixedJoint>,
+            prepare_impulse_joints::<RevoluteJoint>,
+            prepare_impulse_joints::<PrismaticJoint>,
+            prepare_impulse_joints::<DistanceJoint>,
+            #[cfg(feature = "3d")]
+            prepare_impulse_joints::<SphericalJoint>,
+        )
+            .chain()
+            .in_set(SolverSystems::PrepareJoints);
+
+        // The XPBD solver mutates the joint components when preparing joints,
+        // so run after it to keep the schedule deterministic.
+        #[cfg(feature = "xpbd_joints")]
+        let prepare_joints = prepare_joints
+            .after(crate::dynamics::solver::xpbd::prepare_xpbd_joint::<RackAndPinionJoint>);
+
+        app.add_systems(PhysicsSchedule, prepare_joints);
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Leave compliant joints to the XPBD solver if it is enabled.
+        #[cfg(feature = "xpbd_joints")]
+        if app.is_plugin_added::<crate::dynamics::solver::xpbd::XpbdSolverPlugin>() {
+            app.add_systems(
+                PhysicsSchedule,
+                (
+                    route_compliant_joints::<FixedJoint>,
+                    route_compliant_joints::<RevoluteJoint>,
+                    route_compliant_joints::<PrismaticJoint>,
+                    route_compliant_joints::<DistanceJoint>,
+                    #[cfg(feature = "3d")]
+                    route_compliant_joints::<SphericalJoint>,
+                )
+                    .before(SolverSystems::PrepareJoints),
+            );
+            return;
+        }
+
+        // Otherwise, compliance is ignored, so warn about compliant joints.
+        app.add_systems(
+            PhysicsSchedule,
+            (
+                warn_compliant_joints::<FixedJoint>,
+                warn_compliant_joints::<RevoluteJoint>,
+                warn_compliant_joints::<PrismaticJoint>,
+                warn_compliant_joints::<DistanceJoint>,
+                #[cfg(feature = "3d")]
+                warn_compliant_joints::<SphericalJoint>,
+            )
+                .before(SolverSystems::PrepareJoints),
+        );
+    }
+}
+
+/// A trait for [joints](dynamics::joints) that can be solved by the [`ImpulseJointSolverPlugin`].
+pub trait ImpulseJoint: Component + EntityConstraint<2> {
+    /// Creates the [`JointConstraintData`] for the joint at the start of the time step.
+    ///
+    /// Returns `None` if the joint cannot be solved yet, for example because its local frames
+    /// have not been computed.
+    fn constraint_data(
+        &self,
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+    ) -> Option<JointConstraintData>;
+
+    /// Returns `true` if any of the compliance parameters of the joint are non-zero.
+    ///
+    /// Compliance is not supported by the [`ImpulseJointSolverPlugin`], so compliant joints
+    /// are solved by the [`XpbdSolverPlugin`] instead if it is enabled.
+    fn is_compliant(&self) -> bool;
+}
+
+/// Data stored for each joint solved by the [`ImpulseJointSolverPlugin`].
+///
+/// This stores the constraint data of the previous time step, which is used for warm starting.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct ImpulseJointSolverData {
+    /// The constraint data of the joint from the previous time step.
+    pub previous: Option<JointConstraintData>,
+}
+
+/// The default [`SoftnessCoefficients`] used for joints.
+///
+/// Joints with a [`JointSoftness`] component compute their own coefficients instead.
+///
+/// **Note**: This resource is updated automatically and not intended to be modified manually.
+/// Use the [`SolverConfig`] resource instead for tuning joint behavior.
+#[derive(Resource, Clone, Copy, Debug, Deref, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct JointSoftnessCoefficients(pub SoftnessCoefficients);
+
+impl Default for JointSoftnessCoefficients {
+    fn default() -> Self {
+        Self(SoftnessParameters::new(2.0, 60.0).compute_coefficients(1.0 / 60.0))
+    }
+}
+
+fn update_joint_softness(
+    mut coefficients: ResMut<JointSoftnessCoefficients>,
+    solver_config: Res<SolverConfig>,
+    physics_time: Res<Time<Physics>>,
+    substep_time: Res<Time<Substeps>>,
+) {
+    if solver_config.is_changed() || physics_time.is_changed() || substep_time.is_changed() {
+        let dt = physics_time.delta_secs_f64() as Scalar;
+        let h = substep_time.delta_secs_f64() as Scalar;
+
+        // Like for contacts, the frequency should at most be half of the time step.
+        let max_hz = 1.0 / (dt * 2.0);
+        let hz = solver_config.joint_frequency_factor * max_hz.min(0.25 / h);
+
+        coefficients.0 =
+            SoftnessParameters::new(solver_config.joint_damping_ratio, hz).compute_coefficients(h);
+    }
+}
+
+/// Removes the [`ImpulseJointSolverData`] of joints with non-zero compliance so that they are solved
+/// by the [`XpbdSolverPlugin`], and adds it back when their compliance is set to zero.
+#[cfg(feature = "xpbd_joints")]
+fn route_compliant_joints<J: ImpulseJoint>(
+    joints: Query<(Entity, &J, Has<ImpulseJointSolverData>), Changed<J>>,
+    mut commands: Commands,
+) {
+    for (entity, joint, is_impulse_joint) in &joints {
+        let is_compliant = joint.is_compliant();
+        if is_compliant && is_impulse_joint {
+            commands.entity(entity).remove::<ImpulseJointSolverData>();
+        } else if !is_compliant && !is_impulse_joint {
+            commands
+                .entity(entity)
+                .insert(ImpulseJointSolverData::default());
+        }
+    }
+}
+
+/// Logs a warning once if a joint of type `J` has a non-zero compliance
+/// that cannot be solved because the [`XpbdSolverPlugin`] is not enabled.
+fn warn_compliant_joints<J: ImpulseJoint>(joints: Query<&J, Changed<J>>, mut warned: Local<bool>) {
+    if *warned || !joints.iter().any(ImpulseJoint::is_compliant) {
+        return;
+    }
+
+    warn!(
+        "{} has a non-zero compliance, which is ignored by the `ImpulseJointSolverPlugin`. \
+        Enable the `XpbdSolverPlugin` to solve compliant joints, or use `JointSoftness` instead.",
+        disqualified::ShortName::of::<J>()
+    );
+    *warned = true;
+}
+
+/// The body data needed for preparing joint constraints.
+#[derive(QueryData)]
+pub struct JointBodyQuery {
+    /// The position of the body.
+    pub position: Read<Position>,
+    /// The rotation of the body.
+    pub rotation: Read<Rotation>,
+    /// The center of mass of the body in local space.
+    pub center_of_mass: Read<ComputedCenterOfMass>,
+    /// Whether the body has a [`SolverBody`], meaning that it is an awake dynamic or kinematic body.
+    pub is_active: Has<SolverBody>,
+}
+
+/// Prepares [`JointConstraint`]s for joints of type `J` and adds them to the [`ConstraintGraph`].
+fn prepare_impulse_joints<J: ImpulseJoint>(
+    joints: Query<
+        (Entity, &J, &ImpulseJointSolverData, Option<&JointSoftness>),
+        (Without<RigidBody>, Without<JointDisabled>),
+    >,
+    bodies: Query<JointBodyQuery, Without<RigidBodyDisabled>>,
+    mut constraint_graph: ResMut<ConstraintGraph>,
+    default_softness: Res<JointSoftnessCoefficients>,
+    substep_time: Res<Time<Substeps>>,
+) {
+    let h = substep_time.delta_secs_f64() as Scalar;
+
+    for (entity, joint, solver_data, joint_softness) in &joints {
+        let [entity1, entity2] = joint.entities();
+
+        let Ok([body1, body2]) = bodies.get_many([entity1, entity2]) else {
+            continue;
+        };
+
+        // Joints between two static or sleeping bodies don't need to be solved.
+        if !body1.is_active && !body2.is_active {
+            continue;
+        }
+
+        let Some(mut data) = joint.constraint_data(&body1, &body2) else {
+            continue;
+        };
+
+        // Warm start the joint with the impulses from the previous time step.
+        if let Some(previous) = &solver_data.previous {
+            data.copy_impulses(previous);
+        }
+
+        let (softness, always_soft) = match joint_softness {
+            Some(joint_softness) => (
+                SoftnessParameters::new(joint_softness.damping_ratio, joint_softness.frequency)
+                    .compute_coefficients(h),
+                true,
+            ),
+            None => (default_softness.0, false),
+        };
+
+        constraint_graph.push_joint(JointConstraint {
+            entity,
+            body1: entity1,
+            body2: entity2,
+            is_static1: !body1.is_active,
+            is_static2: !body2.is_active,
+            softness,
+            always_soft,
+            data,
+        });
+    }
+}
+
+/// Stores the joint constraints for the next time step's warm starting, writes [`JointForces`],
+/// and removes the joint constraints from the [`ConstraintGraph`].
+pub(crate) fn store_joint_impulses(
+    mut joints: Query<(&mut ImpulseJointSolverData, Option<&mut JointForces>)>,
+    mut constraint_graph: ResMut<ConstraintGraph>,
+    mut diagnostics: ResMut<SolverDiagnostics>,
+    substep_time: Res<Time<Substeps>>,
+) {
+    let start = crate::utils::Instant::now();
+
+    let inv_h = (substep_time.delta_secs_f64() as Scalar).recip_or_zero();
+    let mut joint_count = 0;
+
+    for color in constraint_graph.colors.iter() {
+        joint_count += color.joint_constraints.len();
+
+        for constraint in &color.joint_constraints {
+            let Ok((mut solver_data, forces)) = joints.get_mut(constraint.entity) else {
+                continue;
+            };
+
+            // The accumulated impulses are for the last substep.
+            if let Some(mut forces) = forces {
+                forces.set_force(constraint.linear_impulse() * inv_h);
+                forces.set_torque(constraint.angular_impulse() * inv_h);
+            }
+
+            solver_data.previous = Some(constraint.data.clone());
+        }
+    }
+
+    constraint_graph.clear_joints();
+
+    diagnostics.joint_constraint_count = joint_count as u32;
+    diagnostics.store_impulses += start.elapsed();
+}
@@ -0,0 +1,194 @@
+use super::{
+    ImpulseJoint, Jacobian, JointBodies, JointBodyQueryItem, JointConstraintData, JointFrames,
+    JointSolveContext, angular_error, solve_angular_lock, solve_limits,
+};
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+impl ImpulseJoint for PrismaticJoint {
+    fn constraint_data(
+        &self,
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+    ) -> Option<JointConstraintData> {
+        let frames = JointFrames::new(
+            body1,
+            body2,
+            self.local_anchor1()?,
+            self.local_anchor2()?,
+            self.local_basis1()?,
+            self.local_basis2()?,
+        );
+        Some(JointConstraintData::Prismatic(
+            PrismaticJointConstraint::new(self, frames),
+        ))
+    }
+
+    fn is_compliant(&self) -> bool {
+        self.align_compliance != 0.0 || self.angle_compliance != 0.0 || self.limit_compliance != 0.0
+    }
+}
+
+/// Constraint data for solving a [`PrismaticJoint`] with impulses.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct PrismaticJointConstraint {
+    /// The frames of the joint at the start of the time step.
+    pub frames: JointFrames,
+    /// The slider axis in the local space of the joint frames.
+    pub slider_axis: Vector,
+    /// The limits of the translation along the slider axis.
+    pub limits: Option<DistanceLimit>,
+    /// The accumulated impulse of the angular constraint.
+    pub angle_impulse: AngularVector,
+    /// The accumulated impulse of the constraint keeping the anchors on the slider axis.
+    #[cfg(feature = "2d")]
+    pub perpendicular_impulse: Scalar,
+    /// The accumulated impulses of the constraints keeping the anchors on the slider axis.
+    #[cfg(feature = "3d")]
+    pub perpendicular_impulse: Vector2,
+    /// The accumulated impulse of the lower translation limit.
+    pub lower_impulse: Scalar,
+    /// The accumulated impulse of the upper translation limit.
+    pub upper_impulse: Scalar,
+}
+
+impl PrismaticJointConstraint {
+    /// Creates a new [`PrismaticJointConstraint`] for the given joint and frames.
+    pub fn new(joint: &PrismaticJoint, frames: JointFrames) -> Self {
+        Self {
+            frames,
+            slider_axis: joint.slider_axis,
+            limits: joint.limits,
+            angle_impulse: AngularVector::ZERO,
+            #[cfg(feature = "2d")]
+            perpendicular_impulse: 0.0,
+            #[cfg(feature = "3d")]
+            perpendicular_impulse: Vector2::ZERO,
+            lower_impulse: 0.0,
+            upper_impulse: 0.0,
+        }
+    }
+
+    /// Copies the accumulated impulses from the previous time step for warm starting.
+    pub fn copy_impulses(&mut self, previous: &Self) {
+        self.angle_impulse = previous.angle_impulse;
+        self.perpendicular_impulse = previous.perpendicular_impulse;
+        if self.limits.is_some() {
+            self.lower_impulse = previous.lower_impulse;
+            self.upper_impulse = previous.upper_impulse;
+        }
+    }
+
+    /// Returns the linear impulse applied to the second body.
+    pub fn linear_impulse(&self) -> Vector {
+        let [axis, perpendicular @ ..] = self.axes(self.frames.basis1);
+        self.total_linear_impulse(axis, perpendicular)
+    }
+
+    /// Returns the angular impulse applied to the second body.
+    pub fn angular_impulse(&self) -> AngularVector {
+        self.angle_impulse
+    }
+
+    /// Returns the world-space slider axis followed by the axes perpendicular to it.
+    #[cfg(feature = "2d")]
+    fn axes(&self, basis1: Rot) -> [Vector; 2] {
+        let axis = basis1 * self.slider_axis;
+        [axis, axis.perp()]
+    }
+
+    /// Returns the world-space slider axis followed by the axes perpendicular to it.
+    #[cfg(feature = "3d")]
+    fn axes(&self, basis1: Rot) -> [Vector; 3] {
+        let (b, c) = self.slider_axis.any_orthonormal_pair();
+        [basis1 * self.slider_axis, basis1 * b, basis1 * c]
+    }
+
+    /// Returns the combined linear impulse of the limits and the perpendicular constraints.
+    fn total_linear_impulse(&self, axis: Vector, perpendicular: [Vector; DIM - 1]) -> Vector {
+        let limit_impulse = (self.lower_impulse - self.upper_impulse) * axis;
+
+        #[cfg(feature = "2d")]
+        {
+            limit_impulse + self.perpendicular_impulse * perpendicular[0]
+        }
+        #[cfg(feature = "3d")]
+        {
+            limit_impulse
+                + self.perpendicular_impulse.x * perpendicular[0]
+                + self.perpendicular_impulse.y * perpendicular[1]
+        }
+    }
+
+    pub(super) fn warm_start(&self, bodies: &mut JointBodies, coefficient: Scalar) {
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let (basis1, _) = self.frames.bases(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+        let [axis, perpendicular @ ..] = self.axes(basis1);
+
+        // The axes rotate with the first body, so the impulse acts on the first body
+        // at the anchor of the second body.
+        let impulse = self.total_linear_impulse(axis, perpendicular);
+        bodies.apply_linear_impulse(r1 + separation, r2, coefficient * impulse);
+        bodies.apply_angular_impulse(coefficient * self.angle_impulse);
+    }
+
+    pub(super) fn solve(&mut self, bodies: &mut JointBodies, context: &JointSolveContext) {
+        // Lock the relative rotation.
+        let (basis1, basis2) = self.frames.bases(bodies.body1, bodies.body2);
+        solve_angular_lock(
+            bodies,
+            context,
+            angular_error(basis1, basis2),
+            &mut self.angle_impulse,
+        );
+
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+        let [axis, perpendicular @ ..] = self.axes(basis1);
+
+        // Limit the translation along the slider axis.
+        if let Some(limits) = self.limits {
+            solve_limits(
+                bodies,
+                context,
+                Jacobian::linear(axis, r1 + separation, r2),
+                axis.dot(separation),
+                (limits.min, limits.max),
+                &mut self.lower_impulse,
+                &mut self.upper_impulse,
+            );
+        }
+
+        // Keep the anchors on the slider axis.
+        #[cfg(feature = "2d")]
+        {
+            Jacobian::linear(perpendicular[0], r1 + separation, r2).solve_equality(
+                bodies,
+                context,
+                perpendicular[0].dot(separation),
+                &mut self.perpendicular_impulse,
+            );
+        }
+        #[cfg(feature = "3d")]
+        {
+            Jacobian::linear(perpendicular[0], r1 + separation, r2).solve_equality(
+                bodies,
+                context,
+                perpendicular[0].dot(separation),
+                &mut self.perpendicular_impulse.x,
+            );
+            Jacobian::linear(perpendicular[1], r1 + separation, r2).solve_equality(
+                bodies,
+                context,
+                perpendicular[1].dot(separation),
+                &mut self.perpendicular_impulse.y,
+            );
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use super::{
+    ImpulseJoint, Jacobian, JointBodies, JointBodyQueryItem, JointConstraintData, JointFrames,
+    JointSolveContext, angular_error, solve_limits, solve_orientation_drive,
+    solve_point_constraint,
+};
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+impl ImpulseJoint for RevoluteJoint {
+    fn constraint_data(
+        &self,
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+    ) -> Option<JointConstraintData> {
+        let frames = JointFrames::new(
+            body1,
+            body2,
+            self.local_anchor1()?,
+            self.local_anchor2()?,
+            self.local_basis1()?,
+            self.local_basis2()?,
+        );
+        Some(JointConstraintData::Revolute(RevoluteJointConstraint::new(
+            self, frames,
+        )))
+    }
+
+    fn is_compliant(&self) -> bool {
+        self.point_compliance != 0.0 || self.align_compliance != 0.0 || self.limit_compliance != 0.0
+    }
+}
+
+/// Constraint data for solving a [`RevoluteJoint`] with impulses.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct RevoluteJointConstraint {
+    /// The frames of the joint at the start of the time step.
+    pub frames: JointFrames,
+    /// The hinge axis in the local space of the joint frames.
+    #[cfg(feature = "3d")]
+    pub hinge_axis: Vector,
+    /// The limits of the relative rotation around the hinge axis.
+    pub angle_limit: Option<AngleLimit>,
+    /// The drive for the relative rotation.
+    pub orientation_drive: Option<OrientationDrive>,
+    /// The accumulated impulse of the point constraint.
+    pub point_impulse: Vector,
+    /// The accumulated impulses of the constraints aligning the hinge axes.
+    #[cfg(feature = "3d")]
+    pub align_impulse: Vector2,
+    /// The accumulated impulse of the lower angle limit.
+    pub lower_impulse: Scalar,
+    /// The accumulated impulse of the upper angle limit.
+    pub upper_impulse: Scalar,
+    /// The accumulated impulse of the orientation drive.
+    pub drive_impulse: AngularVector,
+}
+
+impl RevoluteJointConstraint {
+    /// Creates a new [`RevoluteJointConstraint`] for the given joint and frames.
+    pub fn new(joint: &RevoluteJoint, frames: JointFrames) -> Self {
+        Self {
+            frames,
+            #[cfg(feature = "3d")]
+            hinge_axis: joint.hinge_axis,
+            angle_limit: joint.angle_limit,
+            orientation_drive: joint.orientation_drive,
+            point_impulse: Vector::ZERO,
+            #[cfg(feature = "3d")]
+            align_impulse: Vector2::ZERO,
+            lower_impulse: 0.0,
+            upper_impulse: 0.0,
+            drive_impulse: AngularVector::ZERO,
+        }
+    }
+
+    /// Copies the accumulated impulses from the previous time step for warm starting.
+    pub fn copy_impulses(&mut self, previous: &Self) {
+        self.point_impulse = previous.point_impulse;
+        #[cfg(feature = "3d")]
+        {
+            self.align_impulse = previous.align_impulse;
+        }
+        if self.angle_limit.is_some() {
+            self.lower_impulse = previous.lower_impulse;
+            self.upper_impulse = previous.upper_impulse;
+        }
+        if self.orientation_drive.is_some() {
+            self.drive_impulse = previous.drive_impulse;
+        }
+    }
+
+    /// Returns the linear impulse applied to the second body.
+    pub fn linear_impulse(&self) -> Vector {
+        self.point_impulse
+    }
+
+    /// Returns the angular impulse applied to the second body.
+    pub fn angular_impulse(&self) -> AngularVector {
+        self.total_angular_impulse(self.frames.basis1, self.frames.basis2)
+    }
+
+    /// Returns the combined angular impulse of the alignment constraints, limits, and drive
+    /// for the given world-space bases.
+    #[cfg_attr(feature = "2d", allow(unused_variables))]
+    fn total_angular_impulse(&self, basis1: Rot, basis2: Rot) -> AngularVector {
+        let limit_impulse = (self.lower_impulse - self.upper_impulse) * self.hinge(basis1);
+
+        #[cfg(feature = "2d")]
+        {
+            limit_impulse + self.drive_impulse
+        }
+        #[cfg(feature = "3d")]
+        {
+            let [align1, align2] = self.align_axes(basis1, basis2);
+            self.align_impulse.x * align1
+                + self.align_impulse.y * align2
+                + limit_impulse
+                + self.drive_impulse
+        }
+    }
+
+    /// Returns the world-space hinge axis of the first frame.
+    #[cfg_attr(feature = "2d", allow(unused_variables))]
+    fn hinge(&self, basis1: Rot) -> AngularVector {
+        #[cfg(feature = "2d")]
+        {
+            1.0
+        }
+        #[cfg(feature = "3d")]
+        {
+            basis1 * self.hinge_axis
+        }
+    }
+
+    /// Returns the angular Jacobians of the two constraints that align the hinge axes
+    /// for the given world-space bases.
+    #[cfg(feature = "3d")]
+    fn align_axes(&self, basis1: Rot, basis2: Rot) -> [Vector; 2] {
+        let (b, c) = self.hinge_axis.any_orthonormal_pair();
+        let a2 = basis2 * self.hinge_axis;
+        [a2.cross(basis1 * b), a2.cross(basis1 * c)]
+    }
+
+    /// Returns the angle of the second frame relative to the first frame around the hinge axis.
+    fn angle(&self, basis1: Rot, basis2: Rot) -> Scalar {
+        #[cfg(feature = "2d")]
+        {
+            basis1.angle_between(basis2)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let reference = self.hinge_axis.any_orthonormal_vector();
+            let a1 = basis1 * self.hinge_axis;
+            let b1 = basis1 * reference;
+            let b2 = basis2 * reference;
+            a1.dot(b1.cross(b2)).atan2(b1.dot(b2))
+        }
+    }
+
+    pub(super) fn warm_start(&self, bodies: &mut JointBodies, coefficient: Scalar) {
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let (basis1, basis2) = self.frames.bases(bodies.body1, bodies.body2);
+        let angular_impulse = self.total_angular_impulse(basis1, basis2);
+
+        bodies.apply_linear_impulse(r1, r2, coefficient * self.point_impulse);
+        bodies.apply_angular_impulse(coefficient * angular_impulse);
+    }
+
+    pub(super) fn solve(&mut self, bodies: &mut JointBodies, context: &JointSolveContext) {
+        let (basis1, basis2) = self.frames.bases(bodies.body1, bodies.body2);
+
+        // Align the hinge axes, only allowing rotation around one free axis.
+        #[cfg(feature = "3d")]
+        {
+            let (b, c) = self.hinge_axis.any_orthonormal_pair();
+            let a2 = basis2 * self.hinge_axis;
+            let [align1, align2] = self.align_axes(basis1, basis2);
+
+            Jacobian::angular(align1).solve_equality(
+                bodies,
+                context,
+                a2.dot(basis1 * b),
+                &mut self.align_impulse.x,
+            );
+            Jacobian::angular(align2).solve_equality(
+                bodies,
+                context,
+                a2.dot(basis1 * c),
+                &mut self.align_impulse.y,
+            );
+        }
+
+        // Drive the relative rotation towards the target orientation.
+        if let Some(drive) = self.orientation_drive {
+            #[cfg(feature = "2d")]
+            let target = basis1 * Rotation::radians(drive.target);
+            #[cfg(feature = "3d")]
+            let target = basis1 * drive.target;
+
+            solve_orientation_drive(
+                bodies,
+                context,
+                &drive,
+                angular_error(target, basis2),
+                &mut self.drive_impulse,
+            );
+        }
+
+        // Limit the relative rotation around the hinge axis.
+        if let Some(limit) = self.angle_limit {
+            solve_limits(
+                bodies,
+                context,
+                Jacobian::angular(self.hinge(basis1)),
+                self.angle(basis1, basis2),
+                (limit.min, limit.max),
+                &mut self.lower_impulse,
+                &mut self.upper_impulse,
+            );
+        }
+
+        // Keep the anchors together.
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+        solve_point_constraint(bodies, context, r1, r2, separation, &mut self.point_impulse);
+    }
+}
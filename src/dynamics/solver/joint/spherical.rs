@@ -0,0 +1,205 @@
+use super::{
+    ImpulseJoint, Jacobian, JointBodies, JointBodyQueryItem, JointConstraintData, JointFrames,
+    JointSolveContext, angular_error, solve_limits, solve_orientation_drive,
+    solve_point_constraint,
+};
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+impl ImpulseJoint for SphericalJoint {
+    fn constraint_data(
+        &self,
+        body1: &JointBodyQueryItem,
+        body2: &JointBodyQueryItem,
+    ) -> Option<JointConstraintData> {
+        let frames = JointFrames::new(
+            body1,
+            body2,
+            self.local_anchor1()?,
+            self.local_anchor2()?,
+            self.local_basis1()?,
+            self.local_basis2()?,
+        );
+        Some(JointConstraintData::Spherical(
+            SphericalJointConstraint::new(self, frames),
+        ))
+    }
+
+    fn is_compliant(&self) -> bool {
+        self.point_compliance != 0.0 || self.swing_compliance != 0.0 || self.twist_compliance != 0.0
+    }
+}
+
+/// Constraint data for solving a [`SphericalJoint`] with impulses.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct SphericalJointConstraint {
+    /// The frames of the joint at the start of the time step.
+    pub frames: JointFrames,
+    /// The twist axis in the local space of the joint frames.
+    pub twist_axis: Vector,
+    /// The limits of the swing angle between the twist axes.
+    pub swing_limit: Option<AngleLimit>,
+    /// The limits of the twist angle around the twist axis.
+    pub twist_limit: Option<AngleLimit>,
+    /// The drive for the relative rotation.
+    pub orientation_drive: Option<OrientationDrive>,
+    /// The accumulated impulse of the point constraint.
+    pub point_impulse: Vector,
+    /// The accumulated impulses of the lower and upper swing limits.
+    pub swing_impulse: Vector2,
+    /// The accumulated impulses of the lower and upper twist limits.
+    pub twist_impulse: Vector2,
+    /// The accumulated impulse of the orientation drive.
+    pub drive_impulse: Vector,
+}
+
+impl SphericalJointConstraint {
+    /// Creates a new [`SphericalJointConstraint`] for the given joint and frames.
+    pub fn new(joint: &SphericalJoint, frames: JointFrames) -> Self {
+        Self {
+            frames,
+            twist_axis: joint.twist_axis,
+            swing_limit: joint.swing_limit,
+            twist_limit: joint.twist_limit,
+            orientation_drive: joint.orientation_drive,
+            point_impulse: Vector::ZERO,
+            swing_impulse: Vector2::ZERO,
+            twist_impulse: Vector2::ZERO,
+            drive_impulse: Vector::ZERO,
+        }
+    }
+
+    /// Copies the accumulated impulses from the previous time step for warm starting.
+    pub fn copy_impulses(&mut self, previous: &Self) {
+        self.point_impulse = previous.point_impulse;
+        if self.swing_limit.is_some() {
+            self.swing_impulse = previous.swing_impulse;
+        }
+        if self.twist_limit.is_some() {
+            self.twist_impulse = previous.twist_impulse;
+        }
+        if self.orientation_drive.is_some() {
+            self.drive_impulse = previous.drive_impulse;
+        }
+    }
+
+    /// Returns the linear impulse applied to the second body.
+    pub fn linear_impulse(&self) -> Vector {
+        self.point_impulse
+    }
+
+    /// Returns the angular impulse applied to the second body.
+    pub fn angular_impulse(&self) -> Vector {
+        self.total_angular_impulse(self.frames.basis1, self.frames.basis2)
+    }
+
+    /// Returns the combined angular impulse of the limits and drive for the given world-space bases.
+    fn total_angular_impulse(&self, basis1: Rot, basis2: Rot) -> Vector {
+        let mut impulse = self.drive_impulse;
+        if let Some((axis, _)) = self.swing(basis1, basis2) {
+            impulse += (self.swing_impulse.x - self.swing_impulse.y) * axis;
+        }
+        if let Some((axis, _)) = self.twist(basis1, basis2) {
+            impulse += (self.twist_impulse.x - self.twist_impulse.y) * axis;
+        }
+        impulse
+    }
+
+    /// Returns the axis and angle of the swing between the twist axes,
+    /// or `None` if the axes are parallel.
+    fn swing(&self, basis1: Rot, basis2: Rot) -> Option<(Vector, Scalar)> {
+        let twist_axis1 = basis1 * self.twist_axis;
+        let twist_axis2 = basis2 * self.twist_axis;
+        let axis = twist_axis1.cross(twist_axis2).try_normalize()?;
+        let angle = twist_axis1.dot(twist_axis2).clamp(-1.0, 1.0).acos();
+        Some((axis, angle))
+    }
+
+    /// Returns the axis and angle of the twist around the average of the twist axes,
+    /// or `None` if the axes point in opposite directions.
+    fn twist(&self, basis1: Rot, basis2: Rot) -> Option<(Vector, Scalar)> {
+        let axis = (basis1 * self.twist_axis + basis2 * self.twist_axis).try_normalize()?;
+
+        // Project reference axes perpendicular to the twist axis onto the twist plane.
+        let reference = self.twist_axis.any_orthonormal_vector();
+        let reference1 = basis1 * reference;
+        let reference2 = basis2 * reference;
+        let reference1 = (reference1 - axis.dot(reference1) * axis).try_normalize()?;
+        let reference2 = (reference2 - axis.dot(reference2) * axis).try_normalize()?;
+
+        let angle = axis
+            .dot(reference1.cross(reference2))
+            .atan2(reference1.dot(reference2));
+        Some((axis, angle))
+    }
+
+    pub(super) fn warm_start(&self, bodies: &mut JointBodies, coefficient: Scalar) {
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let (basis1, basis2) = self.frames.bases(bodies.body1, bodies.body2);
+        let angular_impulse = self.total_angular_impulse(basis1, basis2);
+
+        bodies.apply_linear_impulse(r1, r2, coefficient * self.point_impulse);
+        bodies.apply_angular_impulse(coefficient * angular_impulse);
+    }
+
+    pub(super) fn solve(&mut self, bodies: &mut JointBodies, context: &JointSolveContext) {
+        let (basis1, basis2) = self.frames.bases(bodies.body1, bodies.body2);
+
+        // Drive the relative rotation towards the target orientation.
+        if let Some(drive) = self.orientation_drive {
+            solve_orientation_drive(
+                bodies,
+                context,
+                &drive,
+                angular_error(basis1 * drive.target, basis2),
+                &mut self.drive_impulse,
+            );
+        }
+
+        // Limit the swing angle. A lower limit of zero would only fight the limit
+        // at the singularity where the twist axes are parallel, so it is ignored.
+        if let Some(limit) = self.swing_limit
+            && let Some((axis, angle)) = self.swing(basis1, basis2)
+        {
+            let min = if limit.min > 0.0 {
+                limit.min
+            } else {
+                Scalar::NEG_INFINITY
+            };
+            solve_limits(
+                bodies,
+                context,
+                Jacobian::angular(axis),
+                angle,
+                (min, limit.max),
+                &mut self.swing_impulse.x,
+                &mut self.swing_impulse.y,
+            );
+        }
+
+        // Limit the twist angle.
+        if let Some(limit) = self.twist_limit
+            && let Some((axis, angle)) = self.twist(basis1, basis2)
+        {
+            solve_limits(
+                bodies,
+                context,
+                Jacobian::angular(axis),
+                angle,
+                (limit.min, limit.max),
+                &mut self.twist_impulse.x,
+                &mut self.twist_impulse.y,
+            );
+        }
+
+        // Keep the anchors together.
+        let (r1, r2) = self.frames.anchors(bodies.body1, bodies.body2);
+        let separation = self.frames.separation(bodies.body1, bodies.body2, r1, r2);
+        solve_point_constraint(bodies, context, r1, r2, separation, &mut self.point_impulse);
+    }
+}
@@ -8,25 +8,68 @@ pub use plugin::{JointComponentId, JointGraphPlugin};
 
 use crate::{
     data_structures::{
+        bit_vec::BitVec,
         graph::{EdgeIndex, NodeIndex},
         sparse_secondary_map::SparseSecondaryEntityMap,
         stable_graph::StableUnGraph,
     },
-    dynamics::solver::islands::IslandNode,
+    dynamics::solver::{
+        constraint_graph::{GRAPH_COLOR_COUNT, assign_color},
+        islands::IslandNode,
+    },
 };
-use bevy::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
+use core::any::TypeId;
 
 // TODO: Once we have many-to-many relationships, we could potentially represent the joint graph in the ECS.
 
 /// A resource for the joint graph, tracking how [rigid bodies] are connected by [joints].
 ///
+/// The graph also holds up to [`GRAPH_COLOR_COUNT`] [`JointGraphColor`]s used by the XPBD joint solver.
+/// Like the colors of the [`ConstraintGraph`], each color is a set of joints that share no non-static bodies,
+/// allowing the joints within a color to be solved in parallel. Joints are colored before each time step,
+/// and the colors are cleared after the time step.
+///
 /// [rigid bodies]: crate::dynamics::RigidBody
 /// [joints]: crate::dynamics::joints
-#[derive(Resource, Clone, Debug, Default)]
+/// [`ConstraintGraph`]: crate::dynamics::solver::constraint_graph::ConstraintGraph
+#[derive(Resource, Clone, Debug)]
 pub struct JointGraph {
     graph: StableUnGraph<Entity, JointGraphEdge>,
     entity_to_body: SparseSecondaryEntityMap<NodeIndex>,
     entity_to_joint: SparseSecondaryEntityMap<EdgeIndex>,
+    colors: Vec<JointGraphColor>,
+    /// The types of the constraints that have been assigned to colors for the current time step.
+    colored_constraint_types: HashSet<TypeId>,
+}
+
+impl Default for JointGraph {
+    fn default() -> Self {
+        Self {
+            graph: StableUnGraph::default(),
+            entity_to_body: SparseSecondaryEntityMap::default(),
+            entity_to_joint: SparseSecondaryEntityMap::default(),
+            colors: (0..GRAPH_COLOR_COUNT)
+                .map(|_| JointGraphColor::default())
+                .collect(),
+            colored_constraint_types: HashSet::default(),
+        }
+    }
+}
+
+/// A color in the [`JointGraph`]. Each color is a set of bodies and joints
+/// that can be solved in parallel without race conditions.
+///
+/// Only awake dynamic and kinematic bodies are included in graph coloring.
+/// Joints attached to static or sleeping bodies use a dummy [`SolverBody`] for them.
+///
+/// [`SolverBody`]: crate::dynamics::solver::solver_body::SolverBody
+#[derive(Clone, Debug, Default)]
+pub struct JointGraphColor {
+    /// A bit vector representing the bodies that are part of this color, indexed by the body index.
+    pub body_set: BitVec,
+    /// The entities of the joints in this color.
+    pub joints: Vec<Entity>,
 }
 
 /// A stable identifier for a [`JointGraphEdge`].
@@ -115,6 +158,78 @@ impl JointGraph {
         &self.graph
     }
 
+    /// Returns the [`JointGraphColor`]s of the graph.
+    ///
+    /// The last color, [`COLOR_OVERFLOW_INDEX`], is used for joints that cannot find a color.
+    /// They are solved serially on a single thread.
+    ///
+    /// [`COLOR_OVERFLOW_INDEX`]: crate::dynamics::solver::constraint_graph::COLOR_OVERFLOW_INDEX
+    #[inline]
+    pub fn colors(&self) -> &[JointGraphColor] {
+        &self.colors
+    }
+
+    /// Returns mutable access to the [`JointGraphColor`]s of the graph.
+    #[inline]
+    pub fn colors_mut(&mut self) -> &mut [JointGraphColor] {
+        &mut self.colors
+    }
+
+    /// Adds a joint to a [`JointGraphColor`] that does not contain either of its non-static bodies,
+    /// and returns the index of the color.
+    ///
+    /// If no such color exists, the joint is added to the overflow color at [`COLOR_OVERFLOW_INDEX`].
+    ///
+    /// [`COLOR_OVERFLOW_INDEX`]: crate::dynamics::solver::constraint_graph::COLOR_OVERFLOW_INDEX
+    #[inline]
+    pub fn color_joint(
+        &mut self,
+        joint: Entity,
+        body1: Entity,
+        body2: Entity,
+        is_static1: bool,
+        is_static2: bool,
+    ) -> usize {
+        debug_assert!(!is_static1 || !is_static2);
+
+        let color_index = assign_color(
+            &mut self.colors,
+            |color| &mut color.body_set,
+            body1,
+            body2,
+            is_static1,
+            is_static2,
+        );
+
+        self.colors[color_index].joints.push(joint);
+
+        color_index
+    }
+
+    /// Marks the constraints of type `C` as assigned to colors for the current time step.
+    ///
+    /// Constraints of types that are not marked are solved serially by the XPBD solver.
+    #[inline]
+    pub fn mark_colored<C: 'static>(&mut self) {
+        self.colored_constraint_types.insert(TypeId::of::<C>());
+    }
+
+    /// Returns `true` if the constraints of type `C` have been assigned to colors for the current time step.
+    #[inline]
+    pub fn is_colored<C: 'static>(&self) -> bool {
+        self.colored_constraint_types.contains(&TypeId::of::<C>())
+    }
+
+    /// Removes all joints from the [`JointGraphColor`]s.
+    #[inline]
+    pub fn clear_colors(&mut self) {
+        for color in &mut self.colors {
+            color.body_set.clear();
+            color.joints.clear();
+        }
+        self.colored_constraint_types.clear();
+    }
+
     /// Returns the [`NodeIndex`] of the given entity in the joint graph.
     ///
     /// If the entity is not in the graph, `None` is returned.
@@ -310,3 +425,108 @@ impl JointGraph {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::solver::constraint_graph::{COLOR_OVERFLOW_INDEX, DYNAMIC_COLOR_COUNT};
+    use bevy::{ecs::entity::hash_map::EntityHashMap, platform::collections::HashSet};
+
+    /// Colors a joint for each pair of bodies, returning the bodies of each joint.
+    /// The first body of each pair is static if `is_static1` is `true`.
+    fn color_joints(
+        world: &mut World,
+        joint_graph: &mut JointGraph,
+        pairs: &[(Entity, Entity)],
+        is_static1: bool,
+    ) -> EntityHashMap<(Entity, Entity)> {
+        let mut joint_bodies = EntityHashMap::default();
+        for &(body1, body2) in pairs {
+            let joint = world.spawn_empty().id();
+            joint_graph.color_joint(joint, body1, body2, is_static1, false);
+            joint_bodies.insert(joint, (body1, body2));
+        }
+        joint_bodies
+    }
+
+    #[test]
+    fn joints_in_color_share_no_dynamic_bodies() {
+        let mut world = World::new();
+        let mut joint_graph = JointGraph::default();
+
+        let ground = world.spawn_empty().id();
+        let bodies: Vec<Entity> = (0..16).map(|_| world.spawn_empty().id()).collect();
+
+        // A chain of dynamic bodies, each of which is also attached to the static ground.
+        let chain: Vec<(Entity, Entity)> = bodies.windows(2).map(|w| (w[0], w[1])).collect();
+        let anchors: Vec<(Entity, Entity)> = bodies.iter().map(|&body| (ground, body)).collect();
+        let mut joint_bodies = color_joints(&mut world, &mut joint_graph, &chain, false);
+        joint_bodies.extend(color_joints(&mut world, &mut joint_graph, &anchors, true));
+
+        let colors = joint_graph.colors();
+        let mut colored_joints = 0;
+
+        for color in &colors[..COLOR_OVERFLOW_INDEX] {
+            // Static bodies can be shared by joints in the same color.
+            let mut color_bodies = HashSet::new();
+            for joint in &color.joints {
+                let (body1, body2) = joint_bodies[joint];
+                if body1 != ground {
+                    assert!(color_bodies.insert(body1));
+                }
+                assert!(color_bodies.insert(body2));
+            }
+            colored_joints += color.joints.len();
+        }
+
+        // Every joint is colored exactly once, and none of them overflow.
+        assert_eq!(colored_joints, joint_bodies.len());
+        assert!(colors[COLOR_OVERFLOW_INDEX].joints.is_empty());
+    }
+
+    #[test]
+    fn joints_overflow_when_colors_are_exhausted() {
+        let mut world = World::new();
+        let mut joint_graph = JointGraph::default();
+
+        // More joints share the hub body than there are dynamic colors.
+        let hub = world.spawn_empty().id();
+        let pairs: Vec<(Entity, Entity)> = (0..DYNAMIC_COLOR_COUNT + 5)
+            .map(|_| (hub, world.spawn_empty().id()))
+            .collect();
+        let joint_bodies = color_joints(&mut world, &mut joint_graph, &pairs, false);
+
+        let colors = joint_graph.colors();
+        for color in &colors[..DYNAMIC_COLOR_COUNT] {
+            assert_eq!(color.joints.len(), 1);
+        }
+        assert_eq!(colors[COLOR_OVERFLOW_INDEX].joints.len(), 5);
+
+        // The overflow joints are still tracked by the graph.
+        let total: usize = colors.iter().map(|color| color.joints.len()).sum();
+        assert_eq!(total, joint_bodies.len());
+
+        joint_graph.clear_colors();
+        assert!(
+            joint_graph
+                .colors()
+                .iter()
+                .all(|color| color.joints.is_empty())
+        );
+    }
+
+    #[test]
+    fn colored_constraint_types_are_cleared() {
+        use crate::prelude::{DistanceJoint, FixedJoint};
+
+        let mut joint_graph = JointGraph::default();
+
+        // Constraint types are colored separately, so joints of other types are solved serially.
+        joint_graph.mark_colored::<DistanceJoint>();
+        assert!(joint_graph.is_colored::<DistanceJoint>());
+        assert!(!joint_graph.is_colored::<FixedJoint>());
+
+        joint_graph.clear_colors();
+        assert!(!joint_graph.is_colored::<DistanceJoint>());
+    }
+}
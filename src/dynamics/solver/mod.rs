@@ -8,15 +8,18 @@ pub use plugin::*;
 pub mod constraint_graph;
 pub mod contact;
 pub mod islands;
+pub mod joint;
 pub mod joint_graph;
 pub mod schedule;
 pub mod softness_parameters;
 pub mod solver_body;
+mod target_joint;
 #[cfg(feature = "xpbd_joints")]
 pub mod xpbd;
 
 mod diagnostics;
 pub use diagnostics::SolverDiagnostics;
+pub use target_joint::TargetJointSolverData;
 
 use crate::{
     dynamics::solver::{joint_graph::JointGraphPlugin, solver_body::SolverBodyPlugin},
@@ -40,7 +43,8 @@ use bevy::{app::PluginGroupBuilder, prelude::*};
 /// | [`IslandPlugin`]                  | Manages [simulation islands](dynamics::solver::islands) for sleeping and waking.                                                                           |
 /// | [`IslandSleepingPlugin`]          | Manages sleeping and waking of [simulation islands](dynamics::solver::islands).                                                                            |
 /// | [`JointGraphPlugin`]              | Manages the [`JointGraph`](joint_graph::JointGraph) for each joint type.                                                                                   |
-/// | [`XpbdSolverPlugin`]              | Solves joints using Extended Position-Based Dynamics (XPBD). Requires the `xpbd_joints` feature.                                                           |
+/// | [`ImpulseJointSolverPlugin`]      | Solves [joints](dynamics::joints) with impulses together with contacts.                                                                                    |
+/// | [`XpbdSolverPlugin`]              | Solves coupling joints, compliant joints, and user constraints using Extended Position-Based Dynamics (XPBD). Requires the `xpbd_joints` feature.          |
 ///
 /// Refer to the documentation of the plugins for more information about their responsibilities and implementations.
 #[derive(Debug, Default)]
@@ -65,13 +69,17 @@ impl PluginGroup for SolverPlugins {
             .add(SolverSchedulePlugin)
             .add(IntegratorPlugin::default())
             .add(SolverPlugin::new_with_length_unit(self.length_unit))
+            .add(ImpulseJointSolverPlugin)
             .add(CcdPlugin)
             .add(IslandPlugin)
             .add(IslandSleepingPlugin)
             .add(JointGraphPlugin::<FixedJoint>::default())
             .add(JointGraphPlugin::<RevoluteJoint>::default())
             .add(JointGraphPlugin::<PrismaticJoint>::default())
-            .add(JointGraphPlugin::<DistanceJoint>::default());
+            .add(JointGraphPlugin::<DistanceJoint>::default())
+            .add(JointGraphPlugin::<PulleyJoint>::default())
+            .add(JointGraphPlugin::<GearJoint>::default())
+            .add(JointGraphPlugin::<RackAndPinionJoint>::default());
 
         #[cfg(feature = "3d")]
         let builder = builder.add(JointGraphPlugin::<SphericalJoint>::default());
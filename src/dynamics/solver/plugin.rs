@@ -7,6 +7,7 @@ use crate::{
                 COLOR_OVERFLOW_INDEX, ConstraintGraph, ContactManifoldHandle, GraphColor,
             },
             contact::ContactConstraint,
+            joint::{JointConstraint, store_joint_impulses},
             schedule::SubstepSolverSystems,
             softness_parameters::{SoftnessCoefficients, SoftnessParameters},
             solver_body::{SolverBody, SolverBodyInertia},
@@ -15,7 +16,10 @@ use crate::{
     prelude::*,
 };
 use bevy::{
-    ecs::{query::QueryData, system::lifetimeless::Read},
+    ecs::{
+        query::{QueryData, QueryFilter},
+        system::lifetimeless::Read,
+    },
     prelude::*,
 };
 use core::cmp::Ordering;
@@ -32,8 +36,10 @@ use core::cmp::Ordering;
 /// [Speculative collision](dynamics::ccd#speculative-collision) is used by default to prevent tunneling.
 /// Optional [sweep-based Continuous Collision Detection (CCD)](dynamics::ccd#swept-ccd) is handled by the [`CcdPlugin`].
 ///
-/// [Joints](dynamics::joints) and user constraints are currently solved using [Extended Position-Based Dynamics (XPBD)](super::xpbd)
-/// if the `xpbd_joints` feature is enabled. In the future, they may transition to an impulse-based approach as well.
+/// [Joints](dynamics::joints) are prepared by the [`ImpulseJointSolverPlugin`] and added to the [`ConstraintGraph`],
+/// where they are warm started and solved together with contacts using the same soft constraint formulation.
+/// Coupling joints and user constraints are solved using [Extended Position-Based Dynamics (XPBD)](super::xpbd)
+/// if the `xpbd_joints` feature is enabled.
 ///
 /// ## Solver Bodies
 ///
@@ -61,10 +67,10 @@ use core::cmp::Ordering;
 ///     5. [Solve constraints without bias to relax velocities](SubstepSolverSystems::Relax)
 /// 5. [Apply restitution](SolverSystems::Restitution)
 /// 6. [Write back solver body data to rigid bodies](SolverSystems::Finalize)
-/// 7. [Store contact impulses for next frame's warm starting](SolverSystems::StoreContactImpulses)
+/// 7. [Store contact and joint impulses for next frame's warm starting](SolverSystems::StoreContactImpulses)
 ///
-/// If the `xpbd_joints` feature is enabled, the [`XpbdSolverPlugin`] can also be added to solve joints
-/// using Extended Position-Based Dynamics (XPBD).
+/// If the `xpbd_joints` feature is enabled, the [`XpbdSolverPlugin`] can also be added to solve coupling joints
+/// and user constraints using Extended Position-Based Dynamics (XPBD).
 pub struct SolverPlugin {
     length_unit: Scalar,
 }
@@ -92,6 +98,9 @@ impl Plugin for SolverPlugin {
             .init_resource::<ContactConstraints>()
             .init_resource::<ConstraintGraph>();
 
+        // Prepare target joints. They are solved as soft constraints alongside contacts.
+        app.add_plugins(super::target_joint::plugin);
+
         if app
             .world()
             .get_resource::<PhysicsLengthUnit>()
@@ -115,8 +124,12 @@ impl Plugin for SolverPlugin {
         // Apply restitution.
         physics.add_systems(solve_restitution.in_set(SolverSystems::Restitution));
 
-        // Store the current contact impulses for the next frame's warm starting.
-        physics.add_systems(store_contact_impulses.in_set(SolverSystems::StoreContactImpulses));
+        // Store the current contact and joint impulses for the next frame's warm starting.
+        physics.add_systems(
+            (store_contact_impulses, store_joint_impulses)
+                .chain()
+                .in_set(SolverSystems::StoreContactImpulses),
+        );
 
         // Get the `SubstepSchedule`, and panic if it doesn't exist.
         let substeps = app
@@ -126,14 +139,32 @@ impl Plugin for SolverPlugin {
         // Warm start the impulses.
         // This applies the impulses stored from the previous substep,
         // which improves convergence.
-        substeps.add_systems(warm_start.in_set(SubstepSolverSystems::WarmStart));
+        substeps.add_systems(
+            (warm_start, super::target_joint::warm_start_target_joints)
+                .chain()
+                .in_set(SubstepSolverSystems::WarmStart),
+        );
 
         // Solve velocities using a position bias.
-        substeps.add_systems(solve_contacts::<true>.in_set(SubstepSolverSystems::SolveConstraints));
+        substeps.add_systems(
+            (
+                solve_constraints::<true>,
+                super::target_joint::solve_target_joints,
+            )
+                .chain()
+                .in_set(SubstepSolverSystems::SolveConstraints),
+        );
 
         // Relax biased velocities and impulses.
         // This reduces overshooting caused by warm starting.
-        substeps.add_systems(solve_contacts::<false>.in_set(SubstepSolverSystems::Relax));
+        substeps.add_systems(
+            (
+                solve_constraints::<false>,
+                super::target_joint::solve_target_joints,
+            )
+                .chain()
+                .in_set(SubstepSolverSystems::Relax),
+        );
 
         // Perform constraint damping.
         substeps.add_systems(
@@ -239,6 +270,25 @@ pub struct SolverConfig {
     /// Default: `1.5`
     pub contact_frequency_factor: Scalar,
 
+    /// The damping ratio used for stabilizing [joints](dynamics::joints)
+    /// solved by the [`ImpulseJointSolverPlugin`].
+    ///
+    /// Joints with a [`JointSoftness`] component use their own damping ratio instead.
+    ///
+    /// Default: `2.0`
+    pub joint_damping_ratio: Scalar,
+
+    /// Scales the frequency used for stabilizing [joints](dynamics::joints)
+    /// solved by the [`ImpulseJointSolverPlugin`].
+    ///
+    /// Like for contacts, the frequency is computed using the time step and substep count.
+    /// A higher frequency corrects joint drift faster, but can hurt stability.
+    ///
+    /// Joints with a [`JointSoftness`] component use their own frequency instead.
+    ///
+    /// Default: `2.0`
+    pub joint_frequency_factor: Scalar,
+
     /// The maximum speed at which overlapping bodies are pushed apart by the solver.
     ///
     /// With a small value, overlap is resolved gently and gradually, while large values
@@ -293,6 +343,8 @@ impl Default for SolverConfig {
         Self {
             contact_damping_ratio: 10.0,
             contact_frequency_factor: 1.5,
+            joint_damping_ratio: 2.0,
+            joint_frequency_factor: 2.0,
             max_overlap_solve_speed: 4.0,
             warm_start_coefficient: 1.0,
             restitution_threshold: 1.0,
@@ -459,10 +511,11 @@ fn warm_start(
     let start = crate::utils::Instant::now();
 
     // Warm start overflow constraints serially. They have lower priority, so they are solved first.
-    for constraint in constraint_graph.colors[COLOR_OVERFLOW_INDEX]
-        .contact_constraints
-        .iter_mut()
-    {
+    let overflow = &mut constraint_graph.colors[COLOR_OVERFLOW_INDEX];
+    for constraint in overflow.joint_constraints.iter() {
+        warm_start_joint_internal(&bodies, constraint, solver_config.warm_start_coefficient);
+    }
+    for constraint in overflow.contact_constraints.iter_mut() {
         warm_start_internal(&bodies, constraint, solver_config.warm_start_coefficient);
     }
 
@@ -471,8 +524,13 @@ fn warm_start(
         .colors
         .iter_mut()
         .take(COLOR_OVERFLOW_INDEX)
-        .filter(|color| !color.contact_constraints.is_empty())
+        .filter(|color| {
+            !color.contact_constraints.is_empty() || !color.joint_constraints.is_empty()
+        })
     {
+        crate::utils::par_for_each(&mut color.joint_constraints, 64, |_i, constraint| {
+            warm_start_joint_internal(&bodies, constraint, solver_config.warm_start_coefficient);
+        });
         crate::utils::par_for_each(&mut color.contact_constraints, 64, |_i, constraint| {
             warm_start_internal(&bodies, constraint, solver_config.warm_start_coefficient);
         });
@@ -514,8 +572,56 @@ fn warm_start_internal(
     constraint.warm_start(body1, body2, inertia1, inertia2, warm_start_coefficient);
 }
 
-/// Solves contacts by iterating through the given contact constraints
-/// and applying impulses to colliding rigid bodies.
+fn warm_start_joint_internal(
+    bodies: &Query<(&mut SolverBody, &SolverBodyInertia)>,
+    constraint: &JointConstraint,
+    warm_start_coefficient: Scalar,
+) {
+    with_joint_bodies(
+        bodies,
+        [constraint.body1, constraint.body2],
+        |body1, body2, inertia1, inertia2| {
+            constraint.warm_start(body1, body2, inertia1, inertia2, warm_start_coefficient);
+        },
+    );
+}
+
+/// Calls `f` with the solver bodies and inertias of the two bodies constrained by a joint.
+///
+/// Bodies without a [`SolverBody`], such as static bodies, are replaced with dummy bodies.
+pub(crate) fn with_joint_bodies<F: QueryFilter>(
+    bodies: &Query<(&mut SolverBody, &SolverBodyInertia), F>,
+    [entity1, entity2]: [Entity; 2],
+    f: impl FnOnce(&mut SolverBody, &mut SolverBody, &SolverBodyInertia, &SolverBodyInertia),
+) {
+    let mut dummy_body1 = SolverBody::DUMMY;
+    let mut dummy_body2 = SolverBody::DUMMY;
+
+    let (mut body1, mut inertia1) = (&mut dummy_body1, &SolverBodyInertia::DUMMY);
+    let (mut body2, mut inertia2) = (&mut dummy_body2, &SolverBodyInertia::DUMMY);
+
+    // Get the solver bodies for the two jointed entities.
+    if let Ok((body, inertia)) = unsafe { bodies.get_unchecked(entity1) } {
+        body1 = body.into_inner();
+        inertia1 = inertia;
+    }
+    if let Ok((body, inertia)) = unsafe { bodies.get_unchecked(entity2) } {
+        body2 = body.into_inner();
+        inertia2 = inertia;
+    }
+
+    // If a body has a higher dominance, it is treated as a static or kinematic body.
+    match (inertia1.dominance() - inertia2.dominance()).cmp(&0) {
+        Ordering::Greater => inertia1 = &SolverBodyInertia::DUMMY,
+        Ordering::Less => inertia2 = &SolverBodyInertia::DUMMY,
+        _ => {}
+    }
+
+    f(body1, body2, inertia1, inertia2);
+}
+
+/// Solves joints and contacts by iterating through the joint and contact constraints
+/// in the [`ConstraintGraph`] and applying impulses to the rigid bodies.
 ///
 /// This solve is done `iterations` times. With a substepped solver,
 /// `iterations` should typically be `1`, as substeps will handle the iteration.
@@ -528,7 +634,7 @@ fn warm_start_internal(
 /// See [`SubstepSolverSystems::SolveConstraints`] and [`SubstepSolverSystems::Relax`] for more information.
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
-fn solve_contacts<const USE_BIAS: bool>(
+fn solve_constraints<const USE_BIAS: bool>(
     bodies: Query<(&mut SolverBody, &SolverBodyInertia)>,
     mut constraint_graph: ResMut<ConstraintGraph>,
     solver_config: Res<SolverConfig>,
@@ -542,10 +648,11 @@ fn solve_contacts<const USE_BIAS: bool>(
     let max_overlap_solve_speed = solver_config.max_overlap_solve_speed * length_unit.0;
 
     // Solve overflow constraints serially. They have lower priority, so they are solved first.
-    for constraint in constraint_graph.colors[COLOR_OVERFLOW_INDEX]
-        .contact_constraints
-        .iter_mut()
-    {
+    let overflow = &mut constraint_graph.colors[COLOR_OVERFLOW_INDEX];
+    for constraint in overflow.joint_constraints.iter_mut() {
+        solve_joint_internal::<USE_BIAS>(&bodies, constraint, delta_secs);
+    }
+    for constraint in overflow.contact_constraints.iter_mut() {
         solve_contacts_internal::<USE_BIAS>(
             &bodies,
             constraint,
@@ -554,13 +661,18 @@ fn solve_contacts<const USE_BIAS: bool>(
         );
     }
 
-    // Solve contact constraints in each color in parallel.
+    // Solve joint and contact constraints in each color in parallel.
     for color in constraint_graph
         .colors
         .iter_mut()
         .take(COLOR_OVERFLOW_INDEX)
-        .filter(|color| !color.contact_constraints.is_empty())
+        .filter(|color| {
+            !color.contact_constraints.is_empty() || !color.joint_constraints.is_empty()
+        })
     {
+        crate::utils::par_for_each(&mut color.joint_constraints, 64, |_i, constraint| {
+            solve_joint_internal::<USE_BIAS>(&bodies, constraint, delta_secs);
+        });
         crate::utils::par_for_each(&mut color.contact_constraints, 64, |_i, constraint| {
             solve_contacts_internal::<USE_BIAS>(
                 &bodies,
@@ -578,6 +690,20 @@ fn solve_contacts<const USE_BIAS: bool>(
     }
 }
 
+fn solve_joint_internal<const USE_BIAS: bool>(
+    bodies: &Query<(&mut SolverBody, &SolverBodyInertia)>,
+    constraint: &mut JointConstraint,
+    delta_secs: Scalar,
+) {
+    with_joint_bodies(
+        bodies,
+        [constraint.body1, constraint.body2],
+        |body1, body2, inertia1, inertia2| {
+            constraint.solve(body1, body2, inertia1, inertia2, delta_secs, USE_BIAS);
+        },
+    );
+}
+
 fn solve_contacts_internal<const USE_BIAS: bool>(
     bodies: &Query<(&mut SolverBody, &SolverBodyInertia)>,
     constraint: &mut ContactConstraint,
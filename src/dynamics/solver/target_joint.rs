@@ -0,0 +1,184 @@
+//! Solver systems for [`TargetJoint`]s.
+//!
+//! Target joints are solved as soft velocity constraints alongside contacts,
+//! using the same [soft constraint](super::softness_parameters) formulation.
+
+use crate::{
+    dynamics::solver::{
+        softness_parameters::{SoftnessCoefficients, SoftnessParameters},
+        solver_body::{SolverBody, SolverBodyInertia},
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Prepares target joints and writes back their forces.
+///
+/// The joints are warm started and solved by the [`SolverPlugin`] after contacts and other joints.
+pub(super) fn plugin(app: &mut App) {
+    app.register_required_components::<TargetJoint, TargetJointSolverData>();
+
+    app.add_systems(
+        PhysicsSchedule,
+        prepare_target_joints.in_set(SolverSystems::PrepareJoints),
+    );
+    app.add_systems(
+        PhysicsSchedule,
+        writeback_target_joint_forces.in_set(SolverSystems::Finalize),
+    );
+}
+
+/// Constraint data required by the solver for a [`TargetJoint`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct TargetJointSolverData {
+    /// The world-space anchor point relative to the center of mass of the body
+    /// at the start of the time step.
+    pub(super) world_r: Vector,
+    /// The offset from the target to the center of mass of the body at the start of the time step.
+    pub(super) target_offset: Vector,
+    /// The soft constraint coefficients for the substep.
+    pub(super) softness: SoftnessCoefficients,
+    /// The maximum impulse that can be applied in a substep.
+    pub(super) max_impulse: Scalar,
+    /// The accumulated impulse, used for warm starting.
+    pub(super) impulse: Vector,
+    /// Whether the joint is active for the time step.
+    pub(super) active: bool,
+}
+
+impl Default for TargetJointSolverData {
+    fn default() -> Self {
+        Self {
+            world_r: Vector::ZERO,
+            target_offset: Vector::ZERO,
+            softness: SoftnessCoefficients {
+                bias: 0.0,
+                mass_scale: 1.0,
+                impulse_scale: 0.0,
+            },
+            max_impulse: 0.0,
+            impulse: Vector::ZERO,
+            active: false,
+        }
+    }
+}
+
+fn prepare_target_joints(
+    mut joints: Query<(&TargetJoint, &mut TargetJointSolverData), Without<JointDisabled>>,
+    bodies: Query<(&Position, &Rotation, &ComputedCenterOfMass), With<SolverBody>>,
+    substep_time: Res<Time<Substeps>>,
+) {
+    let h = substep_time.delta_secs_f64() as Scalar;
+
+    for (joint, mut solver_data) in &mut joints {
+        let (Some(local_anchor), Ok((position, rotation, center_of_mass))) =
+            (joint.local_anchor(), bodies.get(joint.body))
+        else {
+            solver_data.active = false;
+            solver_data.impulse = Vector::ZERO;
+            continue;
+        };
+
+        let world_center_of_mass = position.0 + rotation * center_of_mass.0;
+
+        solver_data.world_r = rotation * (local_anchor - center_of_mass.0);
+        solver_data.target_offset = world_center_of_mass - joint.target;
+        solver_data.softness =
+            SoftnessParameters::new(joint.damping_ratio, joint.frequency).compute_coefficients(h);
+        solver_data.max_impulse = joint.max_force * h;
+        solver_data.active = true;
+    }
+}
+
+pub(super) fn warm_start_target_joints(
+    mut bodies: Query<(&mut SolverBody, &SolverBodyInertia)>,
+    joints: Query<(&TargetJoint, &TargetJointSolverData), Without<JointDisabled>>,
+) {
+    for (joint, solver_data) in &joints {
+        if !solver_data.active {
+            continue;
+        }
+
+        let Ok((mut body, inertia)) = bodies.get_mut(joint.body) else {
+            continue;
+        };
+
+        let r = body.delta_rotation * solver_data.world_r;
+        let p = solver_data.impulse;
+
+        body.linear_velocity += p * inertia.effective_inv_mass();
+        body.angular_velocity += inertia.effective_inv_angular_inertia() * cross(r, p);
+    }
+}
+
+pub(super) fn solve_target_joints(
+    mut bodies: Query<(&mut SolverBody, &SolverBodyInertia)>,
+    mut joints: Query<(&TargetJoint, &mut TargetJointSolverData), Without<JointDisabled>>,
+) {
+    for (joint, mut solver_data) in &mut joints {
+        if !solver_data.active {
+            continue;
+        }
+
+        let Ok((mut body, inertia)) = bodies.get_mut(joint.body) else {
+            continue;
+        };
+
+        let inv_mass = inertia.effective_inv_mass();
+        let inv_angular_inertia = inertia.effective_inv_angular_inertia();
+
+        let r = body.delta_rotation * solver_data.world_r;
+
+        // The current offset from the target to the anchor.
+        let separation = solver_data.target_offset + body.delta_position + r;
+
+        // The effective mass matrix `K = M⁻¹ + [r]ᵀ I⁻¹ [r]`.
+        #[cfg(feature = "2d")]
+        let k = {
+            let k11 = inv_mass.x + inv_angular_inertia * r.y * r.y;
+            let k12 = -inv_angular_inertia * r.x * r.y;
+            let k22 = inv_mass.y + inv_angular_inertia * r.x * r.x;
+            Matrix::from_cols(Vector::new(k11, k12), Vector::new(k12, k22))
+        };
+        #[cfg(feature = "3d")]
+        let k = {
+            let column =
+                |axis: Vector| inv_mass * axis - r.cross(inv_angular_inertia * r.cross(axis));
+            Matrix::from_cols(column(Vector::X), column(Vector::Y), column(Vector::Z))
+        };
+
+        if k.determinant().abs() <= Scalar::EPSILON {
+            continue;
+        }
+
+        // The target joint is always soft, even when relaxing velocities.
+        let softness = solver_data.softness;
+        let velocity = body.velocity_at_point(r);
+        let bias = softness.bias * separation;
+
+        let mut impulse = -softness.mass_scale * (k.inverse() * (velocity + bias))
+            - softness.impulse_scale * solver_data.impulse;
+
+        // Clamp the accumulated impulse to the maximum force.
+        let old_impulse = solver_data.impulse;
+        solver_data.impulse = (old_impulse + impulse).clamp_length_max(solver_data.max_impulse);
+        impulse = solver_data.impulse - old_impulse;
+
+        body.linear_velocity += impulse * inv_mass;
+        body.angular_velocity += inv_angular_inertia * cross(r, impulse);
+    }
+}
+
+fn writeback_target_joint_forces(
+    mut joints: Query<(&TargetJointSolverData, &mut JointForces)>,
+    substep_time: Res<Time<Substeps>>,
+) {
+    let h = substep_time.delta_secs_f64() as Scalar;
+
+    for (solver_data, mut forces) in &mut joints {
+        forces.set_force(solver_data.impulse * h.recip_or_zero());
+    }
+}
@@ -0,0 +1,153 @@
+use super::{
+    JointCoordinateShared, RevoluteJointSolverData, angular_inverse_mass, apply_angular_impulse,
+    delta_angle, revolute_axis, revolute_start_angle,
+};
+use crate::{
+    dynamics::solver::{
+        solver_body::{SolverBody, SolverBodyInertia},
+        xpbd::*,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Constraint data required by the XPBD constraint solver for a [`GearJoint`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct GearJointSolverData {
+    /// The angles of the gears relative to the other bodies of their revolute joints
+    /// at the start of the time step, or `None` if a gear is not attached to its revolute joint.
+    pub(super) start_angles: Option<[Scalar; 2]>,
+    /// The world-space rotation axes of the gears at the start of the time step.
+    pub(super) axes: [AngularVector; 2],
+    /// The unwrapped angles of the gears.
+    pub(super) angles: [JointCoordinateShared; 2],
+    /// The value of `angle1 + ratio * angle2` that the joint maintains.
+    pub(super) rest: Option<Scalar>,
+    pub(super) total_lagrange: AngularVector,
+}
+
+impl XpbdConstraintSolverData for GearJointSolverData {
+    fn clear_lagrange_multipliers(&mut self) {
+        self.total_lagrange = AngularVector::ZERO;
+    }
+
+    fn total_rotation_lagrange(&self) -> AngularVector {
+        self.total_lagrange
+    }
+}
+
+/// Returns the sign of the angle of a revolute joint for the given gear body.
+fn gear_sign(joint: &RevoluteJoint, gear: Entity) -> Option<Scalar> {
+    if joint.body2 == gear {
+        Some(1.0)
+    } else if joint.body1 == gear {
+        Some(-1.0)
+    } else {
+        None
+    }
+}
+
+/// Reads the angles and axes of the [`RevoluteJoint`]s coupled by [`GearJoint`]s.
+/// Must run after the [`RevoluteJoint`]s have been prepared.
+pub(crate) fn update_gear_joint_coordinates(
+    mut joints: Query<
+        (&GearJoint, &mut GearJointSolverData),
+        (Without<RigidBody>, Without<JointDisabled>),
+    >,
+    revolute_joints: Query<(&RevoluteJoint, &RevoluteJointSolverData), Without<JointDisabled>>,
+) {
+    for (joint, mut solver_data) in &mut joints {
+        solver_data.start_angles = None;
+
+        let Ok([(revolute1, revolute_data1), (revolute2, revolute_data2)]) =
+            revolute_joints.get_many([joint.joint1, joint.joint2])
+        else {
+            continue;
+        };
+        let (Some(sign1), Some(sign2)) = (
+            gear_sign(revolute1, joint.body1),
+            gear_sign(revolute2, joint.body2),
+        ) else {
+            continue;
+        };
+
+        // The angles are flipped for gears that are the first body of their revolute joint,
+        // so that they increase when the gears rotate about the hinge axes.
+        solver_data.start_angles = Some([
+            sign1 * revolute_start_angle(revolute_data1),
+            sign2 * revolute_start_angle(revolute_data2),
+        ]);
+        solver_data.axes = [revolute_axis(revolute_data1), revolute_axis(revolute_data2)];
+    }
+}
+
+impl XpbdConstraint<2> for GearJoint {
+    type SolverData = GearJointSolverData;
+
+    fn prepare(
+        &mut self,
+        _bodies: [&RigidBodyQueryReadOnlyItem; 2],
+        solver_data: &mut GearJointSolverData,
+    ) {
+        let Some(start_angles) = solver_data.start_angles else {
+            return;
+        };
+
+        solver_data.angles[0].prepare(start_angles[0], true);
+        solver_data.angles[1].prepare(start_angles[1], true);
+
+        // Initialize the rest value from the initial configuration.
+        if solver_data.rest.is_none() {
+            solver_data.rest = Some(
+                solver_data.angles[0].unwrapped + self.ratio * solver_data.angles[1].unwrapped,
+            );
+        }
+    }
+
+    fn solve(
+        &mut self,
+        bodies: [&mut SolverBody; 2],
+        inertias: [&SolverBodyInertia; 2],
+        solver_data: &mut GearJointSolverData,
+        dt: Scalar,
+    ) {
+        let [body1, body2] = bodies;
+        let [inertia1, inertia2] = inertias;
+
+        let (Some(start_angles), Some(rest)) = (solver_data.start_angles, solver_data.rest) else {
+            return;
+        };
+        let [axis1, axis2] = solver_data.axes;
+
+        // Compute the current angles of the gears.
+        let angle1 =
+            solver_data.angles[0].current(start_angles[0] + delta_angle(body1, axis1), true);
+        let angle2 =
+            solver_data.angles[1].current(start_angles[1] + delta_angle(body2, axis2), true);
+
+        let c = angle1 + self.ratio * angle2 - rest;
+
+        // Compute generalized inverse masses. The gradient for the second gear is scaled by the ratio.
+        let w1 = angular_inverse_mass(inertia1, axis1);
+        let w2 = angular_inverse_mass(inertia2, axis2);
+        let w = [w1, self.ratio * self.ratio * w2];
+
+        // Compute Lagrange multiplier update, essentially the signed magnitude of the correction.
+        let delta_lagrange = compute_lagrange_update(0.0, c, &w, self.compliance, dt);
+
+        if delta_lagrange.abs() <= Scalar::EPSILON {
+            return;
+        }
+
+        let impulse1 = delta_lagrange * axis1;
+        let impulse2 = delta_lagrange * self.ratio * axis2;
+        solver_data.total_lagrange += impulse1;
+
+        // Rotate the gears towards the configuration given by the ratio.
+        apply_angular_impulse(body1, inertia1, impulse1);
+        apply_angular_impulse(body2, inertia2, impulse2);
+    }
+}
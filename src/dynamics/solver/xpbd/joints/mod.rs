@@ -1,18 +1,33 @@
 //! XPBD joint constraints.
 
 mod shared;
-pub use shared::{FixedAngleConstraintShared, PointConstraintShared};
+pub use shared::{
+    FixedAngleConstraintShared, JointCoordinateShared, OrientationDriveShared,
+    PointConstraintShared,
+};
+use shared::{
+    angular_inverse_mass, apply_angular_impulse, delta_angle, prismatic_axis,
+    prismatic_start_translation, revolute_axis, revolute_start_angle,
+};
 
 mod distance;
 mod fixed;
+mod gear;
 mod prismatic;
+mod pulley;
+mod rack_and_pinion;
 mod revolute;
 #[cfg(feature = "3d")]
 mod spherical;
 
 pub use distance::DistanceJointSolverData;
 pub use fixed::FixedJointSolverData;
+pub use gear::GearJointSolverData;
+pub(crate) use gear::update_gear_joint_coordinates;
 pub use prismatic::PrismaticJointSolverData;
+pub use pulley::PulleyJointSolverData;
+pub use rack_and_pinion::RackAndPinionJointSolverData;
+pub(crate) use rack_and_pinion::update_rack_and_pinion_joint_coordinates;
 pub use revolute::RevoluteJointSolverData;
 #[cfg(feature = "3d")]
 pub use spherical::SphericalJointSolverData;
@@ -0,0 +1,150 @@
+use crate::{
+    dynamics::solver::{
+        solver_body::{SolverBody, SolverBodyInertia},
+        xpbd::*,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Constraint data required by the XPBD constraint solver for a [`PulleyJoint`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct PulleyJointSolverData {
+    pub(super) world_r1: Vector,
+    pub(super) world_r2: Vector,
+    /// The offset from the first ground anchor to the center of mass of the first body
+    /// at the start of the time step.
+    pub(super) ground_offset1: Vector,
+    /// The offset from the second ground anchor to the center of mass of the second body
+    /// at the start of the time step.
+    pub(super) ground_offset2: Vector,
+    pub(super) total_lagrange: Vector,
+}
+
+impl XpbdConstraintSolverData for PulleyJointSolverData {
+    fn clear_lagrange_multipliers(&mut self) {
+        self.total_lagrange = Vector::ZERO;
+    }
+
+    fn total_position_lagrange(&self) -> Vector {
+        self.total_lagrange
+    }
+}
+
+impl XpbdConstraint<2> for PulleyJoint {
+    type SolverData = PulleyJointSolverData;
+
+    fn prepare(
+        &mut self,
+        bodies: [&RigidBodyQueryReadOnlyItem; 2],
+        solver_data: &mut PulleyJointSolverData,
+    ) {
+        let [body1, body2] = bodies;
+
+        let JointAnchor::Local(local_anchor1) = self.anchor1 else {
+            return;
+        };
+        let JointAnchor::Local(local_anchor2) = self.anchor2 else {
+            return;
+        };
+
+        solver_data.world_r1 = body1.rotation * (local_anchor1 - body1.center_of_mass.0);
+        solver_data.world_r2 = body2.rotation * (local_anchor2 - body2.center_of_mass.0);
+        solver_data.ground_offset1 =
+            body1.position.0 + body1.rotation * body1.center_of_mass.0 - self.ground_anchor1;
+        solver_data.ground_offset2 =
+            body2.position.0 + body2.rotation * body2.center_of_mass.0 - self.ground_anchor2;
+
+        // Initialize the rope length from the initial configuration.
+        if self.length.is_none() {
+            let length1 = (solver_data.ground_offset1 + solver_data.world_r1).length();
+            let length2 = (solver_data.ground_offset2 + solver_data.world_r2).length();
+            self.length = Some(length1 + self.ratio * length2);
+        }
+    }
+
+    fn solve(
+        &mut self,
+        bodies: [&mut SolverBody; 2],
+        inertias: [&SolverBodyInertia; 2],
+        solver_data: &mut PulleyJointSolverData,
+        dt: Scalar,
+    ) {
+        let [body1, body2] = bodies;
+        let [inertia1, inertia2] = inertias;
+
+        let Some(length) = self.length else {
+            return;
+        };
+
+        let world_r1 = body1.delta_rotation * solver_data.world_r1;
+        let world_r2 = body2.delta_rotation * solver_data.world_r2;
+
+        // The rope segments from the ground anchors to the anchors on the bodies.
+        let segment1 = solver_data.ground_offset1 + body1.delta_position + world_r1;
+        let segment2 = solver_data.ground_offset2 + body2.delta_position + world_r2;
+        let length1 = segment1.length();
+        let length2 = segment2.length();
+
+        // The rope can only resist stretching.
+        let c = length1 + self.ratio * length2 - length;
+        if c <= Scalar::EPSILON || length1 <= Scalar::EPSILON || length2 <= Scalar::EPSILON {
+            return;
+        }
+
+        let dir1 = segment1 / length1;
+        let dir2 = segment2 / length2;
+
+        let inv_mass1 = inertia1.effective_inv_mass();
+        let inv_mass2 = inertia2.effective_inv_mass();
+        let inv_angular_inertia1 = inertia1.effective_inv_angular_inertia();
+        let inv_angular_inertia2 = inertia2.effective_inv_angular_inertia();
+
+        // Compute generalized inverse masses. The gradient for the second body is scaled by the ratio.
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(
+            self,
+            inv_mass1.max_element(),
+            inv_angular_inertia1,
+            world_r1,
+            dir1,
+        );
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(
+            self,
+            inv_mass2.max_element(),
+            inv_angular_inertia2,
+            world_r2,
+            dir2,
+        );
+        let w = [w1, self.ratio * self.ratio * w2];
+
+        // Compute Lagrange multiplier update, essentially the signed magnitude of the correction.
+        let delta_lagrange = compute_lagrange_update(0.0, c, &w, self.compliance, dt);
+        let impulse1 = delta_lagrange * dir1;
+        let impulse2 = delta_lagrange * self.ratio * dir2;
+        solver_data.total_lagrange += impulse1;
+
+        // Pull both bodies towards their ground anchors.
+        body1.delta_position += impulse1 * inv_mass1;
+        body2.delta_position += impulse2 * inv_mass2;
+
+        #[cfg(feature = "2d")]
+        {
+            let delta_angle = Self::get_delta_rot(inv_angular_inertia1, world_r1, impulse1);
+            body1.delta_rotation = body1.delta_rotation.add_angle_fast(delta_angle);
+            let delta_angle = Self::get_delta_rot(inv_angular_inertia2, world_r2, impulse2);
+            body2.delta_rotation = body2.delta_rotation.add_angle_fast(delta_angle);
+        }
+        #[cfg(feature = "3d")]
+        {
+            let delta_quat = Self::get_delta_rot(inv_angular_inertia1, world_r1, impulse1);
+            body1.delta_rotation.0 = delta_quat * body1.delta_rotation.0;
+            let delta_quat = Self::get_delta_rot(inv_angular_inertia2, world_r2, impulse2);
+            body2.delta_rotation.0 = delta_quat * body2.delta_rotation.0;
+        }
+    }
+}
+
+impl PositionConstraint for PulleyJoint {}
@@ -0,0 +1,172 @@
+use super::{
+    JointCoordinateShared, PrismaticJointSolverData, RevoluteJointSolverData, angular_inverse_mass,
+    apply_angular_impulse, delta_angle, prismatic_axis, prismatic_start_translation, revolute_axis,
+    revolute_start_angle,
+};
+use crate::{
+    dynamics::solver::{
+        solver_body::{SolverBody, SolverBodyInertia},
+        xpbd::*,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Constraint data required by the XPBD constraint solver for a [`RackAndPinionJoint`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq)]
+pub struct RackAndPinionJointSolverData {
+    /// The angle of the pinion and the translation of the rack relative to the other bodies of their joints
+    /// at the start of the time step, or `None` if the pinion or rack is not attached to its joint.
+    pub(super) start_coordinates: Option<[Scalar; 2]>,
+    /// The world-space rotation axis of the pinion at the start of the time step.
+    pub(super) pinion_axis: AngularVector,
+    /// The world-space translation axis of the rack at the start of the time step.
+    pub(super) rack_axis: Vector,
+    /// The unwrapped angle of the pinion.
+    pub(super) angle: JointCoordinateShared,
+    /// The translation of the rack.
+    pub(super) translation: JointCoordinateShared,
+    /// The value of `translation - ratio * angle` that the joint maintains.
+    pub(super) rest: Option<Scalar>,
+    pub(super) total_lagrange: Vector,
+}
+
+impl XpbdConstraintSolverData for RackAndPinionJointSolverData {
+    fn clear_lagrange_multipliers(&mut self) {
+        self.total_lagrange = Vector::ZERO;
+    }
+
+    fn total_position_lagrange(&self) -> Vector {
+        self.total_lagrange
+    }
+}
+
+/// Returns the sign of the coordinate of a joint between `body1` and `body2` for the given body.
+fn coordinate_sign(body1: Entity, body2: Entity, body: Entity) -> Option<Scalar> {
+    if body2 == body {
+        Some(1.0)
+    } else if body1 == body {
+        Some(-1.0)
+    } else {
+        None
+    }
+}
+
+/// Reads the angles, translations, and axes of the [`RevoluteJoint`]s and [`PrismaticJoint`]s
+/// coupled by [`RackAndPinionJoint`]s.
+/// Must run after the [`RevoluteJoint`]s and [`PrismaticJoint`]s have been prepared.
+pub(crate) fn update_rack_and_pinion_joint_coordinates(
+    mut joints: Query<
+        (&RackAndPinionJoint, &mut RackAndPinionJointSolverData),
+        (Without<RigidBody>, Without<JointDisabled>),
+    >,
+    revolute_joints: Query<(&RevoluteJoint, &RevoluteJointSolverData), Without<JointDisabled>>,
+    prismatic_joints: Query<(&PrismaticJoint, &PrismaticJointSolverData), Without<JointDisabled>>,
+) {
+    for (joint, mut solver_data) in &mut joints {
+        solver_data.start_coordinates = None;
+
+        let (Ok((revolute, revolute_data)), Ok((prismatic, prismatic_data))) = (
+            revolute_joints.get(joint.revolute_joint),
+            prismatic_joints.get(joint.prismatic_joint),
+        ) else {
+            continue;
+        };
+        let (Some(pinion_sign), Some(rack_sign)) = (
+            coordinate_sign(revolute.body1, revolute.body2, joint.pinion),
+            coordinate_sign(prismatic.body1, prismatic.body2, joint.rack),
+        ) else {
+            continue;
+        };
+
+        // The coordinates are flipped for bodies that are the first body of their joint,
+        // so that they increase when the pinion rotates about the hinge axis
+        // and when the rack moves along the free axis.
+        solver_data.start_coordinates = Some([
+            pinion_sign * revolute_start_angle(revolute_data),
+            rack_sign * prismatic_start_translation(prismatic_data),
+        ]);
+        solver_data.pinion_axis = revolute_axis(revolute_data);
+        solver_data.rack_axis = prismatic_axis(prismatic_data);
+    }
+}
+
+impl XpbdConstraint<2> for RackAndPinionJoint {
+    type SolverData = RackAndPinionJointSolverData;
+
+    fn prepare(
+        &mut self,
+        _bodies: [&RigidBodyQueryReadOnlyItem; 2],
+        solver_data: &mut RackAndPinionJointSolverData,
+    ) {
+        let Some([start_angle, start_translation]) = solver_data.start_coordinates else {
+            return;
+        };
+
+        solver_data.angle.prepare(start_angle, true);
+        solver_data.translation.prepare(start_translation, false);
+
+        // Initialize the rest value from the initial configuration.
+        if solver_data.rest.is_none() {
+            solver_data.rest =
+                Some(solver_data.translation.unwrapped - self.ratio * solver_data.angle.unwrapped);
+        }
+    }
+
+    fn solve(
+        &mut self,
+        bodies: [&mut SolverBody; 2],
+        inertias: [&SolverBodyInertia; 2],
+        solver_data: &mut RackAndPinionJointSolverData,
+        dt: Scalar,
+    ) {
+        let [pinion, rack] = bodies;
+        let [pinion_inertia, rack_inertia] = inertias;
+
+        let (Some([start_angle, start_translation]), Some(rest)) =
+            (solver_data.start_coordinates, solver_data.rest)
+        else {
+            return;
+        };
+        let pinion_axis = solver_data.pinion_axis;
+        let rack_axis = solver_data.rack_axis;
+
+        // Compute the current angle of the pinion and translation of the rack.
+        let angle = solver_data
+            .angle
+            .current(start_angle + delta_angle(pinion, pinion_axis), true);
+        let translation = solver_data.translation.current(
+            start_translation + rack.delta_position.dot(rack_axis),
+            false,
+        );
+
+        let c = translation - self.ratio * angle - rest;
+
+        // Compute generalized inverse masses. The gradient for the pinion is scaled by the ratio.
+        let rack_inv_mass = rack_inertia.effective_inv_mass();
+        let w1 = angular_inverse_mass(pinion_inertia, pinion_axis);
+        let w2 = rack_axis.dot(rack_inv_mass * rack_axis);
+        let w = [self.ratio * self.ratio * w1, w2];
+
+        // Compute Lagrange multiplier update, essentially the signed magnitude of the correction.
+        let delta_lagrange = compute_lagrange_update(0.0, c, &w, self.compliance, dt);
+
+        if delta_lagrange.abs() <= Scalar::EPSILON {
+            return;
+        }
+
+        let force = delta_lagrange * rack_axis;
+        solver_data.total_lagrange += force;
+
+        // Move the rack and rotate the pinion towards the configuration given by the ratio.
+        rack.delta_position += rack_inv_mass * force;
+        apply_angular_impulse(
+            pinion,
+            pinion_inertia,
+            -delta_lagrange * self.ratio * pinion_axis,
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::PointConstraintShared;
+use super::{OrientationDriveShared, PointConstraintShared};
 use crate::{
     dynamics::solver::{
         solver_body::{SolverBody, SolverBodyInertia},
@@ -27,6 +27,7 @@ pub struct RevoluteJointSolverData {
     pub(super) b2: Vector,
     pub(super) total_align_lagrange: AngularVector,
     pub(super) total_limit_lagrange: AngularVector,
+    pub(super) drive: OrientationDriveShared,
 }
 
 impl XpbdConstraintSolverData for RevoluteJointSolverData {
@@ -34,6 +35,7 @@ impl XpbdConstraintSolverData for RevoluteJointSolverData {
         self.point_constraint.clear_lagrange_multipliers();
         self.total_align_lagrange = AngularVector::ZERO;
         self.total_limit_lagrange = AngularVector::ZERO;
+        self.drive.clear_lagrange_multipliers();
     }
 
     fn total_position_lagrange(&self) -> Vector {
@@ -41,7 +43,7 @@ impl XpbdConstraintSolverData for RevoluteJointSolverData {
     }
 
     fn total_rotation_lagrange(&self) -> AngularVector {
-        self.total_align_lagrange + self.total_limit_lagrange
+        self.total_align_lagrange + self.total_limit_lagrange + self.drive.total_rotation_lagrange()
     }
 }
 
@@ -87,6 +89,17 @@ impl XpbdConstraint<2> for RevoluteJoint {
             solver_data.b2 =
                 *bodies[1].rotation * local_basis2 * self.hinge_axis.any_orthonormal_vector();
         }
+
+        // Prepare the orientation drive.
+        if let Some(drive) = &self.orientation_drive {
+            solver_data.drive.prepare(
+                bodies[0].rotation,
+                bodies[1].rotation,
+                local_basis1,
+                local_basis2,
+                drive,
+            );
+        }
     }
 
     fn solve(
@@ -122,6 +135,11 @@ impl XpbdConstraint<2> for RevoluteJoint {
             );
         }
 
+        // Drive the relative rotation towards the target orientation
+        if let Some(drive) = &self.orientation_drive {
+            solver_data.drive.solve([body1, body2], inertias, drive, dt);
+        }
+
         // Apply angle limits when rotating around the free axis
         self.apply_angle_limits(
             body1,
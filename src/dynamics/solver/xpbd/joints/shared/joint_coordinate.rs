@@ -0,0 +1,140 @@
+use crate::{
+    dynamics::solver::{
+        solver_body::{SolverBody, SolverBodyInertia},
+        xpbd::{
+            joints::{PrismaticJointSolverData, RevoluteJointSolverData},
+            *,
+        },
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Tracks a joint coordinate across time steps, unwrapping angles so that
+/// the coordinate is continuous even after several full revolutions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct JointCoordinateShared {
+    /// The raw value of the coordinate at the start of the time step.
+    pub start: Scalar,
+    /// The accumulated value of the coordinate at the start of the time step.
+    pub unwrapped: Scalar,
+    /// Whether the coordinate has been initialized.
+    pub initialized: bool,
+}
+
+impl JointCoordinateShared {
+    /// Updates the coordinate with its raw value at the start of the time step.
+    ///
+    /// If `angular` is `true`, the change in the coordinate is wrapped to the `[-π, π]` range.
+    pub fn prepare(&mut self, start: Scalar, angular: bool) {
+        if self.initialized {
+            let delta = start - self.start;
+            self.unwrapped += if angular { wrap_angle(delta) } else { delta };
+        } else {
+            self.unwrapped = start;
+            self.initialized = true;
+        }
+        self.start = start;
+    }
+
+    /// Returns the accumulated value of the coordinate given its current raw value.
+    pub fn current(&self, current: Scalar, angular: bool) -> Scalar {
+        let delta = current - self.start;
+        self.unwrapped + if angular { wrap_angle(delta) } else { delta }
+    }
+}
+
+/// Wraps the given angle to the `[-π, π]` range.
+fn wrap_angle(angle: Scalar) -> Scalar {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Returns the angle of a [`RevoluteJoint`] at the start of the time step.
+pub(crate) fn revolute_start_angle(solver_data: &RevoluteJointSolverData) -> Scalar {
+    #[cfg(feature = "2d")]
+    {
+        solver_data.rotation_difference
+    }
+    #[cfg(feature = "3d")]
+    {
+        let sin = solver_data.a1.dot(solver_data.b1.cross(solver_data.b2));
+        let cos = solver_data.b1.dot(solver_data.b2);
+        sin.atan2(cos)
+    }
+}
+
+/// Returns the world-space hinge axis of a [`RevoluteJoint`] at the start of the time step.
+///
+/// The angle of the joint increases when the second body rotates about this axis.
+#[cfg_attr(feature = "2d", allow(unused_variables))]
+pub(crate) fn revolute_axis(solver_data: &RevoluteJointSolverData) -> AngularVector {
+    #[cfg(feature = "2d")]
+    {
+        1.0
+    }
+    #[cfg(feature = "3d")]
+    {
+        solver_data.a1
+    }
+}
+
+/// Returns the translation of a [`PrismaticJoint`] along its free axis at the start of the time step.
+pub(crate) fn prismatic_start_translation(solver_data: &PrismaticJointSolverData) -> Scalar {
+    let separation = solver_data.world_r2 - solver_data.world_r1 + solver_data.center_difference;
+    solver_data.free_axis1.dot(separation)
+}
+
+/// Returns the world-space free axis of a [`PrismaticJoint`] at the start of the time step.
+///
+/// The translation of the joint increases when the second body moves along this axis.
+pub(crate) fn prismatic_axis(solver_data: &PrismaticJointSolverData) -> Vector {
+    solver_data.free_axis1
+}
+
+/// Returns the angle that a body has rotated about the given `axis` during the time step.
+pub(crate) fn delta_angle(body: &SolverBody, axis: AngularVector) -> Scalar {
+    #[cfg(feature = "2d")]
+    {
+        axis * body.delta_rotation.as_radians()
+    }
+    #[cfg(feature = "3d")]
+    {
+        body.delta_rotation.0.to_scaled_axis().dot(axis)
+    }
+}
+
+/// Computes the generalized inverse mass of a body for a rotation about the given `axis`.
+pub(crate) fn angular_inverse_mass(inertia: &SolverBodyInertia, axis: AngularVector) -> Scalar {
+    let inv_angular_inertia = inertia.effective_inv_angular_inertia();
+    #[cfg(feature = "2d")]
+    {
+        axis * inv_angular_inertia * axis
+    }
+    #[cfg(feature = "3d")]
+    {
+        axis.dot(inv_angular_inertia * axis)
+    }
+}
+
+/// Applies an angular `impulse` to a body, rotating it proportionally to its inverse angular inertia.
+pub(crate) fn apply_angular_impulse(
+    body: &mut SolverBody,
+    inertia: &SolverBodyInertia,
+    impulse: AngularVector,
+) {
+    let inv_angular_inertia = inertia.effective_inv_angular_inertia();
+    #[cfg(feature = "2d")]
+    {
+        body.delta_rotation = body
+            .delta_rotation
+            .add_angle_fast(inv_angular_inertia * impulse);
+    }
+    #[cfg(feature = "3d")]
+    {
+        let delta_quat = Quaternion::from_scaled_axis(inv_angular_inertia * impulse);
+        body.delta_rotation.0 = delta_quat * body.delta_rotation.0;
+    }
+}
@@ -1,5 +1,13 @@
 mod fixed_angle_constraint;
+mod joint_coordinate;
+mod orientation_drive;
 mod point_constraint;
 
 pub use fixed_angle_constraint::FixedAngleConstraintShared;
+pub use joint_coordinate::JointCoordinateShared;
+pub(crate) use joint_coordinate::{
+    angular_inverse_mass, apply_angular_impulse, delta_angle, prismatic_axis,
+    prismatic_start_translation, revolute_axis, revolute_start_angle,
+};
+pub use orientation_drive::OrientationDriveShared;
 pub use point_constraint::PointConstraintShared;
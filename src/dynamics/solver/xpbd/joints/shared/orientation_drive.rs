@@ -0,0 +1,157 @@
+use crate::{
+    dynamics::solver::{
+        solver_body::{SolverBody, SolverBodyInertia},
+        xpbd::*,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Constraint data required by the XPBD constraint solver for an [`OrientationDrive`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct OrientationDriveShared {
+    /// The rotation difference between the target orientation and the current orientation
+    /// of the second joint frame at the start of the time step.
+    #[cfg(feature = "2d")]
+    pub rotation_difference: Scalar,
+    /// The rotation difference between the target orientation and the current orientation
+    /// of the second joint frame at the start of the time step.
+    #[cfg(feature = "3d")]
+    pub rotation_difference: Quaternion,
+    /// The total Lagrange multiplier across the whole time step.
+    pub total_lagrange: AngularVector,
+}
+
+impl XpbdConstraintSolverData for OrientationDriveShared {
+    fn clear_lagrange_multipliers(&mut self) {
+        self.total_lagrange = AngularVector::ZERO;
+    }
+
+    fn total_rotation_lagrange(&self) -> AngularVector {
+        self.total_lagrange
+    }
+}
+
+impl OrientationDriveShared {
+    /// Prepares the drive with the given rotations, local basis orientations, and target orientation.
+    pub fn prepare(
+        &mut self,
+        rotation1: &Rotation,
+        rotation2: &Rotation,
+        local_basis1: Rot,
+        local_basis2: Rot,
+        drive: &OrientationDrive,
+    ) {
+        // The target orientation of the second frame is the first frame rotated by the target.
+        #[cfg(feature = "2d")]
+        {
+            self.rotation_difference =
+                (*rotation1 * local_basis1 * Rotation::radians(drive.target))
+                    .angle_between(*rotation2 * local_basis2);
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.rotation_difference = (rotation1.0 * local_basis1 * drive.target)
+                * (rotation2.0 * local_basis2).inverse();
+        }
+    }
+
+    /// Solves the drive for the given bodies.
+    ///
+    /// The stiffness and damping are treated implicitly using the damped XPBD update
+    /// from *Detailed Rigid Body Simulation with Extended Position Based Dynamics* by Müller et al.
+    pub fn solve(
+        &mut self,
+        bodies: [&mut SolverBody; 2],
+        inertias: [&SolverBodyInertia; 2],
+        drive: &OrientationDrive,
+        dt: Scalar,
+    ) {
+        let [body1, body2] = bodies;
+        let [inertia1, inertia2] = inertias;
+
+        if drive.stiffness <= 0.0 && drive.damping <= 0.0 {
+            return;
+        }
+
+        let inv_inertia1 = inertia1.effective_inv_angular_inertia();
+        let inv_inertia2 = inertia2.effective_inv_angular_inertia();
+
+        let relative_angular_velocity = body2.angular_velocity - body1.angular_velocity;
+
+        #[cfg(feature = "2d")]
+        let difference =
+            self.rotation_difference + body1.delta_rotation.angle_between(body2.delta_rotation);
+        #[cfg(feature = "3d")]
+        let difference = {
+            let mut rotation_difference = self.rotation_difference
+                * body1.delta_rotation.0
+                * body2.delta_rotation.0.inverse();
+            // Take the shortest path to the target.
+            if rotation_difference.w < 0.0 {
+                rotation_difference = -rotation_difference;
+            }
+            // TODO: The XPBD paper doesn't have this minus sign, but it seems to be needed for stability.
+            //       See `FixedAngleConstraintShared`.
+            -2.0 * rotation_difference.xyz()
+        };
+
+        // The spring and damper terms of the constraint, scaled by h^2.
+        // The damping term is the change in the rotation error caused by the relative angular velocity.
+        let error =
+            dt * dt * (drive.stiffness * difference + drive.damping * relative_angular_velocity);
+
+        // The inverse of the effective compliance, scaled by h^2.
+        let inv_compliance = drive.stiffness * dt * dt + drive.damping * dt;
+
+        #[cfg(feature = "2d")]
+        {
+            if error.abs() <= Scalar::EPSILON {
+                return;
+            }
+
+            let w = inv_inertia1 + inv_inertia2;
+            let delta_lagrange = -error / (w * inv_compliance + 1.0);
+
+            self.apply_angular_lagrange_update(
+                body1,
+                body2,
+                inv_inertia1,
+                inv_inertia2,
+                delta_lagrange,
+            );
+
+            self.total_lagrange += delta_lagrange;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let error_magnitude = error.length();
+
+            if error_magnitude <= Scalar::EPSILON {
+                return;
+            }
+
+            let axis = error / error_magnitude;
+
+            let w1 = self.compute_generalized_inverse_mass(inv_inertia1, axis);
+            let w2 = self.compute_generalized_inverse_mass(inv_inertia2, axis);
+            let delta_lagrange = -error_magnitude / ((w1 + w2) * inv_compliance + 1.0);
+
+            self.apply_angular_lagrange_update(
+                body1,
+                body2,
+                inv_inertia1,
+                inv_inertia2,
+                delta_lagrange,
+                axis,
+            );
+
+            self.total_lagrange += delta_lagrange * axis;
+        }
+    }
+}
+
+impl AngularConstraint for OrientationDriveShared {}
@@ -1,4 +1,4 @@
-use super::PointConstraintShared;
+use super::{OrientationDriveShared, PointConstraintShared};
 use crate::{
     dynamics::solver::{
         solver_body::{SolverBody, SolverBodyInertia},
@@ -21,6 +21,7 @@ pub struct SphericalJointSolverData {
     pub(super) twist_axis2: Vector,
     pub(super) total_swing_lagrange: Vector,
     pub(super) total_twist_lagrange: Vector,
+    pub(super) drive: OrientationDriveShared,
 }
 
 impl XpbdConstraintSolverData for SphericalJointSolverData {
@@ -28,6 +29,7 @@ impl XpbdConstraintSolverData for SphericalJointSolverData {
         self.point_constraint.clear_lagrange_multipliers();
         self.total_swing_lagrange = Vector::ZERO;
         self.total_twist_lagrange = Vector::ZERO;
+        self.drive.clear_lagrange_multipliers();
     }
 
     fn total_position_lagrange(&self) -> Vector {
@@ -35,7 +37,7 @@ impl XpbdConstraintSolverData for SphericalJointSolverData {
     }
 
     fn total_rotation_lagrange(&self) -> AngularVector {
-        self.total_swing_lagrange + self.total_twist_lagrange
+        self.total_swing_lagrange + self.total_twist_lagrange + self.drive.total_rotation_lagrange()
     }
 }
 
@@ -79,6 +81,17 @@ impl XpbdConstraint<2> for SphericalJoint {
         solver_data.swing_axis2 = rot2_mat * (local_basis2 * swing_axis);
         solver_data.twist_axis1 = rot1_mat * (local_basis1 * self.twist_axis);
         solver_data.twist_axis2 = rot2_mat * (local_basis2 * self.twist_axis);
+
+        // Prepare the orientation drive.
+        if let Some(drive) = &self.orientation_drive {
+            solver_data.drive.prepare(
+                body1.rotation,
+                body2.rotation,
+                local_basis1,
+                local_basis2,
+                drive,
+            );
+        }
     }
 
     fn solve(
@@ -99,6 +112,13 @@ impl XpbdConstraint<2> for SphericalJoint {
             dt,
         );
 
+        // Drive the relative rotation towards the target orientation
+        if let Some(drive) = &self.orientation_drive {
+            solver_data
+                .drive
+                .solve([body1, body2], [inertia1, inertia2], drive, dt);
+        }
+
         // Apply swing limits
         self.apply_swing_limits(body1, body2, inertia1, inertia2, solver_data, dt);
 
@@ -32,6 +32,7 @@
 //!     - [`DistanceJoint`]
 #![cfg_attr(feature = "3d", doc = "    - [`SphericalJoint`]")]
 //!     - [`PrismaticJoint`]
+//! - [Soft bodies](dynamics::soft_body) (with the `default-collider` feature)
 //!
 //! Avian's [`ContactConstraint`](dynamics::solver::contact::ContactConstraint)
 //! is impulse-based instead.
@@ -160,6 +161,10 @@
 //! Now, just spawn an instance of the constraint, give it the participating entities, and the constraint should be getting
 //! solved automatically according to the `solve` method!
 //!
+//! [`prepare_xpbd_joint`] also assigns each constraint to a color of the [`JointGraph`](crate::dynamics::solver::joint_graph::JointGraph).
+//! Constraints within the same color do not share any awake bodies, so [`solve_xpbd_joint`] solves them in parallel
+//! when the `parallel` feature is enabled. If [`prepare_xpbd_joint`] is not used, the constraints are solved serially.
+//!
 //! If the constraint is a [joint](crate::dynamics::joints), it is recommended to also add an instance
 //! of [`JointGraphPlugin`](crate::dynamics::solver::joint_graph::JointGraphPlugin) for the constraint type.
 //! This is required for sleeping and the `JointCollisionDisabled` component to work.
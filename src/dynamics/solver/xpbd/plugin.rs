@@ -1,10 +1,12 @@
-use core::cmp::Ordering;
-
 use super::joints::*;
 use crate::{
     dynamics::{
         joints::EntityConstraint,
         solver::{
+            constraint_graph::COLOR_OVERFLOW_INDEX,
+            joint::ImpulseJointSolverData,
+            joint_graph::JointGraph,
+            plugin::with_joint_bodies,
             schedule::SubstepSolverSystems,
             solver_body::{SolverBody, SolverBodyInertia},
             xpbd::{XpbdConstraint, XpbdConstraintSolverData},
@@ -15,6 +17,20 @@ use crate::{
 use bevy::{ecs::component::Mutable, prelude::*};
 
 /// A plugin for a joint solver using Extended Position-Based Dynamics (XPBD).
+///
+/// Joints that are also handled by the [`ImpulseJointSolverPlugin`] are still prepared,
+/// since coupling joints such as the [`GearJoint`] read their prepared data, but they are
+/// only solved with XPBD if the [`ImpulseJointSolverPlugin`] is disabled, or if they have
+/// non-zero compliance, which the [`ImpulseJointSolverPlugin`] doesn't support.
+///
+/// With the [`ImpulseJointSolverPlugin`] enabled, only compliant joints and coupling joints
+/// such as the [`PulleyJoint`], [`GearJoint`], and [`RackAndPinionJoint`] are solved by this plugin,
+/// along with user constraints.
+///
+/// When preparing joints, they are assigned to the colors of the [`JointGraph`] such that joints
+/// in the same color share no awake bodies. Joints within a color are solved in parallel
+/// if the `parallel` feature is enabled. Since most joints are solved by the [`ImpulseJointSolverPlugin`]
+/// by default, this mainly benefits scenes with many compliant or coupling joints.
 pub struct XpbdSolverPlugin;
 
 impl Plugin for XpbdSolverPlugin {
@@ -25,6 +41,9 @@ impl Plugin for XpbdSolverPlugin {
         app.register_required_components::<SphericalJoint, SphericalJointSolverData>();
         app.register_required_components::<PrismaticJoint, PrismaticJointSolverData>();
         app.register_required_components::<DistanceJoint, DistanceJointSolverData>();
+        app.register_required_components::<PulleyJoint, PulleyJointSolverData>();
+        app.register_required_components::<GearJoint, GearJointSolverData>();
+        app.register_required_components::<RackAndPinionJoint, RackAndPinionJointSolverData>();
 
         // Configure scheduling.
         app.configure_sets(
@@ -49,6 +68,12 @@ impl Plugin for XpbdSolverPlugin {
                 prepare_xpbd_joint::<SphericalJoint>,
                 prepare_xpbd_joint::<PrismaticJoint>,
                 prepare_xpbd_joint::<DistanceJoint>,
+                prepare_xpbd_joint::<PulleyJoint>,
+                // Coupling joints read the prepared data of the joints they couple.
+                update_gear_joint_coordinates,
+                prepare_xpbd_joint::<GearJoint>,
+                update_rack_and_pinion_joint_coordinates,
+                prepare_xpbd_joint::<RackAndPinionJoint>,
             )
                 .chain()
                 .in_set(SolverSystems::PrepareJoints),
@@ -80,6 +105,9 @@ impl Plugin for XpbdSolverPlugin {
                 solve_xpbd_joint::<SphericalJoint>,
                 solve_xpbd_joint::<PrismaticJoint>,
                 solve_xpbd_joint::<DistanceJoint>,
+                solve_xpbd_joint::<PulleyJoint>,
+                solve_xpbd_joint::<GearJoint>,
+                solve_xpbd_joint::<RackAndPinionJoint>,
             )
                 .chain()
                 .in_set(XpbdSolverSystems::SolveConstraints),
@@ -97,12 +125,16 @@ impl Plugin for XpbdSolverPlugin {
         app.add_systems(
             PhysicsSchedule,
             (
-                writeback_joint_forces::<FixedJoint>,
-                writeback_joint_forces::<RevoluteJoint>,
+                writeback_joint_forces::<FixedJointSolverData>,
+                writeback_joint_forces::<RevoluteJointSolverData>,
                 #[cfg(feature = "3d")]
-                writeback_joint_forces::<SphericalJoint>,
-                writeback_joint_forces::<PrismaticJoint>,
-                writeback_joint_forces::<DistanceJoint>,
+                writeback_joint_forces::<SphericalJointSolverData>,
+                writeback_joint_forces::<PrismaticJointSolverData>,
+                writeback_joint_forces::<DistanceJointSolverData>,
+                writeback_joint_forces::<PulleyJointSolverData>,
+                writeback_joint_forces::<GearJointSolverData>,
+                writeback_joint_forces::<RackAndPinionJointSolverData>,
+                clear_joint_colors,
             )
                 .chain()
                 .in_set(SolverSystems::Finalize),
@@ -121,73 +153,136 @@ pub enum XpbdSolverSystems {
     VelocityProjection,
 }
 
-/// Iterates through the XPBD joints of a given type and solves them.
+/// Iterates through the XPBD joints of a given type and prepares them.
+///
+/// Joints that are solved with XPBD are also added to the colors of the [`JointGraph`],
+/// allowing [`solve_xpbd_joint`] to solve them in parallel.
+#[allow(clippy::type_complexity)]
 pub fn prepare_xpbd_joint<
     C: Component<Mutability = Mutable> + EntityConstraint<2> + XpbdConstraint<2>,
 >(
-    bodies: Query<RigidBodyQueryReadOnly, Without<RigidBodyDisabled>>,
-    mut joints: Query<(&mut C, &mut C::SolverData), (Without<RigidBody>, Without<JointDisabled>)>,
+    bodies: Query<(RigidBodyQueryReadOnly, Has<SolverBody>), Without<RigidBodyDisabled>>,
+    mut joints: Query<
+        (
+            Entity,
+            &mut C,
+            &mut C::SolverData,
+            Has<ImpulseJointSolverData>,
+        ),
+        (Without<RigidBody>, Without<JointDisabled>),
+    >,
+    mut joint_graph: ResMut<JointGraph>,
 ) where
     C::SolverData: Component<Mutability = Mutable>,
 {
-    for (mut joint, mut solver_data) in &mut joints {
+    for (entity, mut joint, mut solver_data, is_impulse_joint) in &mut joints {
         // Clear the Lagrange multipliers.
         solver_data.clear_lagrange_multipliers();
 
         // Get components for entities
-        if let Ok([body1, body2]) = bodies.get_many(joint.entities()) {
-            joint.prepare([&body1, &body2], &mut solver_data);
+        let [entity1, entity2] = joint.entities();
+        let Ok([(body1, is_active1), (body2, is_active2)]) = bodies.get_many([entity1, entity2])
+        else {
+            continue;
+        };
+
+        joint.prepare([&body1, &body2], &mut solver_data);
+
+        // Joints between two static or sleeping bodies don't need to be solved.
+        if !is_impulse_joint && (is_active1 || is_active2) {
+            joint_graph.color_joint(entity, entity1, entity2, !is_active1, !is_active2);
         }
     }
+
+    joint_graph.mark_colored::<C>();
 }
 
 /// Iterates through the XPBD joints of a given type and solves them.
+///
+/// The joints are solved one [`JointGraph`] color at a time. Joints in the overflow color
+/// are solved serially, while the joints in other colors are solved in parallel
+/// if the `parallel` feature is enabled. Joints solved by the [`ImpulseJointSolverPlugin`] are skipped.
+///
+/// If the joints have not been colored by [`prepare_xpbd_joint`], for example because
+/// the system is not scheduled for the joint type, all of the joints are solved serially instead.
+#[allow(clippy::type_complexity)]
 pub fn solve_xpbd_joint<
     C: Component<Mutability = Mutable> + EntityConstraint<2> + XpbdConstraint<2>,
 >(
     bodies: Query<(&mut SolverBody, &SolverBodyInertia), Without<RigidBodyDisabled>>,
-    mut joints: Query<(&mut C, &mut C::SolverData), (Without<RigidBody>, Without<JointDisabled>)>,
+    mut joints: Query<
+        (&mut C, &mut C::SolverData),
+        (
+            Without<RigidBody>,
+            Without<JointDisabled>,
+            Without<ImpulseJointSolverData>,
+        ),
+    >,
+    mut joint_graph: ResMut<JointGraph>,
     time: Res<Time>,
 ) where
     C::SolverData: Component<Mutability = Mutable>,
 {
     let delta_secs = time.delta_seconds_adjusted();
 
-    let mut dummy_body1 = SolverBody::default();
-    let mut dummy_body2 = SolverBody::default();
-
-    for (mut joint, mut solver_data) in &mut joints {
-        let [entity1, entity2] = joint.entities();
-
-        let (mut body1, mut inertia1) = (&mut dummy_body1, &SolverBodyInertia::DUMMY);
-        let (mut body2, mut inertia2) = (&mut dummy_body2, &SolverBodyInertia::DUMMY);
-
-        // Get the solver bodies for the two colliding entities.
-        if let Ok((body, inertia)) = unsafe { bodies.get_unchecked(entity1) } {
-            body1 = body.into_inner();
-            inertia1 = inertia;
-        }
-        if let Ok((body, inertia)) = unsafe { bodies.get_unchecked(entity2) } {
-            body2 = body.into_inner();
-            inertia2 = inertia;
+    if !joint_graph.is_colored::<C>() {
+        for (mut joint, mut solver_data) in &mut joints {
+            solve_xpbd_joint_internal(&bodies, &mut *joint, &mut *solver_data, delta_secs);
         }
+        return;
+    }
+
+    let colors = joint_graph.colors_mut();
 
-        // If a body has a higher dominance, it is treated as a static or kinematic body.
-        match (inertia1.dominance() - inertia2.dominance()).cmp(&0) {
-            Ordering::Greater => inertia1 = &SolverBodyInertia::DUMMY,
-            Ordering::Less => inertia2 = &SolverBodyInertia::DUMMY,
-            _ => {}
+    // Solve overflow joints serially.
+    for &entity in colors[COLOR_OVERFLOW_INDEX].joints.iter() {
+        // The colors contain joints of all types, so skip joints of other types.
+        if let Ok((mut joint, mut solver_data)) = joints.get_mut(entity) {
+            solve_xpbd_joint_internal(&bodies, &mut *joint, &mut *solver_data, delta_secs);
         }
+    }
 
-        joint.solve(
-            [body1, body2],
-            [inertia1, inertia2],
-            &mut solver_data,
-            delta_secs,
-        );
+    // Solve joints in each color in parallel.
+    for color in colors
+        .iter_mut()
+        .take(COLOR_OVERFLOW_INDEX)
+        .filter(|color| !color.joints.is_empty())
+    {
+        crate::utils::par_for_each(&mut color.joints, 64, |_i, entity| {
+            // The colors contain joints of all types, so skip joints of other types.
+            // SAFETY: Each joint is in only one color, and the joints in a color share no solver bodies.
+            if let Ok((mut joint, mut solver_data)) = unsafe { joints.get_unchecked(*entity) } {
+                solve_xpbd_joint_internal(&bodies, &mut *joint, &mut *solver_data, delta_secs);
+            }
+        });
     }
 }
 
+fn solve_xpbd_joint_internal<C: EntityConstraint<2> + XpbdConstraint<2>>(
+    bodies: &Query<(&mut SolverBody, &SolverBodyInertia), Without<RigidBodyDisabled>>,
+    joint: &mut C,
+    solver_data: &mut C::SolverData,
+    delta_secs: Scalar,
+) {
+    with_joint_bodies(
+        bodies,
+        joint.entities(),
+        |body1, body2, inertia1, inertia2| {
+            joint.solve(
+                [body1, body2],
+                [inertia1, inertia2],
+                solver_data,
+                delta_secs,
+            );
+        },
+    );
+}
+
+/// Removes all joints from the colors of the [`JointGraph`] at the end of the time step.
+fn clear_joint_colors(mut joint_graph: ResMut<JointGraph>) {
+    joint_graph.clear_colors();
+}
+
 /// Updates the linear velocity of all dynamic bodies based on the change in position from the XPBD solver.
 fn project_linear_velocity(
     mut bodies: Query<(&mut SolverBody, &PreSolveDeltaPosition), RigidBodyActiveFilter>,
@@ -239,13 +334,11 @@ fn project_angular_velocity(
     }
 }
 
-fn writeback_joint_forces<C: Component + EntityConstraint<2> + XpbdConstraint<2>>(
-    mut joints: Query<(&C::SolverData, &mut JointForces)>,
+fn writeback_joint_forces<D: Component + XpbdConstraintSolverData>(
+    mut joints: Query<(&D, &mut JointForces), Without<ImpulseJointSolverData>>,
     time: Res<Time>,
     substep_count: Res<SubstepCount>,
-) where
-    C::SolverData: Component<Mutability = Mutable>,
-{
+) {
     let delta_secs = time.delta_seconds_adjusted();
 
     // Detailed Rigid Body Simulation with Extended Position Based Dynamics by Müller et al.
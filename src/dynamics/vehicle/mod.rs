@@ -0,0 +1,1160 @@
+//! Raycast vehicles with suspension, tire friction, steering, and drivetrain input.
+//!
+//! See [`VehiclePlugin`].
+//!
+//! # Overview
+//!
+//! A vehicle consists of a chassis and a number of wheels. The chassis is a normal dynamic
+//! [rigid body](RigidBody) with a [`Vehicle`] component and one or more colliders.
+//! The wheels are separate entities with a [`Wheel`] component and a [`WheelOf`] relationship
+//! pointing to the chassis. Wheels are not rigid bodies themselves. Instead, the forces caused
+//! by the wheels are applied directly to the chassis using [`Forces`].
+//!
+//! The vehicle is controlled by modifying the [`VehicleControls`] of the chassis.
+//!
+#![cfg_attr(
+    feature = "3d",
+    doc = "Each wheel casts a ray or a sphere along the suspension direction of the chassis
+to find the ground. The suspension is modeled as a spring and damper, and pushes the chassis
+away from the ground based on how much the suspension is compressed. Optional [`AntiRollBar`]s
+transfer load between pairs of wheels to reduce body roll in corners."
+)]
+#![cfg_attr(
+    feature = "2d",
+    doc = "In 2D, vehicles are simulated from a top-down perspective, with the chassis facing its local `+Y` axis.
+There is no suspension. Instead, each wheel is always in contact with the ground, and carries an even share
+of the weight of the chassis based on [`Vehicle::normal_acceleration`]."
+)]
+//!
+//! The tires use a simple slip-based friction model. The longitudinal force is proportional
+//! to the *slip ratio*, the difference between the speed of the tire surface and the speed of the ground,
+//! and the lateral force is proportional to the *slip angle*, the angle between the direction the wheel
+//! is pointing in and the direction it is moving in. The combined force is limited by the friction
+//! coefficient of the tire and the load on the wheel.
+//!
+//! The current state of each wheel, such as its contact point, suspension compression, and spin,
+//! is stored in the [`WheelState`] component. This can be used for positioning wheel meshes or sprites,
+//! playing tire screech sounds, and so on.
+//!
+//! # Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     let chassis = commands
+//!         .spawn((
+//!             RigidBody::Dynamic,
+#![cfg_attr(feature = "2d", doc = "            Collider::rectangle(1.8, 4.0),")]
+#![cfg_attr(feature = "3d", doc = "            Collider::cuboid(1.8, 0.6, 4.0),")]
+//!             Vehicle::default(),
+//!         ))
+//!         .id();
+//!
+//!     // Front wheels steer, rear wheels drive.
+#![cfg_attr(
+    feature = "2d",
+    doc = "    let front = [Vector::new(-0.9, 1.4), Vector::new(0.9, 1.4)];
+    let rear = [Vector::new(-0.9, -1.4), Vector::new(0.9, -1.4)];"
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "    let front = [Vector::new(-0.9, -0.3, -1.4), Vector::new(0.9, -0.3, -1.4)];
+    let rear = [Vector::new(-0.9, -0.3, 1.4), Vector::new(0.9, -0.3, 1.4)];"
+)]
+//!
+//!     for anchor in front {
+//!         commands.spawn((WheelOf(chassis), Wheel::new(anchor, 0.35).with_steering(1.0)));
+//!     }
+//!     for anchor in rear {
+//!         commands.spawn((WheelOf(chassis), Wheel::new(anchor, 0.35).with_drive(1.0)));
+//!     }
+//! }
+//!
+//! fn drive(keyboard: Res<ButtonInput<KeyCode>>, mut vehicles: Query<&mut VehicleControls>) {
+//!     for mut controls in &mut vehicles {
+//!         controls.throttle = keyboard.pressed(KeyCode::KeyW) as u8 as Scalar
+//!             - keyboard.pressed(KeyCode::KeyS) as u8 as Scalar;
+//!         controls.steering = keyboard.pressed(KeyCode::KeyD) as u8 as Scalar
+//!             - keyboard.pressed(KeyCode::KeyA) as u8 as Scalar;
+//!         controls.brake = keyboard.pressed(KeyCode::Space) as u8 as Scalar;
+//!     }
+//! }
+//! ```
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// The minimum speed used as the denominator when computing tire slip.
+///
+/// Without this, the slip would approach infinity as the speed of the vehicle approaches zero.
+const LOW_SPEED_THRESHOLD: Scalar = 1.0;
+
+/// A plugin for simulating [raycast vehicles](self).
+///
+/// The plugin is not included in [`PhysicsPlugins`] by default, and must be added manually.
+///
+/// The vehicle systems run in the [`PhysicsSchedule`] in [`VehicleSystems`],
+/// between [`PhysicsStepSystems::First`] and [`PhysicsStepSystems::BroadPhase`].
+pub struct VehiclePlugin;
+
+impl Plugin for VehiclePlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            PhysicsSchedule,
+            VehicleSystems
+                .after(PhysicsStepSystems::First)
+                .after(IntegrationSystems::KinematicTargets)
+                .before(PlatformRiderSystems::DetectSupports)
+                .before(PhysicsStepSystems::BroadPhase),
+        );
+
+        app.add_systems(PhysicsSchedule, update_vehicles.in_set(VehicleSystems));
+    }
+}
+
+/// A system set for [vehicle](self) systems, applying suspension and tire forces to vehicles.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VehicleSystems;
+
+/// A component for the chassis of a [vehicle](self).
+///
+/// The wheels of the vehicle are entities with a [`Wheel`] component
+/// and a [`WheelOf`] relationship pointing to the chassis.
+///
+/// The vehicle is controlled using the [`VehicleControls`] component.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[require(RigidBody, VehicleControls)]
+pub struct Vehicle {
+    /// The maximum drive torque applied by the engine at full throttle, in N⋅m.
+    ///
+    /// The torque is distributed to the wheels based on [`Wheel::drive_factor`].
+    pub engine_torque: Scalar,
+    /// The maximum brake torque applied at full brake, in N⋅m.
+    ///
+    /// The torque is distributed to the wheels based on [`Wheel::brake_factor`].
+    pub brake_torque: Scalar,
+    /// The maximum steering angle of the wheels in radians.
+    ///
+    /// The steering angle of each wheel is scaled by [`Wheel::steering_factor`].
+    pub max_steering_angle: Scalar,
+    /// The anti-roll bars of the vehicle.
+    #[cfg(feature = "3d")]
+    pub anti_roll_bars: Vec<AntiRollBar>,
+    /// A [`SpatialQueryFilter`] that determines which colliders the wheels can drive on.
+    ///
+    /// Colliders attached to the chassis itself are always ignored.
+    #[cfg(feature = "3d")]
+    pub filter: SpatialQueryFilter,
+    /// The acceleration pushing the vehicle against the ground, used for computing
+    /// the load on each wheel. Top-down games typically have no actual gravity,
+    /// so this is configured separately.
+    ///
+    /// The default is `9.81`.
+    #[cfg(feature = "2d")]
+    pub normal_acceleration: Scalar,
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        Self {
+            engine_torque: 1500.0,
+            brake_torque: 3000.0,
+            max_steering_angle: 35.0_f64.to_radians() as Scalar,
+            #[cfg(feature = "3d")]
+            anti_roll_bars: Vec::new(),
+            #[cfg(feature = "3d")]
+            filter: SpatialQueryFilter::default(),
+            #[cfg(feature = "2d")]
+            normal_acceleration: 9.81,
+        }
+    }
+}
+
+impl Vehicle {
+    /// Sets the maximum drive torque applied by the engine at full throttle, in N⋅m.
+    pub fn with_engine_torque(mut self, torque: Scalar) -> Self {
+        self.engine_torque = torque;
+        self
+    }
+
+    /// Sets the maximum brake torque applied at full brake, in N⋅m.
+    pub fn with_brake_torque(mut self, torque: Scalar) -> Self {
+        self.brake_torque = torque;
+        self
+    }
+
+    /// Sets the maximum steering angle of the wheels in radians.
+    pub fn with_max_steering_angle(mut self, angle: Scalar) -> Self {
+        self.max_steering_angle = angle;
+        self
+    }
+
+    /// Adds an [`AntiRollBar`] between the given `left` and `right` wheels.
+    #[cfg(feature = "3d")]
+    pub fn with_anti_roll_bar(mut self, left: Entity, right: Entity, stiffness: Scalar) -> Self {
+        self.anti_roll_bars.push(AntiRollBar {
+            left,
+            right,
+            stiffness,
+        });
+        self
+    }
+
+    /// Sets the [`SpatialQueryFilter`] that determines which colliders the wheels can drive on.
+    #[cfg(feature = "3d")]
+    pub fn with_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// An anti-roll bar connecting the suspension of two [wheels](Wheel), typically on the same axle.
+///
+/// When the suspension of one wheel is compressed more than the other, for example when turning,
+/// the anti-roll bar applies a force that transfers load between the wheels to counteract body roll.
+#[cfg(feature = "3d")]
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct AntiRollBar {
+    /// The left wheel entity.
+    pub left: Entity,
+    /// The right wheel entity.
+    pub right: Entity,
+    /// The stiffness of the anti-roll bar in N/m.
+    pub stiffness: Scalar,
+}
+
+/// The driver input for a [`Vehicle`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct VehicleControls {
+    /// The throttle input in the range `[-1.0, 1.0]`. Negative values drive in reverse.
+    pub throttle: Scalar,
+    /// The brake input in the range `[0.0, 1.0]`.
+    pub brake: Scalar,
+    /// The steering input in the range `[-1.0, 1.0]`. Positive values steer right,
+    /// and negative values steer left.
+    pub steering: Scalar,
+}
+
+/// A [`Relationship`](bevy::ecs::relationship::Relationship) component that attaches a [`Wheel`]
+/// to the chassis of a [`Vehicle`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[relationship(relationship_target = VehicleWheels)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct WheelOf(pub Entity);
+
+/// A [`RelationshipTarget`](bevy::ecs::relationship::RelationshipTarget) component that tracks
+/// which [wheels](Wheel) are attached to the chassis of a [`Vehicle`].
+///
+/// This is automatically inserted and populated with entities that have the [`WheelOf`] component.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[relationship_target(relationship = WheelOf, linked_spawn)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct VehicleWheels(Vec<Entity>);
+
+/// A wheel of a [`Vehicle`], attached to the chassis using the [`WheelOf`] relationship.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+#[require(WheelState)]
+#[cfg_attr(feature = "3d", require(WheelCastShape))]
+pub struct Wheel {
+    /// The point where the wheel is attached to the chassis, in the local space of the chassis.
+    ///
+    #[cfg_attr(
+        feature = "3d",
+        doc = "This is the top of the suspension. When the suspension is fully extended,
+the center of the wheel is [`Suspension::rest_length`] below this point."
+    )]
+    #[cfg_attr(
+        feature = "2d",
+        doc = "This is the point where tire forces are applied."
+    )]
+    pub anchor: Vector,
+    /// The radius of the wheel.
+    pub radius: Scalar,
+    /// The moment of inertia of the wheel around its axle, in kg⋅m².
+    ///
+    /// Lower values make the wheel spin up and lock up faster.
+    pub inertia: Scalar,
+    /// The suspension of the wheel.
+    #[cfg(feature = "3d")]
+    pub suspension: Suspension,
+    /// Determines how the wheel finds the ground.
+    #[cfg(feature = "3d")]
+    pub cast: WheelCast,
+    /// The friction model of the tire.
+    pub friction: TireFriction,
+    /// The fraction of [`Vehicle::max_steering_angle`] that the wheel is steered by.
+    ///
+    /// Typically `1.0` for front wheels and `0.0` for rear wheels.
+    /// Negative values steer the wheel in the opposite direction.
+    pub steering_factor: Scalar,
+    /// The fraction of [`Vehicle::engine_torque`] applied to the wheel.
+    pub drive_factor: Scalar,
+    /// The fraction of [`Vehicle::brake_torque`] applied to the wheel.
+    pub brake_factor: Scalar,
+}
+
+impl Wheel {
+    /// Creates a new [`Wheel`] with the given `anchor` in the local space of the chassis and `radius`.
+    ///
+    /// The wheel is not steered or driven by default, but it is affected by braking.
+    pub fn new(anchor: Vector, radius: Scalar) -> Self {
+        Self {
+            anchor,
+            radius,
+            inertia: 1.0,
+            #[cfg(feature = "3d")]
+            suspension: Suspension::default(),
+            #[cfg(feature = "3d")]
+            cast: WheelCast::Ray,
+            friction: TireFriction::default(),
+            steering_factor: 0.0,
+            drive_factor: 0.0,
+            brake_factor: 1.0,
+        }
+    }
+
+    /// Sets the moment of inertia of the wheel around its axle, in kg⋅m².
+    pub fn with_inertia(mut self, inertia: Scalar) -> Self {
+        self.inertia = inertia;
+        self
+    }
+
+    /// Sets the [`Suspension`] of the wheel.
+    #[cfg(feature = "3d")]
+    pub fn with_suspension(mut self, suspension: Suspension) -> Self {
+        self.suspension = suspension;
+        self
+    }
+
+    /// Sets the [`WheelCast`] that determines how the wheel finds the ground.
+    #[cfg(feature = "3d")]
+    pub fn with_cast(mut self, cast: WheelCast) -> Self {
+        self.cast = cast;
+        self
+    }
+
+    /// Sets the [`TireFriction`] of the wheel.
+    pub fn with_friction(mut self, friction: TireFriction) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the fraction of [`Vehicle::max_steering_angle`] that the wheel is steered by.
+    pub fn with_steering(mut self, factor: Scalar) -> Self {
+        self.steering_factor = factor;
+        self
+    }
+
+    /// Sets the fraction of [`Vehicle::engine_torque`] applied to the wheel.
+    pub fn with_drive(mut self, factor: Scalar) -> Self {
+        self.drive_factor = factor;
+        self
+    }
+
+    /// Sets the fraction of [`Vehicle::brake_torque`] applied to the wheel.
+    pub fn with_brake(mut self, factor: Scalar) -> Self {
+        self.brake_factor = factor;
+        self
+    }
+}
+
+/// The spring and damper suspension of a [`Wheel`].
+#[cfg(feature = "3d")]
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct Suspension {
+    /// The length of the suspension when it is fully extended.
+    pub rest_length: Scalar,
+    /// The spring stiffness of the suspension in N/m.
+    pub stiffness: Scalar,
+    /// The damping coefficient of the suspension in N⋅s/m.
+    pub damping: Scalar,
+    /// The maximum force that the suspension can apply, in N.
+    pub max_force: Scalar,
+}
+
+#[cfg(feature = "3d")]
+impl Default for Suspension {
+    fn default() -> Self {
+        Self {
+            rest_length: 0.5,
+            stiffness: 35_000.0,
+            damping: 4_500.0,
+            max_force: Scalar::MAX,
+        }
+    }
+}
+
+/// Determines how a [`Wheel`] finds the ground.
+#[cfg(feature = "3d")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum WheelCast {
+    /// Casts a ray from the anchor of the wheel along the suspension direction.
+    ///
+    /// This is the cheapest option, but the wheel can clip through edges and small bumps.
+    #[default]
+    Ray,
+    /// Casts a sphere with the radius of the wheel along the suspension direction.
+    ///
+    /// This is more expensive, but handles curbs, edges, and uneven terrain more smoothly.
+    Sphere,
+}
+
+/// The sphere used for [`WheelCast::Sphere`], cached so that a new shape
+/// is only created when the radius of the [`Wheel`] changes.
+#[cfg(feature = "3d")]
+#[derive(Component, Clone, Debug, Default)]
+struct WheelCastShape(Option<(Scalar, Collider)>);
+
+#[cfg(feature = "3d")]
+impl WheelCastShape {
+    /// Returns a sphere with the given `radius`, reusing the cached shape if the radius is unchanged.
+    fn sphere(&mut self, radius: Scalar) -> &Collider {
+        if self
+            .0
+            .as_ref()
+            .is_none_or(|(cached_radius, _)| *cached_radius != radius)
+        {
+            self.0 = Some((radius, Collider::sphere(radius)));
+        }
+        &self.0.as_ref().unwrap().1
+    }
+}
+
+/// The friction model of the tire of a [`Wheel`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct TireFriction {
+    /// The coefficient of friction of the tire, limiting the total tire force relative to the load on the wheel.
+    pub coefficient: Scalar,
+    /// The longitudinal force per unit of load per unit of slip ratio.
+    ///
+    /// Higher values produce more traction for the same amount of wheel spin.
+    pub longitudinal_stiffness: Scalar,
+    /// The lateral force per unit of load per radian of slip angle.
+    ///
+    /// Higher values produce more cornering grip for the same amount of sideways sliding.
+    pub lateral_stiffness: Scalar,
+}
+
+impl Default for TireFriction {
+    fn default() -> Self {
+        Self {
+            coefficient: 1.0,
+            longitudinal_stiffness: 10.0,
+            lateral_stiffness: 8.0,
+        }
+    }
+}
+
+/// The current state of a [`Wheel`], updated by the [`VehiclePlugin`] every physics step.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct WheelState {
+    /// Whether the wheel is in contact with the ground.
+    pub grounded: bool,
+    /// The entity of the collider that the wheel is in contact with, if any.
+    pub ground_entity: Option<Entity>,
+    /// The contact point between the wheel and the ground in world space.
+    pub contact_point: Vector,
+    /// The surface normal of the ground at the contact point in world space.
+    pub contact_normal: Vector,
+    /// The current compression of the suspension.
+    pub compression: Scalar,
+    /// The force applied by the suspension in N.
+    pub suspension_force: Scalar,
+    /// The current steering angle of the wheel in radians.
+    pub steering_angle: Scalar,
+    /// The angular velocity of the wheel around its axle in radians per second.
+    pub angular_velocity: Scalar,
+    /// The accumulated rotation angle of the wheel around its axle in radians, for visuals.
+    pub rotation_angle: Scalar,
+    /// The longitudinal slip ratio of the tire.
+    pub longitudinal_slip: Scalar,
+    /// The lateral slip angle of the tire in radians.
+    pub lateral_slip: Scalar,
+    /// The tire force applied to the chassis in world space.
+    pub tire_force: Vector,
+}
+
+/// Applies suspension, anti-roll, and tire forces to vehicles.
+#[allow(clippy::type_complexity)]
+#[cfg_attr(feature = "2d", allow(unused_variables))]
+fn update_vehicles(
+    mut vehicles: Query<(
+        Entity,
+        &Vehicle,
+        &VehicleControls,
+        &VehicleWheels,
+        &ComputedMass,
+        Forces,
+    )>,
+    mut wheels: Query<(&Wheel, &mut WheelState)>,
+    #[cfg(feature = "3d")] mut cast_shapes: Query<&mut WheelCastShape>,
+    #[cfg(feature = "3d")] spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_secs_f64() as Scalar;
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (entity, vehicle, controls, vehicle_wheels, mass, mut forces) in &mut vehicles {
+        let position = forces.position().0;
+        let rotation = *forces.rotation();
+
+        // Find the ground and apply suspension forces.
+        #[cfg(feature = "3d")]
+        {
+            let up = rotation * Vector::Y;
+            let down = Dir::new_unchecked((-up).f32());
+
+            // Ignore colliders attached to the chassis itself.
+            let predicate = |collider: Entity| {
+                !spatial_query
+                    .collider_of
+                    .get(collider)
+                    .is_ok_and(|collider_of| collider_of.body == entity)
+            };
+
+            for wheel_entity in vehicle_wheels.iter() {
+                let Ok((wheel, mut state)) = wheels.get_mut(wheel_entity) else {
+                    continue;
+                };
+
+                let anchor = position + rotation * wheel.anchor;
+                let max_distance = wheel.suspension.rest_length + wheel.radius;
+
+                // Compute the distance to the ground and the contact point.
+                let hit = match wheel.cast {
+                    WheelCast::Ray => spatial_query
+                        .cast_ray_predicate(
+                            anchor,
+                            down,
+                            max_distance,
+                            true,
+                            &vehicle.filter,
+                            &predicate,
+                        )
+                        .map(|hit| {
+                            (
+                                hit.entity,
+                                hit.distance,
+                                anchor - up * hit.distance,
+                                hit.normal,
+                            )
+                        }),
+                    WheelCast::Sphere => {
+                        let Ok(mut cast_shape) = cast_shapes.get_mut(wheel_entity) else {
+                            continue;
+                        };
+                        spatial_query
+                            .cast_shape_predicate(
+                                cast_shape.sphere(wheel.radius),
+                                anchor,
+                                Quaternion::IDENTITY,
+                                down,
+                                &ShapeCastConfig::from_max_distance(wheel.suspension.rest_length),
+                                &vehicle.filter,
+                                &predicate,
+                            )
+                            .map(|hit| {
+                                (
+                                    hit.entity,
+                                    hit.distance + wheel.radius,
+                                    hit.point1,
+                                    hit.normal1,
+                                )
+                            })
+                    }
+                };
+
+                let previous_compression = state.compression;
+
+                let Some((ground_entity, distance, contact_point, contact_normal)) = hit else {
+                    // The wheel is in the air. Fully extend the suspension.
+                    state.grounded = false;
+                    state.ground_entity = None;
+                    state.contact_point = anchor - up * max_distance;
+                    state.contact_normal = up;
+                    state.compression = 0.0;
+                    state.suspension_force = 0.0;
+                    continue;
+                };
+
+                let compression = (max_distance - distance).max(0.0);
+                let compression_speed = (compression - previous_compression) / delta_secs;
+
+                // Spring and damper force. The suspension can only push, not pull.
+                let suspension_force = (wheel.suspension.stiffness * compression
+                    + wheel.suspension.damping * compression_speed)
+                    .clamp(0.0, wheel.suspension.max_force);
+
+                forces.apply_force_at_point(up * suspension_force, contact_point);
+
+                state.grounded = true;
+                state.ground_entity = Some(ground_entity);
+                state.contact_point = contact_point;
+                state.contact_normal = contact_normal;
+                state.compression = compression;
+                state.suspension_force = suspension_force;
+            }
+
+            // Apply anti-roll forces.
+            for bar in vehicle.anti_roll_bars.iter() {
+                let Ok([(left_wheel, left), (right_wheel, right)]) =
+                    wheels.get_many([bar.left, bar.right])
+                else {
+                    continue;
+                };
+
+                let force = (left.compression - right.compression) * bar.stiffness;
+
+                if left.grounded {
+                    let left_anchor = position + rotation * left_wheel.anchor;
+                    forces.apply_force_at_point(up * force, left_anchor);
+                }
+                if right.grounded {
+                    let right_anchor = position + rotation * right_wheel.anchor;
+                    forces.apply_force_at_point(up * -force, right_anchor);
+                }
+            }
+        }
+
+        // The number of wheels sharing the mass of the chassis,
+        // used for limiting lateral forces.
+        #[cfg(feature = "3d")]
+        let grounded_wheel_count = vehicle_wheels
+            .iter()
+            .filter(|&wheel| wheels.get(wheel).is_ok_and(|(_, state)| state.grounded))
+            .count();
+        #[cfg(feature = "2d")]
+        let grounded_wheel_count = vehicle_wheels.len();
+
+        if grounded_wheel_count == 0 {
+            continue;
+        }
+
+        let mass_per_wheel = mass.value() / grounded_wheel_count as Scalar;
+
+        // Apply tire forces.
+        for wheel_entity in vehicle_wheels.iter() {
+            let Ok((wheel, mut state)) = wheels.get_mut(wheel_entity) else {
+                continue;
+            };
+
+            state.steering_angle =
+                controls.steering * vehicle.max_steering_angle * wheel.steering_factor;
+
+            let drive_torque = controls.throttle * vehicle.engine_torque * wheel.drive_factor;
+            let brake_torque = controls.brake * vehicle.brake_torque * wheel.brake_factor;
+
+            // Compute the directions of the wheel and the load on the tire.
+            #[cfg(feature = "2d")]
+            let (contact_point, forward, side, load) = {
+                // Top-down vehicles always face their local +Y axis.
+                let steering = Rotation::radians(-state.steering_angle);
+                let contact_point = position + rotation * wheel.anchor;
+                let forward = (rotation * steering) * Vector::Y;
+                let side = (rotation * steering) * Vector::X;
+
+                state.grounded = true;
+                state.contact_point = contact_point;
+
+                (
+                    contact_point,
+                    forward,
+                    side,
+                    mass_per_wheel * vehicle.normal_acceleration,
+                )
+            };
+            #[cfg(feature = "3d")]
+            let (contact_point, forward, side, load) = {
+                if !state.grounded {
+                    // Let the wheel spin freely in the air.
+                    state.angular_velocity +=
+                        drive_torque / wheel.inertia.max(Scalar::EPSILON) * delta_secs;
+                    apply_brake(&mut state, brake_torque, wheel.inertia, delta_secs);
+                    state.rotation_angle += state.angular_velocity * delta_secs;
+                    state.longitudinal_slip = 0.0;
+                    state.lateral_slip = 0.0;
+                    state.tire_force = Vector::ZERO;
+                    continue;
+                }
+
+                // Steer around the up axis of the chassis. Bevy's forward direction is -Z.
+                let steering = Quaternion::from_rotation_y(-state.steering_angle);
+                let normal = state.contact_normal;
+                let forward = (rotation.0 * steering) * Vector::NEG_Z;
+                let side = (rotation.0 * steering) * Vector::X;
+
+                // Project the wheel directions onto the ground plane.
+                let forward = (forward - normal * forward.dot(normal)).normalize_or_zero();
+                let side = (side - normal * side.dot(normal)).normalize_or_zero();
+
+                (state.contact_point, forward, side, state.suspension_force)
+            };
+
+            // Compute the velocity of the chassis at the contact point.
+            let contact_velocity = forces.velocity_at_point(contact_point);
+            let forward_speed = contact_velocity.dot(forward);
+            let side_speed = contact_velocity.dot(side);
+            let slip_denominator = forward_speed.abs().max(LOW_SPEED_THRESHOLD);
+
+            // Update the wheel spin. The tire force drives the wheel towards rolling without slipping,
+            // which is solved implicitly to keep stiff tires stable at large time steps.
+            let inverse_inertia = 1.0 / wheel.inertia.max(Scalar::EPSILON);
+            let implicit_factor = delta_secs
+                * wheel.friction.longitudinal_stiffness
+                * load
+                * wheel.radius
+                * wheel.radius
+                * inverse_inertia
+                / slip_denominator;
+            let rolling_angular_velocity = forward_speed / wheel.radius;
+            state.angular_velocity = (state.angular_velocity
+                + drive_torque * inverse_inertia * delta_secs
+                + implicit_factor * rolling_angular_velocity)
+                / (1.0 + implicit_factor);
+            apply_brake(&mut state, brake_torque, wheel.inertia, delta_secs);
+            state.rotation_angle += state.angular_velocity * delta_secs;
+
+            // Compute the tire slip.
+            let slip_ratio =
+                (state.angular_velocity * wheel.radius - forward_speed) / slip_denominator;
+            let slip_angle = side_speed.atan2(slip_denominator);
+
+            // Compute the tire forces.
+            let mut longitudinal_force = wheel.friction.longitudinal_stiffness * slip_ratio * load;
+            let mut lateral_force = -wheel.friction.lateral_stiffness * slip_angle * load;
+
+            // Don't apply more lateral force than is needed to stop the sideways motion
+            // of this wheel's share of the chassis within the time step.
+            let max_lateral_force = side_speed.abs() * mass_per_wheel / delta_secs;
+            lateral_force = lateral_force.clamp(-max_lateral_force, max_lateral_force);
+
+            // Limit the combined force to the friction circle.
+            let max_force = wheel.friction.coefficient * load;
+            let force_magnitude = longitudinal_force.hypot(lateral_force);
+            if force_magnitude > max_force && force_magnitude > 0.0 {
+                let scale = max_force / force_magnitude;
+                longitudinal_force *= scale;
+                lateral_force *= scale;
+            }
+
+            let tire_force = forward * longitudinal_force + side * lateral_force;
+            forces.apply_force_at_point(tire_force, contact_point);
+
+            state.longitudinal_slip = slip_ratio;
+            state.lateral_slip = slip_angle;
+            state.tire_force = tire_force;
+        }
+    }
+}
+
+/// Applies the given brake torque to the spin of a wheel, without reversing its direction.
+fn apply_brake(state: &mut WheelState, brake_torque: Scalar, inertia: Scalar, delta_secs: Scalar) {
+    let brake_delta = brake_torque.abs() / inertia.max(Scalar::EPSILON) * delta_secs;
+    if state.angular_velocity.abs() <= brake_delta {
+        state.angular_velocity = 0.0;
+    } else {
+        state.angular_velocity -= brake_delta.copysign(state.angular_velocity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::tests::create_test_app;
+
+    fn create_app() -> App {
+        create_test_app(VehiclePlugin)
+    }
+
+    /// Spawns a chassis with a mass of 1080 kg and four wheels, with the rear wheels driven.
+    fn spawn_vehicle(app: &mut App, position: Vector) -> (Entity, Vec<Entity>) {
+        let chassis = app
+            .world_mut()
+            .spawn((
+                Vehicle::default(),
+                Position(position),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::from_shape(&Rectangle::new(1.8, 4.0), 150.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::from_shape(&Cuboid::new(1.8, 0.6, 4.0), 250.0),
+            ))
+            .id();
+
+        #[cfg(feature = "2d")]
+        let (front, rear) = (
+            [Vector::new(-0.9, 1.4), Vector::new(0.9, 1.4)],
+            [Vector::new(-0.9, -1.4), Vector::new(0.9, -1.4)],
+        );
+        #[cfg(feature = "3d")]
+        let (front, rear) = (
+            [Vector::new(-0.9, -0.3, -1.4), Vector::new(0.9, -0.3, -1.4)],
+            [Vector::new(-0.9, -0.3, 1.4), Vector::new(0.9, -0.3, 1.4)],
+        );
+
+        let mut wheels = Vec::new();
+        for anchor in front {
+            wheels.push(
+                app.world_mut()
+                    .spawn((
+                        WheelOf(chassis),
+                        Wheel::new(anchor, 0.35).with_steering(1.0),
+                    ))
+                    .id(),
+            );
+        }
+        for anchor in rear {
+            wheels.push(
+                app.world_mut()
+                    .spawn((WheelOf(chassis), Wheel::new(anchor, 0.35).with_drive(1.0)))
+                    .id(),
+            );
+        }
+
+        (chassis, wheels)
+    }
+
+    #[cfg(feature = "3d")]
+    fn spawn_ground(app: &mut App) {
+        app.world_mut().spawn((
+            RigidBody::Static,
+            Collider::cuboid(200.0, 1.0, 200.0),
+            Position(Vector::NEG_Y * 0.5),
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "3d")]
+    fn suspension_rest_height() {
+        let mut app = create_app();
+        spawn_ground(&mut app);
+        let (chassis, wheels) = spawn_vehicle(&mut app, Vector::Y * 1.2);
+
+        // Let the suspension settle.
+        for _ in 0..240 {
+            app.update();
+        }
+
+        // At rest, each spring carries a quarter of the weight of the chassis.
+        let mass: Scalar = 1.8 * 0.6 * 4.0 * 250.0;
+        let suspension = Suspension::default();
+        let expected_compression = mass * 9.81 / 4.0 / suspension.stiffness;
+
+        for wheel in wheels {
+            let state = app.world().get::<WheelState>(wheel).unwrap();
+            assert!(state.grounded);
+            assert_relative_eq!(state.compression, expected_compression, epsilon = 0.005);
+        }
+
+        // The anchors are 0.3 below the center of the chassis, and the ray hits the ground
+        // at the fully extended suspension length plus the wheel radius minus the compression.
+        let expected_height = 0.3 + suspension.rest_length + 0.35 - expected_compression;
+        let entity_ref = app.world().entity(chassis);
+        assert_relative_eq!(
+            entity_ref.get::<Position>().unwrap().y,
+            expected_height,
+            epsilon = 0.01
+        );
+        assert_relative_eq!(
+            entity_ref.get::<LinearVelocity>().unwrap().0,
+            Vector::ZERO,
+            epsilon = 0.01
+        );
+    }
+
+    /// The forward direction of a vehicle with no rotation. Vehicles face local +Y in 2D and -Z in 3D.
+    #[cfg(feature = "2d")]
+    const FORWARD: Vector = Vector::Y;
+    #[cfg(feature = "3d")]
+    const FORWARD: Vector = Vector::NEG_Z;
+
+    /// Spawns a vehicle that is ready to drive, and lets it settle.
+    ///
+    /// Top-down vehicles have no gravity, and 3D vehicles drive on the ground.
+    fn spawn_settled_vehicle(app: &mut App) -> (Entity, Vec<Entity>) {
+        #[cfg(feature = "2d")]
+        app.insert_resource(Gravity::ZERO);
+        #[cfg(feature = "3d")]
+        spawn_ground(app);
+
+        #[cfg(feature = "2d")]
+        let vehicle = spawn_vehicle(app, Vector::ZERO);
+        #[cfg(feature = "3d")]
+        let vehicle = spawn_vehicle(app, Vector::Y * 1.1);
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        vehicle
+    }
+
+    /// Returns the forward and sideways speed of the chassis.
+    fn forward_and_side_speed(app: &App, chassis: Entity) -> (Scalar, Scalar) {
+        let velocity = app.world().get::<LinearVelocity>(chassis).unwrap().0;
+        let rotation = *app.world().get::<Rotation>(chassis).unwrap();
+        (
+            velocity.dot(rotation * FORWARD),
+            velocity.dot(rotation * Vector::X),
+        )
+    }
+
+    /// Returns the angular velocity of the chassis around its up axis.
+    /// Positive values turn left, and negative values turn right.
+    fn yaw_rate(app: &App, chassis: Entity) -> Scalar {
+        let angular_velocity = app.world().get::<AngularVelocity>(chassis).unwrap().0;
+        #[cfg(feature = "2d")]
+        let yaw_rate = angular_velocity;
+        #[cfg(feature = "3d")]
+        let yaw_rate = angular_velocity.y;
+        yaw_rate
+    }
+
+    /// Sets the linear velocity of the chassis, and spins the wheels
+    /// so that they roll along with it.
+    fn set_rolling_velocity(app: &mut App, chassis: Entity, wheels: &[Entity], velocity: Vector) {
+        app.world_mut()
+            .get_mut::<LinearVelocity>(chassis)
+            .unwrap()
+            .0 = velocity;
+        let rolling_speed = velocity.dot(FORWARD) / 0.35;
+        for &wheel in wheels {
+            app.world_mut()
+                .get_mut::<WheelState>(wheel)
+                .unwrap()
+                .angular_velocity = rolling_speed;
+        }
+    }
+
+    #[test]
+    fn longitudinal_drive() {
+        let mut app = create_app();
+        let (chassis, _) = spawn_settled_vehicle(&mut app);
+
+        app.world_mut()
+            .get_mut::<VehicleControls>(chassis)
+            .unwrap()
+            .throttle = 1.0;
+
+        // Drive for two seconds.
+        for _ in 0..120 {
+            app.update();
+        }
+
+        let (forward_speed, side_speed) = forward_and_side_speed(&app, chassis);
+
+        // The engine torque exceeds the grip of the rear tires, so the acceleration is limited
+        // by the friction circle to half of the weight of the chassis, or about 4.9 m/s².
+        assert!(forward_speed > 2.0, "forward speed: {forward_speed}");
+        assert!(forward_speed < 10.5, "forward speed: {forward_speed}");
+        assert_relative_eq!(side_speed, 0.0, epsilon = 0.01);
+    }
+
+    #[test]
+    fn braking_stops_vehicle() {
+        let mut app = create_app();
+        let (chassis, wheels) = spawn_settled_vehicle(&mut app);
+        set_rolling_velocity(&mut app, chassis, &wheels, FORWARD * 10.0);
+        let start = app.world().get::<Position>(chassis).unwrap().0;
+
+        app.world_mut()
+            .get_mut::<VehicleControls>(chassis)
+            .unwrap()
+            .brake = 1.0;
+
+        // Brake for two seconds.
+        for _ in 0..120 {
+            app.update();
+        }
+
+        // The wheels lock up, and the deceleration is limited by the friction of the tires
+        // to about 9.81 m/s², so the vehicle stops in about a second and 5 meters.
+        let (forward_speed, _) = forward_and_side_speed(&app, chassis);
+        assert!(forward_speed.abs() < 0.1, "forward speed: {forward_speed}");
+
+        let stopping_distance =
+            (app.world().get::<Position>(chassis).unwrap().0 - start).dot(FORWARD);
+        assert!(
+            stopping_distance > 4.0 && stopping_distance < 7.0,
+            "stopping distance: {stopping_distance}"
+        );
+        for wheel in wheels {
+            let state = app.world().get::<WheelState>(wheel).unwrap();
+            assert_eq!(state.angular_velocity, 0.0);
+        }
+    }
+
+    #[test]
+    fn rolling_vehicle_keeps_speed_without_brakes() {
+        let mut app = create_app();
+        let (chassis, wheels) = spawn_settled_vehicle(&mut app);
+        set_rolling_velocity(&mut app, chassis, &wheels, FORWARD * 10.0);
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        // Without braking, the wheels keep rolling and the tires apply no longitudinal force.
+        let (forward_speed, _) = forward_and_side_speed(&app, chassis);
+        assert!(forward_speed > 9.0, "forward speed: {forward_speed}");
+    }
+
+    #[test]
+    fn steering_turns_vehicle() {
+        // Positive steering input steers right, and negative input steers left.
+        for steering in [1.0, -1.0] {
+            let mut app = create_app();
+            let (chassis, wheels) = spawn_settled_vehicle(&mut app);
+            set_rolling_velocity(&mut app, chassis, &wheels, FORWARD * 10.0);
+
+            app.world_mut()
+                .get_mut::<VehicleControls>(chassis)
+                .unwrap()
+                .steering = steering;
+
+            for _ in 0..30 {
+                app.update();
+            }
+
+            // The front wheels are steered by the full steering angle.
+            let expected_angle = steering * Vehicle::default().max_steering_angle;
+            for &wheel in &wheels[..2] {
+                let state = app.world().get::<WheelState>(wheel).unwrap();
+                assert_relative_eq!(state.steering_angle, expected_angle);
+            }
+            for &wheel in &wheels[2..] {
+                let state = app.world().get::<WheelState>(wheel).unwrap();
+                assert_eq!(state.steering_angle, 0.0);
+            }
+
+            // Turning right is a negative rotation around the up axis.
+            let yaw_rate = yaw_rate(&app, chassis);
+            assert!(
+                yaw_rate * steering < -0.1,
+                "steering: {steering}, yaw rate: {yaw_rate}"
+            );
+        }
+    }
+
+    #[test]
+    fn lateral_velocity_decays() {
+        let mut app = create_app();
+        let (chassis, wheels) = spawn_settled_vehicle(&mut app);
+
+        // Slide sideways without rolling forward.
+        app.world_mut()
+            .get_mut::<LinearVelocity>(chassis)
+            .unwrap()
+            .0 = Vector::X * 3.0;
+
+        app.update();
+
+        // The tires slip sideways, and the lateral tire forces push against the motion.
+        let (_, initial_side_speed) = forward_and_side_speed(&app, chassis);
+        assert!(initial_side_speed > 0.0);
+        for wheel in wheels {
+            let state = app.world().get::<WheelState>(wheel).unwrap();
+            assert!(state.lateral_slip > 0.0);
+            assert!(state.tire_force.x < 0.0);
+        }
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        // The sideways motion has stopped.
+        let (forward_speed, side_speed) = forward_and_side_speed(&app, chassis);
+        assert!(side_speed.abs() < 0.05, "side speed: {side_speed}");
+        assert!(forward_speed.abs() < 0.05, "forward speed: {forward_speed}");
+    }
+
+    #[test]
+    #[cfg(feature = "3d")]
+    fn anti_roll_bars_reduce_body_roll() {
+        /// Rolls the chassis and returns the largest roll angle reached.
+        fn max_roll_angle(anti_roll_stiffness: Option<Scalar>) -> Scalar {
+            let mut app = create_app();
+            let (chassis, wheels) = spawn_settled_vehicle(&mut app);
+
+            if let Some(stiffness) = anti_roll_stiffness {
+                let mut vehicle = app.world_mut().get_mut::<Vehicle>(chassis).unwrap();
+                vehicle.anti_roll_bars = vec![
+                    AntiRollBar {
+                        left: wheels[0],
+                        right: wheels[1],
+                        stiffness,
+                    },
+                    AntiRollBar {
+                        left: wheels[2],
+                        right: wheels[3],
+                        stiffness,
+                    },
+                ];
+            }
+
+            // Roll the chassis around its forward axis.
+            app.world_mut()
+                .get_mut::<AngularVelocity>(chassis)
+                .unwrap()
+                .0 = Vector::Z * 1.0;
+
+            let mut max_angle: Scalar = 0.0;
+            for _ in 0..30 {
+                app.update();
+                let rotation = *app.world().get::<Rotation>(chassis).unwrap();
+                let up = rotation * Vector::Y;
+                max_angle = max_angle.max(up.x.abs().asin());
+            }
+            max_angle
+        }
+
+        let without_bars = max_roll_angle(None);
+        let with_bars = max_roll_angle(Some(35_000.0));
+
+        assert!(
+            without_bars > 0.01,
+            "roll without anti-roll bars: {without_bars}"
+        );
+        assert!(
+            with_bars < 0.8 * without_bars,
+            "roll with anti-roll bars: {with_bars}, without: {without_bars}"
+        );
+    }
+}
@@ -58,7 +58,15 @@
     feature = "3d",
     doc = "| `collider-from-mesh`   | Allows you to create [`Collider`]s from `Mesh`es.                                                                                                  | Yes             |"
 )]
+#![cfg_attr(
+    feature = "2d",
+    doc = "| `collider-from-image`  | Allows you to create [`Collider`]s from the opaque regions of `Image`s and `Sprite`s.                                                              | No              |"
+)]
 //! | `bevy_scene`           | Enables [`ColliderConstructorHierarchy`] to wait until a [`Scene`] has loaded before processing it.                                                 | Yes             |
+#![cfg_attr(
+    feature = "3d",
+    doc = "| `bevy_animation`       | Enables generating [ragdolls](dynamics::ragdoll) from skinned meshes using the [`RagdollPlugin`]. Also requires `collider-from-mesh`.              | No              |"
+)]
 //! | `bevy_picking`         | Enables physics picking support for [`bevy_picking`] using the [`PhysicsPickingPlugin`]. The plugin must be added separately.                       | Yes             |
 //! | `bevy_diagnostic`      | Enables writing [physics diagnostics] to the [`DiagnosticsStore`] with the [`PhysicsDiagnosticsPlugin`]. The plugin must be added separately.       | No              |
 //! | `diagnostic_ui`        | Enables [physics diagnostics] UI for performance timers and counters using the [`PhysicsDiagnosticsUiPlugin`]. The plugin must be added separately. | No              |
@@ -130,7 +138,11 @@
 //! - [Movement](RigidBody#movement)
 //!     - [Linear](LinearVelocity) and [angular](AngularVelocity) velocity
 //!     - [External forces, impulses, and acceleration](dynamics::rigid_body::forces)
+//!     - [Moving kinematic bodies to target poses](KinematicTarget)
+//!     - [Carrying bodies on moving platforms](dynamics::platform)
 //! - [Gravity] and [gravity scale](GravityScale)
+//!     - [Per-body gravity](GravityOverride)
+//!     - [Gravity fields](GravityField)
 //! - [Mass properties](dynamics::rigid_body::mass_properties)
 //! - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
 //! - [Lock translational and rotational axes](LockedAxes)
@@ -173,13 +185,44 @@
 //!     - [Prismatic joint](PrismaticJoint)
 //!     - [Revolute joint](RevoluteJoint)
 #![cfg_attr(feature = "3d", doc = "    - [Spherical joint](SphericalJoint)")]
+//!     - [Gear joint](GearJoint)
+//!     - [Rack-and-pinion joint](RackAndPinionJoint)
+//!     - [Pulley joint](PulleyJoint)
+//!     - [Target joint](TargetJoint)
 //! - [Temporarily disabling a joint](JointDisabled)
 #![cfg_attr(
     feature = "xpbd_joints",
     doc = "- [Custom XPBD constraints](dynamics::solver::xpbd#constraints) (advanced)"
 )]
+//! - [Articulations](dynamics::articulation) simulated in reduced coordinates
+//!     - [Articulation joints](ArticulationJoint)
+//!     - [Joint drives](ArticulationDrive)
+//!     - [Inverse dynamics](Articulation::inverse_dynamics)
+//!
+//! Motors for ordinary joints are not supported yet, but they will be implemented in a future release.
 //!
-//! Joint motors and articulations are not supported yet, but they will be implemented in a future release.
+//! ## Soft Bodies
+//!
+//! - [Particle-based soft bodies and cloth](dynamics::soft_body)
+//!     - [Constructing soft bodies from meshes](SoftBody::from_mesh)
+//!     - [Coupling with rigid bodies](dynamics::soft_body#coupling-with-rigid-bodies)
+//! - [Ropes and cables](Rope)
+//!     - [Reeling](Rope#reeling)
+//!     - [Tearing](Rope#tearing)
+//!
+//! ## Vehicles
+//!
+//! - [Raycast vehicles](dynamics::vehicle)
+//!     - [Wheels](Wheel) and [suspension](dynamics::vehicle#overview)
+//!     - [Driver input](VehicleControls)
+#![cfg_attr(
+    all(
+        feature = "3d",
+        feature = "collider-from-mesh",
+        feature = "bevy_animation"
+    ),
+    doc = "\n## Ragdolls\n\n- [Ragdolls generated from skinned meshes](dynamics::ragdoll)\n    - [Animated and simulated modes](RagdollMode)"
+)]
 //!
 //! ## Spatial Queries
 //!
@@ -565,6 +608,7 @@ use prelude::*;
 /// | [`ColliderBackendPlugin`]         | Handles generic collider backend logic, like initializing colliders and AABBs and updating related components.                                             |
 /// | [`ColliderHierarchyPlugin`]       | Manages [`ColliderOf`] relationships based on the entity hierarchy.                                                                                        |
 /// | [`ColliderTransformPlugin`]       | Propagates and updates transforms for colliders.
+/// | [`ColliderEditingPlugin`]         | Wakes up sleeping bodies touching regions of colliders that were edited in place.                                                                          |
 #[cfg_attr(
     all(feature = "collider-from-mesh", feature = "default-collider"),
     doc = "| [`ColliderCachePlugin`]           | Caches colliders created from meshes. Requires `collider-from-mesh` and `default-collider` features.                                                       |"
@@ -583,6 +627,18 @@ use prelude::*;
 ///
 /// | Plugin                            | Description                                                                                                                                                |
 /// | --------------------------------- | ---------------------------------------------------------------------------------------------------------------------------------------------------------- |
+/// | [`ArticulationPlugin`]            | Simulates [articulations](dynamics::articulation) in reduced coordinates, with joint drives and inverse dynamics.                                          |
+/// | [`PlatformRiderPlugin`]           | Carries [platform riders](PlatformRider) along with the moving bodies they are standing on.                                                                |
+/// | [`SoftBodyPlugin`]                | Simulates particle-based [soft bodies, cloth, and ropes](dynamics::soft_body) using XPBD (only with `xpbd_joints` feature enabled).                    |
+/// | [`VehiclePlugin`]                 | Simulates [raycast vehicles](dynamics::vehicle) with suspension, tire friction, steering, and drivetrain input.                                          |
+#[cfg_attr(
+    all(
+        feature = "3d",
+        feature = "collider-from-mesh",
+        feature = "bevy_animation"
+    ),
+    doc = "| [`RagdollPlugin`]                 | Generates [ragdolls](dynamics::ragdoll) from skinned meshes and switches them between animation and simulation (only with `bevy_animation` feature enabled). |"
+)]
 /// | [`PhysicsPickingPlugin`]          | Enables a physics picking backend for [`bevy_picking`](bevy::picking) (only with `bevy_picking` feature enabled).                                          |
 /// | [`PhysicsDebugPlugin`]            | Renders physics objects and events like [AABBs](ColliderAabb) and contacts for debugging purposes (only with `debug-plugin` feature enabled).              |
 /// | [`PhysicsDiagnosticsPlugin`]      | Writes [physics diagnostics](diagnostics) to the [`DiagnosticsStore`] (only with `bevy_diagnostic` feature enabled).                                       |
@@ -799,6 +855,7 @@ impl PluginGroup for PhysicsPlugins {
         ))]
         let builder = builder
             .add(ColliderBackendPlugin::<Collider>::new(self.schedule))
+            .add(ColliderEditingPlugin::new(self.schedule))
             .add(NarrowPhasePlugin::<Collider>::default());
 
         // Add solver plugins.
@@ -1,6 +1,7 @@
 //! A physics picking backend for [`bevy_picking`](bevy::picking).
 //!
 //! Add the [`PhysicsPickingPlugin`] to enable picking for [colliders](Collider).
+//! Custom collider types are pickable if they have a [`SpatialQueryBackendPlugin`].
 //! By default, all colliders are pickable. Picking can be disabled for individual entities
 //! by adding [`Pickable::IGNORE`].
 //!
@@ -8,6 +9,11 @@
 //! to `true` and add a [`PhysicsPickable`] component to the desired camera and target entities.
 //!
 //! Cameras can further filter which entities are pickable with the [`PhysicsPickingFilter`] component.
+//!
+//! Dynamic bodies can also be dragged with the primary pointer button by setting
+//! [`PhysicsPickingSettings::drag_bodies`] to `true`. The body is pulled towards the pointer
+//! using a [`TargetJoint`] configured by [`PhysicsPickingSettings::drag_config`],
+//! so it keeps colliding with other bodies while being dragged.
 #![cfg_attr(
     feature = "3d",
     doc = "
@@ -22,9 +28,10 @@ use crate::{
 use bevy::{
     ecs::entity::hash_set::EntityHashSet,
     picking::{
-        PickingSystems,
         backend::{HitData, PointerHits, ray::RayMap},
+        pointer::PointerId,
     },
+    platform::collections::HashMap,
     prelude::*,
 };
 
@@ -62,8 +69,17 @@ pub struct PhysicsPickingPlugin;
 
 impl Plugin for PhysicsPickingPlugin {
     fn build(&self, app: &mut App) {
+        // Note: The systems for updating hits are added by the `SpatialQueryBackendPlugin`
+        //       of each collider type.
         app.init_resource::<PhysicsPickingSettings>()
-            .add_systems(PreUpdate, update_hits.in_set(PickingSystems::Backend));
+            .init_resource::<PhysicsPickingDrags>();
+
+        // Drag bodies with target joints.
+        app.add_observer(start_drag)
+            .add_observer(update_drag)
+            .add_observer(end_drag)
+            .add_observer(cancel_drag)
+            .add_observer(stop_dragging_removed_body);
     }
 
     fn finish(&self, app: &mut App) {
@@ -82,8 +98,65 @@ pub struct PhysicsPickingSettings {
     /// This setting is provided to give you fine-grained control over which cameras and entities
     /// should be used by the physics picking backend at runtime.
     pub require_markers: bool,
+    /// When set to `true`, dynamic bodies can be dragged with the primary pointer button.
+    /// `false` by default.
+    ///
+    /// The body is pulled towards the pointer using a [`TargetJoint`] configured by [`drag_config`](Self::drag_config).
+    /// In 3D, the body is dragged on a plane facing the camera at the initial grab point.
+    pub drag_bodies: bool,
+    /// The configuration of the [`TargetJoint`]s used for dragging bodies when [`drag_bodies`](Self::drag_bodies) is enabled.
+    pub drag_config: PhysicsPickingDragConfig,
+}
+
+/// The configuration of the [`TargetJoint`]s used for dragging bodies with the [`PhysicsPickingPlugin`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct PhysicsPickingDragConfig {
+    /// The frequency of the spring in Hertz. Higher values make dragging stiffer.
+    ///
+    /// Default: `5.0`
+    pub frequency: Scalar,
+    /// The damping ratio of the spring.
+    ///
+    /// Default: `0.7`
+    pub damping_ratio: Scalar,
+    /// The maximum acceleration that dragging can cause. The maximum force of the joint
+    /// is this value multiplied by the mass of the dragged body.
+    ///
+    /// Default: `1000.0`
+    pub max_acceleration: Scalar,
+}
+
+impl Default for PhysicsPickingDragConfig {
+    fn default() -> Self {
+        Self {
+            frequency: 5.0,
+            damping_ratio: 0.7,
+            max_acceleration: 1000.0,
+        }
+    }
+}
+
+/// The state of a body being dragged by a pointer.
+#[derive(Clone, Copy, Debug)]
+struct PointerDrag {
+    /// The body being dragged.
+    body: Entity,
+    /// The [`TargetJoint`] entity used for dragging.
+    joint: Entity,
+    /// The camera that the pointer is dragging in.
+    camera: Entity,
+    /// The initial grab point, used as the origin of the drag plane in 3D.
+    #[cfg_attr(feature = "2d", allow(dead_code))]
+    grab_point: Vec3,
 }
 
+/// The bodies currently being dragged by each pointer.
+#[derive(Resource, Debug, Default)]
+struct PhysicsPickingDrags(HashMap<PointerId, PointerDrag>);
+
 /// An optional component that marks cameras and target entities that should be used in the [`PhysicsPickingPlugin`].
 /// Only needed if [`PhysicsPickingSettings::require_markers`] is set to true.
 #[derive(Debug, Clone, Default, Component, Reflect)]
@@ -132,12 +205,149 @@ impl PhysicsPickingFilter {
     }
 }
 
+/// Starts dragging a body with a [`TargetJoint`] when a pointer starts dragging one of its colliders.
+fn start_drag(
+    drag: On<Pointer<DragStart>>,
+    settings: Res<PhysicsPickingSettings>,
+    mut drags: ResMut<PhysicsPickingDrags>,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<(&RigidBody, &ComputedMass)>,
+    mut commands: Commands,
+) {
+    // Pointer events propagate up the hierarchy, so a drag may already have been started for this pointer.
+    if !settings.drag_bodies
+        || drag.button != PointerButton::Primary
+        || drags.0.contains_key(&drag.pointer_id)
+    {
+        return;
+    }
+
+    let Some(grab_point) = drag.hit.position else {
+        return;
+    };
+    let Ok(&ColliderOf { body }) = colliders.get(drag.entity) else {
+        return;
+    };
+    let Ok((rigid_body, mass)) = bodies.get(body) else {
+        return;
+    };
+    if !rigid_body.is_dynamic() {
+        return;
+    }
+
+    #[cfg(feature = "2d")]
+    let anchor = grab_point.truncate().adjust_precision();
+    #[cfg(feature = "3d")]
+    let anchor = grab_point.adjust_precision();
+
+    let config = settings.drag_config;
+    let joint = commands
+        .spawn(
+            TargetJoint::new(body)
+                .with_anchor(anchor)
+                .with_target(anchor)
+                .with_frequency(config.frequency)
+                .with_damping_ratio(config.damping_ratio)
+                .with_max_force(config.max_acceleration * mass.value()),
+        )
+        .id();
+
+    drags.0.insert(
+        drag.pointer_id,
+        PointerDrag {
+            body,
+            joint,
+            camera: drag.hit.camera,
+            grab_point,
+        },
+    );
+}
+
+/// Moves the target of the [`TargetJoint`] used for dragging to follow the pointer.
+fn update_drag(
+    drag: On<Pointer<Drag>>,
+    drags: Res<PhysicsPickingDrags>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut joints: Query<&mut TargetJoint>,
+) {
+    let Some(state) = drags.0.get(&drag.pointer_id) else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get(state.camera) else {
+        return;
+    };
+
+    let mut viewport_position = drag.pointer_location.position;
+    if let Some(viewport) = camera.logical_viewport_rect() {
+        viewport_position -= viewport.min;
+    }
+    let Ok(ray) = camera.viewport_to_world(camera_transform, viewport_position) else {
+        return;
+    };
+
+    #[cfg(feature = "2d")]
+    let target = ray.origin.truncate().adjust_precision();
+    #[cfg(feature = "3d")]
+    let target = {
+        let plane = InfinitePlane3d::new(camera_transform.forward());
+        let Some(distance) = ray.intersect_plane(state.grab_point, plane) else {
+            return;
+        };
+        ray.get_point(distance).adjust_precision()
+    };
+
+    if let Ok(mut joint) = joints.get_mut(state.joint) {
+        joint.target = target;
+    }
+}
+
+/// Stops dragging a body when the primary pointer button is released.
+fn end_drag(
+    drag: On<Pointer<DragEnd>>,
+    mut drags: ResMut<PhysicsPickingDrags>,
+    mut commands: Commands,
+) {
+    if drag.button != PointerButton::Primary {
+        return;
+    }
+    if let Some(state) = drags.0.remove(&drag.pointer_id) {
+        commands.entity(state.joint).try_despawn();
+    }
+}
+
+/// Stops dragging a body when the pointer is cancelled.
+fn cancel_drag(
+    cancel: On<Pointer<Cancel>>,
+    mut drags: ResMut<PhysicsPickingDrags>,
+    mut commands: Commands,
+) {
+    if let Some(state) = drags.0.remove(&cancel.pointer_id) {
+        commands.entity(state.joint).try_despawn();
+    }
+}
+
+/// Stops dragging a body when it is despawned or its [`RigidBody`] is removed.
+fn stop_dragging_removed_body(
+    remove: On<Remove, RigidBody>,
+    mut drags: ResMut<PhysicsPickingDrags>,
+    mut commands: Commands,
+) {
+    drags.0.retain(|_, state| {
+        if state.body != remove.entity {
+            return true;
+        }
+        commands.entity(state.joint).try_despawn();
+        false
+    });
+}
+
 // Store a const reference to the default filter to avoid unnecessary allocations.
 const DEFAULT_FILTER_REF: &PhysicsPickingFilter =
     &PhysicsPickingFilter(SpatialQueryFilter::DEFAULT);
 
-/// Queries for collider intersections with pointers using [`PhysicsPickingSettings`] and sends [`PointerHits`] events.
-pub fn update_hits(
+/// Queries for intersections between pointers and colliders of type `C`
+/// using [`PhysicsPickingSettings`] and sends [`PointerHits`] events.
+pub fn update_hits<C: QueryCollider>(
     picking_cameras: Query<(
         &Camera,
         Option<&PhysicsPickingFilter>,
@@ -147,7 +357,7 @@ pub fn update_hits(
     pickables: Query<&Pickable>,
     marked_targets: Query<&PhysicsPickable>,
     backend_settings: Res<PhysicsPickingSettings>,
-    spatial_query: SpatialQuery,
+    spatial_query: SpatialQuery<C>,
     mut output_events: MessageWriter<PointerHits>,
     mut diagnostics: ResMut<PhysicsPickingDiagnostics>,
 ) {
@@ -237,3 +447,37 @@ pub fn update_hits(
 
     diagnostics.update_hits = start_time.elapsed();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::create_test_app;
+
+    #[test]
+    fn despawning_dragged_body_removes_target_joint() {
+        let mut app = create_test_app(PhysicsPickingPlugin);
+
+        let body = app.world_mut().spawn(RigidBody::Dynamic).id();
+        let joint = app.world_mut().spawn(TargetJoint::new(body)).id();
+        let camera = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .resource_mut::<PhysicsPickingDrags>()
+            .0
+            .insert(
+                PointerId::Mouse,
+                PointerDrag {
+                    body,
+                    joint,
+                    camera,
+                    grab_point: Vec3::ZERO,
+                },
+            );
+
+        app.world_mut().despawn(body);
+        app.update();
+
+        // The drag is stopped, and the joint no longer references a despawned body.
+        assert!(app.world().get_entity(joint).is_err());
+        assert!(app.world().resource::<PhysicsPickingDrags>().0.is_empty());
+    }
+}
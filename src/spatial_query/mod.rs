@@ -149,10 +149,32 @@
 //! See the documentation of the components and methods for more information.
 //!
 //! To specify which colliders should be considered in the query, use a [spatial query filter](`SpatialQueryFilter`).
+//!
+//! # Radial impulses
+//!
+//! **Radial impulses** use intersection tests to find all dynamic rigid bodies within a radius, and push them
+//! away from a center point. This is useful for explosions, shockwaves, and other area effects.
+//!
+//! Radial impulses can be applied with the [`apply_radial_impulse`](RadialImpulses::apply_radial_impulse) and
+//! [`apply_radial_impulse_occluded`](RadialImpulses::apply_radial_impulse_occluded) methods of the [`RadialImpulses`]
+//! system parameter. The strength of the impulse is attenuated with an [`ImpulseFalloff`].
+//!
+//! The same methods are also available on [`SpatialQuery`], taking a query for the [`Forces`] of bodies
+//! as an additional argument.
+//!
+//! # Custom colliders
+//!
+//! Spatial queries are not limited to [`Collider`]. Custom collider types can implement the [`QueryCollider`] trait
+//! and add the [`SpatialQueryBackendPlugin`] to get their own [`SpatialQueryPipeline`]. They can then be queried
+//! with `SpatialQuery<MyCollider>`, and they are hit by [`RayCaster`]s and the physics picking backend.
 
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 mod pipeline;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+mod query_collider;
 mod query_filter;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+mod radial_impulse;
 mod ray_caster;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 mod shape_caster;
@@ -164,19 +186,29 @@ pub use diagnostics::SpatialQueryDiagnostics;
 
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 pub use pipeline::*;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+pub use query_collider::QueryCollider;
 pub use query_filter::*;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+pub use radial_impulse::{ImpulseFalloff, RadialImpulses};
 pub use ray_caster::*;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 pub use shape_caster::*;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 pub use system_param::*;
 
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+use core::marker::PhantomData;
+
 use crate::prelude::*;
 use bevy::prelude::*;
 
 /// Initializes the [`SpatialQueryPipeline`] resource and handles component-based [spatial queries](spatial_query)
 /// like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting) with
 /// [`RayCaster`] and [`ShapeCaster`].
+///
+/// Spatial queries for [`Collider`] are enabled automatically. For custom collider types
+/// that implement [`QueryCollider`], add the [`SpatialQueryBackendPlugin`] for the collider type.
 pub struct SpatialQueryPlugin;
 
 impl Plugin for SpatialQueryPlugin {
@@ -185,7 +217,9 @@ impl Plugin for SpatialQueryPlugin {
             feature = "default-collider",
             any(feature = "parry-f32", feature = "parry-f64")
         ))]
-        app.init_resource::<SpatialQueryPipeline>();
+        if !app.is_plugin_added::<SpatialQueryBackendPlugin<Collider>>() {
+            app.add_plugins(SpatialQueryBackendPlugin::<Collider>::default());
+        }
 
         let physics_schedule = app
             .get_schedule_mut(PhysicsSchedule)
@@ -194,21 +228,30 @@ impl Plugin for SpatialQueryPlugin {
         physics_schedule.add_systems(
             (
                 update_ray_caster_positions,
-                #[cfg(all(
-                    feature = "default-collider",
-                    any(feature = "parry-f32", feature = "parry-f64")
-                ))]
-                (
-                    update_shape_caster_positions,
-                    update_spatial_query_pipeline,
-                    raycast,
-                    shapecast,
-                )
-                    .chain(),
+                #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+                clear_ray_hits,
             )
                 .chain()
                 .in_set(PhysicsStepSystems::SpatialQuery),
         );
+
+        #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+        physics_schedule.add_systems(
+            truncate_ray_hits
+                .after(clear_ray_hits)
+                .in_set(PhysicsStepSystems::SpatialQuery),
+        );
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        physics_schedule.add_systems(
+            (update_shape_caster_positions, shapecast)
+                .chain()
+                .after(update_spatial_query_pipeline::<Collider>)
+                .in_set(PhysicsStepSystems::SpatialQuery),
+        );
     }
 
     fn finish(&self, app: &mut App) {
@@ -217,13 +260,67 @@ impl Plugin for SpatialQueryPlugin {
     }
 }
 
-/// Updates the [`SpatialQueryPipeline`].
-#[cfg(all(
-    feature = "default-collider",
-    any(feature = "parry-f32", feature = "parry-f64")
-))]
-pub fn update_spatial_query_pipeline(
-    mut spatial_query: SpatialQuery,
+/// A plugin that enables [spatial queries](spatial_query) for colliders of type `C`.
+///
+/// The plugin initializes the [`SpatialQueryPipeline<C>`](SpatialQueryPipeline) resource, keeps it up to date,
+/// and casts [`RayCaster`]s against it. Queries can then be performed using [`SpatialQuery<C>`](SpatialQuery).
+/// If the `bevy_picking` feature is enabled and the [`PhysicsPickingPlugin`] has been added,
+/// colliders of type `C` are also pickable.
+///
+/// The plugin is added automatically for [`Collider`] by the [`SpatialQueryPlugin`].
+/// Custom collider types must implement [`QueryCollider`] and have a [`ColliderBackendPlugin`].
+///
+/// Hits of [`RayCaster`]s are combined across all collider types. [`ShapeCaster`]s
+/// only support [`Collider`], but shapes of custom types can be cast using [`SpatialQuery::cast_shape`].
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+pub struct SpatialQueryBackendPlugin<C: QueryCollider> {
+    _phantom: PhantomData<C>,
+}
+
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+impl<C: QueryCollider> Default for SpatialQueryBackendPlugin<C> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+impl<C: QueryCollider> Plugin for SpatialQueryBackendPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialQueryPipeline<C>>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            (update_spatial_query_pipeline::<C>, raycast::<C>)
+                .chain()
+                .after(clear_ray_hits)
+                .before(truncate_ray_hits)
+                .in_set(PhysicsStepSystems::SpatialQuery),
+        );
+    }
+
+    #[cfg_attr(not(feature = "bevy_picking"), allow(unused_variables))]
+    fn finish(&self, app: &mut App) {
+        // Enable physics picking for the collider type.
+        #[cfg(feature = "bevy_picking")]
+        if app.is_plugin_added::<PhysicsPickingPlugin>() {
+            app.add_systems(
+                PreUpdate,
+                crate::picking::update_hits::<C>.in_set(bevy::picking::PickingSystems::Backend),
+            );
+        }
+    }
+}
+
+/// Updates the [`SpatialQueryPipeline`] for colliders of type `C`.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+pub fn update_spatial_query_pipeline<C: QueryCollider>(
+    mut spatial_query: SpatialQuery<C>,
     mut diagnostics: ResMut<SpatialQueryDiagnostics>,
 ) {
     let start = crate::utils::Instant::now();
@@ -396,10 +493,40 @@ fn update_shape_caster_positions(
     }
 }
 
+/// Clears the [`RayHits`] of all [`RayCaster`]s before the rays are cast against
+/// the pipelines of each collider type.
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
-fn raycast(
+fn clear_ray_hits(mut rays: Query<&mut RayHits>) {
+    for mut hits in &mut rays {
+        if !hits.is_empty() {
+            hits.clear();
+        }
+    }
+}
+
+/// Truncates the [`RayHits`] of all [`RayCaster`]s to `max_hits` once the rays have been cast
+/// against the pipelines of all collider types.
+///
+/// The hits from all pipelines are merged first, and then sorted by distance
+/// so that the closest hits are kept.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn truncate_ray_hits(mut rays: Query<(&RayCaster, &mut RayHits)>) {
+    for (ray, mut hits) in &mut rays {
+        let max_hits = ray.max_hits as usize;
+        if hits.len() <= max_hits {
+            continue;
+        }
+
+        hits.0
+            .sort_unstable_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits.0.truncate(max_hits);
+    }
+}
+
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn raycast<C: QueryCollider>(
     mut rays: Query<(Entity, &mut RayCaster, &mut RayHits)>,
-    spatial_query: SpatialQuery,
+    spatial_query: SpatialQuery<C>,
     mut diagnostics: ResMut<SpatialQueryDiagnostics>,
 ) {
     let start = crate::utils::Instant::now();
@@ -407,8 +534,6 @@ fn raycast(
     for (entity, mut ray, mut hits) in &mut rays {
         if ray.enabled {
             ray.cast(entity, &mut hits, &spatial_query.query_pipeline);
-        } else if !hits.is_empty() {
-            hits.clear();
         }
     }
 
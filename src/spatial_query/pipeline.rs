@@ -1,13 +1,13 @@
-use alloc::sync::Arc;
+use core::cell::Cell;
 
-use crate::prelude::*;
+use crate::{make_isometry, prelude::*};
 use bevy::prelude::*;
 use parry::{
     bounding_volume::{Aabb, BoundingVolume},
     math::Isometry,
     partitioning::{Bvh, BvhBuildStrategy, BvhNode},
     query::{
-        DefaultQueryDispatcher, QueryDispatcher, RayCast, ShapeCastOptions,
+        DefaultQueryDispatcher, PointQuery, Ray, RayCast, ShapeCastOptions,
         details::NormalConstraints,
     },
     shape::{CompositeShape, CompositeShapeRef, Shape, TypedCompositeShape},
@@ -16,10 +16,11 @@ use parry::{
 // TODO: It'd be nice not to store so much duplicate data.
 //       Should we just query the ECS?
 #[derive(Clone)]
-pub(crate) struct BvhProxyData {
+pub(crate) struct BvhProxyData<C: QueryCollider> {
     pub entity: Entity,
-    pub isometry: Isometry<Scalar>,
-    pub collider: Collider,
+    pub position: Vector,
+    pub rotation: Rotation,
+    pub collider: C,
     pub layers: CollisionLayers,
 }
 
@@ -27,50 +28,30 @@ pub(crate) struct BvhProxyData {
 ///
 /// The pipeline maintains a quaternary bounding volume hierarchy `Bvh` of the world's colliders
 /// as an acceleration structure for spatial queries.
+///
+/// The pipeline is generic over the collider type, which must implement [`QueryCollider`].
+/// By default, [`Collider`] is used. Pipelines for custom collider types
+/// are initialized by the [`SpatialQueryBackendPlugin`].
 #[derive(Resource, Clone)]
-pub struct SpatialQueryPipeline {
+pub struct SpatialQueryPipeline<C: QueryCollider = Collider> {
     pub(crate) bvh: Bvh,
-    pub(crate) dispatcher: Arc<dyn QueryDispatcher>,
     // TODO: Store the proxies as `Bvh` leaf data.
-    pub(crate) proxies: Vec<BvhProxyData>,
+    pub(crate) proxies: Vec<BvhProxyData<C>>,
 }
 
-impl Default for SpatialQueryPipeline {
+impl<C: QueryCollider> Default for SpatialQueryPipeline<C> {
     fn default() -> Self {
         Self {
             bvh: Bvh::new(),
-            dispatcher: Arc::new(DefaultQueryDispatcher),
             proxies: Vec::default(),
         }
     }
 }
 
-impl SpatialQueryPipeline {
+impl<C: QueryCollider> SpatialQueryPipeline<C> {
     /// Creates a new [`SpatialQueryPipeline`].
-    pub fn new() -> SpatialQueryPipeline {
-        SpatialQueryPipeline::default()
-    }
-
-    pub(crate) fn as_composite_shape<'a>(
-        &'a self,
-        query_filter: &'a SpatialQueryFilter,
-    ) -> QueryPipelineAsCompositeShape<'a> {
-        QueryPipelineAsCompositeShape {
-            pipeline: self,
-            query_filter,
-        }
-    }
-
-    pub(crate) fn as_composite_shape_with_predicate<'a: 'b, 'b>(
-        &'a self,
-        query_filter: &'a SpatialQueryFilter,
-        predicate: &'a dyn Fn(Entity) -> bool,
-    ) -> QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-        QueryPipelineAsCompositeShapeWithPredicate {
-            pipeline: self,
-            query_filter,
-            predicate,
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 
     /// Updates the associated acceleration structures with a new set of entities.
@@ -81,7 +62,7 @@ impl SpatialQueryPipeline {
                 Entity,
                 &'a Position,
                 &'a Rotation,
-                &'a Collider,
+                &'a C,
                 &'a CollisionLayers,
             ),
         >,
@@ -90,7 +71,8 @@ impl SpatialQueryPipeline {
             colliders.map(
                 |(entity, position, rotation, collider, layers)| BvhProxyData {
                     entity,
-                    isometry: make_isometry(position.0, *rotation),
+                    position: position.0,
+                    rotation: *rotation,
                     collider: collider.clone(),
                     layers: *layers,
                 },
@@ -99,15 +81,13 @@ impl SpatialQueryPipeline {
     }
 
     // TODO: Incremental updates.
-    fn update_internal(&mut self, proxies: impl Iterator<Item = BvhProxyData>) {
+    fn update_internal(&mut self, proxies: impl Iterator<Item = BvhProxyData<C>>) {
         self.proxies.clear();
         self.proxies.extend(proxies);
 
         let aabbs = self.proxies.iter().enumerate().map(|(i, proxy)| {
-            (
-                i,
-                proxy.collider.shape_scaled().compute_aabb(&proxy.isometry),
-            )
+            let aabb = proxy.collider.query_aabb(proxy.position, proxy.rotation);
+            (i, Aabb::new(aabb.min.into(), aabb.max.into()))
         });
 
         self.bvh = Bvh::from_iter(BvhBuildStrategy::Binned, aabbs);
@@ -168,17 +148,67 @@ impl SpatialQueryPipeline {
         filter: &SpatialQueryFilter,
         predicate: &dyn Fn(Entity) -> bool,
     ) -> Option<RayHitData> {
-        let composite = self.as_composite_shape_with_predicate(filter, predicate);
-        let pipeline_shape = CompositeShapeRef(&composite);
-        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        C::pipeline_cast_ray(
+            self,
+            origin,
+            direction,
+            max_distance,
+            solid,
+            filter,
+            predicate,
+        )
+    }
 
-        pipeline_shape
-            .cast_local_ray_and_get_normal(&ray, max_distance, solid)
-            .map(|(index, hit)| RayHitData {
-                entity: self.proxies[index as usize].entity,
-                distance: hit.time_of_impact,
-                normal: hit.normal.into(),
-            })
+    /// Casts a ray by traversing the BVH depth-first, culling nodes that are further away
+    /// than the closest hit found so far. This works for any [`QueryCollider`].
+    pub(crate) fn cast_ray_depth_first(
+        &self,
+        origin: Vector,
+        direction: Dir,
+        max_distance: Scalar,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<RayHitData> {
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
+
+        // The distance to the closest hit so far, used for culling the traversal.
+        let closest_distance = Cell::new(max_distance);
+        let mut closest_hit = None;
+
+        let leaves = self.bvh.leaves(|node: &BvhNode| {
+            node.aabb()
+                .intersects_local_ray(&ray, closest_distance.get())
+        });
+
+        for leaf in leaves {
+            let Some(proxy) = self.proxies.get(leaf as usize) else {
+                continue;
+            };
+
+            if !filter.test(proxy.entity, proxy.layers) || !predicate(proxy.entity) {
+                continue;
+            }
+
+            if let Some((distance, normal)) = proxy.collider.cast_ray(
+                proxy.position,
+                proxy.rotation,
+                origin,
+                direction.adjust_precision(),
+                closest_distance.get(),
+                solid,
+            ) && (closest_hit.is_none() || distance < closest_distance.get())
+            {
+                closest_distance.set(distance);
+                closest_hit = Some(RayHitData {
+                    entity: proxy.entity,
+                    distance,
+                    normal,
+                });
+            }
+        }
+
+        closest_hit
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData) until `max_hits` is reached.
@@ -250,7 +280,7 @@ impl SpatialQueryPipeline {
     ) {
         let proxies = &self.proxies;
 
-        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
 
         let hits = self
             .bvh
@@ -262,17 +292,19 @@ impl SpatialQueryPipeline {
                     return None;
                 }
 
-                let hit = proxy.collider.shape_scaled().cast_ray_and_get_normal(
-                    &proxy.isometry,
-                    &ray,
+                let (distance, normal) = proxy.collider.cast_ray(
+                    proxy.position,
+                    proxy.rotation,
+                    origin,
+                    direction.adjust_precision(),
                     max_distance,
                     solid,
                 )?;
 
                 Some(RayHitData {
                     entity: proxy.entity,
-                    distance: hit.time_of_impact,
-                    normal: hit.normal.into(),
+                    distance,
+                    normal,
                 })
             });
 
@@ -290,7 +322,7 @@ impl SpatialQueryPipeline {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -305,7 +337,7 @@ impl SpatialQueryPipeline {
     #[allow(clippy::too_many_arguments)]
     pub fn cast_shape(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -330,7 +362,7 @@ impl SpatialQueryPipeline {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -346,7 +378,7 @@ impl SpatialQueryPipeline {
     #[allow(clippy::too_many_arguments)]
     pub fn cast_shape_predicate(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -364,32 +396,72 @@ impl SpatialQueryPipeline {
             rotation = Rotation::from(shape_rotation);
         }
 
-        let shape_isometry = make_isometry(origin, rotation);
-        let shape_direction = direction.adjust_precision().into();
-        let composite = self.as_composite_shape_with_predicate(filter, predicate);
-        let pipeline_shape = CompositeShapeRef(&composite);
+        C::pipeline_cast_shape(
+            self, shape, origin, rotation, direction, config, filter, predicate,
+        )
+    }
 
-        pipeline_shape
-            .cast_shape(
-                self.dispatcher.as_ref(),
-                &shape_isometry,
-                &shape_direction,
-                shape.shape_scaled().as_ref(),
-                ShapeCastOptions {
-                    max_time_of_impact: config.max_distance,
-                    stop_at_penetration: !config.ignore_origin_penetration,
-                    compute_impact_geometry_on_penetration: config.compute_contact_on_penetration,
-                    ..default()
-                },
-            )
-            .map(|(index, hit)| ShapeHitData {
-                entity: self.proxies[index as usize].entity,
-                distance: hit.time_of_impact,
-                point1: hit.witness1.into(),
-                point2: hit.witness2.into(),
-                normal1: hit.normal1.into(),
-                normal2: hit.normal2.into(),
-            })
+    /// Casts a shape by traversing the BVH depth-first, culling nodes that are further away
+    /// than the closest hit found so far. This works for any [`QueryCollider`].
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn cast_shape_depth_first(
+        &self,
+        shape: &C,
+        origin: Vector,
+        rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<ShapeHitData> {
+        // The shape hits a node if its origin travels through the node expanded by the extents of the shape.
+        let shape_aabb = shape.query_aabb(origin, rotation);
+        let min_offset = origin - shape_aabb.min + config.target_distance;
+        let max_offset = shape_aabb.max - origin + config.target_distance;
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
+
+        // The distance to the closest hit so far, used for culling the traversal.
+        let closest_distance = Cell::new(config.max_distance);
+        let mut closest_hit: Option<ShapeHitData> = None;
+
+        let leaves = self.bvh.leaves(|node: &BvhNode| {
+            let aabb = node.aabb();
+            let expanded_aabb = Aabb::new(
+                aabb.mins - parry::math::Vector::from(max_offset),
+                aabb.maxs + parry::math::Vector::from(min_offset),
+            );
+            expanded_aabb.intersects_local_ray(&ray, closest_distance.get())
+        });
+
+        for leaf in leaves {
+            let Some(proxy) = self.proxies.get(leaf as usize) else {
+                continue;
+            };
+
+            if !filter.test(proxy.entity, proxy.layers) || !predicate(proxy.entity) {
+                continue;
+            }
+
+            if let Some(hit) = proxy.collider.cast_shape(
+                proxy.position,
+                proxy.rotation,
+                shape,
+                origin,
+                rotation,
+                direction,
+                config,
+            ) && hit.distance <= closest_distance.get()
+                && closest_hit.is_none_or(|closest| hit.distance < closest.distance)
+            {
+                closest_distance.set(hit.distance);
+                closest_hit = Some(ShapeHitData {
+                    entity: proxy.entity,
+                    ..hit
+                });
+            }
+        }
+
+        closest_hit
     }
 
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes computes all [hits](ShapeHitData)
@@ -397,7 +469,7 @@ impl SpatialQueryPipeline {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -413,7 +485,7 @@ impl SpatialQueryPipeline {
     #[allow(clippy::too_many_arguments)]
     pub fn shape_hits(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -443,7 +515,7 @@ impl SpatialQueryPipeline {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -459,7 +531,7 @@ impl SpatialQueryPipeline {
     #[allow(clippy::too_many_arguments)]
     pub fn shape_hits_callback(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -472,54 +544,17 @@ impl SpatialQueryPipeline {
         //       See https://github.com/Jondolf/avian/issues/403.
         let mut query_filter = filter.clone();
 
-        let shape_cast_options = ShapeCastOptions {
-            max_time_of_impact: config.max_distance,
-            target_distance: config.target_distance,
-            stop_at_penetration: !config.ignore_origin_penetration,
-            compute_impact_geometry_on_penetration: config.compute_contact_on_penetration,
-        };
-
-        let rotation: Rotation;
-        #[cfg(feature = "2d")]
-        {
-            rotation = Rotation::radians(shape_rotation);
-        }
-        #[cfg(feature = "3d")]
-        {
-            rotation = Rotation::from(shape_rotation);
-        }
-
-        let shape_isometry = make_isometry(origin, rotation);
-        let shape_direction = direction.adjust_precision().into();
-
-        loop {
-            let composite = self.as_composite_shape(&query_filter);
-            let pipeline_shape = CompositeShapeRef(&composite);
-
-            let hit = pipeline_shape
-                .cast_shape(
-                    self.dispatcher.as_ref(),
-                    &shape_isometry,
-                    &shape_direction,
-                    shape.shape_scaled().as_ref(),
-                    shape_cast_options,
-                )
-                .map(|(index, hit)| ShapeHitData {
-                    entity: self.proxies[index as usize].entity,
-                    distance: hit.time_of_impact,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
-                });
-
-            if let Some(hit) = hit {
-                query_filter.excluded_entities.insert(hit.entity);
+        while let Some(hit) = self.cast_shape(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            config,
+            &query_filter,
+        ) {
+            query_filter.excluded_entities.insert(hit.entity);
 
-                if !callback(hit) {
-                    break;
-                }
-            } else {
+            if !callback(hit) {
                 break;
             }
         }
@@ -567,22 +602,59 @@ impl SpatialQueryPipeline {
         solid: bool,
         filter: &SpatialQueryFilter,
         predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<PointProjection> {
+        C::pipeline_project_point(self, point, solid, filter, predicate)
+    }
+
+    /// Projects a point by traversing the BVH depth-first, culling nodes that are further away
+    /// than the closest projection found so far. This works for any [`QueryCollider`].
+    pub(crate) fn project_point_depth_first(
+        &self,
+        point: Vector,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
     ) -> Option<PointProjection> {
         if self.proxies.is_empty() {
             return None;
         }
 
-        let point = point.into();
-        let composite = self.as_composite_shape_with_predicate(filter, predicate);
-        let pipeline_shape = CompositeShapeRef(&composite);
+        let local_point = point.into();
 
-        let (index, projection) = pipeline_shape.project_local_point(&point, solid);
+        // The distance to the closest projection so far, used for culling the traversal.
+        let closest_distance = Cell::new(Scalar::MAX);
+        let mut closest_projection = None;
 
-        Some(PointProjection {
-            entity: self.proxies[index as usize].entity,
-            point: projection.point.into(),
-            is_inside: projection.is_inside,
-        })
+        let leaves = self.bvh.leaves(|node: &BvhNode| {
+            node.aabb().distance_to_local_point(&local_point, true) <= closest_distance.get()
+        });
+
+        for leaf in leaves {
+            let Some(proxy) = self.proxies.get(leaf as usize) else {
+                continue;
+            };
+
+            if !filter.test(proxy.entity, proxy.layers) || !predicate(proxy.entity) {
+                continue;
+            }
+
+            let (projection, is_inside) =
+                proxy
+                    .collider
+                    .project_point(proxy.position, proxy.rotation, point, solid);
+            let distance = projection.distance(point);
+
+            if distance < closest_distance.get() {
+                closest_distance.set(distance);
+                closest_projection = Some(PointProjection {
+                    entity: proxy.entity,
+                    point: projection,
+                    is_inside,
+                });
+            }
+        }
+
+        closest_projection
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
@@ -626,19 +698,18 @@ impl SpatialQueryPipeline {
         filter: &SpatialQueryFilter,
         mut callback: impl FnMut(Entity) -> bool,
     ) {
-        let point = point.into();
+        let local_point = point.into();
 
         let intersecting_entities = self
             .bvh
-            .leaves(|node: &BvhNode| node.aabb().contains_local_point(&point))
+            .leaves(|node: &BvhNode| node.aabb().contains_local_point(&local_point))
             .filter_map(move |leaf| {
                 let proxy = self.proxies.get(leaf as usize)?;
 
                 if filter.test(proxy.entity, proxy.layers)
                     && proxy
                         .collider
-                        .shape_scaled()
-                        .contains_point(&proxy.isometry, &point)
+                        .contains_point(proxy.position, proxy.rotation, point)
                 {
                     Some(proxy.entity)
                 } else {
@@ -699,12 +770,12 @@ impl SpatialQueryPipeline {
         }
     }
 
-    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that is intersecting the given `shape` with a given position and rotation.
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape`: The shape that intersections are tested against represented as a collider.
     /// - `shape_position`: The position of the shape.
     /// - `shape_rotation`: The rotation of the shape.
     /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
@@ -714,7 +785,7 @@ impl SpatialQueryPipeline {
     /// - [`SpatialQueryPipeline::shape_intersections_callback`]
     pub fn shape_intersections(
         &self,
-        shape: &Collider,
+        shape: &C,
         shape_position: Vector,
         shape_rotation: RotationValue,
         filter: &SpatialQueryFilter,
@@ -727,13 +798,13 @@ impl SpatialQueryPipeline {
         intersections
     }
 
-    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that is intersecting the given `shape` with a given position and rotation, calling `callback` for each
     /// intersection. The search stops when `callback` returns `false` or all intersections have been found.
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape`: The shape that intersections are tested against represented as a collider.
     /// - `shape_position`: The position of the shape.
     /// - `shape_rotation`: The rotation of the shape.
     /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
@@ -744,7 +815,7 @@ impl SpatialQueryPipeline {
     /// - [`SpatialQueryPipeline::shape_intersections`]
     pub fn shape_intersections_callback(
         &self,
-        shape: &Collider,
+        shape: &C,
         shape_position: Vector,
         shape_rotation: RotationValue,
         filter: &SpatialQueryFilter,
@@ -761,12 +832,8 @@ impl SpatialQueryPipeline {
             rotation = Rotation::from(shape_rotation);
         }
 
-        let shape_isometry = make_isometry(shape_position, rotation);
-        let inverse_shape_isometry = shape_isometry.inverse();
-
-        let dispatcher = &*self.dispatcher;
-
-        let shape_aabb = shape.shape_scaled().compute_aabb(&shape_isometry);
+        let shape_aabb = shape.query_aabb(shape_position, rotation);
+        let shape_aabb = Aabb::new(shape_aabb.min.into(), shape_aabb.max.into());
         let entities = self
             .bvh
             .leaves(move |node: &BvhNode| node.aabb().intersects(&shape_aabb))
@@ -777,14 +844,13 @@ impl SpatialQueryPipeline {
                     return None;
                 }
 
-                let isometry = inverse_shape_isometry * proxy.isometry;
-                let intersects = dispatcher
-                    .intersection_test(
-                        &isometry,
-                        shape.shape_scaled().as_ref(),
-                        proxy.collider.shape_scaled().as_ref(),
-                    )
-                    .is_ok_and(|b| b);
+                let intersects = proxy.collider.intersects_shape(
+                    proxy.position,
+                    proxy.rotation,
+                    shape,
+                    shape_position,
+                    rotation,
+                );
 
                 intersects.then_some(proxy.entity)
             });
@@ -797,77 +863,126 @@ impl SpatialQueryPipeline {
     }
 }
 
-pub(crate) struct QueryPipelineAsCompositeShape<'a> {
-    pipeline: &'a SpatialQueryPipeline,
-    query_filter: &'a SpatialQueryFilter,
-}
-
-impl CompositeShape for QueryPipelineAsCompositeShape<'_> {
-    fn map_part_at(
+impl SpatialQueryPipeline<Collider> {
+    /// Casts a ray using parry's best-first traversal, treating the pipeline as a composite shape.
+    pub(crate) fn cast_ray_best_first(
         &self,
-        shape_id: u32,
-        f: &mut dyn FnMut(Option<&Isometry<Scalar>>, &dyn Shape, Option<&dyn NormalConstraints>),
-    ) {
-        self.map_untyped_part_at(shape_id, f);
-    }
+        origin: Vector,
+        direction: Dir,
+        max_distance: Scalar,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<RayHitData> {
+        let composite = self.as_composite_shape_with_predicate(filter, predicate);
+        let pipeline_shape = CompositeShapeRef(&composite);
+        let ray = Ray::new(origin.into(), direction.adjust_precision().into());
 
-    fn bvh(&self) -> &Bvh {
-        &self.pipeline.bvh
+        pipeline_shape
+            .cast_local_ray_and_get_normal(&ray, max_distance, solid)
+            .map(|(index, hit)| RayHitData {
+                entity: self.proxies[index as usize].entity,
+                distance: hit.time_of_impact,
+                normal: hit.normal.into(),
+            })
     }
-}
-
-impl TypedCompositeShape for QueryPipelineAsCompositeShape<'_> {
-    type PartNormalConstraints = ();
-    type PartShape = dyn Shape;
 
-    fn map_typed_part_at<T>(
+    /// Casts a shape using parry's best-first traversal, treating the pipeline as a composite shape.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn cast_shape_best_first(
         &self,
-        shape_id: u32,
-        mut f: impl FnMut(
-            Option<&Isometry<Scalar>>,
-            &Self::PartShape,
-            Option<&Self::PartNormalConstraints>,
-        ) -> T,
-    ) -> Option<T> {
-        let proxy = self.pipeline.proxies.get(shape_id as usize)?;
+        shape: &Collider,
+        origin: Vector,
+        rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<ShapeHitData> {
+        let shape_isometry = make_isometry(origin, rotation);
+        let shape_direction = direction.adjust_precision().into();
+        let composite = self.as_composite_shape_with_predicate(filter, predicate);
+        let pipeline_shape = CompositeShapeRef(&composite);
 
-        if self.query_filter.test(proxy.entity, proxy.layers) {
-            Some(f(
-                Some(&proxy.isometry),
-                proxy.collider.shape_scaled().as_ref(),
-                None,
-            ))
-        } else {
-            None
-        }
+        let (index, hit) = pipeline_shape.cast_shape(
+            &DefaultQueryDispatcher,
+            &shape_isometry,
+            &shape_direction,
+            shape.shape_scaled().as_ref(),
+            ShapeCastOptions {
+                max_time_of_impact: config.max_distance,
+                target_distance: config.target_distance,
+                stop_at_penetration: !config.ignore_origin_penetration,
+                compute_impact_geometry_on_penetration: config.compute_contact_on_penetration,
+            },
+        )?;
+
+        // The pipeline is in world space, but the witness and normal of the cast shape
+        // are in its local space at the time of impact.
+        let shape_position = origin + direction.adjust_precision() * hit.time_of_impact;
+
+        Some(ShapeHitData {
+            entity: self.proxies[index as usize].entity,
+            distance: hit.time_of_impact,
+            point1: hit.witness1.into(),
+            point2: shape_position + rotation * Vector::from(hit.witness2),
+            normal1: hit.normal1.into(),
+            normal2: rotation * Vector::from(hit.normal2),
+        })
     }
 
-    fn map_untyped_part_at<T>(
+    /// Projects a point using parry's best-first traversal, treating the pipeline as a composite shape.
+    pub(crate) fn project_point_best_first(
         &self,
-        shape_id: u32,
-        mut f: impl FnMut(Option<&Isometry<Scalar>>, &dyn Shape, Option<&dyn NormalConstraints>) -> T,
-    ) -> Option<T> {
-        let proxy = self.pipeline.proxies.get(shape_id as usize)?;
+        point: Vector,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<PointProjection> {
+        if self.proxies.is_empty() {
+            return None;
+        }
 
-        if self.query_filter.test(proxy.entity, proxy.layers) {
-            Some(f(
-                Some(&proxy.isometry),
-                proxy.collider.shape_scaled().as_ref(),
-                None,
-            ))
-        } else {
-            None
+        let composite = self.as_composite_shape_with_predicate(filter, predicate);
+        let pipeline_shape = CompositeShapeRef(&composite);
+
+        let (index, projection) = pipeline_shape.project_local_point(&point.into(), solid);
+
+        // If every collider was filtered out, the returned part is not a valid projection.
+        let proxy = self.proxies.get(index as usize)?;
+        if !filter.test(proxy.entity, proxy.layers) || !predicate(proxy.entity) {
+            return None;
+        }
+
+        Some(PointProjection {
+            entity: proxy.entity,
+            point: projection.point.into(),
+            is_inside: projection.is_inside,
+        })
+    }
+
+    fn as_composite_shape_with_predicate<'a>(
+        &'a self,
+        query_filter: &'a SpatialQueryFilter,
+        predicate: &'a dyn Fn(Entity) -> bool,
+    ) -> QueryPipelineAsCompositeShapeWithPredicate<'a> {
+        QueryPipelineAsCompositeShapeWithPredicate {
+            pipeline: self,
+            query_filter,
+            predicate,
         }
     }
 }
 
-pub(crate) struct QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-    pipeline: &'a SpatialQueryPipeline,
+/// A [`SpatialQueryPipeline`] viewed as a parry [`CompositeShape`], which allows
+/// using parry's best-first traversal for queries against [`Collider`]s.
+struct QueryPipelineAsCompositeShapeWithPredicate<'a> {
+    pipeline: &'a SpatialQueryPipeline<Collider>,
     query_filter: &'a SpatialQueryFilter,
-    predicate: &'b dyn Fn(Entity) -> bool,
+    predicate: &'a dyn Fn(Entity) -> bool,
 }
 
-impl CompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_, '_> {
+impl CompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_> {
     fn map_part_at(
         &self,
         shape_id: u32,
@@ -881,7 +996,7 @@ impl CompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_, '_> {
     }
 }
 
-impl TypedCompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_, '_> {
+impl TypedCompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_> {
     type PartNormalConstraints = ();
     type PartShape = dyn Shape;
 
@@ -898,8 +1013,9 @@ impl TypedCompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_, '_>
             && self.query_filter.test(proxy.entity, proxy.layers)
             && (self.predicate)(proxy.entity)
         {
+            let isometry = make_isometry(proxy.position, proxy.rotation);
             Some(f(
-                Some(&proxy.isometry),
+                Some(&isometry),
                 proxy.collider.shape_scaled().as_ref(),
                 None,
             ))
@@ -917,8 +1033,9 @@ impl TypedCompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'_, '_>
             && self.query_filter.test(proxy.entity, proxy.layers)
             && (self.predicate)(proxy.entity)
         {
+            let isometry = make_isometry(proxy.position, proxy.rotation);
             Some(f(
-                Some(&proxy.isometry),
+                Some(&isometry),
                 proxy.collider.shape_scaled().as_ref(),
                 None,
             ))
@@ -941,3 +1058,141 @@ pub struct PointProjection {
     /// True if the point was inside of the collider.
     pub is_inside: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::prelude::*;
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    /// Creates a pipeline with a 10x10 grid of alternating cubes and balls.
+    fn create_pipeline(world: &mut World) -> SpatialQueryPipeline {
+        let colliders: Vec<_> = (0..100)
+            .map(|i| {
+                let position =
+                    Vector::X * (i % 10) as Scalar * 3.0 + Vector::Y * (i / 10) as Scalar * 3.0;
+                #[cfg(feature = "2d")]
+                let collider = if i % 2 == 0 {
+                    Collider::rectangle(1.0, 1.0)
+                } else {
+                    Collider::circle(0.5)
+                };
+                #[cfg(feature = "3d")]
+                let collider = if i % 2 == 0 {
+                    Collider::cuboid(1.0, 1.0, 1.0)
+                } else {
+                    Collider::sphere(0.5)
+                };
+                (
+                    world.spawn_empty().id(),
+                    Position(position),
+                    Rotation::default(),
+                    collider,
+                    CollisionLayers::default(),
+                )
+            })
+            .collect();
+
+        let mut pipeline = SpatialQueryPipeline::new();
+        pipeline.update(
+            colliders
+                .iter()
+                .map(|(entity, position, rotation, collider, layers)| {
+                    (*entity, position, rotation, collider, layers)
+                }),
+        );
+        pipeline
+    }
+
+    /// Rays cast from the left of the grid with slightly different slopes.
+    fn rays() -> impl Iterator<Item = (Vector, Dir)> {
+        (0..20).map(|i| {
+            let origin = Vector::NEG_X * 5.0 + Vector::Y * i as Scalar * 1.4;
+            let direction = Vector::X + Vector::Y * (i as Scalar - 10.0) * 0.013;
+            (origin, Dir::new(direction.f32()).unwrap())
+        })
+    }
+
+    #[test]
+    fn collider_queries_match_depth_first_traversal() {
+        let mut world = World::new();
+        let pipeline = create_pipeline(&mut world);
+        let filter = SpatialQueryFilter::default();
+
+        for (origin, direction) in rays() {
+            let best_first = pipeline.cast_ray(origin, direction, 100.0, true, &filter);
+            let depth_first =
+                pipeline.cast_ray_depth_first(origin, direction, 100.0, true, &filter, &|_| true);
+            assert_eq!(
+                best_first.map(|hit| hit.entity),
+                depth_first.map(|hit| hit.entity)
+            );
+            if let (Some(best_first), Some(depth_first)) = (best_first, depth_first) {
+                assert_relative_eq!(best_first.distance, depth_first.distance, epsilon = 1e-4);
+            }
+
+            #[cfg(feature = "2d")]
+            let shape = Collider::circle(0.25);
+            #[cfg(feature = "3d")]
+            let shape = Collider::sphere(0.25);
+            let config = ShapeCastConfig::from_max_distance(100.0);
+            let best_first =
+                pipeline.cast_shape(&shape, origin, default(), direction, &config, &filter);
+            let depth_first = pipeline.cast_shape_depth_first(
+                &shape,
+                origin,
+                Rotation::default(),
+                direction,
+                &config,
+                &filter,
+                &|_| true,
+            );
+            assert_eq!(
+                best_first.map(|hit| hit.entity),
+                depth_first.map(|hit| hit.entity)
+            );
+            if let (Some(best_first), Some(depth_first)) = (best_first, depth_first) {
+                assert_relative_eq!(best_first.distance, depth_first.distance, epsilon = 1e-4);
+                assert!(best_first.point1.distance(depth_first.point1) < 1e-3);
+                assert!(best_first.point2.distance(depth_first.point2) < 1e-3);
+            }
+
+            let point = origin + direction.adjust_precision() * 10.0;
+            let best_first = pipeline.project_point(point, true, &filter).unwrap();
+            let depth_first = pipeline
+                .project_point_depth_first(point, true, &filter, &|_| true)
+                .unwrap();
+            assert_eq!(best_first.entity, depth_first.entity);
+            assert!(best_first.point.distance(depth_first.point) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn collider_raycast_visits_fewer_colliders() {
+        let mut world = World::new();
+        let pipeline = create_pipeline(&mut world);
+        let filter = SpatialQueryFilter::default();
+
+        // The predicate is called for every collider whose hit is computed.
+        let best_first_tests = Cell::new(0);
+        let depth_first_tests = Cell::new(0);
+
+        for (origin, direction) in rays() {
+            pipeline.cast_ray_predicate(origin, direction, 100.0, true, &filter, &|_| {
+                best_first_tests.set(best_first_tests.get() + 1);
+                true
+            });
+            pipeline.cast_ray_depth_first(origin, direction, 100.0, true, &filter, &|_| {
+                depth_first_tests.set(depth_first_tests.get() + 1);
+                true
+            });
+        }
+
+        // The best-first traversal visits the closest colliders first,
+        // so it should never need to test more colliders than the depth-first traversal.
+        assert!(best_first_tests.get() > 0);
+        assert!(best_first_tests.get() <= depth_first_tests.get());
+    }
+}
@@ -0,0 +1,289 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A trait for colliders that support [spatial queries](spatial_query).
+///
+/// Implementing this trait for an [`AnyCollider`] allows it to be used with the [`SpatialQueryPipeline`],
+/// the [`SpatialQuery`] system parameter, [`RayCaster`], and the physics picking backend,
+/// once the [`SpatialQueryBackendPlugin`] has been added for the collider type.
+///
+/// Unlike [`AnyCollider`], the methods of this trait have no access to [`AnyCollider::Context`],
+/// because spatial queries can be performed outside of systems through the [`SpatialQueryPipeline`].
+/// Any data needed by the queries must be stored in the collider itself.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "2d")]
+/// # use avian2d::{math::*, prelude::*};
+/// # #[cfg(feature = "3d")]
+/// use avian3d::{math::*, prelude::*};
+/// use bevy::prelude::*;
+///
+/// #[derive(Component, Clone)]
+/// struct BallCollider {
+///     radius: Scalar,
+/// }
+/// #
+/// # impl ComputeMassProperties2d for BallCollider {
+/// #     fn mass(&self, density: f32) -> f32 { 0.0 }
+/// #     fn unit_angular_inertia(&self) -> f32 { 0.0 }
+/// #     fn center_of_mass(&self) -> Vec2 { Vec2::ZERO }
+/// # }
+/// #
+/// # impl ComputeMassProperties3d for BallCollider {
+/// #     fn mass(&self, density: f32) -> f32 { 0.0 }
+/// #     fn unit_principal_angular_inertia(&self) -> Vec3 { Vec3::ZERO }
+/// #     fn center_of_mass(&self) -> Vec3 { Vec3::ZERO }
+/// # }
+/// #
+/// # impl AnyCollider for BallCollider {
+/// #     type Context = ();
+/// #     fn aabb_with_context(
+/// #         &self,
+/// #         position: Vector,
+/// #         _: impl Into<Rotation>,
+/// #         _: AabbContext<Self::Context>,
+/// #     ) -> ColliderAabb {
+/// #         ColliderAabb::new(position, Vector::splat(self.radius))
+/// #     }
+/// #     fn contact_manifolds_with_context(
+/// #         &self,
+/// #         _: &Self,
+/// #         _: Vector,
+/// #         _: impl Into<Rotation>,
+/// #         _: Vector,
+/// #         _: impl Into<Rotation>,
+/// #         _: Scalar,
+/// #         _: &mut Vec<ContactManifold>,
+/// #         _: ContactManifoldContext<Self::Context>,
+/// #     ) {}
+/// # }
+///
+/// impl QueryCollider for BallCollider {
+///     fn query_aabb(&self, position: Vector, _rotation: Rotation) -> ColliderAabb {
+///         ColliderAabb::new(position, Vector::splat(self.radius))
+///     }
+///
+///     fn cast_ray(
+///         &self,
+///         position: Vector,
+///         _rotation: Rotation,
+///         ray_origin: Vector,
+///         ray_direction: Vector,
+///         max_distance: Scalar,
+///         solid: bool,
+///     ) -> Option<(Scalar, Vector)> {
+///         // The ray direction is normalized, so the ray-sphere intersection is a simple quadratic.
+///         let offset = ray_origin - position;
+///         let b = offset.dot(ray_direction);
+///         let c = offset.length_squared() - self.radius * self.radius;
+///         let is_inside = c <= 0.0;
+///
+///         if is_inside && solid {
+///             return Some((0.0, Vector::ZERO));
+///         }
+///
+///         let discriminant = b * b - c;
+///         if discriminant < 0.0 || (!is_inside && b > 0.0) {
+///             return None;
+///         }
+///
+///         // Hollow spheres are hit from the inside at the far intersection.
+///         let distance = if is_inside {
+///             -b + discriminant.sqrt()
+///         } else {
+///             -b - discriminant.sqrt()
+///         };
+///
+///         (distance <= max_distance).then(|| {
+///             let normal = (offset + ray_direction * distance) / self.radius;
+///             (distance, normal)
+///         })
+///     }
+///
+///     fn project_point(
+///         &self,
+///         position: Vector,
+///         _rotation: Rotation,
+///         point: Vector,
+///         solid: bool,
+///     ) -> (Vector, bool) {
+///         let offset = point - position;
+///         let is_inside = offset.length_squared() <= self.radius * self.radius;
+///
+///         if is_inside && solid {
+///             (point, true)
+///         } else {
+///             let direction = offset.try_normalize().unwrap_or(Vector::X);
+///             (position + direction * self.radius, is_inside)
+///         }
+///     }
+///
+///     fn contains_point(&self, position: Vector, _rotation: Rotation, point: Vector) -> bool {
+///         position.distance_squared(point) <= self.radius * self.radius
+///     }
+///
+///     fn intersects_shape(
+///         &self,
+///         position: Vector,
+///         _rotation: Rotation,
+///         shape: &Self,
+///         shape_position: Vector,
+///         _shape_rotation: Rotation,
+///     ) -> bool {
+///         let radius_sum = self.radius + shape.radius;
+///         position.distance_squared(shape_position) <= radius_sum * radius_sum
+///     }
+/// }
+///
+/// let ball = BallCollider { radius: 1.0 };
+/// let hit = ball.cast_ray(Vector::ZERO, Rotation::default(), -5.0 * Vector::X, Vector::X, 10.0, true);
+/// assert_eq!(hit, Some((4.0, -Vector::X)));
+/// ```
+pub trait QueryCollider: AnyCollider + Clone {
+    /// Computes the [Axis-Aligned Bounding Box](ColliderAabb) of the collider
+    /// with the given position and rotation.
+    ///
+    /// This is used for the acceleration structure of the [`SpatialQueryPipeline`].
+    fn query_aabb(&self, position: Vector, rotation: Rotation) -> ColliderAabb;
+
+    /// Computes the distance and normal between the given ray and `self`
+    /// transformed by `position` and `rotation`.
+    ///
+    /// The returned tuple is in the format `(distance, normal)`.
+    ///
+    /// # Arguments
+    ///
+    /// - `ray_origin`: Where the ray is cast from.
+    /// - `ray_direction`: What direction the ray is cast in.
+    /// - `max_distance`: The maximum distance the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    ///   Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    fn cast_ray(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        ray_origin: Vector,
+        ray_direction: Vector,
+        max_distance: Scalar,
+        solid: bool,
+    ) -> Option<(Scalar, Vector)>;
+
+    /// Projects the given `point` onto `self` transformed by `position` and `rotation`.
+    /// The returned tuple contains the projected point and whether it is inside the collider.
+    ///
+    /// If `solid` is true and the given `point` is inside of the collider, the projection will be at the point.
+    /// Otherwise, the collider will be treated as hollow, and the projection will be at the collider's boundary.
+    fn project_point(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        point: Vector,
+        solid: bool,
+    ) -> (Vector, bool);
+
+    /// Tests whether the given `point` is inside of `self` transformed by `position` and `rotation`.
+    fn contains_point(&self, position: Vector, rotation: Rotation, point: Vector) -> bool;
+
+    /// Casts the given `shape` against `self` transformed by `position` and `rotation`,
+    /// and computes the [hit](ShapeHitData) if there is one.
+    ///
+    /// The `entity` of the returned hit is ignored and replaced by the [`SpatialQueryPipeline`].
+    ///
+    /// Returns `None` by default, meaning that shape casts are not supported for the collider type.
+    ///
+    /// # Arguments
+    ///
+    /// - `shape`: The shape being cast.
+    /// - `shape_origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    #[allow(clippy::too_many_arguments)]
+    fn cast_shape(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        shape: &Self,
+        shape_origin: Vector,
+        shape_rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+    ) -> Option<ShapeHitData> {
+        let _ = (
+            position,
+            rotation,
+            shape,
+            shape_origin,
+            shape_rotation,
+            direction,
+            config,
+        );
+        None
+    }
+
+    /// Tests whether the given `shape` transformed by `shape_position` and `shape_rotation`
+    /// intersects `self` transformed by `position` and `rotation`.
+    fn intersects_shape(
+        &self,
+        position: Vector,
+        rotation: Rotation,
+        shape: &Self,
+        shape_position: Vector,
+        shape_rotation: Rotation,
+    ) -> bool;
+
+    /// Casts a ray against the colliders in the given `pipeline` and computes the closest hit.
+    ///
+    /// This is called by [`SpatialQueryPipeline::cast_ray_predicate`]. The default implementation
+    /// traverses the BVH of the pipeline depth-first using [`QueryCollider::cast_ray`].
+    #[doc(hidden)]
+    fn pipeline_cast_ray(
+        pipeline: &SpatialQueryPipeline<Self>,
+        origin: Vector,
+        direction: Dir,
+        max_distance: Scalar,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<RayHitData> {
+        pipeline.cast_ray_depth_first(origin, direction, max_distance, solid, filter, predicate)
+    }
+
+    /// Casts a shape against the colliders in the given `pipeline` and computes the closest hit.
+    ///
+    /// This is called by [`SpatialQueryPipeline::cast_shape_predicate`]. The default implementation
+    /// traverses the BVH of the pipeline depth-first using [`QueryCollider::cast_shape`].
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    fn pipeline_cast_shape(
+        pipeline: &SpatialQueryPipeline<Self>,
+        shape: &Self,
+        origin: Vector,
+        rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<ShapeHitData> {
+        pipeline.cast_shape_depth_first(
+            shape, origin, rotation, direction, config, filter, predicate,
+        )
+    }
+
+    /// Projects a point onto the closest collider in the given `pipeline`.
+    ///
+    /// This is called by [`SpatialQueryPipeline::project_point_predicate`]. The default implementation
+    /// traverses the BVH of the pipeline depth-first using [`QueryCollider::project_point`].
+    #[doc(hidden)]
+    fn pipeline_project_point(
+        pipeline: &SpatialQueryPipeline<Self>,
+        point: Vector,
+        solid: bool,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<PointProjection> {
+        pipeline.project_point_depth_first(point, solid, filter, predicate)
+    }
+}
@@ -0,0 +1,538 @@
+use crate::prelude::*;
+use bevy::{
+    ecs::{entity::hash_map::EntityHashMap, system::SystemParam},
+    prelude::*,
+};
+
+/// Determines how the strength of a [radial impulse](SpatialQuery::apply_radial_impulse)
+/// is attenuated based on the distance from its center.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum ImpulseFalloff {
+    /// The impulse has the same strength everywhere within the radius.
+    Constant,
+    /// The strength of the impulse decreases linearly from full strength at the center
+    /// to zero at the radius.
+    #[default]
+    Linear,
+    /// The strength of the impulse decreases quadratically from full strength at the center
+    /// to zero at the radius, resulting in a sharper drop-off than [`ImpulseFalloff::Linear`].
+    Quadratic,
+}
+
+impl ImpulseFalloff {
+    /// Computes the multiplier for the impulse strength at the given `distance`
+    /// for an impulse with the given `radius`.
+    #[inline]
+    pub fn factor(&self, distance: Scalar, radius: Scalar) -> Scalar {
+        if radius <= Scalar::EPSILON {
+            return 0.0;
+        }
+        let t = (1.0 - distance / radius).clamp(0.0, 1.0);
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => t,
+            Self::Quadratic => t * t,
+        }
+    }
+}
+
+/// A system parameter for applying radial impulses to dynamic [rigid bodies](RigidBody),
+/// for explosions, shockwaves, and other area effects.
+///
+/// This combines the [`SpatialQuery`] used for finding the bodies with the query for their [`Forces`],
+/// so the impulses can be applied without passing a query manually. For more control, the lower-level
+/// [`SpatialQuery::apply_radial_impulse`] and [`SpatialQuery::apply_radial_impulse_occluded`]
+/// methods can be used with a custom query instead.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn explode(mut radial_impulses: RadialImpulses) {
+///     let affected = radial_impulses.apply_radial_impulse(
+///         Vector::ZERO,                   // Center
+///         5.0,                            // Radius
+///         20.0,                           // Strength
+///         ImpulseFalloff::Linear,         // Falloff
+///         &SpatialQueryFilter::default(), // Query filter
+///     );
+///
+///     for entity in affected.iter() {
+///         println!("Pushed entity {}", entity);
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct RadialImpulses<'w, 's> {
+    /// The [`SpatialQuery`] used for finding the bodies affected by impulses.
+    pub spatial_query: SpatialQuery<'w, 's>,
+    bodies: Query<'w, 's, (&'static RigidBody, Forces)>,
+}
+
+impl RadialImpulses<'_, '_> {
+    /// Applies an impulse to all dynamic [rigid bodies](RigidBody) within the given `radius`
+    /// of the `center`, pushing them away from it.
+    ///
+    /// The impulse is applied at the closest point on each body's colliders, and its strength
+    /// is attenuated based on the distance to that point using the given [`ImpulseFalloff`].
+    /// Sleeping bodies are woken up.
+    ///
+    /// Returns the entities of the rigid bodies that the impulse was applied to.
+    ///
+    /// See [`SpatialQuery::apply_radial_impulse`] for more information.
+    pub fn apply_radial_impulse(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        self.spatial_query.apply_radial_impulse(
+            center,
+            radius,
+            strength,
+            falloff,
+            filter,
+            &mut self.bodies,
+        )
+    }
+
+    /// Applies an impulse to all dynamic [rigid bodies](RigidBody) within the given `radius`
+    /// of the `center` that are not occluded by colliders matching the `occlusion_filter`,
+    /// pushing them away from it.
+    ///
+    /// Returns the entities of the rigid bodies that the impulse was applied to.
+    ///
+    /// See [`SpatialQuery::apply_radial_impulse_occluded`] for more information.
+    pub fn apply_radial_impulse_occluded(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        filter: &SpatialQueryFilter,
+        occlusion_filter: &SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        self.spatial_query.apply_radial_impulse_occluded(
+            center,
+            radius,
+            strength,
+            falloff,
+            filter,
+            occlusion_filter,
+            &mut self.bodies,
+        )
+    }
+}
+
+/// The closest point on a body to the center of a radial impulse.
+struct RadialImpulseTarget {
+    collider: Entity,
+    point: Vector,
+    distance: Scalar,
+}
+
+impl SpatialQuery<'_, '_> {
+    /// Applies an impulse to all dynamic [rigid bodies](RigidBody) within the given `radius`
+    /// of the `center`, pushing them away from it. This can be used for explosions, shockwaves,
+    /// and other area effects.
+    ///
+    /// The impulse is applied at the closest point on each body's colliders, so bodies
+    /// will also start rotating if the point is not aligned with their center of mass.
+    /// The strength of the impulse is attenuated based on the distance to that point
+    /// using the given [`ImpulseFalloff`]. Sleeping bodies are woken up.
+    ///
+    /// Returns the entities of the rigid bodies that the impulse was applied to.
+    ///
+    /// This is the lower-level version of [`RadialImpulses::apply_radial_impulse`],
+    /// and takes the query for the [`Forces`] of bodies as an argument.
+    ///
+    /// # Arguments
+    ///
+    /// - `center`: The center of the impulse.
+    /// - `radius`: The radius within which bodies are affected.
+    /// - `strength`: The magnitude of the impulse at the center, before falloff is applied.
+    /// - `falloff`: Determines how the strength of the impulse is attenuated based on distance.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are affected.
+    /// - `bodies`: A query for the [`RigidBody`] and [`Forces`] of bodies, used for applying the impulses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn explode(spatial_query: SpatialQuery, mut bodies: Query<(&RigidBody, Forces)>) {
+    ///     let affected = spatial_query.apply_radial_impulse(
+    ///         Vec3::ZERO,                     // Center
+    ///         5.0,                            // Radius
+    ///         20.0,                           // Strength
+    ///         ImpulseFalloff::Linear,         // Falloff
+    ///         &SpatialQueryFilter::default(), // Query filter
+    ///         &mut bodies,                    // Bodies
+    ///     );
+    ///
+    ///     for entity in affected.iter() {
+    ///         println!("Pushed entity {}", entity);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::apply_radial_impulse_occluded`]
+    pub fn apply_radial_impulse(
+        &self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        filter: &SpatialQueryFilter,
+        bodies: &mut Query<(&RigidBody, Forces)>,
+    ) -> Vec<Entity> {
+        let targets = self.radial_impulse_targets(center, radius, filter);
+        self.apply_radial_impulse_to_targets(center, radius, strength, falloff, targets, bodies)
+    }
+
+    /// Applies an impulse to all dynamic [rigid bodies](RigidBody) within the given `radius`
+    /// of the `center` that are not occluded by other colliders, pushing them away from it.
+    ///
+    /// This works like [`SpatialQuery::apply_radial_impulse`], but a ray is cast from the `center`
+    /// towards the closest point on each body. If the ray hits a collider that belongs to another
+    /// entity first, the body is considered to be occluded, and no impulse is applied to it.
+    ///
+    /// Returns the entities of the rigid bodies that the impulse was applied to.
+    ///
+    /// # Arguments
+    ///
+    /// - `center`: The center of the impulse.
+    /// - `radius`: The radius within which bodies are affected.
+    /// - `strength`: The magnitude of the impulse at the center, before falloff is applied.
+    /// - `falloff`: Determines how the strength of the impulse is attenuated based on distance.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are affected.
+    /// - `occlusion_filter`: A [`SpatialQueryFilter`] that determines which colliders can occlude the impulse.
+    /// - `bodies`: A query for the [`RigidBody`] and [`Forces`] of bodies, used for applying the impulses.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::apply_radial_impulse`]
+    #[expect(clippy::too_many_arguments)]
+    pub fn apply_radial_impulse_occluded(
+        &self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        filter: &SpatialQueryFilter,
+        occlusion_filter: &SpatialQueryFilter,
+        bodies: &mut Query<(&RigidBody, Forces)>,
+    ) -> Vec<Entity> {
+        let mut targets = self.radial_impulse_targets(center, radius, filter);
+
+        targets.retain(|body, target| {
+            let Ok(direction) = Dir::new((target.point - center).f32()) else {
+                // The center is inside of the body, so it cannot be occluded.
+                return true;
+            };
+
+            // Ignore colliders that are attached to the target body.
+            let predicate = |entity: Entity| {
+                entity != target.collider
+                    && !self
+                        .collider_of
+                        .get(entity)
+                        .is_ok_and(|collider_of| collider_of.body == *body)
+            };
+
+            self.cast_ray_predicate(
+                center,
+                direction,
+                target.distance,
+                true,
+                occlusion_filter,
+                &predicate,
+            )
+            .is_none()
+        });
+
+        self.apply_radial_impulse_to_targets(center, radius, strength, falloff, targets, bodies)
+    }
+
+    /// Finds the closest point on each rigid body within the given `radius` of the `center`.
+    fn radial_impulse_targets(
+        &self,
+        center: Vector,
+        radius: Scalar,
+        filter: &SpatialQueryFilter,
+    ) -> EntityHashMap<RadialImpulseTarget> {
+        #[cfg(feature = "2d")]
+        let shape = Collider::circle(radius);
+        #[cfg(feature = "3d")]
+        let shape = Collider::sphere(radius);
+
+        let mut targets = EntityHashMap::<RadialImpulseTarget>::default();
+
+        self.shape_intersections_callback(
+            &shape,
+            center,
+            RotationValue::default(),
+            filter,
+            |entity| {
+                let Ok((_, position, rotation, collider, _)) = self.colliders.get(entity) else {
+                    return true;
+                };
+                let Ok(collider_of) = self.collider_of.get(entity) else {
+                    return true;
+                };
+
+                let (point, _) = collider.project_point(*position, *rotation, center, true);
+                let distance = point.distance(center);
+
+                // Only keep the closest collider of each body.
+                targets
+                    .entry(collider_of.body)
+                    .and_modify(|target| {
+                        if distance < target.distance {
+                            *target = RadialImpulseTarget {
+                                collider: entity,
+                                point,
+                                distance,
+                            };
+                        }
+                    })
+                    .or_insert(RadialImpulseTarget {
+                        collider: entity,
+                        point,
+                        distance,
+                    });
+
+                true
+            },
+        );
+
+        targets
+    }
+
+    /// Applies the radial impulse to the given targets, returning the affected bodies.
+    fn apply_radial_impulse_to_targets(
+        &self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        targets: EntityHashMap<RadialImpulseTarget>,
+        bodies: &mut Query<(&RigidBody, Forces)>,
+    ) -> Vec<Entity> {
+        let mut affected = Vec::with_capacity(targets.len());
+
+        for (body, target) in targets {
+            // Only dynamic bodies are affected by impulses.
+            let Ok((rigid_body, mut forces)) = bodies.get_mut(body) else {
+                continue;
+            };
+            if !rigid_body.is_dynamic() {
+                continue;
+            }
+
+            // Push the body away from the center. If the center is inside of the body,
+            // push it away from the center along the direction to its position.
+            let direction = (target.point - center)
+                .try_normalize()
+                .or_else(|| (forces.position().0 - center).try_normalize());
+            let Some(direction) = direction else {
+                continue;
+            };
+
+            let factor = falloff.factor(target.distance, radius);
+            if factor <= 0.0 {
+                continue;
+            }
+
+            forces.apply_linear_impulse_at_point(direction * strength * factor, target.point);
+            affected.push(body);
+        }
+
+        affected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::{prelude::*, tests::create_test_app};
+    use approx::assert_relative_eq;
+    use bevy::{ecs::system::RunSystemOnce, prelude::*, time::TimeUpdateStrategy};
+
+    fn create_app() -> App {
+        let mut app = create_test_app(());
+        app.insert_resource(Gravity::ZERO);
+        app.insert_resource(Time::<Fixed>::from_hz(10.0));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 10.0,
+        )));
+        app
+    }
+
+    fn cube() -> Collider {
+        #[cfg(feature = "2d")]
+        return Collider::rectangle(1.0, 1.0);
+        #[cfg(feature = "3d")]
+        return Collider::cuboid(1.0, 1.0, 1.0);
+    }
+
+    fn spawn_body(app: &mut App, rigid_body: RigidBody, position: Vector) -> Entity {
+        app.world_mut()
+            .spawn((rigid_body, cube(), Position(position)))
+            .id()
+    }
+
+    /// Applies a radial impulse at the origin, returning the affected entities.
+    fn explode(app: &mut App, radius: Scalar, falloff: ImpulseFalloff) -> Vec<Entity> {
+        app.world_mut()
+            .run_system_once(move |mut radial_impulses: RadialImpulses| {
+                radial_impulses.apply_radial_impulse(
+                    Vector::ZERO,
+                    radius,
+                    10.0,
+                    falloff,
+                    &SpatialQueryFilter::default(),
+                )
+            })
+            .unwrap()
+    }
+
+    fn linear_velocity(app: &App, entity: Entity) -> Vector {
+        app.world().get::<LinearVelocity>(entity).unwrap().0
+    }
+
+    #[test]
+    fn radial_impulse_pushes_dynamic_bodies_away_from_center() {
+        let mut app = create_app();
+
+        let right = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 2.0);
+        let below = spawn_body(&mut app, RigidBody::Dynamic, Vector::NEG_Y * 2.0);
+        let far = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 10.0);
+        let static_body = spawn_body(&mut app, RigidBody::Static, Vector::NEG_X * 2.0);
+        let kinematic_body = spawn_body(&mut app, RigidBody::Kinematic, Vector::Y * 2.0);
+
+        // Update the spatial query pipeline.
+        app.update();
+
+        let mut affected = explode(&mut app, 5.0, ImpulseFalloff::Constant);
+        affected.sort();
+        let mut expected = vec![right, below];
+        expected.sort();
+        assert_eq!(affected, expected);
+
+        // The bodies are pushed directly away from the center.
+        let right_velocity = linear_velocity(&app, right);
+        assert!(right_velocity.x > 0.0);
+        assert_relative_eq!(right_velocity.y, 0.0, epsilon = 1e-6);
+        let below_velocity = linear_velocity(&app, below);
+        assert!(below_velocity.y < 0.0);
+        assert_relative_eq!(below_velocity.x, 0.0, epsilon = 1e-6);
+
+        // Bodies outside of the radius and non-dynamic bodies are not affected.
+        assert_eq!(linear_velocity(&app, far), Vector::ZERO);
+        assert_eq!(linear_velocity(&app, static_body), Vector::ZERO);
+        assert_eq!(linear_velocity(&app, kinematic_body), Vector::ZERO);
+    }
+
+    #[test]
+    fn radial_impulse_falloff() {
+        // The closest point on the body is at a distance of 1.5, which is half of the radius.
+        for (falloff, factor) in [
+            (ImpulseFalloff::Constant, 1.0),
+            (ImpulseFalloff::Linear, 0.5),
+            (ImpulseFalloff::Quadratic, 0.25),
+        ] {
+            assert_relative_eq!(falloff.factor(1.5, 3.0), factor);
+            assert_eq!(falloff.factor(3.5, 3.0), 0.0);
+
+            let mut app = create_app();
+            let body = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 2.0);
+            app.update();
+
+            let affected = explode(&mut app, 3.0, falloff);
+            assert_eq!(affected, vec![body]);
+
+            let inverse_mass = app.world().get::<ComputedMass>(body).unwrap().inverse();
+            assert_relative_eq!(
+                linear_velocity(&app, body).x,
+                10.0 * factor * inverse_mass,
+                epsilon = 1e-4
+            );
+        }
+    }
+
+    #[test]
+    fn radial_impulse_wakes_sleeping_bodies() {
+        let mut app = create_app();
+
+        let body = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 2.0);
+
+        // Let the body fall asleep.
+        for _ in 0..20 {
+            app.update();
+        }
+        assert!(app.world().entity(body).contains::<Sleeping>());
+
+        let affected = explode(&mut app, 5.0, ImpulseFalloff::Linear);
+        assert_eq!(affected, vec![body]);
+        app.update();
+
+        assert!(!app.world().entity(body).contains::<Sleeping>());
+        assert!(linear_velocity(&app, body).x > 0.0);
+    }
+
+    #[test]
+    fn occluded_radial_impulse() {
+        let mut app = create_app();
+
+        // A wall between the center and the occluded body.
+        #[cfg(feature = "2d")]
+        let wall = Collider::rectangle(0.2, 4.0);
+        #[cfg(feature = "3d")]
+        let wall = Collider::cuboid(0.2, 4.0, 4.0);
+        app.world_mut()
+            .spawn((RigidBody::Static, wall, Position(Vector::X)));
+
+        let occluded = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 3.0);
+        let visible = spawn_body(&mut app, RigidBody::Dynamic, Vector::NEG_X * 3.0);
+
+        app.update();
+
+        let affected = app
+            .world_mut()
+            .run_system_once(
+                |spatial_query: SpatialQuery, mut bodies: Query<(&RigidBody, Forces)>| {
+                    spatial_query.apply_radial_impulse_occluded(
+                        Vector::ZERO,
+                        5.0,
+                        10.0,
+                        ImpulseFalloff::Constant,
+                        &SpatialQueryFilter::default(),
+                        &SpatialQueryFilter::default(),
+                        &mut bodies,
+                    )
+                },
+            )
+            .unwrap();
+
+        assert_eq!(affected, vec![visible]);
+        assert_eq!(linear_velocity(&app, occluded), Vector::ZERO);
+        assert!(linear_velocity(&app, visible).x < 0.0);
+    }
+}
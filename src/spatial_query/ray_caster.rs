@@ -7,11 +7,6 @@ use bevy::{
     },
     prelude::*,
 };
-#[cfg(all(
-    feature = "default-collider",
-    any(feature = "parry-f32", feature = "parry-f64")
-))]
-use parry::{partitioning::BvhNode, query::RayCast};
 
 /// A component used for [raycasting](spatial_query#raycasting).
 ///
@@ -243,15 +238,19 @@ impl RayCaster {
         self.global_direction = global_direction;
     }
 
+    /// Casts the ray against the colliders in the given `query_pipeline`, adding the hits to `hits`.
+    ///
+    /// The hits are not cleared, so that hits from the pipelines of several collider types can be combined.
+    /// Up to `max_hits` hits are added for each pipeline, so the combined hits must be truncated afterwards.
     #[cfg(all(
         feature = "default-collider",
         any(feature = "parry-f32", feature = "parry-f64")
     ))]
-    pub(crate) fn cast(
+    pub(crate) fn cast<C: QueryCollider>(
         &mut self,
         caster_entity: Entity,
         hits: &mut RayHits,
-        query_pipeline: &SpatialQueryPipeline,
+        query_pipeline: &SpatialQueryPipeline<C>,
     ) {
         if self.ignore_self {
             self.query_filter.excluded_entities.insert(caster_entity);
@@ -259,48 +258,35 @@ impl RayCaster {
             self.query_filter.excluded_entities.remove(&caster_entity);
         }
 
-        hits.clear();
-
         if self.max_hits == 1 {
-            query_pipeline.cast_ray(
+            let hit = query_pipeline.cast_ray(
                 self.global_origin(),
                 self.global_direction(),
                 self.max_distance,
                 self.solid,
                 &self.query_filter,
             );
+
+            // The closest hit across all pipelines is kept when the hits are truncated.
+            if let Some(hit) = hit {
+                hits.push(hit);
+            }
         } else {
-            let ray = parry::query::Ray::new(
-                self.global_origin().into(),
-                self.global_direction().adjust_precision().into(),
-            );
+            let max_hits = self.max_hits as usize;
+            let mut found_hits = 0;
 
-            let found_hits = query_pipeline
-                .bvh
-                .leaves(|node: &BvhNode| node.aabb().intersects_local_ray(&ray, self.max_distance))
-                .filter_map(|leaf| {
-                    let proxy = query_pipeline.proxies.get(leaf as usize)?;
-
-                    if !self.query_filter.test(proxy.entity, proxy.layers) {
-                        return None;
-                    }
-
-                    let hit = proxy.collider.shape_scaled().cast_ray_and_get_normal(
-                        &proxy.isometry,
-                        &ray,
-                        self.max_distance,
-                        self.solid,
-                    )?;
-
-                    Some(RayHitData {
-                        entity: proxy.entity,
-                        distance: hit.time_of_impact,
-                        normal: hit.normal.into(),
-                    })
-                })
-                .take(self.max_hits as usize);
-
-            hits.extend(found_hits);
+            query_pipeline.ray_hits_callback(
+                self.global_origin(),
+                self.global_direction(),
+                self.max_distance,
+                self.solid,
+                &self.query_filter,
+                |hit| {
+                    hits.push(hit);
+                    found_hits += 1;
+                    found_hits < max_hits
+                },
+            );
         }
     }
 }
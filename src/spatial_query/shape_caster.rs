@@ -7,7 +7,6 @@ use bevy::{
     },
     prelude::*,
 };
-use parry::{query::ShapeCastOptions, shape::CompositeShapeRef};
 
 /// A component used for [shapecasting](spatial_query#shapecasting).
 ///
@@ -349,54 +348,29 @@ impl ShapeCaster {
 
         hits.clear();
 
-        let shape_cast_options = ShapeCastOptions {
-            max_time_of_impact: self.max_distance,
+        if self.max_hits == 0 {
+            return;
+        }
+
+        let config = ShapeCastConfig {
+            max_distance: self.max_distance,
             target_distance: self.target_distance,
-            stop_at_penetration: !self.ignore_origin_penetration,
-            compute_impact_geometry_on_penetration: self.compute_contact_on_penetration,
+            compute_contact_on_penetration: self.compute_contact_on_penetration,
+            ignore_origin_penetration: self.ignore_origin_penetration,
         };
 
-        let shape_rotation: Rotation;
-        #[cfg(feature = "2d")]
-        {
-            shape_rotation = Rotation::radians(self.global_shape_rotation());
-        }
-        #[cfg(feature = "3d")]
-        {
-            shape_rotation = Rotation::from(self.global_shape_rotation());
-        }
-
-        let shape_isometry = make_isometry(self.global_origin(), shape_rotation);
-        let shape_direction = self.global_direction().adjust_precision().into();
-
-        while hits.len() < self.max_hits as usize {
-            let composite = query_pipeline.as_composite_shape(&query_filter);
-            let pipeline_shape = CompositeShapeRef(&composite);
-
-            let hit = pipeline_shape
-                .cast_shape(
-                    query_pipeline.dispatcher.as_ref(),
-                    &shape_isometry,
-                    &shape_direction,
-                    self.shape.shape_scaled().as_ref(),
-                    shape_cast_options,
-                )
-                .map(|(index, hit)| ShapeHitData {
-                    entity: query_pipeline.proxies[index as usize].entity,
-                    distance: hit.time_of_impact,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
-                });
-
-            if let Some(hit) = hit {
-                query_filter.excluded_entities.insert(hit.entity);
+        query_pipeline.shape_hits_callback(
+            &self.shape,
+            self.global_origin(),
+            self.global_shape_rotation(),
+            self.global_direction(),
+            &config,
+            &query_filter,
+            |hit| {
                 hits.push(hit);
-            } else {
-                return;
-            }
-        }
+                hits.len() < self.max_hits as usize
+            },
+        );
     }
 }
 
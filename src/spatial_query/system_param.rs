@@ -17,10 +17,15 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 ///       [`aabb_intersections_with_aabb_callback`](SpatialQuery::aabb_intersections_with_aabb_callback)
 ///     - Shape intersections: [`shape_intersections`](SpatialQuery::shape_intersections)
 ///       [`shape_intersections_callback`](SpatialQuery::shape_intersections_callback)
+/// - Radial impulses: [`apply_radial_impulse`](SpatialQuery::apply_radial_impulse) and
+///   [`apply_radial_impulse_occluded`](SpatialQuery::apply_radial_impulse_occluded)
 ///
 /// For simple raycasts and shapecasts, consider using the [`RayCaster`] and [`ShapeCaster`] components that
 /// provide a more ECS-based approach and perform casts on every frame.
 ///
+/// By default, queries are performed against [`Collider`]s. For custom collider types that implement
+/// [`QueryCollider`], use `SpatialQuery<MyCollider>`, and add the [`SpatialQueryBackendPlugin`] for the collider type.
+///
 /// # Example
 ///
 /// ```
@@ -56,7 +61,7 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 /// }
 /// ```
 #[derive(SystemParam)]
-pub struct SpatialQuery<'w, 's> {
+pub struct SpatialQuery<'w, 's, C: QueryCollider = Collider> {
     pub(crate) colliders: Query<
         'w,
         's,
@@ -64,16 +69,17 @@ pub struct SpatialQuery<'w, 's> {
             Entity,
             &'static Position,
             &'static Rotation,
-            &'static Collider,
+            &'static C,
             &'static CollisionLayers,
         ),
         Without<ColliderDisabled>,
     >,
+    pub(crate) collider_of: Query<'w, 's, &'static ColliderOf, Without<ColliderDisabled>>,
     /// The [`SpatialQueryPipeline`].
-    pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
+    pub query_pipeline: ResMut<'w, SpatialQueryPipeline<C>>,
 }
 
-impl SpatialQuery<'_, '_> {
+impl<C: QueryCollider> SpatialQuery<'_, '_, C> {
     /// Updates the colliders in the pipeline. This is done automatically once per physics frame in
     /// [`PhysicsStepSystems::SpatialQuery`], but if you modify colliders or their positions before that, you can
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
@@ -353,7 +359,7 @@ impl SpatialQuery<'_, '_> {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -397,7 +403,7 @@ impl SpatialQuery<'_, '_> {
     #[allow(clippy::too_many_arguments)]
     pub fn cast_shape(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -415,7 +421,7 @@ impl SpatialQuery<'_, '_> {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -467,7 +473,7 @@ impl SpatialQuery<'_, '_> {
     /// - [`SpatialQuery::ray_hits_callback`]
     pub fn cast_shape_predicate(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -491,7 +497,7 @@ impl SpatialQuery<'_, '_> {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -539,7 +545,7 @@ impl SpatialQuery<'_, '_> {
     #[allow(clippy::too_many_arguments)]
     pub fn shape_hits(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -564,7 +570,7 @@ impl SpatialQuery<'_, '_> {
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `shape`: The shape being cast represented as a collider.
     /// - `origin`: Where the shape is cast from.
     /// - `shape_rotation`: The rotation of the shape being cast.
     /// - `direction`: What direction the shape is cast in.
@@ -615,7 +621,7 @@ impl SpatialQuery<'_, '_> {
     #[allow(clippy::too_many_arguments)]
     pub fn shape_hits_callback(
         &self,
-        shape: &Collider,
+        shape: &C,
         origin: Vector,
         shape_rotation: RotationValue,
         direction: Dir,
@@ -891,12 +897,12 @@ impl SpatialQuery<'_, '_> {
             .aabb_intersections_with_aabb_callback(aabb, callback)
     }
 
-    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that is intersecting the given `shape` with a given position and rotation.
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape`: The shape that intersections are tested against represented as a collider.
     /// - `shape_position`: The position of the shape.
     /// - `shape_rotation`: The rotation of the shape.
     /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
@@ -930,7 +936,7 @@ impl SpatialQuery<'_, '_> {
     /// - [`SpatialQuery::shape_intersections_callback`]
     pub fn shape_intersections(
         &self,
-        shape: &Collider,
+        shape: &C,
         shape_position: Vector,
         shape_rotation: RotationValue,
         filter: &SpatialQueryFilter,
@@ -939,13 +945,13 @@ impl SpatialQuery<'_, '_> {
             .shape_intersections(shape, shape_position, shape_rotation, filter)
     }
 
-    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that is intersecting the given `shape` with a given position and rotation, calling `callback` for each
     /// intersection. The search stops when `callback` returns `false` or all intersections have been found.
     ///
     /// # Arguments
     ///
-    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape`: The shape that intersections are tested against represented as a collider.
     /// - `shape_position`: The position of the shape.
     /// - `shape_rotation`: The rotation of the shape.
     /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
@@ -986,7 +992,7 @@ impl SpatialQuery<'_, '_> {
     /// - [`SpatialQuery::shape_intersections`]
     pub fn shape_intersections_callback(
         &self,
-        shape: &Collider,
+        shape: &C,
         shape_position: Vector,
         shape_rotation: RotationValue,
         filter: &SpatialQueryFilter,
@@ -5,6 +5,7 @@ use crate::prelude::*;
 ))]
 use approx::assert_relative_eq;
 use bevy::{
+    app::Plugins,
     ecs::schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel},
     prelude::*,
     time::TimeUpdateStrategy,
@@ -17,6 +18,36 @@ mod determinism_2d;
 fn create_app() -> App {
     let mut app = App::new();
 
+    add_test_plugins(&mut app);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        1.0 / 60.0,
+    )));
+
+    app.finish();
+
+    app
+}
+
+/// Creates an [`App`] with the [`PhysicsPlugins`] and the given additional `plugins`
+/// for the unit tests of individual modules.
+///
+/// The fixed time step runs at 60 Hz, and each update advances time by one time step.
+pub(crate) fn create_test_app<M>(plugins: impl Plugins<M>) -> App {
+    let mut app = App::new();
+
+    add_test_plugins(&mut app);
+    app.add_plugins(plugins)
+        .insert_resource(Time::<Fixed>::from_hz(60.0))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )));
+
+    app.finish();
+
+    app
+}
+
+fn add_test_plugins(app: &mut App) {
     app.add_plugins((
         MinimalPlugins,
         TransformPlugin,
@@ -26,14 +57,7 @@ fn create_app() -> App {
         bevy::mesh::MeshPlugin,
         #[cfg(feature = "bevy_scene")]
         bevy::scene::ScenePlugin,
-    ))
-    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
-        1.0 / 60.0,
-    )));
-
-    app.finish();
-
-    app
+    ));
 }
 
 fn tick_app(app: &mut App, timestep: f64) {